@@ -29,19 +29,35 @@ impl fmt::Display for ApiError {
     }
 }
 
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// The message without the prefix `Display` adds.
+    pub fn message(&self) -> &str {
+        match self {
+            ApiError::NotFound(msg)
+            | ApiError::BadRequest(msg)
+            | ApiError::Conflict(msg)
+            | ApiError::Internal(msg) => msg,
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, message) = match &self {
-            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            ApiError::Conflict(msg) => (StatusCode::CONFLICT, msg),
-            ApiError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-        };
+        let status = self.status();
 
         let body = Json(json!({
             "statusCode": status.as_u16(),
             "error": status.canonical_reason().unwrap_or("Unknown"),
-            "message": message,
+            "message": self.message(),
         }));
 
         (status, body).into_response()
@@ -59,6 +75,7 @@ impl From<DbError> for ApiError {
                 ApiError::Internal(format!("Database connection error: {}", msg))
             }
             DbError::Sqlx(err) => ApiError::Internal(format!("Database error: {}", err)),
+            DbError::Migration(msg) => ApiError::Internal(format!("Migration error: {}", msg)),
         }
     }
 }
@@ -4,17 +4,24 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use super::error::{ApiError, ApiResult};
 use super::state::AppState;
+use ontology_core::db::repository::batch::MAX_BATCH_OPERATIONS;
+use ontology_core::db::repository::{
+    ListQuery, OperationOutcome, RepositoryError, SortOrder, SymbolOperation,
+};
+use ontology_core::domain::SearchHit;
 use ontology_core::domain::Symbol;
 use ontology_core::domain::SymbolSet;
-
-#[derive(Serialize)]
-pub struct SymbolsResponse {
-    pub symbols: Vec<Symbol>,
-    pub total_count: usize,
-}
+use ontology_core::domain::{Archetype, ArchetypeNode};
+use ontology_core::domain::{InterpretationLayer, InterpretationLevel};
+use ontology_core::domain::{RelationDirection, RelationType, SymbolRelation};
+use ontology_core::extraction::{extract_symbols_from_text, ExtractedSymbol};
+use ontology_core::graph::{
+    find_path, related_network, NetworkQuery, PathAlgorithm, PathQuery, SymbolNetwork, SymbolPath,
+};
 
 #[derive(Deserialize, Default)]
 pub struct ListSymbolsQuery {
@@ -22,12 +29,19 @@ pub struct ListSymbolsQuery {
     pub query: Option<String>,
     #[serde(default = "default_limit")]
     pub limit: usize,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    pub sort: Option<SortOrder>,
 }
 
 fn default_limit() -> usize {
     50
 }
 
+/// The most results one page holds; larger limits are lowered to it, and the
+/// rest of a listing is read by following `next_cursor`.
+const MAX_LIMIT: usize = 500;
+
 #[derive(Serialize)]
 pub struct CategoriesResponse {
     pub categories: Vec<String>,
@@ -35,14 +49,7 @@ pub struct CategoriesResponse {
 }
 
 pub async fn get_categories(State(state): State<AppState>) -> ApiResult<Json<CategoriesResponse>> {
-    let symbols = state.symbol_repository.list_symbols(None).await?;
-
-    let categories: Vec<String> = symbols
-        .iter()
-        .map(|s| s.category.clone())
-        .collect::<std::collections::HashSet<_>>()
-        .into_iter()
-        .collect();
+    let categories = state.symbol_repository.list_categories().await?;
 
     Ok(Json(CategoriesResponse {
         total_count: categories.len(),
@@ -52,8 +59,62 @@ pub async fn get_categories(State(state): State<AppState>) -> ApiResult<Json<Cat
 
 #[derive(Serialize)]
 pub struct DomainSymbolsResponse {
-    pub symbols: Vec<Symbol>,
+    pub symbols: Vec<SymbolEntry>,
     pub total_count: usize,
+    /// Pass as `cursor` to get the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Symbols with names close to a query that found nothing.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub did_you_mean: Vec<Suggestion>,
+}
+
+/// A symbol whose name is close to a possibly misspelled query.
+#[derive(Serialize)]
+pub struct Suggestion {
+    pub id: String,
+    pub name: String,
+    pub score: f32,
+}
+
+impl From<SearchHit> for Suggestion {
+    fn from(hit: SearchHit) -> Self {
+        Suggestion {
+            id: hit.symbol.id,
+            name: hit.symbol.name,
+            score: hit.score,
+        }
+    }
+}
+
+/// How many "did you mean" suggestions an empty search returns.
+const SUGGESTION_LIMIT: usize = 5;
+
+/// A listed symbol, with its relevance score when the list came from a search.
+#[derive(Serialize)]
+pub struct SymbolEntry {
+    #[serde(flatten)]
+    pub symbol: Symbol,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+}
+
+impl From<Symbol> for SymbolEntry {
+    fn from(symbol: Symbol) -> Self {
+        SymbolEntry {
+            symbol,
+            score: None,
+        }
+    }
+}
+
+impl From<SearchHit> for SymbolEntry {
+    fn from(hit: SearchHit) -> Self {
+        SymbolEntry {
+            symbol: hit.symbol,
+            score: Some(hit.score),
+        }
+    }
 }
 
 pub async fn repo_list_symbols(
@@ -74,21 +135,53 @@ pub async fn repo_list_symbols(
         }
     }
 
-    let symbols = match (params.category.as_deref(), params.query.as_deref()) {
-        (_, Some(query)) => state.symbol_repository.search_symbols(query).await?,
-        (Some(category), None) => state.symbol_repository.list_symbols(Some(category)).await?,
-        (None, None) => state.symbol_repository.list_symbols(None).await?,
+    let page = ListQuery {
+        category: params.category,
+        limit: Some(params.limit.min(MAX_LIMIT)),
+        cursor: params.cursor,
+        sort: params.sort,
     };
 
-    let total_count = symbols.len();
-    let symbols = symbols.into_iter().take(params.limit).collect();
+    // Search results come most relevant first, each with its score
+    let symbols = match params.query.as_deref() {
+        Some(query) => state
+            .symbol_repository
+            .search_symbols(query, &page)
+            .await?
+            .map(SymbolEntry::from),
+        None => state
+            .symbol_repository
+            .list_symbols(&page)
+            .await?
+            .map(SymbolEntry::from),
+    };
+
+    let did_you_mean = match params.query.as_deref() {
+        Some(query) if symbols.total_count == 0 => suggestions(&state, query).await?,
+        _ => Vec::new(),
+    };
 
     Ok(Json(DomainSymbolsResponse {
-        symbols,
-        total_count,
+        symbols: symbols.items,
+        total_count: symbols.total_count,
+        next_cursor: symbols.next_cursor,
+        did_you_mean,
     }))
 }
 
+/// Symbols named like `query`, or none if the backend cannot fuzzy match.
+async fn suggestions(state: &AppState, query: &str) -> ApiResult<Vec<Suggestion>> {
+    match state
+        .symbol_repository
+        .fuzzy_search_symbols(query, SUGGESTION_LIMIT)
+        .await
+    {
+        Ok(hits) => Ok(hits.into_iter().map(Into::into).collect()),
+        Err(RepositoryError::NotImplemented(_)) => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
 pub async fn repo_get_symbol(
     Path(id): Path<String>,
     State(state): State<AppState>,
@@ -103,10 +196,58 @@ pub async fn repo_get_symbol(
     Ok(Json(symbol))
 }
 
+#[derive(Deserialize, Default)]
+pub struct InterpretationsQuery {
+    pub level: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct InterpretationsResponse {
+    pub symbol_id: String,
+    pub interpretations: HashMap<String, InterpretationLayer>,
+    pub total_count: usize,
+}
+
+pub async fn repo_get_interpretations(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Query(params): Query<InterpretationsQuery>,
+) -> ApiResult<Json<InterpretationsResponse>> {
+    if id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    let level = match params.level.as_deref() {
+        Some(level) => Some(
+            level
+                .parse::<InterpretationLevel>()
+                .map_err(ApiError::BadRequest)?,
+        ),
+        None => None,
+    };
+
+    let symbol = state.symbol_repository.get_symbol(&id).await?;
+
+    let interpretations: HashMap<String, InterpretationLayer> = symbol
+        .interpretations
+        .into_iter()
+        .filter(|(_, layer)| level.is_none_or(|level| layer.level == level))
+        .collect();
+
+    Ok(Json(InterpretationsResponse {
+        symbol_id: symbol.id,
+        total_count: interpretations.len(),
+        interpretations,
+    }))
+}
+
 pub async fn repo_create_symbol(
     State(state): State<AppState>,
-    Json(symbol): Json<Symbol>,
+    Json(mut symbol): Json<Symbol>,
 ) -> ApiResult<Json<Symbol>> {
+    symbol.normalize();
     if symbol.id.trim().is_empty() {
         return Err(ApiError::BadRequest(
             "Symbol ID cannot be empty".to_string(),
@@ -125,9 +266,10 @@ pub async fn repo_create_symbol(
 pub async fn repo_update_symbol(
     Path(id): Path<String>,
     State(state): State<AppState>,
-    Json(symbol): Json<Symbol>,
+    Json(mut symbol): Json<Symbol>,
 ) -> ApiResult<Json<Symbol>> {
-    if id != symbol.id {
+    symbol.normalize();
+    if id.trim() != symbol.id {
         return Err(ApiError::BadRequest(
             "Symbol ID in path does not match ID in body".to_string(),
         ));
@@ -157,10 +299,116 @@ pub async fn repo_delete_symbol(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[derive(Deserialize)]
+pub struct SymbolBatchRequest {
+    pub operations: Vec<SymbolOperation>,
+    /// Apply every operation or, if one fails, none of them
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+#[derive(Serialize)]
+pub struct SymbolBatchResponse {
+    pub results: Vec<SymbolBatchItem>,
+    /// False when an atomic batch was rolled back
+    pub committed: bool,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+#[derive(Serialize)]
+pub struct SymbolBatchItem {
+    pub index: usize,
+    pub op: &'static str,
+    pub id: String,
+    /// The status the operation would have had on its own: 201, 200 or 204 on
+    /// success, and 424 when another operation of an atomic batch failed.
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<Symbol>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub async fn batch_symbols(
+    State(state): State<AppState>,
+    Json(mut request): Json<SymbolBatchRequest>,
+) -> ApiResult<Json<SymbolBatchResponse>> {
+    if request.operations.len() > MAX_BATCH_OPERATIONS {
+        return Err(ApiError::BadRequest(format!(
+            "A batch holds at most {} operations",
+            MAX_BATCH_OPERATIONS
+        )));
+    }
+
+    for operation in &mut request.operations {
+        operation.normalize();
+    }
+    let targets: Vec<(&'static str, String)> = request
+        .operations
+        .iter()
+        .map(|operation| (operation.name(), operation.symbol_id().to_string()))
+        .collect();
+
+    let result = state
+        .symbol_repository
+        .apply_batch(request.operations, request.atomic)
+        .await?;
+    let (committed, succeeded, failed) = (result.committed, result.succeeded(), result.failed());
+    let failed_index = result
+        .outcomes
+        .iter()
+        .position(|outcome| matches!(outcome, OperationOutcome::Failed(_)));
+
+    let results = result
+        .outcomes
+        .into_iter()
+        .zip(targets)
+        .enumerate()
+        .map(|(index, (outcome, (op, id)))| {
+            let (status, symbol, error) = match outcome {
+                OperationOutcome::Created(symbol) => (StatusCode::CREATED, Some(symbol), None),
+                OperationOutcome::Updated(symbol) => (StatusCode::OK, Some(symbol), None),
+                OperationOutcome::Deleted => (StatusCode::NO_CONTENT, None, None),
+                OperationOutcome::Failed(error) => {
+                    let error = ApiError::from(error);
+                    (error.status(), None, Some(error.message().to_string()))
+                }
+                OperationOutcome::Aborted => (
+                    StatusCode::FAILED_DEPENDENCY,
+                    None,
+                    Some(format!(
+                        "Not applied: operation {} of the atomic batch failed",
+                        failed_index.unwrap_or_default()
+                    )),
+                ),
+            };
+            SymbolBatchItem {
+                index,
+                op,
+                id,
+                status: status.as_u16(),
+                symbol,
+                error,
+            }
+        })
+        .collect();
+
+    Ok(Json(SymbolBatchResponse {
+        results,
+        committed,
+        succeeded,
+        failed,
+    }))
+}
+
 #[derive(Serialize)]
 pub struct SymbolSetsResponse {
     pub symbol_sets: Vec<SymbolSet>,
     pub total_count: usize,
+    /// Pass as `cursor` to get the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Deserialize, Default)]
@@ -168,6 +416,9 @@ pub struct ListSymbolSetsQuery {
     pub category: Option<String>,
     #[serde(default = "default_limit")]
     pub limit: usize,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    pub sort: Option<SortOrder>,
 }
 
 pub async fn list_symbol_sets(
@@ -180,22 +431,18 @@ pub async fn list_symbol_sets(
         }
     }
 
-    let symbol_sets = match params.category.as_deref() {
-        Some(category) => {
-            state
-                .symbol_set_repository
-                .list_symbol_sets(Some(category))
-                .await?
-        }
-        None => state.symbol_set_repository.list_symbol_sets(None).await?,
+    let page = ListQuery {
+        category: params.category,
+        limit: Some(params.limit.min(MAX_LIMIT)),
+        cursor: params.cursor,
+        sort: params.sort,
     };
-
-    let total_count = symbol_sets.len();
-    let symbol_sets = symbol_sets.into_iter().take(params.limit).collect();
+    let symbol_sets = state.symbol_set_repository.list_symbol_sets(&page).await?;
 
     Ok(Json(SymbolSetsResponse {
-        symbol_sets,
-        total_count,
+        symbol_sets: symbol_sets.items,
+        total_count: symbol_sets.total_count,
+        next_cursor: symbol_sets.next_cursor,
     }))
 }
 
@@ -216,8 +463,12 @@ pub async fn get_symbol_set(
 #[derive(Deserialize)]
 pub struct SearchSymbolSetsQuery {
     pub query: String,
+    pub category: Option<String>,
     #[serde(default = "default_limit")]
     pub limit: usize,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    pub sort: Option<SortOrder>,
 }
 
 pub async fn search_symbol_sets(
@@ -230,17 +481,21 @@ pub async fn search_symbol_sets(
         ));
     }
 
+    let page = ListQuery {
+        category: params.category,
+        limit: Some(params.limit.min(MAX_LIMIT)),
+        cursor: params.cursor,
+        sort: params.sort,
+    };
     let symbol_sets = state
         .symbol_set_repository
-        .search_symbol_sets(&params.query)
+        .search_symbol_sets(&params.query, &page)
         .await?;
 
-    let total_count = symbol_sets.len();
-    let symbol_sets = symbol_sets.into_iter().take(params.limit).collect();
-
     Ok(Json(SymbolSetsResponse {
-        symbol_sets,
-        total_count,
+        symbol_sets: symbol_sets.items,
+        total_count: symbol_sets.total_count,
+        next_cursor: symbol_sets.next_cursor,
     }))
 }
 
@@ -304,9 +559,75 @@ pub async fn delete_symbol_set(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[derive(Deserialize)]
+pub struct AddMemberRequest {
+    pub symbol_id: String,
+}
+
+pub async fn add_symbol_set_member(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Json(request): Json<AddMemberRequest>,
+) -> Result<StatusCode, ApiError> {
+    if id.trim().is_empty() || request.symbol_id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol set ID and symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    state
+        .symbol_set_repository
+        .add_member(&id, &request.symbol_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn remove_symbol_set_member(
+    Path((id, symbol_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, ApiError> {
+    if id.trim().is_empty() || symbol_id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol set ID and symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    state
+        .symbol_set_repository
+        .remove_member(&id, &symbol_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_symbol_symbol_sets(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<SymbolSetsResponse>> {
+    if id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    let symbol = state.symbol_repository.get_symbol(&id).await?;
+    let symbol_sets = state
+        .symbol_set_repository
+        .list_sets_for_symbol(&symbol.id)
+        .await?;
+
+    Ok(Json(SymbolSetsResponse {
+        total_count: symbol_sets.len(),
+        symbol_sets,
+        next_cursor: None,
+    }))
+}
+
 #[derive(Deserialize)]
 pub struct AddRelatedSymbolRequest {
     pub related_symbol_id: String,
+    #[serde(default)]
+    pub relation_type: RelationType,
+    pub weight: Option<f64>,
 }
 
 pub async fn add_related_symbol(
@@ -325,15 +646,445 @@ pub async fn add_related_symbol(
         ));
     }
 
-    let mut symbol = state.symbol_repository.get_symbol(&id).await?;
-
-    let _ = state
+    // Either end may be given by alias; relations are stored between IDs
+    let symbol = state.symbol_repository.get_symbol(&id).await?;
+    let related = state
         .symbol_repository
         .get_symbol(&request.related_symbol_id)
         .await?;
 
-    symbol.related_symbols.push(request.related_symbol_id);
+    let mut relation = SymbolRelation::new(&symbol.id, &related.id, request.relation_type);
+    relation.weight = request.weight;
 
-    let updated_symbol = state.symbol_repository.update_symbol(symbol).await?;
+    state.symbol_repository.add_relation(relation).await?;
+
+    let updated_symbol = state.symbol_repository.get_symbol(&symbol.id).await?;
     Ok(Json(updated_symbol))
 }
+
+#[derive(Deserialize, Default)]
+pub struct ListRelationsQuery {
+    pub direction: Option<String>,
+    pub relation_type: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RelationsResponse {
+    pub relations: Vec<SymbolRelation>,
+    pub total_count: usize,
+}
+
+pub async fn list_symbol_relations(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Query(params): Query<ListRelationsQuery>,
+) -> ApiResult<Json<RelationsResponse>> {
+    if id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    let direction = match params.direction.as_deref() {
+        Some(direction) => direction
+            .parse::<RelationDirection>()
+            .map_err(ApiError::BadRequest)?,
+        None => RelationDirection::Both,
+    };
+
+    let relation_type = match params.relation_type.as_deref() {
+        Some(relation_type) => Some(
+            relation_type
+                .parse::<RelationType>()
+                .map_err(ApiError::BadRequest)?,
+        ),
+        None => None,
+    };
+
+    let symbol = state.symbol_repository.get_symbol(&id).await?;
+
+    let relations: Vec<SymbolRelation> = state
+        .symbol_repository
+        .list_relations(&symbol.id, direction)
+        .await?
+        .into_iter()
+        .filter(|r| relation_type.is_none_or(|t| r.relation_type == t))
+        .collect();
+
+    Ok(Json(RelationsResponse {
+        total_count: relations.len(),
+        relations,
+    }))
+}
+
+#[derive(Deserialize, Default)]
+pub struct RemoveRelationQuery {
+    #[serde(default)]
+    pub relation_type: RelationType,
+}
+
+pub async fn remove_symbol_relation(
+    Path((id, target_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+    Query(params): Query<RemoveRelationQuery>,
+) -> Result<StatusCode, ApiError> {
+    if id.trim().is_empty() || target_id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    state
+        .symbol_repository
+        .remove_relation(&id, &target_id, params.relation_type)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Parses a comma-separated list of relation types; an empty list means no filter.
+fn parse_relation_types(relation_types: Option<&str>) -> ApiResult<Option<Vec<RelationType>>> {
+    let relation_types = relation_types
+        .unwrap_or_default()
+        .split(',')
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| t.parse::<RelationType>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(ApiError::BadRequest)?;
+
+    Ok((!relation_types.is_empty()).then_some(relation_types))
+}
+
+#[derive(Deserialize, Default)]
+pub struct NetworkQueryParams {
+    pub depth: Option<usize>,
+    /// Comma-separated list of relation types to follow
+    pub relation_types: Option<String>,
+    pub max_nodes: Option<usize>,
+    pub direction: Option<String>,
+}
+
+pub async fn get_symbol_network(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Query(params): Query<NetworkQueryParams>,
+) -> ApiResult<Json<SymbolNetwork>> {
+    if id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    let mut query = NetworkQuery::default();
+    if let Some(depth) = params.depth {
+        if depth == 0 {
+            return Err(ApiError::BadRequest("Depth must be at least 1".to_string()));
+        }
+        query = query.with_depth(depth);
+    }
+    if let Some(max_nodes) = params.max_nodes {
+        query = query.with_max_nodes(max_nodes);
+    }
+    if let Some(direction) = params.direction.as_deref() {
+        query = query.with_direction(
+            direction
+                .parse::<RelationDirection>()
+                .map_err(ApiError::BadRequest)?,
+        );
+    }
+    if let Some(relation_types) = parse_relation_types(params.relation_types.as_deref())? {
+        query = query.with_relation_types(relation_types);
+    }
+
+    let network = related_network(state.symbol_repository.as_ref(), &id, &query).await?;
+    Ok(Json(network))
+}
+
+#[derive(Deserialize, Default)]
+pub struct PathQueryParams {
+    pub max_depth: Option<usize>,
+    /// Comma-separated list of relation types to cross
+    pub relation_types: Option<String>,
+    pub direction: Option<String>,
+    pub algorithm: Option<String>,
+}
+
+pub async fn find_symbol_path(
+    Path((id, target_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+    Query(params): Query<PathQueryParams>,
+) -> ApiResult<Json<SymbolPath>> {
+    if id.trim().is_empty() || target_id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    let mut query = PathQuery::default();
+    if let Some(max_depth) = params.max_depth {
+        query = query.with_max_depth(max_depth);
+    }
+    if let Some(direction) = params.direction.as_deref() {
+        query = query.with_direction(
+            direction
+                .parse::<RelationDirection>()
+                .map_err(ApiError::BadRequest)?,
+        );
+    }
+    if let Some(algorithm) = params.algorithm.as_deref() {
+        query = query.with_algorithm(
+            algorithm
+                .parse::<PathAlgorithm>()
+                .map_err(ApiError::BadRequest)?,
+        );
+    }
+    if let Some(relation_types) = parse_relation_types(params.relation_types.as_deref())? {
+        query = query.with_relation_types(relation_types);
+    }
+
+    match find_path(state.symbol_repository.as_ref(), &id, &target_id, &query).await? {
+        Some(path) => Ok(Json(path)),
+        None => Err(ApiError::NotFound(format!(
+            "No path between {} and {}",
+            id, target_id
+        ))),
+    }
+}
+
+#[derive(Serialize)]
+pub struct ArchetypesResponse {
+    pub archetypes: Vec<Archetype>,
+    pub total_count: usize,
+}
+
+pub async fn list_archetypes(State(state): State<AppState>) -> ApiResult<Json<ArchetypesResponse>> {
+    let archetypes = state.archetype_repository.list_archetypes().await?;
+
+    Ok(Json(ArchetypesResponse {
+        total_count: archetypes.len(),
+        archetypes,
+    }))
+}
+
+pub async fn get_archetype(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<Archetype>> {
+    if id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Archetype ID cannot be empty".to_string(),
+        ));
+    }
+
+    let archetype = state.archetype_repository.get_archetype(&id).await?;
+    Ok(Json(archetype))
+}
+
+pub async fn create_archetype(
+    State(state): State<AppState>,
+    Json(archetype): Json<Archetype>,
+) -> ApiResult<Json<Archetype>> {
+    archetype.validate().map_err(ApiError::BadRequest)?;
+
+    let created = state
+        .archetype_repository
+        .create_archetype(archetype)
+        .await?;
+    Ok(Json(created))
+}
+
+pub async fn update_archetype(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Json(archetype): Json<Archetype>,
+) -> ApiResult<Json<Archetype>> {
+    if id != archetype.id {
+        return Err(ApiError::BadRequest(
+            "Archetype ID in path does not match ID in body".to_string(),
+        ));
+    }
+    archetype.validate().map_err(ApiError::BadRequest)?;
+
+    let updated = state
+        .archetype_repository
+        .update_archetype(archetype)
+        .await?;
+    Ok(Json(updated))
+}
+
+pub async fn delete_archetype(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, ApiError> {
+    if id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Archetype ID cannot be empty".to_string(),
+        ));
+    }
+
+    state.archetype_repository.delete_archetype(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize, Default)]
+pub struct ArchetypeHierarchyQuery {
+    pub root: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ArchetypeHierarchyResponse {
+    pub roots: Vec<ArchetypeNode>,
+    pub total_count: usize,
+}
+
+pub async fn get_archetype_hierarchy(
+    State(state): State<AppState>,
+    Query(params): Query<ArchetypeHierarchyQuery>,
+) -> ApiResult<Json<ArchetypeHierarchyResponse>> {
+    let root = params
+        .root
+        .as_deref()
+        .filter(|root| !root.trim().is_empty());
+    let roots = state.archetype_repository.get_hierarchy(root).await?;
+
+    Ok(Json(ArchetypeHierarchyResponse {
+        total_count: roots.iter().map(ArchetypeNode::size).sum(),
+        roots,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct ArchetypeSymbolsQuery {
+    #[serde(default = "default_include_descendants")]
+    pub include_descendants: bool,
+    pub category: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    pub sort: Option<SortOrder>,
+}
+
+fn default_include_descendants() -> bool {
+    true
+}
+
+pub async fn get_symbols_by_archetype(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Query(params): Query<ArchetypeSymbolsQuery>,
+) -> ApiResult<Json<DomainSymbolsResponse>> {
+    if id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Archetype ID cannot be empty".to_string(),
+        ));
+    }
+
+    let page = ListQuery {
+        category: params.category,
+        limit: Some(params.limit.min(MAX_LIMIT)),
+        cursor: params.cursor,
+        sort: params.sort,
+    };
+    let symbols = state
+        .archetype_repository
+        .list_symbols_by_archetype(&id, params.include_descendants, &page)
+        .await?
+        .map(SymbolEntry::from);
+
+    Ok(Json(DomainSymbolsResponse {
+        symbols: symbols.items,
+        total_count: symbols.total_count,
+        next_cursor: symbols.next_cursor,
+        did_you_mean: Vec::new(),
+    }))
+}
+
+pub async fn list_symbol_archetypes(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<ArchetypesResponse>> {
+    if id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID cannot be empty".to_string(),
+        ));
+    }
+
+    let symbol = state.symbol_repository.get_symbol(&id).await?;
+    let archetypes = state
+        .archetype_repository
+        .list_archetypes_for_symbol(&symbol.id)
+        .await?;
+
+    Ok(Json(ArchetypesResponse {
+        total_count: archetypes.len(),
+        archetypes,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct LinkArchetypeRequest {
+    pub archetype_id: String,
+}
+
+pub async fn link_symbol_archetype(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Json(request): Json<LinkArchetypeRequest>,
+) -> Result<StatusCode, ApiError> {
+    if id.trim().is_empty() || request.archetype_id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID and archetype ID cannot be empty".to_string(),
+        ));
+    }
+
+    state
+        .archetype_repository
+        .link_symbol(&id, &request.archetype_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn unlink_symbol_archetype(
+    Path((id, archetype_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, ApiError> {
+    if id.trim().is_empty() || archetype_id.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Symbol ID and archetype ID cannot be empty".to_string(),
+        ));
+    }
+
+    state
+        .archetype_repository
+        .unlink_symbol(&id, &archetype_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+pub struct AnalyzeRequest {
+    pub text: String,
+}
+
+#[derive(Serialize)]
+pub struct AnalyzeResponse {
+    pub symbols: Vec<ExtractedSymbol>,
+    pub total_count: usize,
+}
+
+pub async fn analyze_text(
+    State(state): State<AppState>,
+    Json(request): Json<AnalyzeRequest>,
+) -> ApiResult<Json<AnalyzeResponse>> {
+    if request.text.trim().is_empty() {
+        return Err(ApiError::BadRequest("Text cannot be empty".to_string()));
+    }
+
+    let symbols =
+        extract_symbols_from_text(state.symbol_repository.as_ref(), &request.text).await?;
+
+    Ok(Json(AnalyzeResponse {
+        total_count: symbols.len(),
+        symbols,
+    }))
+}
@@ -2,35 +2,74 @@ use axum::{
     Router,
     routing::{delete, get, post},
 };
-use sqlx::PgPool;
+use ontology_core::db::repository::RepositoryFactory;
+use std::sync::Arc;
 
 use super::{handlers::*, state::AppState};
 
-pub fn router(db_pool: PgPool) -> Router {
-    let app_state = AppState::new(db_pool.clone());
+pub fn router(repo_factory: Arc<dyn RepositoryFactory>) -> Router {
+    let app_state = AppState::new(repo_factory.as_ref());
 
-    let repository_api = Router::new()
+    Router::new()
         .route("/symbols", get(repo_list_symbols))
         .route("/symbols/{id}", get(repo_get_symbol))
         .route("/symbols/{id}", post(repo_update_symbol))
         .route("/symbols", post(repo_create_symbol))
+        .route("/symbols:batch", post(batch_symbols))
         .route("/symbols/{id}", delete(repo_delete_symbol))
         .route("/symbols/{id}/related", post(add_related_symbol))
+        .route("/symbols/{id}/relations", get(list_symbol_relations))
+        .route("/symbols/{id}/network", get(get_symbol_network))
+        .route("/symbols/{id}/path/{target_id}", get(find_symbol_path))
+        .route(
+            "/symbols/{id}/relations/{target_id}",
+            delete(remove_symbol_relation),
+        )
+        .route(
+            "/symbols/{id}/interpretations",
+            get(repo_get_interpretations),
+        )
+        .route("/symbols/{id}/symbol-sets", get(list_symbol_symbol_sets))
+        .route("/symbols/{id}/archetypes", get(list_symbol_archetypes))
+        .route("/symbols/{id}/archetypes", post(link_symbol_archetype))
+        .route(
+            "/symbols/{id}/archetypes/{archetype_id}",
+            delete(unlink_symbol_archetype),
+        )
         .route("/categories", get(get_categories))
+        .route("/analyze", post(analyze_text))
         .route("/symbol-sets", get(list_symbol_sets))
         .route("/symbol-sets/{id}", get(get_symbol_set))
         .route("/symbol-sets/search", get(search_symbol_sets))
         .route("/symbol-sets", post(create_symbol_set))
         .route("/symbol-sets/{id}", post(update_symbol_set))
         .route("/symbol-sets/{id}", delete(delete_symbol_set))
-        .with_state(app_state);
-
-    repository_api
+        .route("/symbol-sets/{id}/members", post(add_symbol_set_member))
+        .route(
+            "/symbol-sets/{id}/members/{symbol_id}",
+            delete(remove_symbol_set_member),
+        )
+        .route("/archetypes", get(list_archetypes))
+        .route("/archetypes", post(create_archetype))
+        .route("/archetypes/hierarchy", get(get_archetype_hierarchy))
+        .route("/archetypes/{id}", get(get_archetype))
+        .route("/archetypes/{id}", post(update_archetype))
+        .route("/archetypes/{id}", delete(delete_archetype))
+        .route("/archetypes/{id}/symbols", get(get_symbols_by_archetype))
+        .with_state(app_state)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ontology_core::db::DatabasePool;
+    use ontology_core::db::repository::{
+        HttpRepositoryFactory, InMemoryRepositoryFactory, ListQuery, PgRepositoryFactory,
+        RepositoryFactory, SymbolOperation, conformance,
+    };
+    use ontology_core::domain::Symbol;
+    use ontology_core::integrity::IntegrityMode;
+    use sqlx::PgPool;
 
     #[tokio::test]
     async fn test_router_creation() {
@@ -39,7 +78,140 @@ mod tests {
             .expect("Failed to create mock pool");
 
         // This just tests that the router builds successfully
-        let _router = router(pool);
+        let _router = router(Arc::new(PgRepositoryFactory::new(pool)));
         // If we get here, the test passed
     }
+
+    #[tokio::test]
+    async fn test_router_creation_with_sqlite() {
+        let pool = DatabasePool::connect("sqlite::memory:")
+            .await
+            .expect("Failed to open in-memory SQLite database");
+        pool.init().await.expect("Failed to create SQLite schema");
+
+        let _router = router(pool.repository_factory());
+    }
+
+    #[test]
+    fn test_router_creation_with_memory() {
+        let pool = DatabasePool::memory(None).expect("Failed to create in-memory store");
+
+        let _router = router(pool.repository_factory());
+    }
+
+    /// Serves this router over `repositories` and returns HTTP repositories pointed at it.
+    async fn serve(repositories: InMemoryRepositoryFactory) -> HttpRepositoryFactory {
+        let app = Router::new().nest("/api/v2", router(Arc::new(repositories)));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        HttpRepositoryFactory::new(&format!("http://{}", addr)).unwrap()
+    }
+
+    /// The HTTP repositories must behave like a local backend when pointed at this router.
+    #[tokio::test]
+    async fn test_http_repositories_conformance() {
+        let factory = serve(InMemoryRepositoryFactory::new()).await;
+
+        // Fuzzy search has no endpoint, so it is the one check left out
+        conformance::symbol_crud(&factory).await;
+        conformance::symbol_conflicts_and_not_found(&factory).await;
+        conformance::symbol_search(&factory).await;
+        conformance::symbol_aliases(&factory).await;
+        conformance::symbol_embeddings(&factory).await;
+        conformance::symbol_category_filter(&factory).await;
+        conformance::symbol_pagination(&factory).await;
+        conformance::symbol_delete_cascades(&factory).await;
+        conformance::symbol_related_edges(&factory).await;
+        conformance::symbol_upsert_many(&factory).await;
+        conformance::symbol_batches(&factory).await;
+        conformance::symbol_set_crud(&factory).await;
+        conformance::symbol_set_conflicts_and_not_found(&factory).await;
+        conformance::symbol_set_search_and_category_filter(&factory).await;
+        conformance::symbol_set_membership(&factory).await;
+        conformance::symbol_set_member_links(&factory).await;
+        conformance::symbol_set_member_order(&factory).await;
+        conformance::symbol_set_pagination(&factory).await;
+    }
+
+    #[tokio::test]
+    async fn test_listings_are_capped_and_followed() {
+        let factory = serve(InMemoryRepositoryFactory::new()).await;
+        let repository = factory.create_symbol_repository();
+        let symbols = (0..600)
+            .map(|i| {
+                let id = format!("paged-{:03}", i);
+                Symbol::new(id.clone(), id, "paged".to_string(), String::new())
+            })
+            .collect();
+        repository.upsert_many(symbols).await.unwrap();
+
+        let capped = repository
+            .list_symbols(&ListQuery::new().limit(100_000))
+            .await
+            .unwrap();
+        assert_eq!(capped.items.len(), 500);
+        assert_eq!(capped.total_count, 600);
+        assert!(capped.next_cursor.is_some());
+
+        let all = repository.list_symbols(&ListQuery::new()).await.unwrap();
+        assert_eq!(all.items.len(), 600);
+        assert_eq!(all.items[599].id, "paged-599");
+        assert!(all.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_symbol_writes_are_trimmed() {
+        let factory = serve(InMemoryRepositoryFactory::new()).await;
+        let repository = factory.create_symbol_repository();
+
+        let padded = |id: &str, name: &str| {
+            Symbol::new(
+                id.to_string(),
+                name.to_string(),
+                " animal ".to_string(),
+                " Cunning ".to_string(),
+            )
+        };
+        let created = repository
+            .create_symbol(padded(" fox ", " Fox ").with_aliases(vec![" vixen"]))
+            .await
+            .unwrap();
+        assert_eq!((created.id.as_str(), created.name.as_str()), ("fox", "Fox"));
+        assert_eq!(created.category, "animal");
+        assert_eq!(created.description, "Cunning");
+        assert_eq!(created.aliases, vec!["vixen"]);
+
+        // The ID in the path is trimmed too, so it still matches the body's
+        let updated = repository
+            .update_symbol(padded(" fox", " Red fox\n"))
+            .await
+            .unwrap();
+        assert_eq!(updated.name, "Red fox");
+
+        let result = repository
+            .apply_batch(
+                vec![SymbolOperation::Create {
+                    symbol: padded("\towl", "Owl "),
+                }],
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.succeeded(), 1);
+        let owl = repository.get_symbol("owl").await.unwrap();
+        assert_eq!(
+            (owl.name.as_str(), owl.category.as_str()),
+            ("Owl", "animal")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_repositories_strict_integrity() {
+        let factory =
+            serve(InMemoryRepositoryFactory::new().with_integrity(IntegrityMode::Strict)).await;
+
+        conformance::strict_integrity(&factory).await;
+    }
 }
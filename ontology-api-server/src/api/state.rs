@@ -1,25 +1,26 @@
-use sqlx::PgPool;
 use std::sync::Arc;
 
 use ontology_core::db::repository::{
-    PgRepositoryFactory, RepositoryFactory, SymbolRepository, SymbolSetRepository,
+    ArchetypeRepository, RepositoryFactory, SymbolRepository, SymbolSetRepository,
 };
 
 #[derive(Clone)]
 pub struct AppState {
     pub symbol_repository: Arc<dyn SymbolRepository>,
     pub symbol_set_repository: Arc<dyn SymbolSetRepository>,
+    pub archetype_repository: Arc<dyn ArchetypeRepository>,
 }
 
 impl AppState {
-    pub fn new(db_pool: PgPool) -> Self {
-        let factory = PgRepositoryFactory::new(db_pool);
+    pub fn new(factory: &dyn RepositoryFactory) -> Self {
         let symbol_repository = factory.create_symbol_repository();
         let symbol_set_repository = factory.create_symbol_set_repository();
+        let archetype_repository = factory.create_archetype_repository();
 
         Self {
             symbol_repository,
             symbol_set_repository,
+            archetype_repository,
         }
     }
 }
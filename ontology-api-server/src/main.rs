@@ -2,6 +2,7 @@
 // Copyright (c) 2024 Symbol Ontology Contributors
 
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use axum::{routing::get, Router};
@@ -9,8 +10,8 @@ use clap::Parser;
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
-use ontology_core::db::pool::{create_pool, init_database};
-use ontology_core::db::repository::PgRepositoryFactory;
+use ontology_core::db::{DatabaseBackend, DatabasePool};
+use ontology_core::integrity::IntegrityMode;
 
 mod api;
 mod routes;
@@ -22,7 +23,7 @@ struct Args {
     #[arg(short, long, default_value_t = 8080)]
     port: u16,
 
-    /// Database URL
+    /// Database URL (postgres://... or sqlite://path/to/file.db)
     #[arg(
         long,
         env("DATABASE_URL"),
@@ -30,6 +31,18 @@ struct Args {
     )]
     database_url: String,
 
+    /// Storage backend (postgres, sqlite or memory); defaults to the database URL scheme
+    #[arg(long, env("BACKEND"))]
+    backend: Option<DatabaseBackend>,
+
+    /// JSON snapshot to seed the memory backend with
+    #[arg(long, env("SNAPSHOT_PATH"))]
+    snapshot: Option<PathBuf>,
+
+    /// Reject writes naming unknown symbols (strict) or log them (lenient)
+    #[arg(long, env("INTEGRITY_MODE"), default_value_t = IntegrityMode::Lenient)]
+    integrity: IntegrityMode,
+
     /// Log level
     #[arg(short, long, default_value = "info")]
     log_level: String,
@@ -54,23 +67,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::subscriber::set_global_default(subscriber)?;
 
     info!("Symbol Ontology API Server starting up...");
-    info!("Connecting to database: {}", args.database_url);
+    if args.backend != Some(DatabaseBackend::Memory) {
+        info!("Connecting to database: {}", args.database_url);
+    }
+
+    // Open the selected backend, falling back to the database URL scheme
+    let pool =
+        DatabasePool::open(args.backend, &args.database_url, args.snapshot.as_deref()).await?;
+    pool.init().await?;
+    info!("Using {} backend", pool.backend());
+
+    info!("Using {} integrity checks", args.integrity);
 
-    // Initialize database connection
-    let pool = create_pool(&args.database_url).await?;
-    init_database(&pool).await?;
+    let repo_factory = pool.repository_factory_with_integrity(args.integrity);
 
     // Create router with API routes (traditional REST API)
-    let api_router = api::routes::router(pool.clone());
+    let api_router = api::routes::router(Arc::clone(&repo_factory));
 
     // Create main router with both API routes
     let app = Router::new()
         .route("/", get(|| async { "Symbol Ontology API Server" }))
         .route("/health", get(|| async { "OK" }))
-        .nest(
-            "/api/v1",
-            routes::create_api_router(Arc::new(PgRepositoryFactory::new(pool.clone()))),
-        )
+        .nest("/api/v1", routes::create_api_router(repo_factory))
         .nest("/api/v2", api_router);
 
     // Run the server
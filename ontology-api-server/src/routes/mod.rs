@@ -7,7 +7,7 @@ use axum::Router;
 use ontology_core::db::repository::RepositoryFactory;
 use std::sync::Arc;
 
-pub fn create_api_router<F: RepositoryFactory + Send + Sync + 'static>(
+pub fn create_api_router<F: RepositoryFactory + Send + Sync + ?Sized + 'static>(
     repo_factory: Arc<F>,
 ) -> Router {
     Router::new().merge(symbols::router(repo_factory.clone()))
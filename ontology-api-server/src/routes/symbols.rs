@@ -8,7 +8,7 @@ use axum::{
     response::Json,
     routing::{delete, get, post},
 };
-use ontology_core::db::repository::{RepositoryError, RepositoryFactory};
+use ontology_core::db::repository::{ListQuery, RepositoryError, RepositoryFactory};
 use ontology_core::domain::Symbol;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -31,25 +31,31 @@ pub struct ErrorResponse {
     message: String,
 }
 
-pub fn router<F: RepositoryFactory + Send + Sync + 'static>(repo_factory: Arc<F>) -> Router {
+pub fn router<F: RepositoryFactory + Send + Sync + ?Sized + 'static>(
+    repo_factory: Arc<F>,
+) -> Router {
     Router::new()
         .route("/symbols", get(list_symbols::<F>))
-        .route("/symbols/:id", get(get_symbol::<F>))
+        .route("/symbols/{id}", get(get_symbol::<F>))
         .route("/symbols", post(create_symbol::<F>))
-        .route("/symbols/:id", delete(delete_symbol::<F>))
+        .route("/symbols/{id}", delete(delete_symbol::<F>))
         .with_state(repo_factory)
 }
 
-async fn list_symbols<F: RepositoryFactory + Send + Sync + 'static>(
+async fn list_symbols<F: RepositoryFactory + Send + Sync + ?Sized + 'static>(
     State(repo_factory): State<Arc<F>>,
     Query(params): Query<SymbolQuery>,
 ) -> Result<Json<ApiResponse<Vec<Symbol>>>, (StatusCode, Json<ErrorResponse>)> {
     let repo = repo_factory.create_symbol_repository();
 
     let symbols = if let Some(query) = params.query {
-        repo.search_symbols(&query).await
+        repo.search_symbols(&query, &ListQuery::new())
+            .await
+            .map(|hits| hits.items.into_iter().map(|hit| hit.symbol).collect())
     } else {
-        repo.list_symbols(params.category.as_deref()).await
+        repo.list_symbols(&ListQuery::new().category(params.category))
+            .await
+            .map(|page| page.items)
     };
 
     match symbols {
@@ -61,7 +67,7 @@ async fn list_symbols<F: RepositoryFactory + Send + Sync + 'static>(
     }
 }
 
-async fn get_symbol<F: RepositoryFactory + Send + Sync + 'static>(
+async fn get_symbol<F: RepositoryFactory + Send + Sync + ?Sized + 'static>(
     State(repo_factory): State<Arc<F>>,
     Path(id): Path<String>,
 ) -> Result<Json<ApiResponse<Symbol>>, (StatusCode, Json<ErrorResponse>)> {
@@ -76,7 +82,7 @@ async fn get_symbol<F: RepositoryFactory + Send + Sync + 'static>(
     }
 }
 
-async fn create_symbol<F: RepositoryFactory + Send + Sync + 'static>(
+async fn create_symbol<F: RepositoryFactory + Send + Sync + ?Sized + 'static>(
     State(repo_factory): State<Arc<F>>,
     Json(symbol): Json<Symbol>,
 ) -> Result<Json<ApiResponse<Symbol>>, (StatusCode, Json<ErrorResponse>)> {
@@ -91,7 +97,7 @@ async fn create_symbol<F: RepositoryFactory + Send + Sync + 'static>(
     }
 }
 
-async fn delete_symbol<F: RepositoryFactory + Send + Sync + 'static>(
+async fn delete_symbol<F: RepositoryFactory + Send + Sync + ?Sized + 'static>(
     State(repo_factory): State<Arc<F>>,
     Path(id): Path<String>,
 ) -> Result<Json<ApiResponse<String>>, (StatusCode, Json<ErrorResponse>)> {
@@ -1,18 +1,61 @@
-use std::sync::Arc;
+use std::process::Stdio;
+use std::time::Duration;
+
+use ontology_core::domain::Symbol;
+use tokio::process::Command;
+
+/// Starts the server on the memory backend and returns its API base URL; the
+/// server is stopped when the returned child is dropped.
+async fn start_server() -> (tokio::process::Child, String) {
+    // Bind and drop a listener to find a free port
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let child = Command::new(env!("CARGO_BIN_EXE_ontology-api-server"))
+        .args(["--backend", "memory", "--port", &port.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+
+    let base_url = format!("http://127.0.0.1:{}/api/v2", port);
+    for _ in 0..100 {
+        if reqwest::get(format!("{}/symbols", base_url)).await.is_ok() {
+            return (child, base_url);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("server did not start on port {}", port);
+}
 
 #[tokio::test]
 async fn test_symbols_route() {
-    // This is a placeholder test that will be expanded later
-    // For actual API tests, we'd use axum's test utilities
+    let (_server, base_url) = start_server().await;
+    let client = reqwest::Client::new();
 
-    // When implementing:
-    // let app = create_test_app().await;
-    // let response = app
-    //     .oneshot(Request::builder().uri("/symbols").body(Body::empty()).unwrap())
-    //     .await
-    //     .unwrap();
-    // assert_eq!(response.status(), StatusCode::OK);
+    let water = Symbol::new(
+        "water".to_string(),
+        "Water".to_string(),
+        "nature".to_string(),
+        "Emotion and the unconscious".to_string(),
+    );
+    let response = client
+        .post(format!("{}/symbols", base_url))
+        .json(&water)
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
 
-    // For now, just a trivial assertion to make tests compile
-    assert!(true);
+    let response = client
+        .get(format!("{}/symbols", base_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["symbols"][0]["id"], "water");
 }
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use sqlx::{PgPool, SqlitePool};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::db::migrations::Migrator;
+use crate::db::pool::{DbError, DbResult};
+use crate::db::repository::{
+    InMemoryRepositoryFactory, PgRepositoryFactory, RepositoryFactory, SqliteRepositoryFactory,
+};
+use crate::db::{pool, sqlite};
+use crate::integrity::IntegrityMode;
+
+/// The storage engine behind the repositories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    Postgres,
+    Sqlite,
+    /// Process-local store, optionally seeded from a JSON snapshot
+    Memory,
+}
+
+impl DatabaseBackend {
+    /// Picks the backend from the URL scheme (`postgres://`, `postgresql://` or `sqlite:`).
+    pub fn from_url(database_url: &str) -> DbResult<Self> {
+        let scheme = database_url
+            .split_once(':')
+            .map(|(scheme, _)| scheme.to_lowercase())
+            .unwrap_or_default();
+
+        match scheme.as_str() {
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "sqlite" => Ok(Self::Sqlite),
+            "memory" => Ok(Self::Memory),
+            _ => Err(DbError::Connection(format!(
+                "Unsupported database URL scheme '{}', expected postgres://, sqlite: or memory:",
+                scheme
+            ))),
+        }
+    }
+}
+
+impl FromStr for DatabaseBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "postgres" | "postgresql" | "pg" => Ok(Self::Postgres),
+            "sqlite" => Ok(Self::Sqlite),
+            "memory" | "in-memory" => Ok(Self::Memory),
+            _ => Err(format!("Unknown backend: {}", s)),
+        }
+    }
+}
+
+impl Display for DatabaseBackend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Postgres => write!(f, "postgres"),
+            Self::Sqlite => write!(f, "sqlite"),
+            Self::Memory => write!(f, "memory"),
+        }
+    }
+}
+
+/// A connection pool (or in-memory store) for the selected backend.
+#[derive(Debug, Clone)]
+pub enum DatabasePool {
+    Postgres(PgPool),
+    Sqlite(SqlitePool),
+    Memory(InMemoryRepositoryFactory),
+}
+
+impl DatabasePool {
+    pub async fn connect(database_url: &str) -> DbResult<Self> {
+        match DatabaseBackend::from_url(database_url)? {
+            DatabaseBackend::Postgres => Ok(Self::Postgres(pool::create_pool(database_url).await?)),
+            DatabaseBackend::Sqlite => Ok(Self::Sqlite(sqlite::create_pool(database_url).await?)),
+            DatabaseBackend::Memory => Self::memory(None),
+        }
+    }
+
+    /// An empty in-memory store, or one seeded from the snapshot at `snapshot`.
+    pub fn memory(snapshot: Option<&Path>) -> DbResult<Self> {
+        match snapshot {
+            Some(path) => Ok(Self::Memory(InMemoryRepositoryFactory::load(path)?)),
+            None => Ok(Self::Memory(InMemoryRepositoryFactory::new())),
+        }
+    }
+
+    /// Opens `backend` if one is given, otherwise whatever the URL scheme selects.
+    ///
+    /// The snapshot only applies to the memory backend.
+    pub async fn open(
+        backend: Option<DatabaseBackend>,
+        database_url: &str,
+        snapshot: Option<&Path>,
+    ) -> DbResult<Self> {
+        let backend = match backend {
+            Some(backend) => backend,
+            None => DatabaseBackend::from_url(database_url)?,
+        };
+
+        match backend {
+            DatabaseBackend::Memory => Self::memory(snapshot),
+            _ if DatabaseBackend::from_url(database_url)? != backend => Err(DbError::Connection(
+                format!("Database URL does not point at a {} database", backend),
+            )),
+            _ => Self::connect(database_url).await,
+        }
+    }
+
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            Self::Postgres(_) => DatabaseBackend::Postgres,
+            Self::Sqlite(_) => DatabaseBackend::Sqlite,
+            Self::Memory(_) => DatabaseBackend::Memory,
+        }
+    }
+
+    /// Applies any pending schema migrations.
+    ///
+    /// Fails without touching the database if it was migrated by a newer build.
+    pub async fn init(&self) -> DbResult<()> {
+        let migrator = Migrator::new(self);
+        let applied = migrator.up(None).await?;
+        if !applied.is_empty() {
+            info!(
+                "Database schema migrated to version {}",
+                migrator.latest_version()
+            );
+        }
+        Ok(())
+    }
+
+    pub fn repository_factory(&self) -> Arc<dyn RepositoryFactory> {
+        self.repository_factory_with_integrity(IntegrityMode::default())
+    }
+
+    /// Repositories that treat writes naming unknown symbols as `integrity` says.
+    pub fn repository_factory_with_integrity(
+        &self,
+        integrity: IntegrityMode,
+    ) -> Arc<dyn RepositoryFactory> {
+        match self {
+            Self::Postgres(pool) => {
+                Arc::new(PgRepositoryFactory::new(pool.clone()).with_integrity(integrity))
+            }
+            Self::Sqlite(pool) => {
+                Arc::new(SqliteRepositoryFactory::new(pool.clone()).with_integrity(integrity))
+            }
+            Self::Memory(factory) => Arc::new(factory.clone().with_integrity(integrity)),
+        }
+    }
+}
@@ -0,0 +1,527 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # In-Memory Backend
+ *
+ * Process-local storage shared by the `InMemory*Repository` types. It mirrors the
+ * behaviour of the SQL backends (ILIKE-style search, exact category filters,
+ * cascading deletes, set members resolved against the current symbols) so it can
+ * stand in for a database in demos, tests and offline use. A store can be seeded
+ * from a JSON [`Snapshot`].
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::db::pool::{DbError, DbResult};
+use crate::db::repository::interfaces::{RepositoryError, RepositoryResult};
+use crate::domain::archetypes::creates_cycle;
+use crate::domain::search;
+use crate::domain::{Archetype, RelationType, Symbol, SymbolRelation, SymbolSet};
+use crate::embedding;
+
+/// Case-insensitive `LIKE '%query%'`, honouring `%`, `_` and `\` escapes the way
+/// the SQL backends do.
+pub fn ilike_contains(text: &str, query: &str) -> bool {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let mut pattern = vec![Token::Any];
+    let mut chars = query.to_lowercase().chars().collect::<Vec<_>>().into_iter();
+    while let Some(c) = chars.next() {
+        pattern.push(match c {
+            '%' => Token::Any,
+            '_' => Token::One,
+            '\\' => Token::Char(chars.next().unwrap_or('\\')),
+            c => Token::Char(c),
+        });
+    }
+    pattern.push(Token::Any);
+
+    matches_pattern(&text, &pattern)
+}
+
+/// The words of a text search query. Backends without a full-text index match
+/// each as an [`ilike_contains`] pattern and require all of them, as
+/// PostgreSQL's `websearch_to_tsquery` does for plain words.
+pub fn search_words(query: &str) -> Vec<&str> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    if words.is_empty() {
+        vec![query]
+    } else {
+        words
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Token {
+    Any,
+    One,
+    Char(char),
+}
+
+fn matches_pattern(text: &[char], pattern: &[Token]) -> bool {
+    // Classic wildcard matching with backtracking to the last `%`
+    let (mut t, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(Token::Any) => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(Token::One) => {
+                t += 1;
+                p += 1;
+            }
+            Some(Token::Char(c)) if *c == text[t] => {
+                t += 1;
+                p += 1;
+            }
+            _ => match backtrack {
+                Some((any_p, any_t)) => {
+                    p = any_p + 1;
+                    t = any_t + 1;
+                    backtrack = Some((any_p, any_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|token| matches!(token, Token::Any))
+}
+
+/// A symbol set as stored in a snapshot, listing its members by ID.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotSymbolSet {
+    pub id: String,
+
+    pub name: String,
+
+    #[serde(default)]
+    pub category: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    #[serde(default)]
+    pub symbols: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolArchetypeLink {
+    pub symbol_id: String,
+
+    pub archetype_id: String,
+}
+
+/// Serialized contents of an in-memory store.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    #[serde(default)]
+    pub symbols: Vec<Symbol>,
+
+    #[serde(default)]
+    pub symbol_sets: Vec<SnapshotSymbolSet>,
+
+    #[serde(default)]
+    pub relations: Vec<SymbolRelation>,
+
+    #[serde(default)]
+    pub archetypes: Vec<Archetype>,
+
+    #[serde(default)]
+    pub symbol_archetypes: Vec<SymbolArchetypeLink>,
+}
+
+impl Snapshot {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> DbResult<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            DbError::Connection(format!("Failed to read snapshot {}: {}", path.display(), e))
+        })?;
+
+        Self::from_json(&json)
+            .map_err(|e| DbError::Connection(format!("Invalid snapshot {}: {}", path.display(), e)))
+    }
+}
+
+/// The tables of the in-memory backend, kept in insertion order like a fresh heap table.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryData {
+    pub symbols: Vec<Symbol>,
+    pub symbol_sets: Vec<SymbolSet>,
+    pub relations: Vec<SymbolRelation>,
+    pub archetypes: Vec<Archetype>,
+    pub symbol_archetypes: Vec<(String, String)>,
+    /// (set ID, symbol ID) pairs, each set's in member order
+    pub set_members: Vec<(String, String)>,
+    /// Embeddings by symbol ID; not part of snapshots
+    pub embeddings: HashMap<String, Vec<f32>>,
+}
+
+impl MemoryData {
+    pub fn symbol(&self, id: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|s| s.id == id)
+    }
+
+    pub fn archetype(&self, id: &str) -> Option<&Archetype> {
+        self.archetypes.iter().find(|a| a.id == id)
+    }
+
+    pub fn symbol_set(&self, id: &str) -> Option<&SymbolSet> {
+        self.symbol_sets.iter().find(|s| s.id == id)
+    }
+
+    /// Returns the set with its members, in order, as the current symbols.
+    pub fn resolve_set(&self, set: &SymbolSet) -> SymbolSet {
+        let mut resolved = SymbolSet::new(
+            set.id.clone(),
+            set.name.clone(),
+            set.category.clone(),
+            set.description.clone(),
+        );
+        for (_, symbol_id) in self.set_members.iter().filter(|(id, _)| *id == set.id) {
+            if let Some(symbol) = self.symbol(symbol_id) {
+                resolved.add_symbol(symbol.clone());
+            }
+        }
+        resolved
+    }
+
+    /// Stores a set's own fields; its members are kept in `set_members`.
+    pub fn insert_symbol_set(&mut self, set: &SymbolSet) -> RepositoryResult<()> {
+        if self.symbol_set(&set.id).is_some() {
+            return Err(RepositoryError::Conflict(format!(
+                "SymbolSet with ID {} already exists",
+                set.id
+            )));
+        }
+
+        self.symbol_sets.push(SymbolSet::new(
+            set.id.clone(),
+            set.name.clone(),
+            set.category.clone(),
+            set.description.clone(),
+        ));
+        Ok(())
+    }
+
+    /// Makes `symbol_ids` the members of a set, in that order, skipping IDs that
+    /// name no symbol like the SQL backends do.
+    pub fn replace_set_members(&mut self, set_id: &str, symbol_ids: &[String]) {
+        self.set_members.retain(|(id, _)| id != set_id);
+        for symbol_id in symbol_ids {
+            let member = (set_id.to_string(), symbol_id.clone());
+            if self.symbol(symbol_id).is_some() && !self.set_members.contains(&member) {
+                self.set_members.push(member);
+            }
+        }
+    }
+
+    pub fn add_set_member(&mut self, set_id: &str, symbol_id: &str) -> RepositoryResult<()> {
+        if self.symbol_set(set_id).is_none() || self.symbol(symbol_id).is_none() {
+            return Err(RepositoryError::NotFound(format!(
+                "SymbolSet {} or symbol {} not found",
+                set_id, symbol_id
+            )));
+        }
+
+        let member = (set_id.to_string(), symbol_id.to_string());
+        if !self.set_members.contains(&member) {
+            self.set_members.push(member);
+        }
+        Ok(())
+    }
+
+    pub fn insert_symbol(&mut self, symbol: Symbol) -> RepositoryResult<()> {
+        if self.symbol(&symbol.id).is_some() {
+            return Err(RepositoryError::Conflict(format!(
+                "Symbol with ID {} already exists",
+                symbol.id
+            )));
+        }
+        self.check_lookup_keys(&symbol)?;
+
+        self.put_symbol(symbol);
+        Ok(())
+    }
+
+    pub fn replace_symbol(&mut self, symbol: Symbol) -> RepositoryResult<()> {
+        if self.symbol(&symbol.id).is_none() {
+            return Err(RepositoryError::NotFound(format!(
+                "Symbol with id {} not found",
+                symbol.id
+            )));
+        }
+        self.check_lookup_keys(&symbol)?;
+
+        self.put_symbol(symbol);
+        Ok(())
+    }
+
+    /// Inserts `symbols`, replacing those that already exist, then checks their
+    /// aliases and localized names against the result, so a batch may move a
+    /// name from one of its symbols to another. On error the data is left
+    /// half written; apply batches to a copy.
+    pub fn upsert_symbols(&mut self, symbols: &[Symbol]) -> RepositoryResult<()> {
+        for symbol in symbols {
+            self.put_symbol(symbol.clone());
+        }
+
+        let mut batch: Vec<&Symbol> = symbols.iter().collect();
+        batch.sort_by(|a, b| a.id.cmp(&b.id));
+        for symbol in batch {
+            self.check_lookup_keys(symbol)?;
+        }
+        Ok(())
+    }
+
+    /// Stores a symbol over any with its ID and adds its `related_to` edges.
+    fn put_symbol(&mut self, symbol: Symbol) {
+        let (id, related) = (symbol.id.clone(), symbol.related_symbols.clone());
+        match self.symbols.iter_mut().find(|s| s.id == symbol.id) {
+            Some(existing) => {
+                // A stale embedding is worse than none; backfill embeds the new text
+                if embedding::symbol_text(existing) != embedding::symbol_text(&symbol) {
+                    self.embeddings.remove(&symbol.id);
+                }
+                *existing = symbol;
+            }
+            None => self.symbols.push(symbol),
+        }
+        self.sync_related(&id, &related);
+    }
+
+    pub fn remove_symbol(&mut self, id: &str) -> RepositoryResult<()> {
+        let before = self.symbols.len();
+        self.symbols.retain(|s| s.id != id);
+        if self.symbols.len() == before {
+            return Err(RepositoryError::NotFound(format!(
+                "Symbol with id {} not found",
+                id
+            )));
+        }
+
+        // Same cascades as the foreign keys on the SQL tables
+        self.relations.retain(|r| r.source_id != id);
+        self.symbol_archetypes
+            .retain(|(symbol_id, _)| symbol_id != id);
+        self.set_members.retain(|(_, symbol_id)| symbol_id != id);
+        self.embeddings.remove(id);
+
+        // And the references to it from other symbols, as `SymbolQueries::delete` clears
+        self.relations.retain(|r| r.target_id != id);
+        for symbol in &mut self.symbols {
+            symbol.related_symbols.retain(|target_id| target_id != id);
+        }
+        Ok(())
+    }
+
+    /// Same uniqueness rule as `SymbolQueries::create` applies to aliases and
+    /// localized names.
+    fn check_lookup_keys(&self, symbol: &Symbol) -> RepositoryResult<()> {
+        match search::lookup_key_conflict(&self.symbols, symbol) {
+            Some(other) => Err(RepositoryError::Conflict(format!(
+                "An alias or localized name of {} is already used by symbol {}",
+                symbol.id, other.id
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds `related_to` edges for entries of `related_symbols` that have no edge yet,
+    /// and removes those whose target is no longer listed.
+    fn sync_related(&mut self, symbol_id: &str, related: &[String]) {
+        self.relations.retain(|r| {
+            r.source_id != symbol_id
+                || r.relation_type != RelationType::RelatedTo
+                || related.contains(&r.target_id)
+        });
+        for target_id in related {
+            if target_id.is_empty() || target_id == symbol_id {
+                continue;
+            }
+            let linked = self
+                .relations
+                .iter()
+                .any(|r| r.source_id == symbol_id && &r.target_id == target_id);
+            if !linked {
+                self.relations.push(SymbolRelation::new(
+                    symbol_id,
+                    target_id,
+                    RelationType::RelatedTo,
+                ));
+            }
+        }
+    }
+
+    pub fn upsert_relation(&mut self, relation: SymbolRelation) -> RepositoryResult<()> {
+        let source = self
+            .symbols
+            .iter_mut()
+            .find(|s| s.id == relation.source_id)
+            .ok_or_else(|| {
+                RepositoryError::NotFound(format!(
+                    "Symbol with id {} not found",
+                    relation.source_id
+                ))
+            })?;
+
+        // Keep the legacy related_symbols list in step with outgoing edges
+        if !source.related_symbols.contains(&relation.target_id) {
+            source.related_symbols.push(relation.target_id.clone());
+        }
+
+        match self.relations.iter_mut().find(|r| {
+            r.source_id == relation.source_id
+                && r.target_id == relation.target_id
+                && r.relation_type == relation.relation_type
+        }) {
+            Some(existing) => existing.weight = relation.weight,
+            None => self.relations.push(relation),
+        }
+        Ok(())
+    }
+
+    pub fn remove_relation(
+        &mut self,
+        source_id: &str,
+        target_id: &str,
+        relation_type: RelationType,
+    ) -> RepositoryResult<()> {
+        let before = self.relations.len();
+        self.relations.retain(|r| {
+            !(r.source_id == source_id
+                && r.target_id == target_id
+                && r.relation_type == relation_type)
+        });
+        if self.relations.len() == before {
+            return Err(RepositoryError::NotFound(format!(
+                "Relation {} from {} to {} not found",
+                relation_type, source_id, target_id
+            )));
+        }
+
+        // Drop the target from related_symbols once no edge of any type remains
+        let still_related = self
+            .relations
+            .iter()
+            .any(|r| r.source_id == source_id && r.target_id == target_id);
+        if !still_related {
+            if let Some(source) = self.symbols.iter_mut().find(|s| s.id == source_id) {
+                source.related_symbols.retain(|id| id != target_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the parent exists and would not make the hierarchy circular.
+    pub fn check_parent(&self, archetype: &Archetype) -> RepositoryResult<()> {
+        let Some(parent_id) = archetype.parent_id.as_deref() else {
+            return Ok(());
+        };
+
+        if self.archetype(parent_id).is_none() {
+            return Err(RepositoryError::Validation(format!(
+                "Parent archetype {} does not exist",
+                parent_id
+            )));
+        }
+        if creates_cycle(&self.archetypes, &archetype.id, parent_id) {
+            return Err(RepositoryError::Validation(format!(
+                "Archetype {} cannot be placed under its own descendant {}",
+                archetype.id, parent_id
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn link_symbol(&mut self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()> {
+        if self.symbol(symbol_id).is_none() || self.archetype(archetype_id).is_none() {
+            return Err(RepositoryError::NotFound(format!(
+                "Symbol {} or archetype {} not found",
+                symbol_id, archetype_id
+            )));
+        }
+
+        let link = (symbol_id.to_string(), archetype_id.to_string());
+        if !self.symbol_archetypes.contains(&link) {
+            self.symbol_archetypes.push(link);
+        }
+        Ok(())
+    }
+
+    fn load(&mut self, snapshot: Snapshot) -> RepositoryResult<()> {
+        for symbol in snapshot.symbols {
+            self.insert_symbol(symbol)?;
+        }
+
+        for relation in snapshot.relations {
+            relation.validate().map_err(RepositoryError::Validation)?;
+            self.upsert_relation(relation)?;
+        }
+
+        // Parents may be listed after their children, so check them once all are in
+        for archetype in &snapshot.archetypes {
+            archetype.validate().map_err(RepositoryError::Validation)?;
+            if self.archetype(&archetype.id).is_some() {
+                return Err(RepositoryError::Conflict(format!(
+                    "Archetype with ID {} already exists",
+                    archetype.id
+                )));
+            }
+            self.archetypes.push(archetype.clone());
+        }
+        for archetype in &snapshot.archetypes {
+            self.check_parent(archetype)?;
+        }
+
+        for link in snapshot.symbol_archetypes {
+            self.link_symbol(&link.symbol_id, &link.archetype_id)?;
+        }
+
+        for entry in snapshot.symbol_sets {
+            let set = SymbolSet::new(entry.id, entry.name, entry.category, entry.description);
+            self.insert_symbol_set(&set)?;
+            self.replace_set_members(&set.id, &entry.symbols);
+        }
+
+        Ok(())
+    }
+}
+
+/// Shared handle to the in-memory tables; clones see the same data.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    data: Arc<RwLock<MemoryData>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_snapshot(snapshot: Snapshot) -> RepositoryResult<Self> {
+        let store = Self::new();
+        store.write().load(snapshot)?;
+        Ok(store)
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, MemoryData> {
+        // A panic while holding the lock leaves the data consistent enough to keep serving
+        self.data.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, MemoryData> {
+        self.data.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,598 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # Schema Migrations
+ *
+ * Ordered, versioned schema changes for the SQL backends. Every migration has
+ * `up` and `down` statements and is recorded in the `schema_migrations` table
+ * once applied, inside the same transaction as its statements. Versions are kept
+ * in step across backends, so a change one backend doesn't need is recorded there
+ * with no statements.
+ *
+ * The first migrations only use `IF NOT EXISTS` statements, so databases created
+ * before migrations were tracked are adopted without changes. A database that
+ * records a version this build doesn't know about is refused rather than touched.
+ */
+
+use sqlx::{PgPool, SqlitePool};
+use tracing::info;
+
+use crate::db::backend::DatabasePool;
+use crate::db::pool::{DbError, DbResult};
+use crate::db::{schema, sqlite};
+
+/// A single versioned schema change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    pub version: i64,
+
+    pub name: &'static str,
+
+    /// Statements applying the change, run in order
+    pub up: &'static [&'static str],
+
+    /// Statements undoing the change, run in order
+    pub down: &'static [&'static str],
+}
+
+/// Whether a known migration has been applied to the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub migration: &'static Migration,
+
+    pub applied: bool,
+}
+
+pub const POSTGRES_MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_symbols_and_sets",
+        up: &[
+            schema::CREATE_SYMBOLS_TABLE,
+            schema::CREATE_SYMBOL_SETS_TABLE,
+            schema::CREATE_CATEGORY_INDEX,
+            schema::CREATE_TEXT_SEARCH_INDEX,
+        ],
+        down: &[
+            "DROP TABLE IF EXISTS symbol_sets",
+            "DROP TABLE IF EXISTS symbols",
+        ],
+    },
+    Migration {
+        version: 2,
+        name: "create_symbol_relations",
+        up: &[
+            schema::CREATE_SYMBOL_RELATIONS_TABLE,
+            schema::CREATE_RELATIONS_TARGET_INDEX,
+            schema::MIGRATE_RELATED_SYMBOLS,
+        ],
+        down: &["DROP TABLE IF EXISTS symbol_relations"],
+    },
+    Migration {
+        version: 3,
+        name: "create_archetypes",
+        up: &[
+            schema::CREATE_ARCHETYPES_TABLE,
+            schema::CREATE_ARCHETYPES_PARENT_INDEX,
+            schema::CREATE_SYMBOL_ARCHETYPES_TABLE,
+            schema::CREATE_SYMBOL_ARCHETYPES_INDEX,
+        ],
+        down: &[
+            "DROP TABLE IF EXISTS symbol_archetypes",
+            "DROP TABLE IF EXISTS archetypes",
+        ],
+    },
+    Migration {
+        version: 4,
+        name: "rank_text_search",
+        up: &[
+            schema::ADD_SEARCH_VECTOR_COLUMN,
+            schema::CREATE_SEARCH_VECTOR_INDEX,
+            "DROP INDEX IF EXISTS idx_symbols_text_search",
+        ],
+        down: &[
+            "DROP INDEX IF EXISTS idx_symbols_search_vector",
+            "ALTER TABLE symbols DROP COLUMN IF EXISTS search_vector",
+            schema::CREATE_TEXT_SEARCH_INDEX,
+        ],
+    },
+    Migration {
+        version: 5,
+        name: "add_symbol_embeddings",
+        up: &[schema::ADD_EMBEDDING_COLUMN],
+        down: &[
+            "DROP TRIGGER IF EXISTS symbols_clear_embedding ON symbols",
+            "DROP FUNCTION IF EXISTS clear_symbol_embedding()",
+            "DROP INDEX IF EXISTS idx_symbols_embedding",
+            "ALTER TABLE symbols DROP COLUMN IF EXISTS embedding",
+        ],
+    },
+    Migration {
+        version: 6,
+        name: "add_fuzzy_matching",
+        up: &[
+            "CREATE EXTENSION IF NOT EXISTS pg_trgm",
+            "CREATE EXTENSION IF NOT EXISTS fuzzystrmatch",
+        ],
+        down: &[
+            "DROP EXTENSION IF EXISTS fuzzystrmatch",
+            "DROP EXTENSION IF EXISTS pg_trgm",
+        ],
+    },
+    Migration {
+        version: 7,
+        name: "add_symbol_aliases",
+        up: &[
+            schema::ADD_ALIAS_COLUMNS,
+            schema::CREATE_LOOKUP_KEYS_FUNCTION,
+            schema::CREATE_LOOKUP_KEYS_INDEX,
+            "DROP INDEX IF EXISTS idx_symbols_search_vector",
+            "ALTER TABLE symbols DROP COLUMN IF EXISTS search_vector",
+            schema::ADD_ALIASED_SEARCH_VECTOR_COLUMN,
+            schema::CREATE_SEARCH_VECTOR_INDEX,
+        ],
+        down: &[
+            "DROP INDEX IF EXISTS idx_symbols_search_vector",
+            "ALTER TABLE symbols DROP COLUMN IF EXISTS search_vector",
+            schema::ADD_SEARCH_VECTOR_COLUMN,
+            schema::CREATE_SEARCH_VECTOR_INDEX,
+            "DROP INDEX IF EXISTS idx_symbols_lookup_keys",
+            "DROP FUNCTION IF EXISTS symbol_lookup_keys(JSONB, JSONB)",
+            "ALTER TABLE symbols DROP COLUMN IF EXISTS localized_names",
+            "ALTER TABLE symbols DROP COLUMN IF EXISTS aliases",
+        ],
+    },
+    Migration {
+        version: 8,
+        name: "create_symbol_set_members",
+        up: &[
+            schema::CREATE_SYMBOL_SET_MEMBERS_TABLE,
+            schema::CREATE_SYMBOL_SET_MEMBERS_INDEX,
+            schema::MIGRATE_SET_MEMBERS,
+            "ALTER TABLE symbol_sets DROP COLUMN IF EXISTS symbols",
+        ],
+        down: &[
+            "ALTER TABLE symbol_sets ADD COLUMN IF NOT EXISTS symbols JSONB DEFAULT '{}'::JSONB",
+            schema::RESTORE_SET_MEMBERS_COLUMN,
+            "DROP TABLE IF EXISTS symbol_set_members",
+        ],
+    },
+    Migration {
+        version: 9,
+        name: "embed_symbol_aliases",
+        up: &[schema::EMBED_SYMBOL_ALIASES],
+        down: &[schema::UNEMBED_SYMBOL_ALIASES],
+    },
+    // Postgres looks symbols up through the symbol_lookup_keys index
+    Migration {
+        version: 10,
+        name: "create_symbol_lookup_keys",
+        up: &[],
+        down: &[],
+    },
+    Migration {
+        version: 11,
+        name: "index_names",
+        up: &[
+            schema::CREATE_SYMBOLS_NAME_INDEX,
+            schema::CREATE_SYMBOL_SETS_NAME_INDEX,
+        ],
+        down: &[
+            "DROP INDEX IF EXISTS idx_symbol_sets_name",
+            "DROP INDEX IF EXISTS idx_symbols_name",
+        ],
+    },
+];
+
+/// The SQLite counterparts of [`POSTGRES_MIGRATIONS`], version for version.
+pub const SQLITE_MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create_symbols_and_sets",
+        up: &[
+            sqlite::schema::CREATE_SYMBOLS_TABLE,
+            sqlite::schema::CREATE_SYMBOL_SETS_TABLE,
+            sqlite::schema::CREATE_CATEGORY_INDEX,
+        ],
+        down: &[
+            "DROP TABLE IF EXISTS symbol_sets",
+            "DROP TABLE IF EXISTS symbols",
+        ],
+    },
+    Migration {
+        version: 2,
+        name: "create_symbol_relations",
+        up: &[
+            sqlite::schema::CREATE_SYMBOL_RELATIONS_TABLE,
+            sqlite::schema::CREATE_RELATIONS_TARGET_INDEX,
+            sqlite::schema::MIGRATE_RELATED_SYMBOLS,
+        ],
+        down: &["DROP TABLE IF EXISTS symbol_relations"],
+    },
+    Migration {
+        version: 3,
+        name: "create_archetypes",
+        up: &[
+            sqlite::schema::CREATE_ARCHETYPES_TABLE,
+            sqlite::schema::CREATE_ARCHETYPES_PARENT_INDEX,
+            sqlite::schema::CREATE_SYMBOL_ARCHETYPES_TABLE,
+            sqlite::schema::CREATE_SYMBOL_ARCHETYPES_INDEX,
+        ],
+        down: &[
+            "DROP TABLE IF EXISTS symbol_archetypes",
+            "DROP TABLE IF EXISTS archetypes",
+        ],
+    },
+    // SQLite searches without a text index and scores hits itself
+    Migration {
+        version: 4,
+        name: "rank_text_search",
+        up: &[],
+        down: &[],
+    },
+    Migration {
+        version: 5,
+        name: "add_symbol_embeddings",
+        up: &[
+            sqlite::schema::ADD_EMBEDDING_COLUMN,
+            sqlite::schema::CREATE_CLEAR_EMBEDDING_TRIGGER,
+        ],
+        down: &[
+            "DROP TRIGGER IF EXISTS symbols_clear_embedding",
+            "ALTER TABLE symbols DROP COLUMN embedding",
+        ],
+    },
+    // SQLite symbols are fuzzy matched in-process
+    Migration {
+        version: 6,
+        name: "add_fuzzy_matching",
+        up: &[],
+        down: &[],
+    },
+    Migration {
+        version: 7,
+        name: "add_symbol_aliases",
+        up: &[
+            sqlite::schema::ADD_ALIASES_COLUMN,
+            sqlite::schema::ADD_LOCALIZED_NAMES_COLUMN,
+        ],
+        down: &[
+            "ALTER TABLE symbols DROP COLUMN localized_names",
+            "ALTER TABLE symbols DROP COLUMN aliases",
+        ],
+    },
+    Migration {
+        version: 8,
+        name: "create_symbol_set_members",
+        up: &[
+            sqlite::schema::CREATE_SYMBOL_SET_MEMBERS_TABLE,
+            sqlite::schema::CREATE_SYMBOL_SET_MEMBERS_INDEX,
+            sqlite::schema::MIGRATE_SET_MEMBERS,
+            "ALTER TABLE symbol_sets DROP COLUMN symbols",
+        ],
+        down: &[
+            "ALTER TABLE symbol_sets ADD COLUMN symbols TEXT NOT NULL DEFAULT '{}'",
+            sqlite::schema::RESTORE_SET_MEMBERS_COLUMN,
+            "DROP TABLE IF EXISTS symbol_set_members",
+        ],
+    },
+    Migration {
+        version: 9,
+        name: "embed_symbol_aliases",
+        up: &[
+            "DROP TRIGGER IF EXISTS symbols_clear_embedding",
+            sqlite::schema::CREATE_ALIASED_CLEAR_EMBEDDING_TRIGGER,
+            sqlite::schema::CLEAR_ALIASED_EMBEDDINGS,
+        ],
+        down: &[
+            "DROP TRIGGER IF EXISTS symbols_clear_embedding",
+            sqlite::schema::CREATE_CLEAR_EMBEDDING_TRIGGER,
+            sqlite::schema::CLEAR_ALIASED_EMBEDDINGS,
+        ],
+    },
+    // Filled in by apply_sqlite, which folds the keys in Rust
+    Migration {
+        version: SQLITE_LOOKUP_KEYS_VERSION,
+        name: "create_symbol_lookup_keys",
+        up: &[
+            sqlite::schema::CREATE_SYMBOL_LOOKUP_KEYS_TABLE,
+            sqlite::schema::CREATE_SYMBOL_LOOKUP_KEYS_INDEX,
+        ],
+        down: &["DROP TABLE IF EXISTS symbol_lookup_keys"],
+    },
+    Migration {
+        version: 11,
+        name: "index_names",
+        up: &[
+            sqlite::schema::CREATE_SYMBOLS_NAME_INDEX,
+            sqlite::schema::CREATE_SYMBOL_SETS_NAME_INDEX,
+        ],
+        down: &[
+            "DROP INDEX IF EXISTS idx_symbol_sets_name",
+            "DROP INDEX IF EXISTS idx_symbols_name",
+        ],
+    },
+];
+
+/// The SQLite migration adding `symbol_lookup_keys`, whose rows are written
+/// for the existing symbols once its statements have run.
+const SQLITE_LOOKUP_KEYS_VERSION: i64 = 10;
+
+const CREATE_PG_MIGRATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    version BIGINT PRIMARY KEY,
+    name TEXT NOT NULL,
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+)
+"#;
+
+const CREATE_SQLITE_MIGRATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    version INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+/// Serializes concurrent migrators on the same PostgreSQL database.
+const PG_MIGRATION_LOCK: i64 = 0x5359_4d42_4f4c;
+
+/// Applies and reverts the migrations for one database.
+pub struct Migrator<'a> {
+    pool: &'a DatabasePool,
+}
+
+impl<'a> Migrator<'a> {
+    pub fn new(pool: &'a DatabasePool) -> Self {
+        Migrator { pool }
+    }
+
+    /// The migrations known for this backend, oldest first.
+    ///
+    /// The memory backend has no schema, so it has none.
+    pub fn migrations(&self) -> &'static [Migration] {
+        match self.pool {
+            DatabasePool::Postgres(_) => POSTGRES_MIGRATIONS,
+            DatabasePool::Sqlite(_) => SQLITE_MIGRATIONS,
+            DatabasePool::Memory(_) => &[],
+        }
+    }
+
+    pub fn latest_version(&self) -> i64 {
+        self.migrations().last().map_or(0, |m| m.version)
+    }
+
+    /// Versions recorded in the database, oldest first.
+    pub async fn applied_versions(&self) -> DbResult<Vec<i64>> {
+        let versions = match self.pool {
+            DatabasePool::Postgres(pool) => {
+                sqlx::query(CREATE_PG_MIGRATIONS_TABLE)
+                    .execute(pool)
+                    .await?;
+                sqlx::query_scalar("SELECT version FROM schema_migrations ORDER BY version")
+                    .fetch_all(pool)
+                    .await?
+            }
+            DatabasePool::Sqlite(pool) => {
+                sqlx::query(CREATE_SQLITE_MIGRATIONS_TABLE)
+                    .execute(pool)
+                    .await?;
+                sqlx::query_scalar("SELECT version FROM schema_migrations ORDER BY version")
+                    .fetch_all(pool)
+                    .await?
+            }
+            DatabasePool::Memory(_) => Vec::new(),
+        };
+
+        Ok(versions)
+    }
+
+    /// The highest applied version, or 0 for an empty database.
+    pub async fn current_version(&self) -> DbResult<i64> {
+        Ok(self.applied_versions().await?.last().copied().unwrap_or(0))
+    }
+
+    /// Fails if the database records a migration this build doesn't know about,
+    /// which usually means it was migrated by a newer release.
+    pub async fn check(&self) -> DbResult<()> {
+        let applied = self.applied_versions().await?;
+        check_known(self.migrations(), &applied)
+    }
+
+    pub async fn status(&self) -> DbResult<Vec<MigrationStatus>> {
+        let applied = self.applied_versions().await?;
+        check_known(self.migrations(), &applied)?;
+
+        Ok(self
+            .migrations()
+            .iter()
+            .map(|migration| MigrationStatus {
+                migration,
+                applied: applied.contains(&migration.version),
+            })
+            .collect())
+    }
+
+    /// Applies pending migrations up to `target` (the latest if `None`) and
+    /// returns the ones that ran.
+    pub async fn up(&self, target: Option<i64>) -> DbResult<Vec<&'static Migration>> {
+        let target = target.unwrap_or_else(|| self.latest_version());
+        self.check_target(target)?;
+
+        let applied = self.applied_versions().await?;
+        check_known(self.migrations(), &applied)?;
+
+        let mut ran = Vec::new();
+        for migration in self.migrations() {
+            if migration.version > target || applied.contains(&migration.version) {
+                continue;
+            }
+
+            let newly_applied = match self.pool {
+                DatabasePool::Postgres(pool) => apply_pg(pool, migration).await?,
+                DatabasePool::Sqlite(pool) => apply_sqlite(pool, migration).await?,
+                DatabasePool::Memory(_) => false,
+            };
+            if newly_applied {
+                info!(
+                    "Applied migration {} ({})",
+                    migration.version, migration.name
+                );
+                ran.push(migration);
+            }
+        }
+
+        Ok(ran)
+    }
+
+    /// Reverts applied migrations newer than `target`, newest first, and
+    /// returns the ones that ran.
+    pub async fn down(&self, target: i64) -> DbResult<Vec<&'static Migration>> {
+        self.check_target(target)?;
+
+        let applied = self.applied_versions().await?;
+        check_known(self.migrations(), &applied)?;
+
+        let mut reverted = Vec::new();
+        for migration in self.migrations().iter().rev() {
+            if migration.version <= target || !applied.contains(&migration.version) {
+                continue;
+            }
+
+            match self.pool {
+                DatabasePool::Postgres(pool) => revert_pg(pool, migration).await?,
+                DatabasePool::Sqlite(pool) => revert_sqlite(pool, migration).await?,
+                DatabasePool::Memory(_) => {}
+            }
+            info!(
+                "Reverted migration {} ({})",
+                migration.version, migration.name
+            );
+            reverted.push(migration);
+        }
+
+        Ok(reverted)
+    }
+
+    fn check_target(&self, target: i64) -> DbResult<()> {
+        if target == 0 || self.migrations().iter().any(|m| m.version == target) {
+            Ok(())
+        } else {
+            Err(DbError::Migration(format!(
+                "Unknown migration version {} (latest is {})",
+                target,
+                self.latest_version()
+            )))
+        }
+    }
+}
+
+fn check_known(migrations: &[Migration], applied: &[i64]) -> DbResult<()> {
+    let latest = migrations.last().map_or(0, |m| m.version);
+
+    match applied
+        .iter()
+        .find(|version| !migrations.iter().any(|m| m.version == **version))
+    {
+        Some(version) if *version > latest => Err(DbError::Migration(format!(
+            "Database schema is at version {}, but this build only knows migrations up to {}; \
+             upgrade before connecting to this database",
+            version, latest
+        ))),
+        Some(version) => Err(DbError::Migration(format!(
+            "Database records unknown migration version {}",
+            version
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Returns `false` if another process applied the migration first.
+async fn apply_pg(pool: &PgPool, migration: &Migration) -> DbResult<bool> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(PG_MIGRATION_LOCK)
+        .execute(&mut *tx)
+        .await?;
+
+    let done = sqlx::query("SELECT 1 FROM schema_migrations WHERE version = $1")
+        .bind(migration.version)
+        .fetch_optional(&mut *tx)
+        .await?
+        .is_some();
+    if done {
+        return Ok(false);
+    }
+
+    for statement in migration.up {
+        sqlx::query(statement).execute(&mut *tx).await?;
+    }
+
+    sqlx::query("INSERT INTO schema_migrations (version, name) VALUES ($1, $2)")
+        .bind(migration.version)
+        .bind(migration.name)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+async fn revert_pg(pool: &PgPool, migration: &Migration) -> DbResult<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(PG_MIGRATION_LOCK)
+        .execute(&mut *tx)
+        .await?;
+
+    for statement in migration.down {
+        sqlx::query(statement).execute(&mut *tx).await?;
+    }
+
+    sqlx::query("DELETE FROM schema_migrations WHERE version = $1")
+        .bind(migration.version)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn apply_sqlite(pool: &SqlitePool, migration: &Migration) -> DbResult<bool> {
+    let mut tx = pool.begin().await?;
+
+    for statement in migration.up {
+        sqlx::query(statement).execute(&mut *tx).await?;
+    }
+    if migration.version == SQLITE_LOOKUP_KEYS_VERSION {
+        sqlite::queries::SymbolQueries::index_lookup_keys(&mut tx).await?;
+    }
+
+    sqlx::query("INSERT INTO schema_migrations (version, name) VALUES (?1, ?2)")
+        .bind(migration.version)
+        .bind(migration.name)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+async fn revert_sqlite(pool: &SqlitePool, migration: &Migration) -> DbResult<()> {
+    let mut tx = pool.begin().await?;
+
+    for statement in migration.down {
+        sqlx::query(statement).execute(&mut *tx).await?;
+    }
+
+    sqlx::query("DELETE FROM schema_migrations WHERE version = ?1")
+        .bind(migration.version)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
@@ -5,14 +5,22 @@
  * # Database Module
  *
  * This module contains the database models, connection pool, and queries for the symbolic ontology.
+ * PostgreSQL is the primary backend; `sqlite` provides a single-file alternative, `memory` a
+ * process-local store, and `backend` picks between them. `migrations` owns the versioned
+ * schema of the SQL backends.
  */
 
+pub mod backend;
+pub mod memory;
+pub mod migrations;
 pub mod models;
 pub mod pool;
 pub mod queries;
 pub mod repository;
 pub mod schema;
+pub mod sqlite;
 
+pub use backend::{DatabaseBackend, DatabasePool};
 pub use models::{Symbol, SymbolSet};
 pub use pool::{DbError, create_pool, init_database};
 pub use repository::{Repository, RepositoryFactory};
@@ -5,8 +5,11 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, Row, postgres::PgRow};
 use std::collections::HashMap;
 
+use crate::domain::Archetype as DomainArchetype;
 use crate::domain::Symbol as DomainSymbol;
+use crate::domain::SymbolRelation as DomainSymbolRelation;
 use crate::domain::SymbolSet as DomainSymbolSet;
+use crate::domain::interpretations::{InterpretationLayer, interpretations_from_value};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Symbol {
@@ -18,11 +21,15 @@ pub struct Symbol {
 
     pub description: String,
 
-    pub interpretations: HashMap<String, String>,
+    pub interpretations: HashMap<String, InterpretationLayer>,
 
     pub related_symbols: Vec<String>,
 
     pub properties: HashMap<String, String>,
+
+    pub aliases: Vec<String>,
+
+    pub localized_names: HashMap<String, String>,
 }
 
 impl FromRow<'_, PgRow> for Symbol {
@@ -44,14 +51,28 @@ impl FromRow<'_, PgRow> for Symbol {
             .try_get("properties")
             .unwrap_or_else(|_| serde_json::json!({}));
 
+        // Older rows store interpretations as a flat context -> text map
         let interpretations =
-            serde_json::from_value(interpretations_json).unwrap_or_else(|_| HashMap::new());
+            interpretations_from_value(interpretations_json).unwrap_or_else(|_| HashMap::new());
 
         let related_symbols =
             serde_json::from_value(related_symbols_json).unwrap_or_else(|_| Vec::new());
 
         let properties = serde_json::from_value(properties_json).unwrap_or_else(|_| HashMap::new());
 
+        // Rows from before aliases existed have neither column
+        let aliases = row
+            .try_get::<serde_json::Value, _>("aliases")
+            .ok()
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default();
+
+        let localized_names = row
+            .try_get::<serde_json::Value, _>("localized_names")
+            .ok()
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default();
+
         Ok(Symbol {
             id,
             name,
@@ -60,6 +81,8 @@ impl FromRow<'_, PgRow> for Symbol {
             interpretations,
             related_symbols,
             properties,
+            aliases,
+            localized_names,
         })
     }
 }
@@ -74,6 +97,8 @@ impl Symbol {
             interpretations: HashMap::new(),
             related_symbols: Vec::new(),
             properties: HashMap::new(),
+            aliases: Vec::new(),
+            localized_names: HashMap::new(),
         }
     }
 
@@ -100,6 +125,8 @@ impl Symbol {
             interpretations: self.interpretations.clone(),
             related_symbols: self.related_symbols.clone(),
             properties: self.properties.clone(),
+            aliases: self.aliases.clone(),
+            localized_names: self.localized_names.clone(),
         }
     }
 
@@ -112,6 +139,8 @@ impl Symbol {
             interpretations: symbol.interpretations,
             related_symbols: symbol.related_symbols,
             properties: symbol.properties,
+            aliases: symbol.aliases,
+            localized_names: symbol.localized_names,
         }
     }
 }
@@ -126,28 +155,19 @@ pub struct SymbolSet {
 
     pub description: String,
 
-    pub symbols_map: HashMap<String, serde_json::Value>,
+    /// Member IDs in set order, as stored in `symbol_set_members`
+    pub symbol_ids: Vec<String>,
 }
 
 impl FromRow<'_, PgRow> for SymbolSet {
     fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
-        let id: String = row.try_get("id")?;
-        let name: String = row.try_get("name")?;
-        let category: String = row.try_get("category")?;
-        let description: String = row.try_get("description")?;
-
-        let symbols_json: serde_json::Value = row
-            .try_get("symbols")
-            .unwrap_or_else(|_| serde_json::json!({}));
-
-        let symbols_map = serde_json::from_value(symbols_json).unwrap_or_else(|_| HashMap::new());
-
+        // Members live in their own table and are fetched separately
         Ok(SymbolSet {
-            id,
-            name,
-            category,
-            description,
-            symbols_map,
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            category: row.try_get("category")?,
+            description: row.try_get("description")?,
+            symbol_ids: Vec::new(),
         })
     }
 }
@@ -159,35 +179,31 @@ impl SymbolSet {
             name,
             category,
             description,
-            symbols_map: HashMap::new(),
+            symbol_ids: Vec::new(),
         }
     }
 
     pub fn with_symbols(mut self, symbol_ids: Vec<&str>) -> Self {
         for id in symbol_ids {
-            self.symbols_map
-                .insert(id.to_string(), serde_json::Value::Null);
+            if !self.symbol_ids.iter().any(|existing| existing == id) {
+                self.symbol_ids.push(id.to_string());
+            }
         }
         self
     }
 
     pub fn from_domain(set: DomainSymbolSet) -> Self {
-        let mut symbols_map = HashMap::new();
-
-        for (id, _) in set.symbols {
-            symbols_map.insert(id, serde_json::Value::Null);
-        }
-
         Self {
+            symbol_ids: set.symbol_ids(),
             id: set.id,
             name: set.name,
             category: set.category,
             description: set.description,
-            symbols_map,
         }
     }
 
-    pub fn to_domain(&self, symbols: &[Symbol]) -> DomainSymbolSet {
+    /// Builds the domain set with `members`, the set's symbols in set order.
+    pub fn to_domain(&self, members: &[Symbol]) -> DomainSymbolSet {
         let mut result = DomainSymbolSet::new(
             self.id.clone(),
             self.name.clone(),
@@ -195,12 +211,97 @@ impl SymbolSet {
             self.description.clone(),
         );
 
-        for symbol in symbols {
-            if self.symbols_map.contains_key(&symbol.id) {
-                result.add_symbol(symbol.to_domain());
-            }
+        for symbol in members {
+            result.add_symbol(symbol.to_domain());
         }
 
         result
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolRelation {
+    pub source_id: String,
+
+    pub target_id: String,
+
+    pub relation_type: String,
+
+    pub weight: Option<f64>,
+}
+
+impl FromRow<'_, PgRow> for SymbolRelation {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(SymbolRelation {
+            source_id: row.try_get("source_id")?,
+            target_id: row.try_get("target_id")?,
+            relation_type: row.try_get("relation_type")?,
+            weight: row.try_get("weight")?,
+        })
+    }
+}
+
+impl SymbolRelation {
+    pub fn from_domain(relation: DomainSymbolRelation) -> Self {
+        Self {
+            source_id: relation.source_id,
+            target_id: relation.target_id,
+            relation_type: relation.relation_type.to_string(),
+            weight: relation.weight,
+        }
+    }
+
+    pub fn to_domain(&self) -> DomainSymbolRelation {
+        // Unknown types written by newer versions degrade to an untyped link
+        let relation_type = self.relation_type.parse().unwrap_or_default();
+
+        DomainSymbolRelation {
+            source_id: self.source_id.clone(),
+            target_id: self.target_id.clone(),
+            relation_type,
+            weight: self.weight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Archetype {
+    pub id: String,
+
+    pub name: String,
+
+    pub description: String,
+
+    pub parent_id: Option<String>,
+}
+
+impl FromRow<'_, PgRow> for Archetype {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Archetype {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            parent_id: row.try_get("parent_id")?,
+        })
+    }
+}
+
+impl Archetype {
+    pub fn from_domain(archetype: DomainArchetype) -> Self {
+        Self {
+            id: archetype.id,
+            name: archetype.name,
+            description: archetype.description,
+            parent_id: archetype.parent_id,
+        }
+    }
+
+    pub fn to_domain(&self) -> DomainArchetype {
+        DomainArchetype {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            parent_id: self.parent_id.clone(),
+        }
+    }
+}
@@ -4,6 +4,8 @@
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::time::Duration;
 
+use crate::db::backend::DatabasePool;
+
 #[derive(Debug, thiserror::Error)]
 pub enum DbError {
     #[error("Database error: {0}")]
@@ -17,6 +19,9 @@ pub enum DbError {
 
     #[error("Conflict: {0}")]
     Conflict(String),
+
+    #[error("Migration error: {0}")]
+    Migration(String),
 }
 
 pub type DbResult<T> = Result<T, DbError>;
@@ -29,36 +34,7 @@ pub async fn create_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
         .await
 }
 
-pub async fn init_database(pool: &PgPool) -> Result<(), sqlx::Error> {
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS symbols (
-            id TEXT PRIMARY KEY,
-            name TEXT NOT NULL,
-            category TEXT NOT NULL,
-            description TEXT NOT NULL,
-            interpretations JSONB DEFAULT '{}'::JSONB,
-            related_symbols JSONB DEFAULT '[]'::JSONB,
-            properties JSONB DEFAULT '{}'::JSONB
-        )
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS symbol_sets (
-            id TEXT PRIMARY KEY,
-            name TEXT NOT NULL,
-            category TEXT NOT NULL,
-            description TEXT NOT NULL,
-            symbols JSONB DEFAULT '{}'::JSONB
-        )
-        "#,
-    )
-    .execute(pool)
-    .await?;
-
-    Ok(())
+/// Brings the schema up to date by applying any pending migrations.
+pub async fn init_database(pool: &PgPool) -> DbResult<()> {
+    DatabasePool::Postgres(pool.clone()).init().await
 }
@@ -1,19 +1,74 @@
 // SPDX-License-Identifier: MPL-2.0 OR Commercial
 // Copyright (c) 2024 Symbol Ontology Contributors
 
-use sqlx::PgPool;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, PgExecutor, PgPool, Row};
+use std::collections::{HashMap, HashSet};
 use tracing::info;
 
-use crate::db::models::{Symbol, SymbolSet};
+use crate::db::models::{Archetype, Symbol, SymbolRelation, SymbolSet};
 use crate::db::pool::{DbError, DbResult};
+use crate::db::repository::pagination::{Cursor, SortOrder};
+use crate::domain::search::FUZZY_THRESHOLD;
+use crate::domain::{InterpretationLayer, RelationDirection};
+
+type PgQuery<'q> = sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>;
+
+/// The condition keeping the rows that come after a cursor in `sort` order, with
+/// the cursor's name, ID and score bound from parameter `$first` on, and the
+/// `ORDER BY` list for that order. Ties on name are broken by ID.
+fn keyset(sort: SortOrder, first: usize) -> (String, &'static str) {
+    let (name, id, score) = (first, first + 1, first + 2);
+    match sort {
+        SortOrder::Name => (
+            format!("(${name}::text IS NULL OR (name, id) > (${name}, ${id}))"),
+            "name, id",
+        ),
+        SortOrder::NameDesc => (
+            format!("(${name}::text IS NULL OR (name, id) < (${name}, ${id}))"),
+            "name DESC, id DESC",
+        ),
+        SortOrder::Relevance => (
+            format!(
+                "(${score}::real IS NULL OR score < ${score} \
+                 OR (score = ${score} AND (name, id) > (${name}, ${id})))"
+            ),
+            "score DESC, name, id",
+        ),
+    }
+}
+
+/// Binds the parameters of [`keyset`] for the page after `after`, or for the
+/// first page.
+fn bind_after<'q>(query: PgQuery<'q>, sort: SortOrder, after: Option<&Cursor>) -> PgQuery<'q> {
+    let query = query
+        .bind(after.map(|cursor| cursor.name.clone()))
+        .bind(after.map(|cursor| cursor.id.clone()));
+    match sort {
+        SortOrder::Relevance => query.bind(after.and_then(|cursor| cursor.score)),
+        SortOrder::Name | SortOrder::NameDesc => query,
+    }
+}
+
+/// The total the first page counted, carried in the cursor of later pages, or
+/// else the result of `count`, which is only run on the first page.
+async fn total_count(
+    after: Option<&Cursor>,
+    count: impl std::future::Future<Output = Result<i64, sqlx::Error>>,
+) -> DbResult<usize> {
+    match after.and_then(|cursor| cursor.total_count) {
+        Some(total_count) => Ok(total_count),
+        None => Ok(count.await.map_err(DbError::Sqlx)? as usize),
+    }
+}
 
 pub struct SymbolQueries;
 
 impl SymbolQueries {
-    pub async fn get_by_id(pool: &PgPool, id: &str) -> DbResult<Symbol> {
+    pub async fn get_by_id<'e>(executor: impl PgExecutor<'e>, id: &str) -> DbResult<Symbol> {
         sqlx::query_as::<_, Symbol>("SELECT * FROM symbols WHERE id = $1")
             .bind(id)
-            .fetch_one(pool)
+            .fetch_one(executor)
             .await
             .map_err(|e| match e {
                 sqlx::Error::RowNotFound => DbError::NotFound,
@@ -21,57 +76,244 @@ impl SymbolQueries {
             })
     }
 
+    /// The symbol with `key` as an alias or localized name, or else the first
+    /// symbol by ID named `key`, ignoring case.
+    pub async fn resolve(pool: &PgPool, key: &str) -> DbResult<Symbol> {
+        sqlx::query_as::<_, Symbol>(
+            r#"
+            SELECT * FROM symbols
+            WHERE symbol_lookup_keys(aliases, localized_names) @> ARRAY[lower($1)]
+               OR lower(name) = lower($1)
+            ORDER BY symbol_lookup_keys(aliases, localized_names) @> ARRAY[lower($1)] DESC, id
+            LIMIT 1
+            "#,
+        )
+        .bind(key)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => DbError::NotFound,
+            _ => DbError::Sqlx(e),
+        })
+    }
+
+    /// Fails if another symbol already goes by one of `symbol`'s aliases or
+    /// localized names, or by its ID.
+    async fn check_lookup_keys<'e>(executor: impl PgExecutor<'e>, symbol: &Symbol) -> DbResult<()> {
+        let keys = symbol.to_domain().lookup_keys();
+        let taken: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM symbols
+            WHERE id <> $1
+              AND (symbol_lookup_keys(aliases, localized_names) && ($2::TEXT[] || lower($1))
+                   OR lower(id) = ANY($2))
+            ORDER BY id
+            LIMIT 1
+            "#,
+        )
+        .bind(&symbol.id)
+        .bind(&keys)
+        .fetch_optional(executor)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        match taken {
+            Some(id) => Err(DbError::Conflict(format!(
+                "An alias or localized name of {} is already used by symbol {}",
+                symbol.id, id
+            ))),
+            None => Ok(()),
+        }
+    }
+
     pub async fn list(pool: &PgPool, category: Option<&str>) -> DbResult<Vec<Symbol>> {
         match category {
             Some(cat) => sqlx::query_as::<_, Symbol>("SELECT * FROM symbols WHERE category = $1")
                 .bind(cat)
                 .fetch_all(pool)
                 .await
-                .map_err(|e| DbError::Sqlx(e)),
+                .map_err(DbError::Sqlx),
             None => sqlx::query_as::<_, Symbol>("SELECT * FROM symbols")
                 .fetch_all(pool)
                 .await
-                .map_err(|e| DbError::Sqlx(e)),
+                .map_err(DbError::Sqlx),
         }
     }
 
-    pub async fn search(pool: &PgPool, query: &str) -> DbResult<Vec<Symbol>> {
-        let search_pattern = format!("%{}%", query);
-        sqlx::query_as::<_, Symbol>(
-            "SELECT * FROM symbols WHERE name ILIKE $1 OR description ILIKE $1",
+    /// Up to `limit` symbols in `category` that come after `after` in `sort`
+    /// order, and how many symbols there are in the category in all.
+    pub async fn page(
+        pool: &PgPool,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<Symbol>, usize)> {
+        let (keyset, order) = keyset(sort, 3);
+        let sql = format!(
+            r#"
+            SELECT * FROM symbols
+            WHERE ($1::text IS NULL OR category = $1) AND {keyset}
+            ORDER BY {order}
+            LIMIT $2
+            "#
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(category)
+            .bind(limit.map(|limit| limit as i64));
+        let rows = bind_after(rows, sort, after)
+            .try_map(|row: PgRow| Symbol::from_row(&row))
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM symbols WHERE $1::text IS NULL OR category = $1",
+        )
+        .bind(category)
+        .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
+    }
+
+    /// The distinct categories of all symbols, in byte order.
+    pub async fn categories(pool: &PgPool) -> DbResult<Vec<String>> {
+        sqlx::query_scalar(
+            r#"SELECT category FROM symbols GROUP BY category ORDER BY category COLLATE "C""#,
         )
-        .bind(&search_pattern)
         .fetch_all(pool)
         .await
-        .map_err(|e| DbError::Sqlx(e))
+        .map_err(DbError::Sqlx)
     }
 
-    pub async fn create(pool: &PgPool, symbol: &Symbol) -> DbResult<Symbol> {
-        // Check if symbol already exists
-        let exists = sqlx::query("SELECT 1 FROM symbols WHERE id = $1")
-            .bind(&symbol.id)
-            .fetch_optional(pool)
+    /// Full-text search over the weighted `search_vector` document: up to
+    /// `limit` matches in `category` that come after `after` in `sort` order,
+    /// each with its rank, and how many matches there are in all. `query` uses
+    /// web search syntax (`"quoted phrases"`, `or`, `-not`); substring matches
+    /// on the name, aliases, localized names or description are kept, scored by
+    /// rank alone.
+    pub async fn search(
+        pool: &PgPool,
+        query: &str,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<(Symbol, f32)>, usize)> {
+        // Matches against the tsquery `q.query` built from $1
+        const MATCHES: &str = r#"
+            (s.search_vector @@ q.query OR s.name ILIKE $2 OR s.description ILIKE $2
+             OR EXISTS (
+                 SELECT 1
+                 FROM unnest(symbol_lookup_keys(s.aliases, s.localized_names)) AS k
+                 WHERE k ILIKE $2
+             ))
+            AND ($3::text IS NULL OR s.category = $3)
+        "#;
+        let search_pattern = format!("%{}%", query);
+        let (keyset, order) = keyset(sort, 5);
+        let sql = format!(
+            r#"
+            SELECT * FROM (
+                SELECT s.*, ts_rank(s.search_vector, q.ranked) AS score
+                FROM symbols s, (
+                    -- ts_rank scores any query with a negated term as ~0, so rank
+                    -- against the positive terms only
+                    SELECT query, CASE WHEN querytree(query) IN ('', 'T') THEN query
+                                       ELSE querytree(query)::tsquery END AS ranked
+                    FROM websearch_to_tsquery('english', $1) AS query
+                ) AS q
+                WHERE {MATCHES}
+            ) AS hits
+            WHERE {keyset}
+            ORDER BY {order}
+            LIMIT $4
+            "#
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(query)
+            .bind(&search_pattern)
+            .bind(category)
+            .bind(limit.map(|limit| limit as i64));
+        let rows = bind_after(rows, sort, after)
+            .try_map(|row: PgRow| Ok((Symbol::from_row(&row)?, row.try_get("score")?)))
+            .fetch_all(pool)
             .await
-            .map_err(|e| DbError::Sqlx(e))?
-            .is_some();
+            .map_err(DbError::Sqlx)?;
 
-        if exists {
-            return Err(DbError::Conflict(format!(
-                "Symbol with ID {} already exists",
-                symbol.id
-            )));
-        }
+        let count_sql = format!(
+            r#"
+            SELECT COUNT(*)
+            FROM symbols s, websearch_to_tsquery('english', $1) AS q(query)
+            WHERE {MATCHES}
+            "#
+        );
+        let count = sqlx::query_scalar(&count_sql)
+            .bind(query)
+            .bind(&search_pattern)
+            .bind(category)
+            .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
+    }
+
+    /// Symbols with a name, alias or localized name like `query`, scored as
+    /// [`crate::domain::search::name_similarity`] does, most similar first.
+    pub async fn fuzzy_search(
+        pool: &PgPool,
+        query: &str,
+        limit: usize,
+    ) -> DbResult<Vec<(Symbol, f32)>> {
+        sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT s.*, (
+                    SELECT max(GREATEST(
+                        similarity(form, $1),
+                        -- levenshtein refuses strings longer than 255 characters
+                        CASE WHEN char_length(form) <= 255 AND char_length($1) <= 255
+                             THEN 1 - levenshtein(lower(form), lower($1))::real
+                                      / GREATEST(char_length(form), char_length($1), 1)
+                             ELSE 0 END
+                    ))
+                    FROM unnest(
+                        s.name || symbol_lookup_keys(s.aliases, s.localized_names)
+                    ) AS form
+                )::real AS score
+                FROM symbols s
+            ) AS scored
+            WHERE score >= $2
+            ORDER BY score DESC, name
+            LIMIT $3
+            "#,
+        )
+        .bind(query)
+        .bind(FUZZY_THRESHOLD)
+        .bind(limit as i64)
+        .try_map(|row: PgRow| Ok((Symbol::from_row(&row)?, row.try_get("score")?)))
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
+
+    /// Inserts a symbol and its `related_to` edges. Run it in a transaction so
+    /// a failure leaves neither behind.
+    pub async fn create(conn: &mut PgConnection, symbol: &Symbol) -> DbResult<Symbol> {
+        Self::check_lookup_keys(&mut *conn, symbol).await?;
 
         // Convert JSON fields for storage
         let interpretations = serde_json::to_value(&symbol.interpretations).unwrap_or_default();
         let related_symbols = serde_json::to_value(&symbol.related_symbols).unwrap_or_default();
         let properties = serde_json::to_value(&symbol.properties).unwrap_or_default();
+        let aliases = serde_json::to_value(&symbol.aliases).unwrap_or_default();
+        let localized_names = serde_json::to_value(&symbol.localized_names).unwrap_or_default();
 
-        sqlx::query(
+        let inserted = sqlx::query(
             r#"
-            INSERT INTO symbols (id, name, category, description, interpretations, related_symbols, properties)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING *
+            INSERT INTO symbols (id, name, category, description, interpretations, related_symbols, properties,
+                                 aliases, localized_names)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (id) DO NOTHING
             "#,
         )
         .bind(&symbol.id)
@@ -81,37 +323,43 @@ impl SymbolQueries {
         .bind(interpretations)
         .bind(related_symbols)
         .bind(properties)
-        .execute(pool)
+        .bind(aliases)
+        .bind(localized_names)
+        .execute(&mut *conn)
         .await
-        .map_err(|e| DbError::Sqlx(e))?;
+        .map_err(DbError::Sqlx)?;
+
+        if inserted.rows_affected() == 0 {
+            return Err(DbError::Conflict(format!(
+                "Symbol with ID {} already exists",
+                symbol.id
+            )));
+        }
+
+        RelationQueries::sync_related(&mut *conn, &symbol.id, &symbol.related_symbols).await?;
 
         // Return the created symbol
-        Self::get_by_id(pool, &symbol.id).await
+        Self::get_by_id(conn, &symbol.id).await
     }
 
-    pub async fn update(pool: &PgPool, symbol: &Symbol) -> DbResult<Symbol> {
-        // Check if symbol exists
-        let exists = sqlx::query("SELECT 1 FROM symbols WHERE id = $1")
-            .bind(&symbol.id)
-            .fetch_optional(pool)
-            .await
-            .map_err(|e| DbError::Sqlx(e))?
-            .is_some();
-
-        if !exists {
-            return Err(DbError::NotFound);
-        }
+    /// Replaces a symbol and adds its new `related_to` edges. Run it in a
+    /// transaction so a failure leaves neither behind.
+    pub async fn update(conn: &mut PgConnection, symbol: &Symbol) -> DbResult<Symbol> {
+        Self::check_lookup_keys(&mut *conn, symbol).await?;
 
         // Convert JSON fields for storage
         let interpretations = serde_json::to_value(&symbol.interpretations).unwrap_or_default();
         let related_symbols = serde_json::to_value(&symbol.related_symbols).unwrap_or_default();
         let properties = serde_json::to_value(&symbol.properties).unwrap_or_default();
+        let aliases = serde_json::to_value(&symbol.aliases).unwrap_or_default();
+        let localized_names = serde_json::to_value(&symbol.localized_names).unwrap_or_default();
 
-        sqlx::query(
+        let updated = sqlx::query(
             r#"
             UPDATE symbols 
             SET name = $2, category = $3, description = $4, 
-                interpretations = $5, related_symbols = $6, properties = $7
+                interpretations = $5, related_symbols = $6, properties = $7,
+                aliases = $8, localized_names = $9
             WHERE id = $1
             "#,
         )
@@ -122,32 +370,136 @@ impl SymbolQueries {
         .bind(interpretations)
         .bind(related_symbols)
         .bind(properties)
-        .execute(pool)
+        .bind(aliases)
+        .bind(localized_names)
+        .execute(&mut *conn)
         .await
-        .map_err(|e| DbError::Sqlx(e))?;
+        .map_err(DbError::Sqlx)?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        RelationQueries::sync_related(&mut *conn, &symbol.id, &symbol.related_symbols).await?;
 
         // Return the updated symbol
-        Self::get_by_id(pool, &symbol.id).await
+        Self::get_by_id(conn, &symbol.id).await
     }
 
-    pub async fn delete(pool: &PgPool, id: &str) -> DbResult<()> {
-        // Check if symbol exists
-        let exists = sqlx::query("SELECT 1 FROM symbols WHERE id = $1")
+    /// Inserts `symbols`, replacing those that already exist, in three
+    /// statements however many there are: one for the rows, one checking their
+    /// aliases and localized names against the result, and one for their
+    /// `related_to` edges. Run it in a transaction so a failure leaves nothing
+    /// behind. The IDs must be distinct.
+    pub async fn upsert_many(conn: &mut PgConnection, symbols: &[Symbol]) -> DbResult<()> {
+        if symbols.is_empty() {
+            return Ok(());
+        }
+        let ids: Vec<&str> = symbols.iter().map(|s| s.id.as_str()).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO symbols (id, name, category, description, interpretations, related_symbols, properties,
+                                 aliases, localized_names)
+            SELECT id, name, category, description, interpretations, related_symbols, properties,
+                   aliases, localized_names
+            FROM jsonb_to_recordset($1) AS s(
+                id TEXT, name TEXT, category TEXT, description TEXT, interpretations JSONB,
+                related_symbols JSONB, properties JSONB, aliases JSONB, localized_names JSONB
+            )
+            ON CONFLICT (id) DO UPDATE
+            SET name = EXCLUDED.name, category = EXCLUDED.category,
+                description = EXCLUDED.description, interpretations = EXCLUDED.interpretations,
+                related_symbols = EXCLUDED.related_symbols, properties = EXCLUDED.properties,
+                aliases = EXCLUDED.aliases, localized_names = EXCLUDED.localized_names
+            "#,
+        )
+        .bind(sqlx::types::Json(symbols))
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        // Checked once all rows are written, so a batch may move a name from
+        // one of its symbols to another
+        let taken: Option<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT s.id, other.id FROM symbols s
+            JOIN symbols other
+              ON other.id <> s.id
+             AND (symbol_lookup_keys(other.aliases, other.localized_names)
+                      && (symbol_lookup_keys(s.aliases, s.localized_names) || lower(s.id))
+                  OR lower(other.id) = ANY(symbol_lookup_keys(s.aliases, s.localized_names)))
+            WHERE s.id = ANY($1)
+            ORDER BY s.id, other.id
+            LIMIT 1
+            "#,
+        )
+        .bind(&ids)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if let Some((id, other)) = taken {
+            return Err(DbError::Conflict(format!(
+                "An alias or localized name of {} is already used by symbol {}",
+                id, other
+            )));
+        }
+
+        RelationQueries::sync_related_many(conn, &ids).await
+    }
+
+    /// The IDs among `ids` that name a symbol.
+    pub async fn existing_ids<'e>(
+        executor: impl PgExecutor<'e>,
+        ids: &[String],
+    ) -> DbResult<HashSet<String>> {
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let found: Vec<String> = sqlx::query_scalar("SELECT id FROM symbols WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(executor)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        Ok(found.into_iter().collect())
+    }
+
+    /// Deletes a symbol and every reference to it. Run it in a transaction so a
+    /// failure leaves no reference half cleared.
+    pub async fn delete(conn: &mut PgConnection, id: &str) -> DbResult<()> {
+        let deleted = sqlx::query("DELETE FROM symbols WHERE id = $1")
             .bind(id)
-            .fetch_optional(pool)
+            .execute(&mut *conn)
             .await
-            .map_err(|e| DbError::Sqlx(e))?
-            .is_some();
+            .map_err(DbError::Sqlx)?;
 
-        if !exists {
+        if deleted.rows_affected() == 0 {
             return Err(DbError::NotFound);
         }
 
-        sqlx::query("DELETE FROM symbols WHERE id = $1")
+        // The foreign keys take the symbol's own relations, set memberships and
+        // archetype links; references to it from other symbols go here
+        sqlx::query("DELETE FROM symbol_relations WHERE target_id = $1")
             .bind(id)
-            .execute(pool)
+            .execute(&mut *conn)
             .await
-            .map_err(|e| DbError::Sqlx(e))?;
+            .map_err(DbError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            UPDATE symbols
+            SET related_symbols = related_symbols - $1
+            WHERE jsonb_typeof(related_symbols) = 'array'
+              AND related_symbols ? $1
+            "#,
+        )
+        .bind(id)
+        .execute(conn)
+        .await
+        .map_err(DbError::Sqlx)?;
 
         Ok(())
     }
@@ -163,16 +515,17 @@ impl SymbolQueries {
                 description: "Celestial body at the center of our solar system".to_string(),
                 interpretations: [(
                     "default".to_string(),
-                    "Represents life, energy, and vitality".to_string(),
+                    InterpretationLayer::universal("Represents life, energy, and vitality"),
                 )]
-                .iter()
-                .cloned()
+                .into_iter()
                 .collect(),
                 related_symbols: vec!["light".to_string(), "day".to_string()],
                 properties: [("element".to_string(), "fire".to_string())]
                     .iter()
                     .cloned()
                     .collect(),
+                aliases: Vec::new(),
+                localized_names: HashMap::new(),
             },
             Symbol {
                 id: "moon".to_string(),
@@ -181,16 +534,17 @@ impl SymbolQueries {
                 description: "Natural satellite of Earth".to_string(),
                 interpretations: [(
                     "default".to_string(),
-                    "Represents intuition, femininity and cycles".to_string(),
+                    InterpretationLayer::universal("Represents intuition, femininity and cycles"),
                 )]
-                .iter()
-                .cloned()
+                .into_iter()
                 .collect(),
                 related_symbols: vec!["night".to_string(), "tide".to_string()],
                 properties: [("element".to_string(), "water".to_string())]
                     .iter()
                     .cloned()
                     .collect(),
+                aliases: Vec::new(),
+                localized_names: HashMap::new(),
             },
             Symbol {
                 id: "light".to_string(),
@@ -199,16 +553,19 @@ impl SymbolQueries {
                 description: "Electromagnetic radiation visible to the human eye".to_string(),
                 interpretations: [(
                     "default".to_string(),
-                    "Represents knowledge, truth, and enlightenment".to_string(),
+                    InterpretationLayer::universal(
+                        "Represents knowledge, truth, and enlightenment",
+                    ),
                 )]
-                .iter()
-                .cloned()
+                .into_iter()
                 .collect(),
                 related_symbols: vec!["sun".to_string(), "illumination".to_string()],
                 properties: [("element".to_string(), "fire".to_string())]
                     .iter()
                     .cloned()
                     .collect(),
+                aliases: Vec::new(),
+                localized_names: HashMap::new(),
             },
         ];
 
@@ -218,11 +575,13 @@ impl SymbolQueries {
                 .bind(&symbol.id)
                 .fetch_optional(pool)
                 .await
-                .map_err(|e| DbError::Sqlx(e))?
+                .map_err(DbError::Sqlx)?
                 .is_some();
 
             if !exists {
-                Self::create(pool, &symbol).await?;
+                let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+                Self::create(&mut tx, &symbol).await?;
+                tx.commit().await.map_err(DbError::Sqlx)?;
                 info!("Created test symbol: {}", symbol.id);
             } else {
                 info!("Test symbol already exists: {}", symbol.id);
@@ -234,6 +593,59 @@ impl SymbolQueries {
     }
 }
 
+pub struct EmbeddingQueries;
+
+// pgvector's sqlx support targets a newer sqlx, so vectors are sent as REAL[]
+// and cast on the server
+impl EmbeddingQueries {
+    pub async fn set(pool: &PgPool, id: &str, embedding: &[f32]) -> DbResult<()> {
+        let updated =
+            sqlx::query("UPDATE symbols SET embedding = $2::real[]::vector WHERE id = $1")
+                .bind(id)
+                .bind(embedding)
+                .execute(pool)
+                .await
+                .map_err(DbError::Sqlx)?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn list_unembedded(pool: &PgPool) -> DbResult<Vec<Symbol>> {
+        sqlx::query_as::<_, Symbol>("SELECT * FROM symbols WHERE embedding IS NULL")
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)
+    }
+
+    /// The `limit` embedded symbols nearest to `embedding` by cosine distance,
+    /// scored by cosine similarity.
+    pub async fn nearest(
+        pool: &PgPool,
+        embedding: &[f32],
+        limit: usize,
+    ) -> DbResult<Vec<(Symbol, f32)>> {
+        sqlx::query(
+            r#"
+            SELECT s.*, (1 - (s.embedding <=> $1::real[]::vector))::real AS score
+            FROM symbols s
+            WHERE s.embedding IS NOT NULL AND vector_norm(s.embedding) > 0
+            ORDER BY s.embedding <=> $1::real[]::vector
+            LIMIT $2
+            "#,
+        )
+        .bind(embedding)
+        .bind(limit as i64)
+        .try_map(|row: PgRow| Ok((Symbol::from_row(&row)?, row.try_get("score")?)))
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
+}
+
 pub struct SymbolSetQueries;
 
 impl SymbolSetQueries {
@@ -248,126 +660,718 @@ impl SymbolSetQueries {
             })
     }
 
-    pub async fn list(pool: &PgPool, category: Option<&str>) -> DbResult<Vec<SymbolSet>> {
-        match category {
-            Some(cat) => {
-                sqlx::query_as::<_, SymbolSet>("SELECT * FROM symbol_sets WHERE category = $1")
-                    .bind(cat)
-                    .fetch_all(pool)
-                    .await
-                    .map_err(|e| DbError::Sqlx(e))
-            }
-            None => sqlx::query_as::<_, SymbolSet>("SELECT * FROM symbol_sets")
-                .fetch_all(pool)
-                .await
-                .map_err(|e| DbError::Sqlx(e)),
-        }
+    /// Up to `limit` sets in `category` that come after `after` in `sort`
+    /// order, and how many sets there are in the category in all.
+    pub async fn page(
+        pool: &PgPool,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<SymbolSet>, usize)> {
+        let (keyset, order) = keyset(sort, 3);
+        let sql = format!(
+            r#"
+            SELECT * FROM symbol_sets
+            WHERE ($1::text IS NULL OR category = $1) AND {keyset}
+            ORDER BY {order}
+            LIMIT $2
+            "#
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(category)
+            .bind(limit.map(|limit| limit as i64));
+        let rows = bind_after(rows, sort, after)
+            .try_map(|row: PgRow| SymbolSet::from_row(&row))
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM symbol_sets WHERE $1::text IS NULL OR category = $1",
+        )
+        .bind(category)
+        .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
     }
 
-    pub async fn search(pool: &PgPool, query: &str) -> DbResult<Vec<SymbolSet>> {
+    /// Like [`Self::page`], but only the sets whose name or description
+    /// contain `query`.
+    pub async fn search(
+        pool: &PgPool,
+        query: &str,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<SymbolSet>, usize)> {
         let search_pattern = format!("%{}%", query);
-        sqlx::query_as::<_, SymbolSet>(
-            "SELECT * FROM symbol_sets WHERE name ILIKE $1 OR description ILIKE $1",
+        const MATCHES: &str = r#"
+            (name ILIKE $1 OR description ILIKE $1)
+            AND ($2::text IS NULL OR category = $2)
+        "#;
+        let (keyset, order) = keyset(sort, 4);
+        let sql = format!(
+            r#"
+            SELECT * FROM symbol_sets
+            WHERE {MATCHES} AND {keyset}
+            ORDER BY {order}
+            LIMIT $3
+            "#
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(&search_pattern)
+            .bind(category)
+            .bind(limit.map(|limit| limit as i64));
+        let rows = bind_after(rows, sort, after)
+            .try_map(|row: PgRow| SymbolSet::from_row(&row))
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count_sql = format!("SELECT COUNT(*) FROM symbol_sets WHERE {MATCHES}");
+        let count = sqlx::query_scalar(&count_sql)
+            .bind(&search_pattern)
+            .bind(category)
+            .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
+    }
+
+    /// The members of the sets with `set_ids`, as (set ID, symbol) pairs in set order.
+    pub async fn members(pool: &PgPool, set_ids: &[String]) -> DbResult<Vec<(String, Symbol)>> {
+        sqlx::query(
+            r#"
+            SELECT m.set_id, s.* FROM symbol_set_members m
+            JOIN symbols s ON s.id = m.symbol_id
+            WHERE m.set_id = ANY($1)
+            ORDER BY m.set_id, m.position, m.symbol_id
+            "#,
         )
-        .bind(&search_pattern)
+        .bind(set_ids)
+        .try_map(|row: PgRow| Ok((row.try_get("set_id")?, Symbol::from_row(&row)?)))
         .fetch_all(pool)
         .await
-        .map_err(|e| DbError::Sqlx(e))
+        .map_err(DbError::Sqlx)
+    }
+
+    /// Makes `symbol_ids` the members of a set, in that order. IDs that name no
+    /// symbol are skipped.
+    async fn replace_members(
+        conn: &mut sqlx::PgConnection,
+        set_id: &str,
+        symbol_ids: &[String],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM symbol_set_members WHERE set_id = $1")
+            .bind(set_id)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO symbol_set_members (set_id, symbol_id, position)
+            SELECT $1, member.symbol_id, member.position::INTEGER
+            FROM unnest($2::text[]) WITH ORDINALITY AS member(symbol_id, position)
+            WHERE EXISTS (SELECT 1 FROM symbols s WHERE s.id = member.symbol_id)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(set_id)
+        .bind(symbol_ids)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
     }
 
     pub async fn create(pool: &PgPool, set: &SymbolSet) -> DbResult<SymbolSet> {
-        // Check if set already exists
-        let exists = sqlx::query("SELECT 1 FROM symbol_sets WHERE id = $1")
-            .bind(&set.id)
-            .fetch_optional(pool)
-            .await
-            .map_err(|e| DbError::Sqlx(e))?
-            .is_some();
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
 
-        if exists {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO symbol_sets (id, name, category, description)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(&set.id)
+        .bind(&set.name)
+        .bind(&set.category)
+        .bind(&set.description)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if inserted.rows_affected() == 0 {
             return Err(DbError::Conflict(format!(
                 "SymbolSet with ID {} already exists",
                 set.id
             )));
         }
 
-        // Convert JSON field for storage
-        let symbols_map = serde_json::to_value(&set.symbols_map).unwrap_or_default();
+        Self::replace_members(&mut tx, &set.id, &set.symbol_ids)
+            .await
+            .map_err(DbError::Sqlx)?;
+        tx.commit().await.map_err(DbError::Sqlx)?;
 
-        sqlx::query(
+        // Return the created set
+        Self::get_by_id(pool, &set.id).await
+    }
+
+    pub async fn update(pool: &PgPool, set: &SymbolSet) -> DbResult<SymbolSet> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        let updated = sqlx::query(
             r#"
-            INSERT INTO symbol_sets (id, name, category, description, symbols)
-            VALUES ($1, $2, $3, $4, $5)
+            UPDATE symbol_sets
+            SET name = $2, category = $3, description = $4
+            WHERE id = $1
             "#,
         )
         .bind(&set.id)
         .bind(&set.name)
         .bind(&set.category)
         .bind(&set.description)
-        .bind(symbols_map)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| DbError::Sqlx(e))?;
+        .map_err(DbError::Sqlx)?;
 
-        // Return the created set
+        if updated.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Self::replace_members(&mut tx, &set.id, &set.symbol_ids)
+            .await
+            .map_err(DbError::Sqlx)?;
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        // Return the updated set
         Self::get_by_id(pool, &set.id).await
     }
 
-    pub async fn update(pool: &PgPool, set: &SymbolSet) -> DbResult<SymbolSet> {
-        // Check if set exists
-        let exists = sqlx::query("SELECT 1 FROM symbol_sets WHERE id = $1")
-            .bind(&set.id)
-            .fetch_optional(pool)
+    pub async fn delete(pool: &PgPool, id: &str) -> DbResult<()> {
+        // Members go with the set through the foreign key
+        let deleted = sqlx::query("DELETE FROM symbol_sets WHERE id = $1")
+            .bind(id)
+            .execute(pool)
             .await
-            .map_err(|e| DbError::Sqlx(e))?
+            .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Adds a symbol to the end of a set; adding a member again changes nothing.
+    pub async fn add_member(pool: &PgPool, set_id: &str, symbol_id: &str) -> DbResult<()> {
+        let both_exist = sqlx::query(
+            r#"
+            SELECT 1
+            WHERE EXISTS (SELECT 1 FROM symbol_sets WHERE id = $1)
+              AND EXISTS (SELECT 1 FROM symbols WHERE id = $2)
+            "#,
+        )
+        .bind(set_id)
+        .bind(symbol_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(DbError::Sqlx)?
+        .is_some();
+
+        if !both_exist {
+            return Err(DbError::NotFound);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO symbol_set_members (set_id, symbol_id, position)
+            SELECT $1, $2, coalesce(max(position), 0) + 1
+            FROM symbol_set_members WHERE set_id = $1
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(set_id)
+        .bind(symbol_id)
+        .execute(pool)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    pub async fn remove_member(pool: &PgPool, set_id: &str, symbol_id: &str) -> DbResult<()> {
+        let deleted =
+            sqlx::query("DELETE FROM symbol_set_members WHERE set_id = $1 AND symbol_id = $2")
+                .bind(set_id)
+                .bind(symbol_id)
+                .execute(pool)
+                .await
+                .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    /// The sets a symbol belongs to, by name.
+    pub async fn list_for_symbol(pool: &PgPool, symbol_id: &str) -> DbResult<Vec<SymbolSet>> {
+        sqlx::query_as::<_, SymbolSet>(
+            r#"
+            SELECT ss.* FROM symbol_sets ss
+            JOIN symbol_set_members m ON m.set_id = ss.id
+            WHERE m.symbol_id = $1
+            ORDER BY ss.name, ss.id
+            "#,
+        )
+        .bind(symbol_id)
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
+}
+
+pub struct RelationQueries;
+
+impl RelationQueries {
+    pub async fn list(
+        pool: &PgPool,
+        symbol_id: &str,
+        direction: RelationDirection,
+    ) -> DbResult<Vec<SymbolRelation>> {
+        let sql = match direction {
+            RelationDirection::Outgoing => "SELECT * FROM symbol_relations WHERE source_id = $1",
+            RelationDirection::Incoming => "SELECT * FROM symbol_relations WHERE target_id = $1",
+            RelationDirection::Both => {
+                "SELECT * FROM symbol_relations WHERE source_id = $1 OR target_id = $1"
+            }
+        };
+
+        sqlx::query_as::<_, SymbolRelation>(sql)
+            .bind(symbol_id)
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)
+    }
+
+    pub async fn add(pool: &PgPool, relation: &SymbolRelation) -> DbResult<SymbolRelation> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        let exists = sqlx::query("SELECT 1 FROM symbols WHERE id = $1")
+            .bind(&relation.source_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DbError::Sqlx)?
             .is_some();
 
         if !exists {
             return Err(DbError::NotFound);
         }
 
-        // Convert JSON field for storage
-        let symbols_map = serde_json::to_value(&set.symbols_map).unwrap_or_default();
+        let created = sqlx::query_as::<_, SymbolRelation>(
+            r#"
+            INSERT INTO symbol_relations (source_id, target_id, relation_type, weight)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (source_id, target_id, relation_type)
+            DO UPDATE SET weight = EXCLUDED.weight
+            RETURNING *
+            "#,
+        )
+        .bind(&relation.source_id)
+        .bind(&relation.target_id)
+        .bind(&relation.relation_type)
+        .bind(relation.weight)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
 
+        // Keep the legacy related_symbols list in step with outgoing edges
         sqlx::query(
             r#"
-            UPDATE symbol_sets 
-            SET name = $2, category = $3, description = $4, symbols = $5
+            UPDATE symbols
+            SET related_symbols = related_symbols || to_jsonb($2::TEXT)
             WHERE id = $1
+              AND jsonb_typeof(related_symbols) = 'array'
+              AND NOT related_symbols ? $2
             "#,
         )
-        .bind(&set.id)
-        .bind(&set.name)
-        .bind(&set.category)
-        .bind(&set.description)
-        .bind(symbols_map)
-        .execute(pool)
+        .bind(&relation.source_id)
+        .bind(&relation.target_id)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| DbError::Sqlx(e))?;
+        .map_err(DbError::Sqlx)?;
 
-        // Return the updated set
-        Self::get_by_id(pool, &set.id).await
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        Ok(created)
     }
 
-    pub async fn delete(pool: &PgPool, id: &str) -> DbResult<()> {
-        // Check if set exists
-        let exists = sqlx::query("SELECT 1 FROM symbol_sets WHERE id = $1")
+    pub async fn remove(
+        pool: &PgPool,
+        source_id: &str,
+        target_id: &str,
+        relation_type: &str,
+    ) -> DbResult<()> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        let deleted = sqlx::query(
+            "DELETE FROM symbol_relations WHERE source_id = $1 AND target_id = $2 AND relation_type = $3",
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .bind(relation_type)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        // Drop the target from related_symbols once no edge of any type remains
+        sqlx::query(
+            r#"
+            UPDATE symbols
+            SET related_symbols = related_symbols - $2
+            WHERE id = $1
+              AND jsonb_typeof(related_symbols) = 'array'
+              AND NOT EXISTS (
+                SELECT 1 FROM symbol_relations r
+                WHERE r.source_id = $1 AND r.target_id = $2
+              )
+            "#,
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Adds `related_to` edges for entries of `related_symbols` that have no edge yet,
+    /// and removes those whose target is no longer listed.
+    pub async fn sync_related(
+        conn: &mut PgConnection,
+        symbol_id: &str,
+        related: &[String],
+    ) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM symbol_relations
+            WHERE source_id = $1 AND relation_type = 'related_to'
+              AND NOT target_id = ANY($2)
+            "#,
+        )
+        .bind(symbol_id)
+        .bind(related)
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if related.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO symbol_relations (source_id, target_id, relation_type)
+            SELECT DISTINCT $1, t.target_id, 'related_to'
+            FROM UNNEST($2::TEXT[]) AS t(target_id)
+            WHERE t.target_id <> '' AND t.target_id <> $1
+              AND NOT EXISTS (
+                SELECT 1 FROM symbol_relations r
+                WHERE r.source_id = $1 AND r.target_id = t.target_id
+              )
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(symbol_id)
+        .bind(related)
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// [`Self::sync_related`] for each of the stored symbols `symbol_ids`, in
+    /// two statements.
+    pub async fn sync_related_many(conn: &mut PgConnection, symbol_ids: &[&str]) -> DbResult<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM symbol_relations r
+            USING symbols s
+            WHERE r.source_id = s.id AND s.id = ANY($1)
+              AND r.relation_type = 'related_to'
+              AND NOT (jsonb_typeof(s.related_symbols) = 'array'
+                       AND s.related_symbols ? r.target_id)
+            "#,
+        )
+        .bind(symbol_ids)
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO symbol_relations (source_id, target_id, relation_type)
+            SELECT DISTINCT s.id, rel.target_id, 'related_to'
+            FROM symbols s
+            CROSS JOIN LATERAL jsonb_array_elements_text(
+                CASE WHEN jsonb_typeof(s.related_symbols) = 'array' THEN s.related_symbols ELSE '[]'::JSONB END
+            ) AS rel(target_id)
+            WHERE s.id = ANY($1)
+              AND rel.target_id <> '' AND rel.target_id <> s.id
+              AND NOT EXISTS (
+                SELECT 1 FROM symbol_relations r
+                WHERE r.source_id = s.id AND r.target_id = rel.target_id
+              )
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(symbol_ids)
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+}
+
+pub struct ArchetypeQueries;
+
+impl ArchetypeQueries {
+    pub async fn get_by_id(pool: &PgPool, id: &str) -> DbResult<Archetype> {
+        sqlx::query_as::<_, Archetype>("SELECT * FROM archetypes WHERE id = $1")
             .bind(id)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => DbError::NotFound,
+                _ => DbError::Sqlx(e),
+            })
+    }
+
+    pub async fn list(pool: &PgPool) -> DbResult<Vec<Archetype>> {
+        sqlx::query_as::<_, Archetype>("SELECT * FROM archetypes ORDER BY name")
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)
+    }
+
+    pub async fn create(pool: &PgPool, archetype: &Archetype) -> DbResult<Archetype> {
+        let exists = sqlx::query("SELECT 1 FROM archetypes WHERE id = $1")
+            .bind(&archetype.id)
             .fetch_optional(pool)
             .await
-            .map_err(|e| DbError::Sqlx(e))?
+            .map_err(DbError::Sqlx)?
             .is_some();
 
-        if !exists {
-            return Err(DbError::NotFound);
+        if exists {
+            return Err(DbError::Conflict(format!(
+                "Archetype with ID {} already exists",
+                archetype.id
+            )));
         }
 
-        sqlx::query("DELETE FROM symbol_sets WHERE id = $1")
+        sqlx::query_as::<_, Archetype>(
+            r#"
+            INSERT INTO archetypes (id, name, description, parent_id)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(&archetype.id)
+        .bind(&archetype.name)
+        .bind(&archetype.description)
+        .bind(&archetype.parent_id)
+        .fetch_one(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
+
+    pub async fn update(pool: &PgPool, archetype: &Archetype) -> DbResult<Archetype> {
+        sqlx::query_as::<_, Archetype>(
+            r#"
+            UPDATE archetypes
+            SET name = $2, description = $3, parent_id = $4
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(&archetype.id)
+        .bind(&archetype.name)
+        .bind(&archetype.description)
+        .bind(&archetype.parent_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => DbError::NotFound,
+            _ => DbError::Sqlx(e),
+        })
+    }
+
+    pub async fn delete(pool: &PgPool, id: &str) -> DbResult<()> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        // Move children up to the deleted archetype's parent
+        sqlx::query(
+            r#"
+            UPDATE archetypes
+            SET parent_id = (SELECT parent_id FROM archetypes WHERE id = $1)
+            WHERE parent_id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        let deleted = sqlx::query("DELETE FROM archetypes WHERE id = $1")
             .bind(id)
-            .execute(pool)
+            .execute(&mut *tx)
             .await
-            .map_err(|e| DbError::Sqlx(e))?;
+            .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    pub async fn link_symbol(pool: &PgPool, symbol_id: &str, archetype_id: &str) -> DbResult<()> {
+        let both_exist = sqlx::query(
+            r#"
+            SELECT 1
+            WHERE EXISTS (SELECT 1 FROM symbols WHERE id = $1)
+              AND EXISTS (SELECT 1 FROM archetypes WHERE id = $2)
+            "#,
+        )
+        .bind(symbol_id)
+        .bind(archetype_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(DbError::Sqlx)?
+        .is_some();
+
+        if !both_exist {
+            return Err(DbError::NotFound);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO symbol_archetypes (symbol_id, archetype_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(symbol_id)
+        .bind(archetype_id)
+        .execute(pool)
+        .await
+        .map_err(DbError::Sqlx)?;
 
         Ok(())
     }
+
+    pub async fn unlink_symbol(pool: &PgPool, symbol_id: &str, archetype_id: &str) -> DbResult<()> {
+        let deleted =
+            sqlx::query("DELETE FROM symbol_archetypes WHERE symbol_id = $1 AND archetype_id = $2")
+                .bind(symbol_id)
+                .bind(archetype_id)
+                .execute(pool)
+                .await
+                .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Up to `limit` symbols in `category` linked to `archetype_id`, or with
+    /// `include_descendants` to any archetype below it, that come after `after`
+    /// in `sort` order, and how many such symbols there are in all.
+    pub async fn list_symbols(
+        pool: &PgPool,
+        archetype_id: &str,
+        include_descendants: bool,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<Symbol>, usize)> {
+        // The symbols linked to archetype $1, or with $2 to any below it, in category $3
+        const LINKED: &str = r#"
+            WITH RECURSIVE tree AS (
+                SELECT id FROM archetypes WHERE id = $1
+                UNION
+                SELECT a.id FROM archetypes a JOIN tree t ON a.parent_id = t.id WHERE $2
+            ),
+            linked AS (
+                SELECT * FROM symbols
+                WHERE id IN (
+                    SELECT symbol_id FROM symbol_archetypes
+                    WHERE archetype_id IN (SELECT id FROM tree)
+                )
+                  AND ($3::text IS NULL OR category = $3)
+            )
+        "#;
+        let (keyset, order) = keyset(sort, 5);
+        let sql = format!(
+            r#"
+            {LINKED}
+            SELECT * FROM linked
+            WHERE {keyset}
+            ORDER BY {order}
+            LIMIT $4
+            "#
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(archetype_id)
+            .bind(include_descendants)
+            .bind(category)
+            .bind(limit.map(|limit| limit as i64));
+        let rows = bind_after(rows, sort, after)
+            .try_map(|row: PgRow| Symbol::from_row(&row))
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count_sql = format!("{LINKED} SELECT COUNT(*) FROM linked");
+        let count = sqlx::query_scalar(&count_sql)
+            .bind(archetype_id)
+            .bind(include_descendants)
+            .bind(category)
+            .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
+    }
+
+    pub async fn list_for_symbol(pool: &PgPool, symbol_id: &str) -> DbResult<Vec<Archetype>> {
+        sqlx::query_as::<_, Archetype>(
+            r#"
+            SELECT a.* FROM archetypes a
+            JOIN symbol_archetypes sa ON sa.archetype_id = a.id
+            WHERE sa.symbol_id = $1
+            ORDER BY a.name
+            "#,
+        )
+        .bind(symbol_id)
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
 }
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::db::pool::DbError;
+use crate::db::queries::ArchetypeQueries;
+use crate::db::repository::interfaces::{
+    ArchetypeRepository, Repository, RepositoryError, RepositoryResult,
+};
+use crate::db::repository::pagination::{scope_filter, ListQuery, Page, SortOrder};
+use crate::domain::archetypes::creates_cycle;
+use crate::domain::{Archetype, Symbol};
+
+pub struct PgArchetypeRepository {
+    pool: PgPool,
+}
+
+impl PgArchetypeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        PgArchetypeRepository { pool }
+    }
+
+    /// Checks that the parent exists and would not make the hierarchy circular.
+    async fn check_parent(&self, archetype: &Archetype) -> RepositoryResult<()> {
+        let Some(parent_id) = archetype.parent_id.as_deref() else {
+            return Ok(());
+        };
+
+        let archetypes = self.list_archetypes().await?;
+        if !archetypes.iter().any(|a| a.id == parent_id) {
+            return Err(RepositoryError::Validation(format!(
+                "Parent archetype {} does not exist",
+                parent_id
+            )));
+        }
+        if creates_cycle(&archetypes, &archetype.id, parent_id) {
+            return Err(RepositoryError::Validation(format!(
+                "Archetype {} cannot be placed under its own descendant {}",
+                archetype.id, parent_id
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Repository for PgArchetypeRepository {}
+
+fn map_db_error(e: DbError, not_found: impl FnOnce() -> String) -> RepositoryError {
+    match e {
+        DbError::NotFound => RepositoryError::NotFound(not_found()),
+        DbError::Conflict(msg) => RepositoryError::Conflict(msg),
+        _ => RepositoryError::Internal(format!("Database error: {}", e)),
+    }
+}
+
+#[async_trait]
+impl ArchetypeRepository for PgArchetypeRepository {
+    async fn get_archetype(&self, id: &str) -> RepositoryResult<Archetype> {
+        ArchetypeQueries::get_by_id(&self.pool, id)
+            .await
+            .map(|a| a.to_domain())
+            .map_err(|e| map_db_error(e, || format!("Archetype with id {} not found", id)))
+    }
+
+    async fn list_archetypes(&self) -> RepositoryResult<Vec<Archetype>> {
+        let db_archetypes = ArchetypeQueries::list(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        Ok(db_archetypes.iter().map(|a| a.to_domain()).collect())
+    }
+
+    async fn create_archetype(&self, archetype: Archetype) -> RepositoryResult<Archetype> {
+        archetype.validate().map_err(RepositoryError::Validation)?;
+        self.check_parent(&archetype).await?;
+
+        let db_archetype = crate::db::models::Archetype::from_domain(archetype);
+        ArchetypeQueries::create(&self.pool, &db_archetype)
+            .await
+            .map(|a| a.to_domain())
+            .map_err(|e| map_db_error(e, String::new))
+    }
+
+    async fn update_archetype(&self, archetype: Archetype) -> RepositoryResult<Archetype> {
+        archetype.validate().map_err(RepositoryError::Validation)?;
+        self.check_parent(&archetype).await?;
+
+        let id = archetype.id.clone();
+        let db_archetype = crate::db::models::Archetype::from_domain(archetype);
+        ArchetypeQueries::update(&self.pool, &db_archetype)
+            .await
+            .map(|a| a.to_domain())
+            .map_err(|e| map_db_error(e, || format!("Archetype with id {} not found", id)))
+    }
+
+    async fn delete_archetype(&self, id: &str) -> RepositoryResult<()> {
+        ArchetypeQueries::delete(&self.pool, id)
+            .await
+            .map_err(|e| map_db_error(e, || format!("Archetype with id {} not found", id)))
+    }
+
+    async fn link_symbol(&self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()> {
+        ArchetypeQueries::link_symbol(&self.pool, symbol_id, archetype_id)
+            .await
+            .map_err(|e| {
+                map_db_error(e, || {
+                    format!(
+                        "Symbol {} or archetype {} not found",
+                        symbol_id, archetype_id
+                    )
+                })
+            })
+    }
+
+    async fn unlink_symbol(&self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()> {
+        ArchetypeQueries::unlink_symbol(&self.pool, symbol_id, archetype_id)
+            .await
+            .map_err(|e| {
+                map_db_error(e, || {
+                    format!(
+                        "Symbol {} is not linked to archetype {}",
+                        symbol_id, archetype_id
+                    )
+                })
+            })
+    }
+
+    async fn list_symbols_by_archetype(
+        &self,
+        archetype_id: &str,
+        include_descendants: bool,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<Symbol>> {
+        let filter = [archetype_id, scope_filter(include_descendants)];
+        let (sort, after) = query.resolve(SortOrder::Name, false, &filter)?;
+
+        // Distinguish an unknown archetype from one without symbols
+        self.get_archetype(archetype_id).await?;
+
+        let (db_symbols, total_count) = ArchetypeQueries::list_symbols(
+            &self.pool,
+            archetype_id,
+            include_descendants,
+            query.category.as_deref(),
+            sort,
+            after.as_ref(),
+            query.limit.map(|limit| limit + 1),
+        )
+        .await
+        .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        let symbols = db_symbols.iter().map(|s| s.to_domain()).collect();
+        Ok(Page::from_rows(symbols, query, &filter, sort, total_count))
+    }
+
+    async fn list_archetypes_for_symbol(
+        &self,
+        symbol_id: &str,
+    ) -> RepositoryResult<Vec<Archetype>> {
+        let db_archetypes = ArchetypeQueries::list_for_symbol(&self.pool, symbol_id)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        Ok(db_archetypes.iter().map(|a| a.to_domain()).collect())
+    }
+}
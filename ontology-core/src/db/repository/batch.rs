@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+//! Batches of symbol writes, applied by [`SymbolRepository::apply_batch`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::db::repository::interfaces::{RepositoryError, RepositoryResult, SymbolRepository};
+use crate::domain::Symbol;
+
+/// Most operations the API server accepts in one batch.
+pub const MAX_BATCH_OPERATIONS: usize = 1000;
+
+/// One write in a batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SymbolOperation {
+    Create {
+        symbol: Symbol,
+    },
+    Update {
+        symbol: Symbol,
+    },
+    /// Creates the symbol, or replaces it if it exists
+    Upsert {
+        symbol: Symbol,
+    },
+    Delete {
+        id: String,
+    },
+}
+
+impl SymbolOperation {
+    /// The ID of the symbol the operation writes.
+    pub fn symbol_id(&self) -> &str {
+        match self {
+            SymbolOperation::Create { symbol }
+            | SymbolOperation::Update { symbol }
+            | SymbolOperation::Upsert { symbol } => &symbol.id,
+            SymbolOperation::Delete { id } => id,
+        }
+    }
+
+    /// The `op` tag of the operation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SymbolOperation::Create { .. } => "create",
+            SymbolOperation::Update { .. } => "update",
+            SymbolOperation::Upsert { .. } => "upsert",
+            SymbolOperation::Delete { .. } => "delete",
+        }
+    }
+
+    /// Trims the symbol ID, and the name and category of a written symbol, as
+    /// [`Symbol::normalize`] does.
+    pub fn normalize(&mut self) {
+        match self {
+            SymbolOperation::Create { symbol }
+            | SymbolOperation::Update { symbol }
+            | SymbolOperation::Upsert { symbol } => symbol.normalize(),
+            SymbolOperation::Delete { id } => *id = id.trim().to_string(),
+        }
+    }
+
+    /// Checks what can be checked without the store: IDs and names are not
+    /// blank and the symbol passes [`Symbol::validate`].
+    pub fn validate(&self) -> RepositoryResult<()> {
+        if self.symbol_id().trim().is_empty() {
+            return Err(RepositoryError::Validation(
+                "Symbol ID cannot be empty".to_string(),
+            ));
+        }
+
+        match self {
+            SymbolOperation::Create { symbol }
+            | SymbolOperation::Update { symbol }
+            | SymbolOperation::Upsert { symbol } => {
+                if symbol.name.trim().is_empty() {
+                    return Err(RepositoryError::Validation(
+                        "Symbol name cannot be empty".to_string(),
+                    ));
+                }
+                symbol.validate().map_err(RepositoryError::Validation)
+            }
+            SymbolOperation::Delete { .. } => Ok(()),
+        }
+    }
+}
+
+/// What became of one operation of a batch.
+#[derive(Debug)]
+pub enum OperationOutcome {
+    /// The symbol was created, as stored
+    Created(Symbol),
+    /// The symbol was replaced, as stored
+    Updated(Symbol),
+    Deleted,
+    Failed(RepositoryError),
+    /// Not applied, or rolled back, because another operation of an atomic
+    /// batch failed
+    Aborted,
+}
+
+impl OperationOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(
+            self,
+            OperationOutcome::Created(_) | OperationOutcome::Updated(_) | OperationOutcome::Deleted
+        )
+    }
+}
+
+/// The outcome of every operation of a batch, in the order they were given.
+#[derive(Debug)]
+pub struct BatchResult {
+    pub outcomes: Vec<OperationOutcome>,
+    /// False when an atomic batch was rolled back; the operations that
+    /// succeeded are stored otherwise
+    pub committed: bool,
+}
+
+impl BatchResult {
+    pub fn committed(outcomes: Vec<OperationOutcome>) -> Self {
+        BatchResult {
+            outcomes,
+            committed: true,
+        }
+    }
+
+    /// An atomic batch of `len` operations rolled back because operation
+    /// `index` failed with `error`.
+    pub fn rolled_back(len: usize, index: usize, error: RepositoryError) -> Self {
+        let mut outcomes: Vec<_> = (0..len).map(|_| OperationOutcome::Aborted).collect();
+        outcomes[index] = OperationOutcome::Failed(error);
+        BatchResult {
+            outcomes,
+            committed: false,
+        }
+    }
+
+    pub fn succeeded(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.is_success()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.outcomes.len() - self.succeeded()
+    }
+}
+
+/// Checks the symbols of a bulk upsert: each passes [`Symbol::validate`] and no
+/// two share an ID, which a single upsert cannot write.
+pub fn validate_upserts(symbols: &[Symbol]) -> RepositoryResult<()> {
+    let mut seen = HashSet::new();
+    for symbol in symbols {
+        symbol.validate().map_err(RepositoryError::Validation)?;
+        if !seen.insert(symbol.id.as_str()) {
+            return Err(RepositoryError::Validation(format!(
+                "Symbol {} appears more than once in the batch",
+                symbol.id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The IDs the creates, updates and upserts of `operations` write. An atomic
+/// batch stores them all or none, so its symbols may name any of them as
+/// related symbols, wherever they come in the batch.
+pub fn written_ids(operations: &[SymbolOperation]) -> HashSet<String> {
+    operations
+        .iter()
+        .filter(|operation| !matches!(operation, SymbolOperation::Delete { .. }))
+        .map(|operation| operation.symbol_id().to_string())
+        .collect()
+}
+
+/// Applies `operations` one at a time through the repository's own methods,
+/// so each succeeds or fails on its own.
+pub async fn apply_each<R: SymbolRepository + ?Sized>(
+    repository: &R,
+    operations: Vec<SymbolOperation>,
+) -> BatchResult {
+    let mut outcomes = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let outcome = apply(repository, operation)
+            .await
+            .unwrap_or_else(OperationOutcome::Failed);
+        outcomes.push(outcome);
+    }
+    BatchResult::committed(outcomes)
+}
+
+async fn apply<R: SymbolRepository + ?Sized>(
+    repository: &R,
+    operation: SymbolOperation,
+) -> RepositoryResult<OperationOutcome> {
+    operation.validate()?;
+
+    match operation {
+        SymbolOperation::Create { symbol } => repository
+            .create_symbol(symbol)
+            .await
+            .map(OperationOutcome::Created),
+        SymbolOperation::Update { symbol } => repository
+            .update_symbol(symbol)
+            .await
+            .map(OperationOutcome::Updated),
+        SymbolOperation::Upsert { symbol } => {
+            match repository.update_symbol(symbol.clone()).await {
+                Err(RepositoryError::NotFound(_)) => repository
+                    .create_symbol(symbol)
+                    .await
+                    .map(OperationOutcome::Created),
+                result => result.map(OperationOutcome::Updated),
+            }
+        }
+        SymbolOperation::Delete { id } => repository
+            .delete_symbol(&id)
+            .await
+            .map(|()| OperationOutcome::Deleted),
+    }
+}
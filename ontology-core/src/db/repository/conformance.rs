@@ -0,0 +1,1844 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # Repository Conformance Suite
+ *
+ * Backend-agnostic checks that every [`RepositoryFactory`] implementation is
+ * expected to pass, so the PostgreSQL, SQLite and in-memory backends can be held
+ * to the same behaviour. Call [`run_all`] from a test with the factory under test:
+ *
+ * ```no_run
+ * # async fn example() {
+ * use ontology_core::db::repository::{conformance, InMemoryRepositoryFactory};
+ *
+ * conformance::run_all(&InMemoryRepositoryFactory::new()).await;
+ * # }
+ * ```
+ *
+ * Each check panics with a descriptive message on the first mismatch. Every
+ * record it creates gets an ID and category unique to the run and is removed
+ * again afterwards, so the suite is safe to point at a shared database.
+ */
+
+use uuid::Uuid;
+
+use crate::db::repository::batch::{OperationOutcome, SymbolOperation};
+use crate::db::repository::factory::RepositoryFactory;
+use crate::db::repository::interfaces::{
+    RepositoryError, RepositoryResult, SymbolRepository, SymbolSetRepository,
+};
+use crate::db::repository::pagination::{ListQuery, SortOrder};
+use crate::domain::{
+    RelationDirection, RelationType, SearchHit, Symbol, SymbolRelation, SymbolSet,
+};
+use crate::embedding::EMBEDDING_DIMENSIONS;
+
+/// Runs every check in the suite against `factory`.
+pub async fn run_all(factory: &dyn RepositoryFactory) {
+    symbol_crud(factory).await;
+    symbol_conflicts_and_not_found(factory).await;
+    symbol_search(factory).await;
+    symbol_fuzzy_search(factory).await;
+    symbol_aliases(factory).await;
+    symbol_embeddings(factory).await;
+    symbol_category_filter(factory).await;
+    symbol_pagination(factory).await;
+    symbol_delete_cascades(factory).await;
+    symbol_related_edges(factory).await;
+    symbol_upsert_many(factory).await;
+    symbol_batches(factory).await;
+    symbol_set_crud(factory).await;
+    symbol_set_conflicts_and_not_found(factory).await;
+    symbol_set_search_and_category_filter(factory).await;
+    symbol_set_membership(factory).await;
+    symbol_set_member_links(factory).await;
+    symbol_set_member_order(factory).await;
+    symbol_set_pagination(factory).await;
+}
+
+/// IDs and categories scoped to a single check, so runs never collide.
+struct Run {
+    prefix: String,
+}
+
+impl Run {
+    fn new(check: &str) -> Self {
+        let id = Uuid::new_v4().simple().to_string();
+        Run {
+            prefix: format!("conformance-{}-{}", check, &id[..8]),
+        }
+    }
+
+    fn id(&self, name: &str) -> String {
+        format!("{}-{}", self.prefix, name)
+    }
+
+    fn category(&self) -> String {
+        format!("{}-category", self.prefix)
+    }
+
+    fn symbol(&self, name: &str, description: &str) -> Symbol {
+        Symbol::new(
+            self.id(&name.to_lowercase()),
+            format!("{} {}", name, self.prefix),
+            self.category(),
+            description.to_string(),
+        )
+    }
+
+    fn symbol_set(&self, name: &str, description: &str) -> SymbolSet {
+        SymbolSet::new(
+            self.id(&name.to_lowercase()),
+            format!("{} {}", name, self.prefix),
+            self.category(),
+            description.to_string(),
+        )
+    }
+
+    /// Only the IDs that belong to this run, in the order they were returned.
+    fn own_ids<'a>(&self, ids: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+        ids.into_iter()
+            .filter(|id| id.starts_with(&self.prefix))
+            .cloned()
+            .collect()
+    }
+}
+
+fn sorted(mut ids: Vec<String>) -> Vec<String> {
+    ids.sort();
+    ids
+}
+
+/// This run's hits for `query`, in the order they were returned.
+async fn search_hits(repository: &dyn SymbolRepository, run: &Run, query: &str) -> Vec<SearchHit> {
+    let found = repository
+        .search_symbols(query, &ListQuery::new())
+        .await
+        .unwrap_or_else(|e| panic!("search_symbols({:?}) failed: {}", query, e));
+    found
+        .items
+        .into_iter()
+        .filter(|hit| hit.symbol.id.starts_with(&run.prefix))
+        .collect()
+}
+
+async fn search_ids(repository: &dyn SymbolRepository, run: &Run, query: &str) -> Vec<String> {
+    let hits = search_hits(repository, run, query).await;
+    sorted(hits.into_iter().map(|hit| hit.symbol.id).collect())
+}
+
+async fn list_ids(
+    repository: &dyn SymbolRepository,
+    run: &Run,
+    category: Option<String>,
+) -> Vec<String> {
+    let found = repository
+        .list_symbols(&ListQuery::new().category(category))
+        .await
+        .expect("list_symbols should succeed");
+    let ids: Vec<String> = found.items.into_iter().map(|s| s.id).collect();
+    sorted(run.own_ids(&ids))
+}
+
+/// IDs of the sets `symbol_id` belongs to, in the order they were returned.
+async fn set_ids_for(repository: &dyn SymbolSetRepository, symbol_id: &str) -> Vec<String> {
+    let sets = repository
+        .list_sets_for_symbol(symbol_id)
+        .await
+        .expect("list_sets_for_symbol should succeed");
+    sets.into_iter().map(|set| set.id).collect()
+}
+
+async fn cleanup(factory: &dyn RepositoryFactory, symbols: &[&Symbol], sets: &[&SymbolSet]) {
+    let symbol_repository = factory.create_symbol_repository();
+    let symbol_set_repository = factory.create_symbol_set_repository();
+
+    for set in sets {
+        let _ = symbol_set_repository.delete_symbol_set(&set.id).await;
+    }
+    for symbol in symbols {
+        let _ = symbol_repository.delete_symbol(&symbol.id).await;
+    }
+}
+
+/// Created symbols read back unchanged, updates replace them and deletes remove them.
+pub async fn symbol_crud(factory: &dyn RepositoryFactory) {
+    let run = Run::new("symbol-crud");
+    let repository = factory.create_symbol_repository();
+
+    let mut water = run.symbol("Water", "Emotions and the unconscious");
+    water.add_interpretation("jungian".to_string(), "The unconscious".to_string());
+    water
+        .properties
+        .insert("element".to_string(), "water".to_string());
+
+    let created = repository
+        .create_symbol(water.clone())
+        .await
+        .expect("create_symbol should succeed");
+    assert_eq!(created, water, "create_symbol should return the new symbol");
+
+    let fetched = repository
+        .get_symbol(&water.id)
+        .await
+        .expect("get_symbol should find a created symbol");
+    assert_eq!(fetched, water, "get_symbol should round-trip every field");
+
+    let mut updated = water.clone();
+    updated.description = "The flow of feeling".to_string();
+    updated
+        .properties
+        .insert("temperature".to_string(), "cold".to_string());
+    repository
+        .update_symbol(updated.clone())
+        .await
+        .expect("update_symbol should succeed");
+    assert_eq!(
+        repository.get_symbol(&water.id).await.unwrap(),
+        updated,
+        "get_symbol should return the updated symbol"
+    );
+
+    repository
+        .delete_symbol(&water.id)
+        .await
+        .expect("delete_symbol should succeed");
+    assert!(
+        matches!(
+            repository.get_symbol(&water.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "a deleted symbol should no longer be found"
+    );
+}
+
+/// Duplicate IDs conflict; unknown IDs are reported as not found.
+pub async fn symbol_conflicts_and_not_found(factory: &dyn RepositoryFactory) {
+    let run = Run::new("symbol-errors");
+    let repository = factory.create_symbol_repository();
+    let fire = run.symbol("Fire", "Transformation");
+
+    repository.create_symbol(fire.clone()).await.unwrap();
+
+    let mut duplicate = fire.clone();
+    duplicate.name = "Another fire".to_string();
+    let conflict = repository.create_symbol(duplicate).await;
+    assert!(
+        matches!(conflict, Err(RepositoryError::Conflict(_))),
+        "creating a duplicate symbol should conflict, got {:?}",
+        conflict
+    );
+    assert_eq!(
+        repository.get_symbol(&fire.id).await.unwrap().name,
+        fire.name,
+        "a conflicting create should leave the original untouched"
+    );
+
+    let missing = run.symbol("Missing", "Never created");
+    let results = [
+        ("get_symbol", repository.get_symbol(&missing.id).await.err()),
+        (
+            "update_symbol",
+            repository.update_symbol(missing.clone()).await.err(),
+        ),
+        (
+            "delete_symbol",
+            repository.delete_symbol(&missing.id).await.err(),
+        ),
+    ];
+    for (operation, error) in results {
+        assert!(
+            matches!(error, Some(RepositoryError::NotFound(_))),
+            "{} on an unknown symbol should be NotFound, got {:?}",
+            operation,
+            error
+        );
+    }
+
+    cleanup(factory, &[&fire], &[]).await;
+}
+
+/// Search is case-insensitive over the name, description, interpretation texts
+/// and property values, and ranks name matches above the others. The name and
+/// description also match as substrings, with `LIKE` wildcards and backslash escapes.
+pub async fn symbol_search(factory: &dyn RepositoryFactory) {
+    let run = Run::new("symbol-search");
+    let repository = factory.create_symbol_repository();
+
+    let river = run.symbol("River", "The flow of life");
+    let ocean = run.symbol("Ocean", "Vast depths of the RIVER mouth");
+    let mut stone = run.symbol("Stone", "Permanence");
+    stone.add_interpretation("river".to_string(), "Worn smooth by rivers".to_string());
+    stone
+        .properties
+        .insert("texture".to_string(), "Granite".to_string());
+    let tide_pool = run.symbol("Tide_Pool", "Small worlds");
+    let tidexpool = run.symbol("TidexPool", "Not a tidal pool");
+    let symbols = [&river, &ocean, &stone, &tide_pool, &tidexpool];
+
+    for symbol in symbols {
+        repository.create_symbol(symbol.clone()).await.unwrap();
+    }
+
+    let search = |query| search_ids(repository.as_ref(), &run, query);
+
+    let hits = search_hits(repository.as_ref(), &run, "rIvEr").await;
+    assert_eq!(
+        hits.iter()
+            .map(|hit| hit.symbol.id.clone())
+            .collect::<Vec<_>>(),
+        vec![river.id.clone(), ocean.id.clone(), stone.id.clone()],
+        "search should match name, description and interpretations case-insensitively, \
+         ranking name matches first and interpretation matches last"
+    );
+    assert!(
+        hits.windows(2).all(|pair| pair[0].score > pair[1].score),
+        "hits should come with decreasing scores, got {:?}",
+        hits.iter().map(|hit| hit.score).collect::<Vec<_>>()
+    );
+    assert_eq!(hits[0].symbol, river, "hits should carry the whole symbol");
+
+    assert_eq!(
+        search("granite").await,
+        vec![stone.id.clone()],
+        "search should match property values"
+    );
+    assert_eq!(
+        search("flow of").await,
+        vec![river.id.clone()],
+        "search should match inside the description"
+    );
+    assert_eq!(
+        search("mouth vast").await,
+        vec![ocean.id.clone()],
+        "every word of a query should match, in any order"
+    );
+    assert_eq!(
+        search("granite river").await,
+        vec![stone.id.clone()],
+        "the words of a query may match different fields"
+    );
+    assert!(
+        search("river pebble").await.is_empty(),
+        "a symbol should only match if all words of the query do"
+    );
+    assert_eq!(
+        search("tide_pool").await,
+        sorted(vec![tide_pool.id.clone(), tidexpool.id.clone()]),
+        "`_` should match any single character"
+    );
+    assert_eq!(
+        search("tide\\_pool").await,
+        vec![tide_pool.id.clone()],
+        "an escaped `_` should only match itself"
+    );
+    assert_eq!(
+        search("small%worlds").await,
+        vec![tide_pool.id.clone()],
+        "`%` should match any run of characters"
+    );
+    assert!(
+        search("category").await.is_empty(),
+        "search should not look at the category"
+    );
+
+    cleanup(factory, &symbols, &[]).await;
+}
+
+/// Misspelled names still find their symbols, closest first, and unrelated
+/// names find nothing.
+pub async fn symbol_fuzzy_search(factory: &dyn RepositoryFactory) {
+    let run = Run::new("fuzzy-search");
+    let repository = factory.create_symbol_repository();
+
+    // The run prefix would dominate name similarity, so only the IDs carry it
+    let named = |name: &str| {
+        Symbol::new(
+            run.id(&name.to_lowercase()),
+            name.to_string(),
+            run.category(),
+            String::new(),
+        )
+    };
+    let serpent = named("Serpent");
+    let labyrinth = named("Labyrinth");
+    let moon = named("Moon");
+    let mood = named("Mood");
+    let symbols = [&serpent, &labyrinth, &moon, &mood];
+
+    for symbol in symbols {
+        repository.create_symbol(symbol.clone()).await.unwrap();
+    }
+
+    let fuzzy = |query: &'static str| {
+        let repository = repository.as_ref();
+        let run = &run;
+        async move {
+            let found = repository
+                .fuzzy_search_symbols(query, 1000)
+                .await
+                .unwrap_or_else(|e| panic!("fuzzy_search_symbols({:?}) failed: {}", query, e));
+            found
+                .into_iter()
+                .filter(|hit| hit.symbol.id.starts_with(&run.prefix))
+                .collect::<Vec<_>>()
+        }
+    };
+    let ids = |hits: &[SearchHit]| {
+        hits.iter()
+            .map(|hit| hit.symbol.id.clone())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        ids(&fuzzy("serpant").await),
+        vec![serpent.id.clone()],
+        "a misspelled name should find its symbol"
+    );
+    assert_eq!(
+        ids(&fuzzy("LABYRITH").await),
+        vec![labyrinth.id.clone()],
+        "fuzzy matching should ignore case"
+    );
+
+    let hits = fuzzy("moon").await;
+    assert_eq!(
+        ids(&hits),
+        vec![moon.id.clone(), mood.id.clone()],
+        "the closest name should come first"
+    );
+    assert!(
+        (hits[0].score - 1.0).abs() < 1e-6 && hits[1].score < hits[0].score,
+        "an exact name should score 1, got {:?}",
+        hits.iter().map(|hit| hit.score).collect::<Vec<_>>()
+    );
+    assert_eq!(hits[0].symbol, moon, "hits should carry the whole symbol");
+
+    assert!(
+        fuzzy("xylophone").await.is_empty(),
+        "unrelated names should not match"
+    );
+    assert!(
+        repository
+            .fuzzy_search_symbols("moon", 1)
+            .await
+            .unwrap()
+            .len()
+            <= 1,
+        "fuzzy search should honour the limit"
+    );
+
+    cleanup(factory, &symbols, &[]).await;
+}
+
+/// `get_symbol` resolves aliases and localized names ignoring case, and no two
+/// symbols may share one.
+pub async fn symbol_aliases(factory: &dyn RepositoryFactory) {
+    let run = Run::new("aliases");
+    let repository = factory.create_symbol_repository();
+
+    let mut serpent = run.symbol("Serpent", "Renewal");
+    serpent.add_alias(format!("Snake {}", run.prefix));
+    serpent
+        .add_localized_name("es", format!("Serpiente {}", run.prefix))
+        .unwrap();
+    let dragon = run.symbol("Dragon", "Primal power");
+
+    repository.create_symbol(serpent.clone()).await.unwrap();
+    repository.create_symbol(dragon.clone()).await.unwrap();
+
+    let resolved = |key: String| {
+        let repository = repository.as_ref();
+        async move {
+            repository
+                .get_symbol(&key)
+                .await
+                .unwrap_or_else(|e| panic!("get_symbol({:?}) failed: {}", key, e))
+        }
+    };
+    assert_eq!(
+        resolved(serpent.id.clone()).await,
+        serpent,
+        "aliases and localized names should round-trip"
+    );
+    assert_eq!(
+        resolved(format!("SNAKE {}", run.prefix)).await.id,
+        serpent.id,
+        "get_symbol should resolve an alias ignoring case"
+    );
+    assert_eq!(
+        resolved(format!("serpiente {}", run.prefix)).await.id,
+        serpent.id,
+        "get_symbol should resolve a localized name"
+    );
+    assert_eq!(
+        resolved(dragon.name.to_lowercase()).await.id,
+        dragon.id,
+        "get_symbol should resolve a name"
+    );
+    assert_eq!(
+        repository.get_symbol_by_id(&serpent.id).await.unwrap().id,
+        serpent.id
+    );
+    let by_alias = repository
+        .get_symbol_by_id(&format!("Snake {}", run.prefix))
+        .await;
+    assert!(
+        matches!(by_alias, Err(RepositoryError::NotFound(_))),
+        "get_symbol_by_id should not resolve aliases, got {:?}",
+        by_alias
+    );
+
+    let mut thief = dragon.clone();
+    thief.add_alias(format!("snake {}", run.prefix));
+    let conflict = repository.update_symbol(thief).await;
+    assert!(
+        matches!(conflict, Err(RepositoryError::Conflict(_))),
+        "an alias already used by another symbol should conflict, got {:?}",
+        conflict
+    );
+
+    let mut invalid = dragon.clone();
+    invalid
+        .localized_names
+        .insert("not a tag".to_string(), "Drache".to_string());
+    let invalid = repository.update_symbol(invalid).await;
+    assert!(
+        matches!(invalid, Err(RepositoryError::Validation(_))),
+        "a localized name under an invalid language tag should be rejected, got {:?}",
+        invalid
+    );
+    assert_eq!(
+        resolved(dragon.id.clone()).await,
+        dragon,
+        "rejected updates should leave the symbol untouched"
+    );
+
+    let found: Vec<String> = repository
+        .search_symbols(&format!("snake {}", run.prefix), &ListQuery::new())
+        .await
+        .unwrap()
+        .items
+        .into_iter()
+        .map(|hit| hit.symbol.id)
+        .collect();
+    assert_eq!(
+        found,
+        vec![serpent.id.clone()],
+        "search should match aliases"
+    );
+
+    cleanup(factory, &[&serpent, &dragon], &[]).await;
+}
+
+/// Embeddings are searched nearest first by cosine similarity, and editing a
+/// symbol's text drops its embedding. Backends without vector support must
+/// report `NotImplemented`.
+pub async fn symbol_embeddings(factory: &dyn RepositoryFactory) {
+    let run = Run::new("embeddings");
+    let repository = factory.create_symbol_repository();
+
+    let north = run.symbol("North", "Up");
+    let northeast = run.symbol("Northeast", "Up and right");
+    let east = run.symbol("East", "Right");
+    let south = run.symbol("South", "Down");
+    let symbols = [&north, &northeast, &east, &south];
+
+    for symbol in symbols {
+        repository.create_symbol(symbol.clone()).await.unwrap();
+    }
+
+    // Mostly one axis, so unrelated embeddings in a shared database rank far below
+    let direction = |x: f32, y: f32| {
+        let mut embedding = vec![0.0; EMBEDDING_DIMENSIONS];
+        embedding[0] = x;
+        embedding[1] = y;
+        embedding
+    };
+    match repository
+        .set_embedding(&north.id, &direction(1.0, 0.0))
+        .await
+    {
+        Err(RepositoryError::NotImplemented(_)) => {
+            cleanup(factory, &symbols, &[]).await;
+            return;
+        }
+        result => result.expect("set_embedding should succeed"),
+    }
+    for (symbol, embedding) in [
+        (&northeast, direction(0.8, 0.6)),
+        (&east, direction(0.6, 0.8)),
+    ] {
+        repository
+            .set_embedding(&symbol.id, &embedding)
+            .await
+            .expect("set_embedding should succeed");
+    }
+
+    let unembedded = repository
+        .list_unembedded_symbols()
+        .await
+        .expect("list_unembedded_symbols should succeed");
+    assert_eq!(
+        run.own_ids(unembedded.iter().map(|s| &s.id)),
+        vec![south.id.clone()],
+        "only symbols without an embedding should be listed as unembedded"
+    );
+
+    let hits: Vec<SearchHit> = repository
+        .semantic_search_symbols(&direction(2.0, 0.0), 10)
+        .await
+        .expect("semantic_search_symbols should succeed")
+        .into_iter()
+        .filter(|hit| hit.symbol.id.starts_with(&run.prefix))
+        .collect();
+    assert_eq!(
+        hits.iter()
+            .map(|hit| hit.symbol.id.clone())
+            .collect::<Vec<_>>(),
+        vec![north.id.clone(), northeast.id.clone(), east.id.clone()],
+        "semantic search should return embedded symbols nearest first"
+    );
+    let scores: Vec<f32> = hits.iter().map(|hit| hit.score).collect();
+    assert!(
+        scores
+            .iter()
+            .zip([1.0, 0.8, 0.6])
+            .all(|(score, expected)| (score - expected).abs() < 1e-4),
+        "scores should be cosine similarities, got {:?}",
+        scores
+    );
+
+    assert!(
+        matches!(
+            repository.set_embedding(&north.id, &[1.0, 0.0]).await,
+            Err(RepositoryError::Validation(_))
+        ),
+        "an embedding of the wrong length should be rejected"
+    );
+    assert!(
+        matches!(
+            repository
+                .set_embedding(&run.id("missing"), &direction(1.0, 0.0))
+                .await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "embedding an unknown symbol should be NotFound"
+    );
+
+    let mut moved = north.clone();
+    moved.description = "Somewhere else".to_string();
+    repository.update_symbol(moved).await.unwrap();
+    let mut renamed_category = east.clone();
+    renamed_category.category = format!("{}-other", run.category());
+    repository.update_symbol(renamed_category).await.unwrap();
+    let mut aliased = northeast.clone();
+    aliased.add_alias(format!("Nordost {}", run.prefix));
+    repository.update_symbol(aliased).await.unwrap();
+    let unembedded = repository.list_unembedded_symbols().await.unwrap();
+    assert_eq!(
+        sorted(run.own_ids(unembedded.iter().map(|s| &s.id))),
+        sorted(vec![
+            north.id.clone(),
+            northeast.id.clone(),
+            south.id.clone()
+        ]),
+        "changing a symbol's text or names, but not its category, should drop its embedding"
+    );
+
+    cleanup(factory, &symbols, &[]).await;
+}
+
+/// Listing by category is an exact, case-sensitive match; no category lists everything.
+pub async fn symbol_category_filter(factory: &dyn RepositoryFactory) {
+    let run = Run::new("symbol-category");
+    let repository = factory.create_symbol_repository();
+
+    let moon = run.symbol("Moon", "Cycles");
+    let sun = run.symbol("Sun", "Vitality");
+    let mut owl = run.symbol("Owl", "Wisdom");
+    owl.category = format!("{}-animals", run.category());
+    let symbols = [&moon, &sun, &owl];
+
+    for symbol in symbols {
+        repository.create_symbol(symbol.clone()).await.unwrap();
+    }
+
+    let listed = |category| list_ids(repository.as_ref(), &run, category);
+
+    assert_eq!(
+        listed(Some(run.category())).await,
+        sorted(vec![moon.id.clone(), sun.id.clone()]),
+        "list_symbols should return exactly the symbols in the category"
+    );
+    assert!(
+        listed(Some(run.category().to_uppercase())).await.is_empty(),
+        "category filters should be case-sensitive"
+    );
+    assert!(
+        listed(Some(format!("{}-unused", run.category())))
+            .await
+            .is_empty(),
+        "an unused category should list nothing"
+    );
+    assert_eq!(
+        listed(None).await,
+        sorted(vec![moon.id.clone(), sun.id.clone(), owl.id.clone()]),
+        "list_symbols without a category should return every symbol"
+    );
+
+    let categories: Vec<String> = repository
+        .list_categories()
+        .await
+        .expect("list_categories should succeed")
+        .into_iter()
+        .filter(|category| category.starts_with(&run.category()))
+        .collect();
+    assert_eq!(
+        categories,
+        vec![run.category(), format!("{}-animals", run.category())],
+        "list_categories should return each category once, in order"
+    );
+
+    cleanup(factory, &symbols, &[]).await;
+}
+
+/// Following `next_cursor` visits every result once, in the same order as an
+/// unpaged request, with the first page's total on every page; bad queries,
+/// and cursors reused for another category or search, are rejected.
+pub async fn symbol_pagination(factory: &dyn RepositoryFactory) {
+    let run = Run::new("symbol-pagination");
+    let repository = factory.create_symbol_repository();
+
+    let mut symbols: Vec<Symbol> = ["Ash", "Birch", "Cedar", "Elm", "Oak"]
+        .iter()
+        .map(|name| run.symbol(name, "A tree"))
+        .collect();
+    // Same name as the oak, so the order falls back to the ID
+    let mut twin = run.symbol("Oak", "Another tree");
+    twin.id = run.id("oak-2");
+    symbols.push(twin);
+
+    for symbol in &symbols {
+        repository.create_symbol(symbol.clone()).await.unwrap();
+    }
+    let by_name: Vec<String> = symbols.iter().map(|s| s.id.clone()).collect();
+
+    let in_category = ListQuery::new().category(Some(run.category()));
+    for (sort, expected) in [
+        (SortOrder::Name, by_name.clone()),
+        (SortOrder::NameDesc, by_name.iter().rev().cloned().collect()),
+    ] {
+        let mut ids = Vec::new();
+        let mut pages = 0;
+        let mut query = in_category.clone().sort(Some(sort)).limit(4);
+        loop {
+            let page = repository
+                .list_symbols(&query)
+                .await
+                .unwrap_or_else(|e| panic!("list_symbols by {} failed: {}", sort, e));
+            assert!(page.items.len() <= 4, "pages should hold at most the limit");
+            assert_eq!(
+                page.total_count, 6,
+                "every page should count all the results"
+            );
+            ids.extend(page.items.into_iter().map(|s| s.id));
+            pages += 1;
+            match page.next_cursor {
+                Some(cursor) => query = query.cursor(Some(cursor)),
+                None => break,
+            }
+        }
+        assert_eq!(
+            ids, expected,
+            "paging by {} should visit every symbol in order",
+            sort
+        );
+        assert_eq!(pages, 2, "six symbols should take two pages of four");
+    }
+
+    let text = run.prefix.clone();
+    let unpaged = repository
+        .search_symbols(&text, &ListQuery::new())
+        .await
+        .expect("search_symbols should succeed");
+    let mut paged = Vec::new();
+    let mut query = ListQuery::new().limit(2);
+    loop {
+        let page = repository.search_symbols(&text, &query).await.unwrap();
+        assert_eq!(
+            page.total_count, 6,
+            "every search page should count all the matches"
+        );
+        paged.extend(page.items);
+        match page.next_cursor {
+            Some(cursor) => query = query.cursor(Some(cursor)),
+            None => break,
+        }
+    }
+    let ids =
+        |hits: &[SearchHit]| -> Vec<String> { hits.iter().map(|h| h.symbol.id.clone()).collect() };
+    assert_eq!(
+        ids(&paged),
+        ids(&unpaged.items),
+        "paged search should match unpaged search"
+    );
+    assert_eq!(
+        unpaged.items.len(),
+        6,
+        "search should find every symbol of the run"
+    );
+
+    let first = repository
+        .list_symbols(&in_category.clone().limit(1))
+        .await
+        .unwrap();
+    let cursor = first
+        .next_cursor
+        .expect("a full page should have a next cursor");
+
+    // A symbol added between pages does not change the total already reported
+    let late = run.symbol("Willow", "A late tree");
+    repository.create_symbol(late.clone()).await.unwrap();
+    let second = repository
+        .list_symbols(&in_category.clone().limit(1).cursor(Some(cursor.clone())))
+        .await
+        .unwrap();
+    assert_eq!(
+        second.total_count, first.total_count,
+        "later pages should report the total the first page counted"
+    );
+    repository.delete_symbol(&late.id).await.unwrap();
+
+    let search_cursor = repository
+        .search_symbols(&text, &ListQuery::new().limit(1))
+        .await
+        .unwrap()
+        .next_cursor
+        .expect("a full search page should have a next cursor");
+    match repository
+        .search_symbols(
+            "Oak",
+            &ListQuery::new().limit(1).cursor(Some(search_cursor)),
+        )
+        .await
+    {
+        Err(RepositoryError::Validation(_)) => {}
+        other => panic!(
+            "search_symbols should reject a cursor from another search, got {:?}",
+            other
+        ),
+    }
+
+    for (query, reason) in [
+        (in_category.clone().limit(0), "a zero limit"),
+        (
+            ListQuery::new()
+                .category(Some(format!("{}-animals", run.category())))
+                .cursor(Some(cursor.clone())),
+            "a cursor from another category",
+        ),
+        (
+            in_category.clone().cursor(Some("not a cursor")),
+            "a malformed cursor",
+        ),
+        (
+            in_category
+                .clone()
+                .cursor(Some(cursor))
+                .sort(Some(SortOrder::NameDesc)),
+            "a cursor from another sort order",
+        ),
+        (
+            in_category.clone().sort(Some(SortOrder::Relevance)),
+            "listing by relevance",
+        ),
+    ] {
+        match repository.list_symbols(&query).await {
+            Err(RepositoryError::Validation(_)) => {}
+            other => panic!("list_symbols should reject {}, got {:?}", reason, other),
+        }
+    }
+
+    let refs: Vec<&Symbol> = symbols.iter().collect();
+    cleanup(factory, &refs, &[]).await;
+}
+
+/// Deleting a symbol removes the references to it held by other symbols,
+/// relations and sets.
+pub async fn symbol_delete_cascades(factory: &dyn RepositoryFactory) {
+    let run = Run::new("delete-cascade");
+    let symbols = factory.create_symbol_repository();
+    let sets = factory.create_symbol_set_repository();
+
+    let sun = run.symbol("Sun", "Consciousness");
+    let moon = run.symbol("Moon", "The unconscious");
+    let day = run
+        .symbol("Day", "Waking life")
+        .with_related(vec![&sun.id, &moon.id]);
+    for symbol in [&sun, &moon, &day] {
+        symbols.create_symbol(symbol.clone()).await.unwrap();
+    }
+    let relations = match symbols
+        .add_relation(SymbolRelation::new(
+            &moon.id,
+            &sun.id,
+            RelationType::OppositeOf,
+        ))
+        .await
+    {
+        Err(RepositoryError::NotImplemented(_)) => false,
+        result => result.map(|_| true).expect("add_relation should succeed"),
+    };
+    let mut lights = run.symbol_set("Lights", "The two great lights");
+    lights.add_symbol(sun.clone());
+    lights.add_symbol(moon.clone());
+    sets.create_symbol_set(lights.clone()).await.unwrap();
+
+    symbols.delete_symbol(&sun.id).await.unwrap();
+
+    assert_eq!(
+        symbols.get_symbol(&day.id).await.unwrap().related_symbols,
+        vec![moon.id.clone()],
+        "deleted symbols should leave related_symbols"
+    );
+    assert_eq!(
+        symbols.get_symbol(&moon.id).await.unwrap().related_symbols,
+        Vec::<String>::new(),
+        "deleted relation targets should leave related_symbols"
+    );
+    if relations {
+        assert!(
+            symbols
+                .list_relations(&moon.id, RelationDirection::Outgoing)
+                .await
+                .unwrap()
+                .is_empty(),
+            "relations to a deleted symbol should be removed"
+        );
+    }
+    assert_eq!(
+        sorted(
+            sets.get_symbol_set(&lights.id)
+                .await
+                .unwrap()
+                .symbols
+                .into_keys()
+                .collect()
+        ),
+        vec![moon.id.clone()],
+        "deleted symbols should leave their sets"
+    );
+
+    cleanup(factory, &[&sun, &moon, &day], &[&lights]).await;
+}
+
+/// Every entry of `related_symbols` has a `related_to` edge, and dropping an
+/// entry drops its edge. Backends without relations must report
+/// `NotImplemented`.
+pub async fn symbol_related_edges(factory: &dyn RepositoryFactory) {
+    let run = Run::new("related-edges");
+    let repository = factory.create_symbol_repository();
+
+    let sun = run.symbol("Sun", "Consciousness");
+    let moon = run.symbol("Moon", "The unconscious");
+    let day = run
+        .symbol("Day", "Waking life")
+        .with_related(vec![&sun.id, &moon.id]);
+    for symbol in [&sun, &moon, &day] {
+        repository.create_symbol(symbol.clone()).await.unwrap();
+    }
+
+    let related_to = |id: String| {
+        let repository = repository.as_ref();
+        async move {
+            let relations = repository
+                .list_relations(&id, RelationDirection::Outgoing)
+                .await?;
+            Ok::<_, RepositoryError>(sorted(
+                relations
+                    .into_iter()
+                    .filter(|r| r.relation_type == RelationType::RelatedTo)
+                    .map(|r| r.target_id)
+                    .collect(),
+            ))
+        }
+    };
+    match related_to(day.id.clone()).await {
+        Err(RepositoryError::NotImplemented(_)) => {
+            cleanup(factory, &[&sun, &moon, &day], &[]).await;
+            return;
+        }
+        edges => assert_eq!(
+            edges.expect("list_relations should succeed"),
+            sorted(vec![sun.id.clone(), moon.id.clone()]),
+            "related_symbols should have related_to edges"
+        ),
+    }
+
+    let day = day.with_related(vec![&moon.id]);
+    repository.update_symbol(day.clone()).await.unwrap();
+    assert_eq!(
+        related_to(day.id.clone()).await.unwrap(),
+        vec![moon.id.clone()],
+        "an update dropping a related symbol should drop its edge"
+    );
+
+    let day = day.with_related(Vec::new());
+    repository.upsert_many(vec![day.clone()]).await.unwrap();
+    assert_eq!(
+        related_to(day.id.clone()).await.unwrap(),
+        Vec::<String>::new(),
+        "a bulk upsert dropping related symbols should drop their edges"
+    );
+
+    cleanup(factory, &[&sun, &moon, &day], &[]).await;
+}
+
+/// Bulk upserts create and replace symbols together, naming each other if
+/// they like, and store nothing when any of them is rejected.
+pub async fn symbol_upsert_many(factory: &dyn RepositoryFactory) {
+    let run = Run::new("upsert-many");
+    let repository = factory.create_symbol_repository();
+
+    let stream = format!("Stream {}", run.prefix);
+    let mut river = run.symbol("River", "Time");
+    river.add_alias(stream.clone());
+    repository.create_symbol(river.clone()).await.unwrap();
+
+    // The river gives up its alias to the ocean in the same batch
+    let mut ocean = run.symbol("Ocean", "The collective unconscious");
+    ocean.add_alias(stream.clone());
+    let ocean = ocean.with_related(vec![&river.id]);
+    let river = run
+        .symbol("River", "The flow of time")
+        .with_related(vec![&ocean.id]);
+    let stored = repository
+        .upsert_many(vec![river.clone(), ocean.clone()])
+        .await
+        .expect("upsert_many should succeed");
+    assert_eq!(
+        stored,
+        vec![river.clone(), ocean.clone()],
+        "upsert_many should return the symbols as stored, in order"
+    );
+    for symbol in [&river, &ocean] {
+        assert_eq!(
+            &repository.get_symbol(&symbol.id).await.unwrap(),
+            symbol,
+            "upserted symbols should read back unchanged"
+        );
+    }
+    assert_eq!(
+        repository.get_symbol(&stream).await.unwrap().id,
+        ocean.id,
+        "a moved alias should resolve to its new symbol"
+    );
+
+    let mut lake = run.symbol("Lake", "Stillness");
+    lake.add_alias(stream.to_lowercase());
+    let sea = run.symbol("Sea", "Vastness");
+    let conflict = repository
+        .upsert_many(vec![sea.clone(), lake.clone()])
+        .await;
+    assert!(
+        matches!(conflict, Err(RepositoryError::Conflict(_))),
+        "an alias already used by another symbol should conflict, got {:?}",
+        conflict
+    );
+    for symbol in [&sea, &lake] {
+        assert!(
+            matches!(
+                repository.get_symbol(&symbol.id).await,
+                Err(RepositoryError::NotFound(_))
+            ),
+            "a rejected batch should store none of its symbols"
+        );
+    }
+
+    let duplicate = repository.upsert_many(vec![sea.clone(), sea.clone()]).await;
+    assert!(
+        matches!(duplicate, Err(RepositoryError::Validation(_))),
+        "a symbol appearing twice should be a Validation error, got {:?}",
+        duplicate
+    );
+
+    let mut invalid = lake.clone();
+    invalid
+        .localized_names
+        .insert("not a tag".to_string(), "See".to_string());
+    let invalid = repository.upsert_many(vec![sea.clone(), invalid]).await;
+    assert!(
+        matches!(invalid, Err(RepositoryError::Validation(_))),
+        "an invalid symbol should be a Validation error, got {:?}",
+        invalid
+    );
+    assert!(
+        matches!(
+            repository.get_symbol(&sea.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "an invalid batch should store none of its symbols"
+    );
+
+    assert_eq!(
+        repository.upsert_many(Vec::new()).await.unwrap(),
+        Vec::<Symbol>::new(),
+        "an empty batch should store nothing"
+    );
+
+    cleanup(factory, &[&river, &ocean], &[]).await;
+}
+
+/// Batches report an outcome per operation. A non-atomic batch keeps the
+/// operations that succeed; an atomic one keeps all or none of them.
+pub async fn symbol_batches(factory: &dyn RepositoryFactory) {
+    let run = Run::new("batches");
+    let repository = factory.create_symbol_repository();
+
+    let sun = run.symbol("Sun", "Consciousness");
+    let moon = run.symbol("Moon", "The unconscious");
+    let comet = run.symbol("Comet", "Never created");
+    repository.create_symbol(sun.clone()).await.unwrap();
+
+    let bright_sun = run.symbol("Sun", "Radiance");
+    let mut invalid = run.symbol("Star", "Guidance");
+    invalid
+        .localized_names
+        .insert("not a tag".to_string(), "Stern".to_string());
+    let result = repository
+        .apply_batch(
+            vec![
+                SymbolOperation::Create {
+                    symbol: moon.clone(),
+                },
+                SymbolOperation::Create {
+                    symbol: sun.clone(),
+                },
+                SymbolOperation::Update {
+                    symbol: comet.clone(),
+                },
+                SymbolOperation::Upsert {
+                    symbol: bright_sun.clone(),
+                },
+                SymbolOperation::Create { symbol: invalid },
+                SymbolOperation::Delete {
+                    id: moon.id.clone(),
+                },
+            ],
+            false,
+        )
+        .await
+        .expect("apply_batch should succeed");
+    assert!(result.committed, "a non-atomic batch should be committed");
+    assert!(
+        matches!(
+            result.outcomes.as_slice(),
+            [
+                OperationOutcome::Created(created),
+                OperationOutcome::Failed(RepositoryError::Conflict(_)),
+                OperationOutcome::Failed(RepositoryError::NotFound(_)),
+                OperationOutcome::Updated(updated),
+                OperationOutcome::Failed(RepositoryError::Validation(_)),
+                OperationOutcome::Deleted,
+            ] if created == &moon && updated == &bright_sun
+        ),
+        "each operation should succeed or fail on its own, got {:?}",
+        result.outcomes
+    );
+    assert_eq!((result.succeeded(), result.failed()), (3, 3));
+    assert_eq!(
+        repository.get_symbol(&sun.id).await.unwrap(),
+        bright_sun,
+        "the operations that succeeded should be stored"
+    );
+    assert!(
+        matches!(
+            repository.get_symbol(&moon.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "later operations should see earlier ones"
+    );
+
+    let star = run.symbol("Star", "Guidance");
+    let dark_sun = run.symbol("Sun", "Eclipse");
+    let atomic = |operations| repository.apply_batch(operations, true);
+    let result = atomic(vec![
+        SymbolOperation::Create {
+            symbol: star.clone(),
+        },
+        SymbolOperation::Update {
+            symbol: dark_sun.clone(),
+        },
+        SymbolOperation::Delete {
+            id: comet.id.clone(),
+        },
+    ])
+    .await
+    .expect("apply_batch should succeed");
+    assert!(!result.committed, "a failed atomic batch should roll back");
+    assert!(
+        matches!(
+            result.outcomes.as_slice(),
+            [
+                OperationOutcome::Aborted,
+                OperationOutcome::Aborted,
+                OperationOutcome::Failed(RepositoryError::NotFound(_)),
+            ]
+        ),
+        "the other operations of a failed atomic batch should be aborted, got {:?}",
+        result.outcomes
+    );
+    assert!(
+        matches!(
+            repository.get_symbol(&star.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "a failed atomic batch should store nothing"
+    );
+    assert_eq!(
+        repository.get_symbol(&sun.id).await.unwrap(),
+        bright_sun,
+        "a failed atomic batch should change nothing"
+    );
+
+    // The star names a symbol created later in the batch
+    let star = star.with_related(vec![&moon.id]);
+    let result = atomic(vec![
+        SymbolOperation::Create {
+            symbol: star.clone(),
+        },
+        SymbolOperation::Upsert {
+            symbol: moon.clone(),
+        },
+        SymbolOperation::Update {
+            symbol: dark_sun.clone(),
+        },
+        SymbolOperation::Delete {
+            id: star.id.clone(),
+        },
+    ])
+    .await
+    .expect("apply_batch should succeed");
+    assert!(result.committed, "an atomic batch should be committed");
+    assert!(
+        matches!(
+            result.outcomes.as_slice(),
+            [
+                OperationOutcome::Created(created),
+                OperationOutcome::Created(_),
+                OperationOutcome::Updated(updated),
+                OperationOutcome::Deleted,
+            ] if created == &star && updated == &dark_sun
+        ),
+        "every operation of an atomic batch should succeed, got {:?}",
+        result.outcomes
+    );
+    assert_eq!(
+        repository.get_symbol(&moon.id).await.unwrap(),
+        moon,
+        "a committed atomic batch should be stored"
+    );
+    assert!(
+        matches!(
+            repository.get_symbol(&star.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "later operations of an atomic batch should see earlier ones"
+    );
+
+    cleanup(factory, &[&sun, &moon], &[]).await;
+}
+
+/// Writes naming unknown symbols fail with `Validation` and store nothing,
+/// while known targets, a symbol naming itself and the symbols written by the
+/// same bulk upsert or atomic batch are accepted.
+///
+/// Not part of [`run_all`]: `factory` must be in
+/// [`IntegrityMode::Strict`](crate::integrity::IntegrityMode::Strict) mode.
+pub async fn strict_integrity(factory: &dyn RepositoryFactory) {
+    let run = Run::new("strict-integrity");
+    let symbols = factory.create_symbol_repository();
+    let sets = factory.create_symbol_set_repository();
+
+    let sun = run.symbol("Sun", "Consciousness");
+    let moon = run.symbol("Moon", "The unconscious");
+    let comet = run.symbol("Comet", "Never created");
+    let is_validation =
+        |result: RepositoryResult<()>| matches!(result, Err(RepositoryError::Validation(_)));
+
+    assert!(
+        is_validation(
+            symbols
+                .create_symbol(moon.clone().with_related(vec![&sun.id]))
+                .await
+                .map(|_| ())
+        ),
+        "creating a symbol related to an unknown symbol should be a Validation error"
+    );
+    assert!(
+        matches!(
+            symbols.get_symbol(&moon.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "a rejected symbol should not be stored"
+    );
+
+    symbols
+        .create_symbol(sun.clone().with_related(vec![&sun.id]))
+        .await
+        .expect("a symbol may name itself");
+    symbols
+        .create_symbol(moon.clone().with_related(vec![&sun.id]))
+        .await
+        .expect("a symbol may name known symbols");
+
+    assert!(
+        is_validation(
+            symbols
+                .update_symbol(moon.clone().with_related(vec![&sun.id, &comet.id]))
+                .await
+                .map(|_| ())
+        ),
+        "updating a symbol to name an unknown symbol should be a Validation error"
+    );
+    assert_eq!(
+        symbols.get_symbol(&moon.id).await.unwrap().related_symbols,
+        vec![sun.id.clone()],
+        "a rejected update should change nothing"
+    );
+    // The API server looks the target up first, so it answers NotFound instead
+    let added = symbols
+        .add_relation(SymbolRelation::new(
+            &moon.id,
+            &comet.id,
+            RelationType::RelatedTo,
+        ))
+        .await;
+    assert!(
+        matches!(
+            added,
+            Err(RepositoryError::Validation(_)
+                | RepositoryError::NotFound(_)
+                | RepositoryError::NotImplemented(_))
+        ),
+        "a relation to an unknown symbol should be rejected"
+    );
+
+    let planet = run.symbol("Planet", "Wandering");
+    let star = run.symbol("Star", "Guidance");
+    assert!(
+        is_validation(
+            symbols
+                .upsert_many(vec![
+                    planet.clone(),
+                    star.clone().with_related(vec![&comet.id]),
+                ])
+                .await
+                .map(|_| ())
+        ),
+        "a bulk upsert naming an unknown symbol should be a Validation error"
+    );
+    assert!(
+        matches!(
+            symbols.get_symbol(&planet.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "a rejected bulk upsert should store nothing"
+    );
+    symbols
+        .upsert_many(vec![
+            planet.clone().with_related(vec![&star.id]),
+            star.clone().with_related(vec![&planet.id]),
+        ])
+        .await
+        .expect("the symbols of a bulk upsert may name each other");
+    let result = symbols
+        .apply_batch(
+            vec![
+                SymbolOperation::Update {
+                    symbol: star.clone().with_related(vec![&comet.id]),
+                },
+                SymbolOperation::Create {
+                    symbol: comet.clone(),
+                },
+                SymbolOperation::Delete {
+                    id: comet.id.clone(),
+                },
+            ],
+            true,
+        )
+        .await
+        .expect("apply_batch should succeed");
+    assert!(
+        result.committed,
+        "an atomic batch may name symbols it writes later, got {:?}",
+        result.outcomes
+    );
+
+    let mut sky = run.symbol_set("Sky", "What is above");
+    sky.add_symbol(sun.clone());
+    sky.add_symbol(comet.clone());
+    assert!(
+        is_validation(sets.create_symbol_set(sky.clone()).await.map(|_| ())),
+        "a set with an unknown member should be a Validation error"
+    );
+    assert!(
+        matches!(
+            sets.get_symbol_set(&sky.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "a rejected set should not be stored"
+    );
+    sky.remove_symbol(&comet.id);
+    sets.create_symbol_set(sky.clone())
+        .await
+        .expect("a set of known symbols should be stored");
+
+    cleanup(factory, &[&sun, &moon, &planet, &star], &[&sky]).await;
+}
+
+/// Created sets read back with their metadata, can be updated and deleted.
+pub async fn symbol_set_crud(factory: &dyn RepositoryFactory) {
+    let run = Run::new("set-crud");
+    let repository = factory.create_symbol_set_repository();
+
+    let set = run.symbol_set("Elements", "The classical elements");
+    repository
+        .create_symbol_set(set.clone())
+        .await
+        .expect("create_symbol_set should succeed");
+
+    let fetched = repository
+        .get_symbol_set(&set.id)
+        .await
+        .expect("get_symbol_set should find a created set");
+    assert_eq!(
+        (&fetched.name, &fetched.category, &fetched.description),
+        (&set.name, &set.category, &set.description),
+        "get_symbol_set should round-trip the set's fields"
+    );
+    assert!(
+        fetched.symbols.is_empty(),
+        "a new set should have no members"
+    );
+
+    let mut updated = set.clone();
+    updated.description = "Earth, water, air and fire".to_string();
+    repository
+        .update_symbol_set(updated.clone())
+        .await
+        .expect("update_symbol_set should succeed");
+    assert_eq!(
+        repository
+            .get_symbol_set(&set.id)
+            .await
+            .unwrap()
+            .description,
+        updated.description,
+        "get_symbol_set should return the updated set"
+    );
+
+    repository
+        .delete_symbol_set(&set.id)
+        .await
+        .expect("delete_symbol_set should succeed");
+    assert!(
+        matches!(
+            repository.get_symbol_set(&set.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "a deleted set should no longer be found"
+    );
+}
+
+/// Duplicate set IDs conflict; unknown set IDs are reported as not found.
+pub async fn symbol_set_conflicts_and_not_found(factory: &dyn RepositoryFactory) {
+    let run = Run::new("set-errors");
+    let repository = factory.create_symbol_set_repository();
+    let set = run.symbol_set("Seasons", "The turning year");
+
+    repository.create_symbol_set(set.clone()).await.unwrap();
+
+    let conflict = repository.create_symbol_set(set.clone()).await;
+    assert!(
+        matches!(conflict, Err(RepositoryError::Conflict(_))),
+        "creating a duplicate set should conflict, got {:?}",
+        conflict
+    );
+
+    let missing = run.symbol_set("Missing", "Never created");
+    let results = [
+        (
+            "get_symbol_set",
+            repository.get_symbol_set(&missing.id).await.err(),
+        ),
+        (
+            "update_symbol_set",
+            repository.update_symbol_set(missing.clone()).await.err(),
+        ),
+        (
+            "delete_symbol_set",
+            repository.delete_symbol_set(&missing.id).await.err(),
+        ),
+    ];
+    for (operation, error) in results {
+        assert!(
+            matches!(error, Some(RepositoryError::NotFound(_))),
+            "{} on an unknown set should be NotFound, got {:?}",
+            operation,
+            error
+        );
+    }
+
+    cleanup(factory, &[], &[&set]).await;
+}
+
+/// Set search covers the set's own name and description; category filters are exact.
+pub async fn symbol_set_search_and_category_filter(factory: &dyn RepositoryFactory) {
+    let run = Run::new("set-search");
+    let symbols = factory.create_symbol_repository();
+    let repository = factory.create_symbol_set_repository();
+
+    let serpent = run.symbol("Serpent", "Renewal");
+    symbols.create_symbol(serpent.clone()).await.unwrap();
+
+    let mut animals = run.symbol_set("Animals", "Creatures of DREAMS");
+    animals.add_symbol(serpent.clone());
+    let mut colours = run.symbol_set("Colours", "Hues");
+    colours.category = format!("{}-other", run.category());
+    let sets = [&animals, &colours];
+
+    for set in sets {
+        repository.create_symbol_set(set.clone()).await.unwrap();
+    }
+
+    let found = repository
+        .search_symbol_sets("dreams", &ListQuery::new())
+        .await
+        .unwrap();
+    let ids: Vec<String> = found.items.into_iter().map(|s| s.id).collect();
+    assert_eq!(
+        run.own_ids(&ids),
+        vec![animals.id.clone()],
+        "set search should match the description case-insensitively"
+    );
+
+    let found = repository
+        .search_symbol_sets("serpent", &ListQuery::new())
+        .await
+        .unwrap();
+    let ids: Vec<String> = found.items.into_iter().map(|s| s.id).collect();
+    assert!(
+        run.own_ids(&ids).is_empty(),
+        "set search should not match the names of member symbols"
+    );
+
+    let found = repository
+        .list_symbol_sets(&ListQuery::new().category(Some(run.category())))
+        .await
+        .unwrap();
+    let ids: Vec<String> = found.items.into_iter().map(|s| s.id).collect();
+    assert_eq!(
+        run.own_ids(&ids),
+        vec![animals.id.clone()],
+        "list_symbol_sets should return exactly the sets in the category"
+    );
+
+    let found = repository
+        .list_symbol_sets(&ListQuery::new())
+        .await
+        .unwrap();
+    let ids: Vec<String> = found.items.into_iter().map(|s| s.id).collect();
+    assert_eq!(
+        sorted(run.own_ids(&ids)),
+        sorted(vec![animals.id.clone(), colours.id.clone()]),
+        "list_symbol_sets without a category should return every set"
+    );
+
+    cleanup(factory, &[&serpent], &sets).await;
+}
+
+/// Set members are read back as the current symbols: unknown members are dropped,
+/// updates to a member show through and deleted members disappear.
+pub async fn symbol_set_membership(factory: &dyn RepositoryFactory) {
+    let run = Run::new("set-members");
+    let symbols = factory.create_symbol_repository();
+    let repository = factory.create_symbol_set_repository();
+
+    let earth = run.symbol("Earth", "Stability");
+    let air = run.symbol("Air", "Intellect");
+    for symbol in [&earth, &air] {
+        symbols.create_symbol(symbol.clone()).await.unwrap();
+    }
+
+    let mut set = run.symbol_set("Elements", "The classical elements");
+    set.add_symbol(earth.clone());
+    set.add_symbol(air.clone());
+    set.add_symbol(run.symbol("Aether", "Never created"));
+    repository.create_symbol_set(set.clone()).await.unwrap();
+
+    let members = |set: SymbolSet| sorted(set.symbols.into_keys().collect());
+
+    let fetched = repository.get_symbol_set(&set.id).await.unwrap();
+    assert_eq!(
+        members(fetched.clone()),
+        sorted(vec![earth.id.clone(), air.id.clone()]),
+        "members should be resolved to existing symbols only"
+    );
+    assert_eq!(
+        fetched.symbols[&earth.id], earth,
+        "members should carry the full symbol"
+    );
+
+    let mut heavier = earth.clone();
+    heavier.description = "Solid ground".to_string();
+    symbols.update_symbol(heavier.clone()).await.unwrap();
+    assert_eq!(
+        repository.get_symbol_set(&set.id).await.unwrap().symbols[&earth.id].description,
+        heavier.description,
+        "members should reflect updates to the symbol"
+    );
+
+    symbols.delete_symbol(&air.id).await.unwrap();
+    assert_eq!(
+        members(repository.get_symbol_set(&set.id).await.unwrap()),
+        vec![earth.id.clone()],
+        "deleted symbols should drop out of their sets"
+    );
+
+    let mut trimmed = set.clone();
+    trimmed.remove_symbol(&earth.id);
+    repository.update_symbol_set(trimmed).await.unwrap();
+    assert!(
+        repository
+            .get_symbol_set(&set.id)
+            .await
+            .unwrap()
+            .symbols
+            .is_empty(),
+        "updating a set should replace its members"
+    );
+
+    let listed = repository
+        .list_symbol_sets(&ListQuery::new().category(Some(run.category())))
+        .await
+        .unwrap();
+    assert!(
+        listed.items.iter().all(|s| s.symbols.is_empty()),
+        "listed sets should resolve members the same way as get_symbol_set"
+    );
+
+    cleanup(factory, &[&earth, &air], &[&set]).await;
+}
+
+/// Members keep the order they were added in, through reads and updates.
+pub async fn symbol_set_member_order(factory: &dyn RepositoryFactory) {
+    let run = Run::new("set-order");
+    let symbols = factory.create_symbol_repository();
+    let repository = factory.create_symbol_set_repository();
+
+    let cup = run.symbol("Cup", "Receptivity");
+    let anchor = run.symbol("Anchor", "Hope");
+    let bell = run.symbol("Bell", "A call");
+    for symbol in [&cup, &anchor, &bell] {
+        symbols.create_symbol(symbol.clone()).await.unwrap();
+    }
+    let set = run.symbol_set("Emblems", "Emblems in the order they were added");
+    repository.create_symbol_set(set.clone()).await.unwrap();
+
+    for symbol in [&cup, &anchor, &bell] {
+        repository.add_member(&set.id, &symbol.id).await.unwrap();
+    }
+    let added = vec![cup.id.clone(), anchor.id.clone(), bell.id.clone()];
+    let fetched = repository.get_symbol_set(&set.id).await.unwrap();
+    assert_eq!(
+        fetched.symbol_ids(),
+        added,
+        "members should come back in the order they were added"
+    );
+
+    let mut renamed = fetched;
+    renamed.name = format!("Renamed emblems {}", run.prefix);
+    repository.update_symbol_set(renamed).await.unwrap();
+    assert_eq!(
+        repository
+            .get_symbol_set(&set.id)
+            .await
+            .unwrap()
+            .symbol_ids(),
+        added,
+        "updating a set should keep its members in order"
+    );
+
+    cleanup(factory, &[&cup, &anchor, &bell], &[&set]).await;
+}
+
+/// Members can be added and removed one at a time, and a symbol's sets listed.
+pub async fn symbol_set_member_links(factory: &dyn RepositoryFactory) {
+    let run = Run::new("set-links");
+    let symbols = factory.create_symbol_repository();
+    let repository = factory.create_symbol_set_repository();
+
+    let sun = run.symbol("Sun", "Consciousness");
+    let moon = run.symbol("Moon", "The unconscious");
+    for symbol in [&sun, &moon] {
+        symbols.create_symbol(symbol.clone()).await.unwrap();
+    }
+    let mut planets = run.symbol_set("Planets", "The classical planets");
+    planets.add_symbol(sun.clone());
+    let lights = run.symbol_set("Lights", "The two great lights");
+    for set in [&planets, &lights] {
+        repository.create_symbol_set(set.clone()).await.unwrap();
+    }
+
+    let members = |set: SymbolSet| sorted(set.symbols.into_keys().collect());
+
+    repository.add_member(&lights.id, &sun.id).await.unwrap();
+    repository.add_member(&lights.id, &moon.id).await.unwrap();
+    repository
+        .add_member(&lights.id, &moon.id)
+        .await
+        .expect("adding a member again should succeed");
+    assert_eq!(
+        members(repository.get_symbol_set(&lights.id).await.unwrap()),
+        sorted(vec![sun.id.clone(), moon.id.clone()]),
+        "added symbols should be members of the set"
+    );
+
+    for (set_id, symbol_id) in [
+        (lights.id.clone(), run.id("comet")),
+        (run.id("missing"), sun.id.clone()),
+    ] {
+        assert!(
+            matches!(
+                repository.add_member(&set_id, &symbol_id).await,
+                Err(RepositoryError::NotFound(_))
+            ),
+            "adding {} to {} should be NotFound",
+            symbol_id,
+            set_id
+        );
+    }
+
+    assert_eq!(
+        set_ids_for(repository.as_ref(), &sun.id).await,
+        vec![lights.id.clone(), planets.id.clone()],
+        "a symbol's sets should be listed by name"
+    );
+    let listed = repository.list_sets_for_symbol(&moon.id).await.unwrap();
+    assert_eq!(
+        listed
+            .iter()
+            .map(|set| members(set.clone()))
+            .collect::<Vec<_>>(),
+        vec![sorted(vec![sun.id.clone(), moon.id.clone()])],
+        "listed sets should carry all their members"
+    );
+
+    repository.remove_member(&lights.id, &sun.id).await.unwrap();
+    assert_eq!(
+        members(repository.get_symbol_set(&lights.id).await.unwrap()),
+        vec![moon.id.clone()],
+        "removed symbols should leave the set"
+    );
+    assert!(
+        matches!(
+            repository.remove_member(&lights.id, &sun.id).await,
+            Err(RepositoryError::NotFound(_))
+        ),
+        "removing a symbol that is not a member should be NotFound"
+    );
+    assert_eq!(
+        set_ids_for(repository.as_ref(), &sun.id).await,
+        vec![planets.id.clone()]
+    );
+
+    repository.delete_symbol_set(&planets.id).await.unwrap();
+    assert!(
+        set_ids_for(repository.as_ref(), &sun.id).await.is_empty(),
+        "deleted sets should no longer be listed for their members"
+    );
+
+    cleanup(factory, &[&sun, &moon], &[&planets, &lights]).await;
+}
+
+/// Symbol sets page the same way symbols do.
+pub async fn symbol_set_pagination(factory: &dyn RepositoryFactory) {
+    let run = Run::new("set-pagination");
+    let repository = factory.create_symbol_set_repository();
+
+    let sets: Vec<SymbolSet> = ["Elements", "Planets", "Seasons"]
+        .iter()
+        .map(|name| run.symbol_set(name, "A cycle"))
+        .collect();
+    for set in &sets {
+        repository.create_symbol_set(set.clone()).await.unwrap();
+    }
+    let by_name: Vec<String> = sets.iter().map(|s| s.id.clone()).collect();
+
+    let mut ids = Vec::new();
+    let mut list_cursor = None;
+    let mut query = ListQuery::new().category(Some(run.category())).limit(2);
+    loop {
+        let page = repository.list_symbol_sets(&query).await.unwrap();
+        list_cursor = list_cursor.or(page.next_cursor.clone());
+        assert_eq!(page.total_count, 3, "every page should count all the sets");
+        ids.extend(page.items.into_iter().map(|s| s.id));
+        match page.next_cursor {
+            Some(cursor) => query = query.cursor(Some(cursor)),
+            None => break,
+        }
+    }
+    assert_eq!(ids, by_name, "paging should visit every set in name order");
+
+    let mut ids = Vec::new();
+    let mut query = ListQuery::new().sort(Some(SortOrder::NameDesc)).limit(2);
+    loop {
+        let page = repository
+            .search_symbol_sets(&run.prefix, &query)
+            .await
+            .unwrap();
+        ids.extend(page.items.into_iter().map(|s| s.id));
+        match page.next_cursor {
+            Some(cursor) => query = query.cursor(Some(cursor)),
+            None => break,
+        }
+    }
+    let expected: Vec<String> = by_name.iter().rev().cloned().collect();
+    assert_eq!(
+        ids, expected,
+        "paging a search should visit every match in order"
+    );
+
+    match repository
+        .search_symbol_sets(
+            &run.prefix,
+            &ListQuery::new().sort(Some(SortOrder::Relevance)),
+        )
+        .await
+    {
+        Err(RepositoryError::Validation(_)) => {}
+        other => panic!("set searches should not sort by relevance, got {:?}", other),
+    }
+
+    match repository
+        .search_symbol_sets(
+            &run.prefix,
+            &ListQuery::new()
+                .category(Some(run.category()))
+                .cursor(list_cursor),
+        )
+        .await
+    {
+        Err(RepositoryError::Validation(_)) => {}
+        other => panic!(
+            "set searches should reject a cursor from a listing, got {:?}",
+            other
+        ),
+    }
+
+    let refs: Vec<&SymbolSet> = sets.iter().collect();
+    cleanup(factory, &[], &refs).await;
+}
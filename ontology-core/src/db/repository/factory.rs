@@ -1,35 +1,181 @@
 // SPDX-License-Identifier: MPL-2.0 OR Commercial
 // Copyright (c) 2024 Symbol Ontology Contributors
 
-use sqlx::PgPool;
+use sqlx::{PgPool, SqlitePool};
+use std::path::Path;
 use std::sync::Arc;
 
+use crate::db::memory::{MemoryStore, Snapshot};
+use crate::db::pool::{DbError, DbResult};
 use crate::db::repository::{
-    interfaces::{SymbolRepository, SymbolSetRepository},
-    PgSymbolRepository, PgSymbolSetRepository,
+    interfaces::{ArchetypeRepository, RepositoryResult, SymbolRepository, SymbolSetRepository},
+    ApiClient, HttpArchetypeRepository, HttpSymbolRepository, HttpSymbolSetRepository,
+    InMemoryArchetypeRepository, InMemorySymbolRepository, InMemorySymbolSetRepository,
+    PgArchetypeRepository, PgSymbolRepository, PgSymbolSetRepository, SqliteArchetypeRepository,
+    SqliteSymbolRepository, SqliteSymbolSetRepository,
 };
+use crate::integrity::IntegrityMode;
 
-pub trait RepositoryFactory {
+pub trait RepositoryFactory: Send + Sync {
     fn create_symbol_repository(&self) -> Arc<dyn SymbolRepository>;
     fn create_symbol_set_repository(&self) -> Arc<dyn SymbolSetRepository>;
+    fn create_archetype_repository(&self) -> Arc<dyn ArchetypeRepository>;
 }
 
 pub struct PgRepositoryFactory {
     pool: PgPool,
+    integrity: IntegrityMode,
 }
 
 impl PgRepositoryFactory {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            integrity: IntegrityMode::default(),
+        }
+    }
+
+    /// How the repositories treat writes that name unknown symbols.
+    pub fn with_integrity(mut self, integrity: IntegrityMode) -> Self {
+        self.integrity = integrity;
+        self
     }
 }
 
 impl RepositoryFactory for PgRepositoryFactory {
     fn create_symbol_repository(&self) -> Arc<dyn SymbolRepository> {
-        Arc::new(PgSymbolRepository::new(self.pool.clone()))
+        Arc::new(PgSymbolRepository::new(self.pool.clone()).with_integrity(self.integrity))
+    }
+
+    fn create_symbol_set_repository(&self) -> Arc<dyn SymbolSetRepository> {
+        Arc::new(PgSymbolSetRepository::new(self.pool.clone()).with_integrity(self.integrity))
+    }
+
+    fn create_archetype_repository(&self) -> Arc<dyn ArchetypeRepository> {
+        Arc::new(PgArchetypeRepository::new(self.pool.clone()))
+    }
+}
+
+pub struct SqliteRepositoryFactory {
+    pool: SqlitePool,
+    integrity: IntegrityMode,
+}
+
+impl SqliteRepositoryFactory {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            integrity: IntegrityMode::default(),
+        }
+    }
+
+    /// How the repositories treat writes that name unknown symbols.
+    pub fn with_integrity(mut self, integrity: IntegrityMode) -> Self {
+        self.integrity = integrity;
+        self
+    }
+}
+
+impl RepositoryFactory for SqliteRepositoryFactory {
+    fn create_symbol_repository(&self) -> Arc<dyn SymbolRepository> {
+        Arc::new(SqliteSymbolRepository::new(self.pool.clone()).with_integrity(self.integrity))
+    }
+
+    fn create_symbol_set_repository(&self) -> Arc<dyn SymbolSetRepository> {
+        Arc::new(SqliteSymbolSetRepository::new(self.pool.clone()).with_integrity(self.integrity))
+    }
+
+    fn create_archetype_repository(&self) -> Arc<dyn ArchetypeRepository> {
+        Arc::new(SqliteArchetypeRepository::new(self.pool.clone()))
+    }
+}
+
+/// Repositories sharing one in-memory store, optionally seeded from a JSON snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryRepositoryFactory {
+    store: MemoryStore,
+    integrity: IntegrityMode,
+}
+
+impl InMemoryRepositoryFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_snapshot(snapshot: Snapshot) -> RepositoryResult<Self> {
+        Ok(Self {
+            store: MemoryStore::from_snapshot(snapshot)?,
+            integrity: IntegrityMode::default(),
+        })
+    }
+
+    /// How the repositories treat writes that name unknown symbols. Snapshots
+    /// are loaded as they are; `integrity check` reports what they leave dangling.
+    pub fn with_integrity(mut self, integrity: IntegrityMode) -> Self {
+        self.integrity = integrity;
+        self
+    }
+
+    /// Reads a snapshot file and seeds a new store with it.
+    pub fn load(path: impl AsRef<Path>) -> DbResult<Self> {
+        let path = path.as_ref();
+        Self::from_snapshot(Snapshot::load(path)?)
+            .map_err(|e| DbError::Connection(format!("Invalid snapshot {}: {}", path.display(), e)))
+    }
+
+    pub fn store(&self) -> &MemoryStore {
+        &self.store
+    }
+}
+
+impl RepositoryFactory for InMemoryRepositoryFactory {
+    fn create_symbol_repository(&self) -> Arc<dyn SymbolRepository> {
+        Arc::new(
+            InMemorySymbolRepository::with_store(self.store.clone()).with_integrity(self.integrity),
+        )
     }
 
     fn create_symbol_set_repository(&self) -> Arc<dyn SymbolSetRepository> {
-        Arc::new(PgSymbolSetRepository::new(self.pool.clone()))
+        Arc::new(
+            InMemorySymbolSetRepository::with_store(self.store.clone())
+                .with_integrity(self.integrity),
+        )
+    }
+
+    fn create_archetype_repository(&self) -> Arc<dyn ArchetypeRepository> {
+        Arc::new(InMemoryArchetypeRepository::with_store(self.store.clone()))
+    }
+}
+
+/// Repositories that go through an API server's `/api/v2` endpoints instead of
+/// a database.
+#[derive(Debug, Clone)]
+pub struct HttpRepositoryFactory {
+    client: ApiClient,
+}
+
+impl HttpRepositoryFactory {
+    pub fn new(api_url: &str) -> RepositoryResult<Self> {
+        Ok(Self {
+            client: ApiClient::new(api_url)?,
+        })
+    }
+
+    pub fn client(&self) -> &ApiClient {
+        &self.client
+    }
+}
+
+impl RepositoryFactory for HttpRepositoryFactory {
+    fn create_symbol_repository(&self) -> Arc<dyn SymbolRepository> {
+        Arc::new(HttpSymbolRepository::new(self.client.clone()))
+    }
+
+    fn create_symbol_set_repository(&self) -> Arc<dyn SymbolSetRepository> {
+        Arc::new(HttpSymbolSetRepository::new(self.client.clone()))
+    }
+
+    fn create_archetype_repository(&self) -> Arc<dyn ArchetypeRepository> {
+        Arc::new(HttpArchetypeRepository::new(self.client.clone()))
     }
 }
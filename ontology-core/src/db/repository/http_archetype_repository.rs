@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::db::repository::http_client::{ApiClient, SymbolsResponse};
+use crate::db::repository::interfaces::{ArchetypeRepository, Repository, RepositoryResult};
+use crate::db::repository::pagination::{ListQuery, Page};
+use crate::domain::{Archetype, ArchetypeNode, Symbol};
+
+/// Archetypes read from and written to an API server rather than a database.
+#[derive(Debug, Clone)]
+pub struct HttpArchetypeRepository {
+    client: ApiClient,
+}
+
+impl HttpArchetypeRepository {
+    pub fn new(client: ApiClient) -> Self {
+        HttpArchetypeRepository { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct ArchetypesResponse {
+    archetypes: Vec<Archetype>,
+}
+
+#[derive(Deserialize)]
+struct HierarchyResponse {
+    roots: Vec<ArchetypeNode>,
+}
+
+#[derive(Serialize)]
+struct LinkArchetypeRequest<'a> {
+    archetype_id: &'a str,
+}
+
+impl Repository for HttpArchetypeRepository {}
+
+#[async_trait]
+impl ArchetypeRepository for HttpArchetypeRepository {
+    async fn get_archetype(&self, id: &str) -> RepositoryResult<Archetype> {
+        self.client.get(&["archetypes", id], &[]).await
+    }
+
+    async fn list_archetypes(&self) -> RepositoryResult<Vec<Archetype>> {
+        let response: ArchetypesResponse = self.client.get(&["archetypes"], &[]).await?;
+        Ok(response.archetypes)
+    }
+
+    async fn create_archetype(&self, archetype: Archetype) -> RepositoryResult<Archetype> {
+        self.client.post(&["archetypes"], &archetype).await
+    }
+
+    async fn update_archetype(&self, archetype: Archetype) -> RepositoryResult<Archetype> {
+        self.client
+            .post(&["archetypes", &archetype.id], &archetype)
+            .await
+    }
+
+    async fn delete_archetype(&self, id: &str) -> RepositoryResult<()> {
+        self.client.delete(&["archetypes", id], &[]).await
+    }
+
+    async fn link_symbol(&self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()> {
+        self.client
+            .post_empty(
+                &["symbols", symbol_id, "archetypes"],
+                &LinkArchetypeRequest { archetype_id },
+            )
+            .await
+    }
+
+    async fn unlink_symbol(&self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()> {
+        self.client
+            .delete(&["symbols", symbol_id, "archetypes", archetype_id], &[])
+            .await
+    }
+
+    async fn list_symbols_by_archetype(
+        &self,
+        archetype_id: &str,
+        include_descendants: bool,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<Symbol>> {
+        let params = [("include_descendants", include_descendants.to_string())];
+        self.client
+            .get_page::<SymbolsResponse<Symbol>, _>(
+                &["archetypes", archetype_id, "symbols"],
+                &params,
+                query,
+            )
+            .await
+    }
+
+    async fn list_archetypes_for_symbol(
+        &self,
+        symbol_id: &str,
+    ) -> RepositoryResult<Vec<Archetype>> {
+        let response: ArchetypesResponse = self
+            .client
+            .get(&["symbols", symbol_id, "archetypes"], &[])
+            .await?;
+        Ok(response.archetypes)
+    }
+
+    async fn get_hierarchy(&self, root_id: Option<&str>) -> RepositoryResult<Vec<ArchetypeNode>> {
+        let query: Vec<(&str, String)> = root_id
+            .map(|root| ("root", root.to_string()))
+            .into_iter()
+            .collect();
+        let response: HierarchyResponse = self
+            .client
+            .get(&["archetypes", "hierarchy"], &query)
+            .await?;
+        Ok(response.roots)
+    }
+}
@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::db::repository::interfaces::{RepositoryError, RepositoryResult};
+use crate::db::repository::pagination::{ListQuery, Page};
+
+/// Path of the API version the HTTP repositories speak.
+const API_PATH: [&str; 2] = ["api", "v2"];
+
+/// How long a single request to the API server may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Page size asked for when reading a whole listing a page at a time.
+const LIST_PAGE_SIZE: usize = 500;
+
+/// Query parameters asking the server for the page `query` describes.
+fn page_params(query: &ListQuery) -> Vec<(&str, String)> {
+    let mut params = Vec::new();
+    if let Some(limit) = query.limit {
+        params.push(("limit", limit.to_string()));
+    }
+    if let Some(category) = &query.category {
+        params.push(("category", category.clone()));
+    }
+    if let Some(cursor) = &query.cursor {
+        params.push(("cursor", cursor.clone()));
+    }
+    if let Some(sort) = query.sort {
+        params.push(("sort", sort.to_string()));
+    }
+    params
+}
+
+/// A page of symbols or search hits, as the listing endpoints return it.
+#[derive(Deserialize)]
+pub(crate) struct SymbolsResponse<T> {
+    symbols: Vec<T>,
+    total_count: usize,
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+impl<T> From<SymbolsResponse<T>> for Page<T> {
+    fn from(response: SymbolsResponse<T>) -> Self {
+        Page {
+            items: response.symbols,
+            next_cursor: response.next_cursor,
+            total_count: response.total_count,
+        }
+    }
+}
+
+/// A client for the API server's `/api/v2` endpoints, shared by the HTTP
+/// repositories.
+///
+/// Error responses are mapped back onto [`RepositoryError`] by status code, so
+/// callers see the same errors as they would from a local backend.
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    client: Client,
+    base_url: Url,
+}
+
+impl ApiClient {
+    /// Creates a client for the API server at `api_url`, such as
+    /// `http://localhost:8080`. A URL that already ends in `/api/v2` is used as is.
+    pub fn new(api_url: &str) -> RepositoryResult<Self> {
+        let invalid = |reason: String| {
+            RepositoryError::Validation(format!("Invalid API URL '{}': {}", api_url, reason))
+        };
+
+        let mut base_url = Url::parse(api_url).map_err(|e| invalid(e.to_string()))?;
+        if !matches!(base_url.scheme(), "http" | "https") {
+            return Err(invalid("expected an http:// or https:// URL".to_string()));
+        }
+        base_url.set_query(None);
+        base_url.set_fragment(None);
+        {
+            let mut path = base_url
+                .path_segments_mut()
+                .map_err(|_| invalid("URL cannot have a path".to_string()))?;
+            path.pop_if_empty();
+        }
+        let segments: Vec<&str> = base_url.path_segments().into_iter().flatten().collect();
+        if !segments.ends_with(&API_PATH) {
+            base_url
+                .path_segments_mut()
+                .map_err(|_| invalid("URL cannot have a path".to_string()))?
+                .extend(API_PATH);
+        }
+
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                RepositoryError::Internal(format!("Failed to build HTTP client: {}", e))
+            })?;
+
+        Ok(ApiClient { client, base_url })
+    }
+
+    /// The `/api/v2` URL requests are made under.
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// The URL of `segments` under the API path, each segment percent-encoded.
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.extend(segments);
+        }
+        url
+    }
+
+    pub(crate) async fn get<T: DeserializeOwned>(
+        &self,
+        segments: &[&str],
+        query: &[(&str, String)],
+    ) -> RepositoryResult<T> {
+        let response = send(self.client.get(self.url(segments)).query(query)).await?;
+        read_json(response).await
+    }
+
+    /// Gets the page `query` describes from a listing endpoint, sending
+    /// `params` along. The server caps how many results a page holds, so a
+    /// query without a limit follows `next_cursor` until every page is read.
+    pub(crate) async fn get_page<R, T>(
+        &self,
+        segments: &[&str],
+        params: &[(&str, String)],
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<T>>
+    where
+        R: DeserializeOwned + Into<Page<T>>,
+    {
+        let get = |query: ListQuery| async move {
+            let mut query_params = page_params(&query);
+            query_params.extend_from_slice(params);
+            self.get::<R>(segments, &query_params).await.map(Into::into)
+        };
+
+        if query.limit.is_some() {
+            return get(query.clone()).await;
+        }
+
+        let mut page = query.clone().limit(LIST_PAGE_SIZE);
+        let mut items = Vec::new();
+        loop {
+            let next = get(page.clone()).await?;
+            items.extend(next.items);
+            match next.next_cursor {
+                Some(cursor) => page.cursor = Some(cursor),
+                None => {
+                    return Ok(Page {
+                        items,
+                        next_cursor: None,
+                        total_count: next.total_count,
+                    })
+                }
+            }
+        }
+    }
+
+    pub(crate) async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        segments: &[&str],
+        body: &B,
+    ) -> RepositoryResult<T> {
+        let response = send(self.client.post(self.url(segments)).json(body)).await?;
+        read_json(response).await
+    }
+
+    /// Posts `body` to an endpoint that answers without content.
+    pub(crate) async fn post_empty<B: Serialize + ?Sized>(
+        &self,
+        segments: &[&str],
+        body: &B,
+    ) -> RepositoryResult<()> {
+        send(self.client.post(self.url(segments)).json(body)).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn delete(
+        &self,
+        segments: &[&str],
+        query: &[(&str, String)],
+    ) -> RepositoryResult<()> {
+        send(self.client.delete(self.url(segments)).query(query)).await?;
+        Ok(())
+    }
+}
+
+async fn send(request: RequestBuilder) -> RepositoryResult<Response> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| RepositoryError::Internal(format!("Request to API server failed: {}", e)))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    // The API server reports errors as {"statusCode", "error", "message"}
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|value| value.get("message")?.as_str().map(str::to_string))
+        .unwrap_or(body);
+    Err(status_error(status, message))
+}
+
+/// The error an API response with `status` stands for.
+pub(crate) fn status_error(status: StatusCode, message: String) -> RepositoryError {
+    match status {
+        StatusCode::NOT_FOUND => RepositoryError::NotFound(message),
+        StatusCode::CONFLICT => RepositoryError::Conflict(message),
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+            RepositoryError::Validation(message)
+        }
+        _ => match message.strip_prefix("Not implemented: ") {
+            Some(message) => RepositoryError::NotImplemented(message.to_string()),
+            None => {
+                RepositoryError::Internal(format!("API server returned {}: {}", status, message))
+            }
+        },
+    }
+}
+
+async fn read_json<T: DeserializeOwned>(response: Response) -> RepositoryResult<T> {
+    response.json().await.map_err(|e| {
+        RepositoryError::Internal(format!("Unexpected response from API server: {}", e))
+    })
+}
@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::db::repository::batch::{self, BatchResult, OperationOutcome, SymbolOperation};
+use crate::db::repository::http_client::{status_error, ApiClient, SymbolsResponse};
+use crate::db::repository::interfaces::{
+    Repository, RepositoryError, RepositoryResult, SymbolRepository,
+};
+use crate::db::repository::pagination::{ListQuery, Page};
+use crate::domain::search::SearchHit;
+use crate::domain::{RelationDirection, RelationType, Symbol, SymbolRelation};
+
+/// Symbols read from and written to an API server rather than a database.
+///
+/// Fuzzy and semantic search have no endpoints of their own, so they report
+/// `NotImplemented` like any backend without them. Bulk upserts go through an
+/// atomic batch, which checks each symbol against those before it rather
+/// than against the whole batch.
+#[derive(Debug, Clone)]
+pub struct HttpSymbolRepository {
+    client: ApiClient,
+}
+
+impl HttpSymbolRepository {
+    pub fn new(client: ApiClient) -> Self {
+        HttpSymbolRepository { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct CategoriesResponse {
+    categories: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RelationsResponse {
+    relations: Vec<SymbolRelation>,
+}
+
+#[derive(Serialize)]
+struct AddRelatedSymbolRequest<'a> {
+    related_symbol_id: &'a str,
+    relation_type: RelationType,
+    weight: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct BatchRequest {
+    operations: Vec<SymbolOperation>,
+    atomic: bool,
+}
+
+#[derive(Deserialize)]
+struct BatchResponse {
+    results: Vec<BatchItem>,
+    committed: bool,
+}
+
+#[derive(Deserialize)]
+struct BatchItem {
+    status: u16,
+    #[serde(default)]
+    symbol: Option<Symbol>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl From<BatchItem> for OperationOutcome {
+    fn from(item: BatchItem) -> Self {
+        let status = StatusCode::from_u16(item.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        match (status, item.symbol) {
+            (StatusCode::CREATED, Some(symbol)) => OperationOutcome::Created(symbol),
+            (StatusCode::OK, Some(symbol)) => OperationOutcome::Updated(symbol),
+            (StatusCode::NO_CONTENT, _) => OperationOutcome::Deleted,
+            (StatusCode::FAILED_DEPENDENCY, _) => OperationOutcome::Aborted,
+            (status, _) => {
+                OperationOutcome::Failed(status_error(status, item.error.unwrap_or_default()))
+            }
+        }
+    }
+}
+
+fn direction_param(direction: RelationDirection) -> &'static str {
+    match direction {
+        RelationDirection::Outgoing => "outgoing",
+        RelationDirection::Incoming => "incoming",
+        RelationDirection::Both => "both",
+    }
+}
+
+impl Repository for HttpSymbolRepository {}
+
+#[async_trait]
+impl SymbolRepository for HttpSymbolRepository {
+    async fn get_symbol(&self, id: &str) -> RepositoryResult<Symbol> {
+        self.client.get(&["symbols", id], &[]).await
+    }
+
+    async fn list_symbols(&self, query: &ListQuery) -> RepositoryResult<Page<Symbol>> {
+        self.client
+            .get_page::<SymbolsResponse<Symbol>, _>(&["symbols"], &[], query)
+            .await
+    }
+
+    async fn list_categories(&self) -> RepositoryResult<Vec<String>> {
+        let response: CategoriesResponse = self.client.get(&["categories"], &[]).await?;
+        Ok(response.categories)
+    }
+
+    async fn search_symbols(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SearchHit>> {
+        let params = [("query", text.to_string())];
+        self.client
+            .get_page::<SymbolsResponse<SearchHit>, _>(&["symbols"], &params, query)
+            .await
+    }
+
+    async fn create_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
+        self.client.post(&["symbols"], &symbol).await
+    }
+
+    async fn update_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
+        self.client.post(&["symbols", &symbol.id], &symbol).await
+    }
+
+    async fn delete_symbol(&self, id: &str) -> RepositoryResult<()> {
+        self.client.delete(&["symbols", id], &[]).await
+    }
+
+    /// Sends the symbols as one atomic batch, so at most
+    /// [`batch::MAX_BATCH_OPERATIONS`] of them at a time.
+    async fn upsert_many(&self, symbols: Vec<Symbol>) -> RepositoryResult<Vec<Symbol>> {
+        batch::validate_upserts(&symbols)?;
+        let operations = symbols
+            .into_iter()
+            .map(|symbol| SymbolOperation::Upsert { symbol })
+            .collect();
+        let result = self.apply_batch(operations, true).await?;
+
+        let mut stored = Vec::with_capacity(result.outcomes.len());
+        for outcome in result.outcomes {
+            match outcome {
+                OperationOutcome::Created(symbol) | OperationOutcome::Updated(symbol) => {
+                    stored.push(symbol)
+                }
+                OperationOutcome::Failed(error) => return Err(error),
+                OperationOutcome::Deleted | OperationOutcome::Aborted => {}
+            }
+        }
+        Ok(stored)
+    }
+
+    async fn apply_batch(
+        &self,
+        operations: Vec<SymbolOperation>,
+        atomic: bool,
+    ) -> RepositoryResult<BatchResult> {
+        // The server would refuse the whole batch; say so before sending it
+        if operations.len() > batch::MAX_BATCH_OPERATIONS {
+            return Err(RepositoryError::Validation(format!(
+                "A batch holds at most {} operations, got {}",
+                batch::MAX_BATCH_OPERATIONS,
+                operations.len()
+            )));
+        }
+        let request = BatchRequest { operations, atomic };
+        let response: BatchResponse = self.client.post(&["symbols:batch"], &request).await?;
+        Ok(BatchResult {
+            outcomes: response.results.into_iter().map(Into::into).collect(),
+            committed: response.committed,
+        })
+    }
+
+    async fn add_relation(&self, relation: SymbolRelation) -> RepositoryResult<SymbolRelation> {
+        // The server answers with the updated source symbol, not the relation
+        let request = AddRelatedSymbolRequest {
+            related_symbol_id: &relation.target_id,
+            relation_type: relation.relation_type,
+            weight: relation.weight,
+        };
+        let _: Symbol = self
+            .client
+            .post(&["symbols", &relation.source_id, "related"], &request)
+            .await?;
+        Ok(relation)
+    }
+
+    async fn remove_relation(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        relation_type: RelationType,
+    ) -> RepositoryResult<()> {
+        self.client
+            .delete(
+                &["symbols", source_id, "relations", target_id],
+                &[("relation_type", relation_type.to_string())],
+            )
+            .await
+    }
+
+    async fn list_relations(
+        &self,
+        symbol_id: &str,
+        direction: RelationDirection,
+    ) -> RepositoryResult<Vec<SymbolRelation>> {
+        let response: RelationsResponse = self
+            .client
+            .get(
+                &["symbols", symbol_id, "relations"],
+                &[("direction", direction_param(direction).to_string())],
+            )
+            .await?;
+        Ok(response.relations)
+    }
+}
@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::db::repository::http_client::ApiClient;
+use crate::db::repository::interfaces::{Repository, RepositoryResult, SymbolSetRepository};
+use crate::db::repository::pagination::{ListQuery, Page};
+use crate::domain::SymbolSet;
+
+/// Symbol sets read from and written to an API server rather than a database.
+#[derive(Debug, Clone)]
+pub struct HttpSymbolSetRepository {
+    client: ApiClient,
+}
+
+impl HttpSymbolSetRepository {
+    pub fn new(client: ApiClient) -> Self {
+        HttpSymbolSetRepository { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct SymbolSetsResponse {
+    symbol_sets: Vec<SymbolSet>,
+    total_count: usize,
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+impl From<SymbolSetsResponse> for Page<SymbolSet> {
+    fn from(response: SymbolSetsResponse) -> Self {
+        Page {
+            items: response.symbol_sets,
+            next_cursor: response.next_cursor,
+            total_count: response.total_count,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct AddMemberRequest<'a> {
+    symbol_id: &'a str,
+}
+
+impl Repository for HttpSymbolSetRepository {}
+
+#[async_trait]
+impl SymbolSetRepository for HttpSymbolSetRepository {
+    async fn get_symbol_set(&self, id: &str) -> RepositoryResult<SymbolSet> {
+        self.client.get(&["symbol-sets", id], &[]).await
+    }
+
+    async fn list_symbol_sets(&self, query: &ListQuery) -> RepositoryResult<Page<SymbolSet>> {
+        self.client
+            .get_page::<SymbolSetsResponse, _>(&["symbol-sets"], &[], query)
+            .await
+    }
+
+    async fn search_symbol_sets(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SymbolSet>> {
+        let params = [("query", text.to_string())];
+        self.client
+            .get_page::<SymbolSetsResponse, _>(&["symbol-sets", "search"], &params, query)
+            .await
+    }
+
+    async fn create_symbol_set(&self, symbol_set: SymbolSet) -> RepositoryResult<SymbolSet> {
+        self.client.post(&["symbol-sets"], &symbol_set).await
+    }
+
+    async fn update_symbol_set(&self, symbol_set: SymbolSet) -> RepositoryResult<SymbolSet> {
+        self.client
+            .post(&["symbol-sets", &symbol_set.id], &symbol_set)
+            .await
+    }
+
+    async fn delete_symbol_set(&self, id: &str) -> RepositoryResult<()> {
+        self.client.delete(&["symbol-sets", id], &[]).await
+    }
+
+    async fn add_member(&self, set_id: &str, symbol_id: &str) -> RepositoryResult<()> {
+        self.client
+            .post_empty(
+                &["symbol-sets", set_id, "members"],
+                &AddMemberRequest { symbol_id },
+            )
+            .await
+    }
+
+    async fn remove_member(&self, set_id: &str, symbol_id: &str) -> RepositoryResult<()> {
+        self.client
+            .delete(&["symbol-sets", set_id, "members", symbol_id], &[])
+            .await
+    }
+
+    async fn list_sets_for_symbol(&self, symbol_id: &str) -> RepositoryResult<Vec<SymbolSet>> {
+        let response: SymbolSetsResponse = self
+            .client
+            .get(&["symbols", symbol_id, "symbol-sets"], &[])
+            .await?;
+        Ok(response.symbol_sets)
+    }
+}
@@ -1,8 +1,15 @@
 // SPDX-License-Identifier: MPL-2.0 OR Commercial
 // Copyright (c) 2024 Symbol Ontology Contributors
 
-use crate::domain::{Symbol, SymbolSet};
+use crate::db::repository::batch::{self, BatchResult, SymbolOperation};
+use crate::db::repository::pagination::{ListQuery, Page};
+use crate::domain::archetypes::build_hierarchy;
+use crate::domain::{
+    Archetype, ArchetypeNode, RelationDirection, RelationType, SearchHit, Symbol, SymbolRelation,
+    SymbolSet,
+};
 use async_trait::async_trait;
+use std::collections::BTreeSet;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
@@ -51,30 +58,205 @@ pub trait Repository {}
 
 #[async_trait]
 pub trait SymbolRepository: Repository + Send + Sync {
+    /// Finds a symbol by ID or, failing that, by alias, localized name or name,
+    /// ignoring case.
     async fn get_symbol(&self, id: &str) -> RepositoryResult<Symbol>;
 
-    async fn list_symbols(&self, category: Option<&str>) -> RepositoryResult<Vec<Symbol>>;
+    /// Finds a symbol by its exact ID, without the fallbacks of
+    /// [`SymbolRepository::get_symbol`].
+    async fn get_symbol_by_id(&self, id: &str) -> RepositoryResult<Symbol> {
+        match self.get_symbol(id).await {
+            Ok(symbol) if symbol.id != id => Err(RepositoryError::NotFound(format!(
+                "Symbol with id {} not found",
+                id
+            ))),
+            result => result,
+        }
+    }
+
+    /// Lists a page of symbols, by name unless the query asks otherwise.
+    async fn list_symbols(&self, query: &ListQuery) -> RepositoryResult<Page<Symbol>>;
 
-    async fn search_symbols(&self, query: &str) -> RepositoryResult<Vec<Symbol>>;
+    /// Finds symbols whose name, aliases, localized names, description,
+    /// interpretations or properties match `text`, most relevant first unless
+    /// the query asks otherwise.
+    async fn search_symbols(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SearchHit>>;
+
+    /// Lists the distinct categories of the stored symbols, in byte order.
+    async fn list_categories(&self) -> RepositoryResult<Vec<String>> {
+        let symbols = self.list_symbols(&ListQuery::new()).await?;
+        let categories: BTreeSet<String> = symbols.items.into_iter().map(|s| s.category).collect();
+        Ok(categories.into_iter().collect())
+    }
 
     async fn create_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol>;
 
     async fn update_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol>;
 
     async fn delete_symbol(&self, id: &str) -> RepositoryResult<()>;
+
+    /// Creates the symbols that do not exist yet and replaces those that do, in
+    /// one transaction: either all of them are stored or none is. Symbols in
+    /// the batch may name each other as related symbols.
+    async fn upsert_many(&self, _symbols: Vec<Symbol>) -> RepositoryResult<Vec<Symbol>> {
+        Err(RepositoryError::NotImplemented(
+            "Bulk upserts are not supported by this repository".to_string(),
+        ))
+    }
+
+    /// Applies `operations` in order. An atomic batch stops at the first
+    /// failure and rolls back the operations before it; otherwise each
+    /// operation succeeds or fails on its own.
+    async fn apply_batch(
+        &self,
+        operations: Vec<SymbolOperation>,
+        atomic: bool,
+    ) -> RepositoryResult<BatchResult> {
+        if atomic {
+            return Err(RepositoryError::NotImplemented(
+                "Atomic batches are not supported by this repository".to_string(),
+            ));
+        }
+        Ok(batch::apply_each(self, operations).await)
+    }
+
+    async fn add_relation(&self, _relation: SymbolRelation) -> RepositoryResult<SymbolRelation> {
+        Err(RepositoryError::NotImplemented(
+            "Symbol relations are not supported by this repository".to_string(),
+        ))
+    }
+
+    async fn remove_relation(
+        &self,
+        _source_id: &str,
+        _target_id: &str,
+        _relation_type: RelationType,
+    ) -> RepositoryResult<()> {
+        Err(RepositoryError::NotImplemented(
+            "Symbol relations are not supported by this repository".to_string(),
+        ))
+    }
+
+    async fn list_relations(
+        &self,
+        _symbol_id: &str,
+        _direction: RelationDirection,
+    ) -> RepositoryResult<Vec<SymbolRelation>> {
+        Err(RepositoryError::NotImplemented(
+            "Symbol relations are not supported by this repository".to_string(),
+        ))
+    }
+
+    /// Finds the `limit` symbols whose names are closest to a possibly
+    /// misspelled `query`, most similar first; see
+    /// [`crate::domain::search::name_similarity`].
+    async fn fuzzy_search_symbols(
+        &self,
+        _query: &str,
+        _limit: usize,
+    ) -> RepositoryResult<Vec<SearchHit>> {
+        Err(RepositoryError::NotImplemented(
+            "Fuzzy search is not supported by this repository".to_string(),
+        ))
+    }
+
+    /// Stores the embedding of a symbol's text, replacing any previous one.
+    /// Updating a symbol's text drops its embedding.
+    async fn set_embedding(&self, _id: &str, _embedding: &[f32]) -> RepositoryResult<()> {
+        Err(RepositoryError::NotImplemented(
+            "Embeddings are not supported by this repository".to_string(),
+        ))
+    }
+
+    /// Lists the symbols that have no embedding yet.
+    async fn list_unembedded_symbols(&self) -> RepositoryResult<Vec<Symbol>> {
+        Err(RepositoryError::NotImplemented(
+            "Embeddings are not supported by this repository".to_string(),
+        ))
+    }
+
+    /// Finds the `limit` embedded symbols nearest to `embedding`, scored by
+    /// cosine similarity.
+    async fn semantic_search_symbols(
+        &self,
+        _embedding: &[f32],
+        _limit: usize,
+    ) -> RepositoryResult<Vec<SearchHit>> {
+        Err(RepositoryError::NotImplemented(
+            "Embeddings are not supported by this repository".to_string(),
+        ))
+    }
 }
 
 #[async_trait]
 pub trait SymbolSetRepository: Repository + Send + Sync {
     async fn get_symbol_set(&self, id: &str) -> RepositoryResult<SymbolSet>;
 
-    async fn list_symbol_sets(&self, category: Option<&str>) -> RepositoryResult<Vec<SymbolSet>>;
+    /// Lists a page of symbol sets, by name unless the query asks otherwise.
+    async fn list_symbol_sets(&self, query: &ListQuery) -> RepositoryResult<Page<SymbolSet>>;
 
-    async fn search_symbol_sets(&self, query: &str) -> RepositoryResult<Vec<SymbolSet>>;
+    /// Finds symbol sets whose name or description match `text`, by name
+    /// unless the query asks otherwise.
+    async fn search_symbol_sets(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SymbolSet>>;
 
     async fn create_symbol_set(&self, symbol_set: SymbolSet) -> RepositoryResult<SymbolSet>;
 
     async fn update_symbol_set(&self, symbol_set: SymbolSet) -> RepositoryResult<SymbolSet>;
 
     async fn delete_symbol_set(&self, id: &str) -> RepositoryResult<()>;
+
+    /// Adds a symbol to the end of a set; adding a member again changes nothing.
+    async fn add_member(&self, set_id: &str, symbol_id: &str) -> RepositoryResult<()>;
+
+    async fn remove_member(&self, set_id: &str, symbol_id: &str) -> RepositoryResult<()>;
+
+    /// Lists the sets a symbol belongs to, by name.
+    async fn list_sets_for_symbol(&self, symbol_id: &str) -> RepositoryResult<Vec<SymbolSet>>;
+}
+
+#[async_trait]
+pub trait ArchetypeRepository: Repository + Send + Sync {
+    async fn get_archetype(&self, id: &str) -> RepositoryResult<Archetype>;
+
+    async fn list_archetypes(&self) -> RepositoryResult<Vec<Archetype>>;
+
+    async fn create_archetype(&self, archetype: Archetype) -> RepositoryResult<Archetype>;
+
+    async fn update_archetype(&self, archetype: Archetype) -> RepositoryResult<Archetype>;
+
+    /// Deletes an archetype; its children move up to its parent.
+    async fn delete_archetype(&self, id: &str) -> RepositoryResult<()>;
+
+    async fn link_symbol(&self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()>;
+
+    async fn unlink_symbol(&self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()>;
+
+    /// Lists a page of the symbols linked to an archetype, optionally including
+    /// those linked to any of its descendants, by name unless `query` says otherwise.
+    async fn list_symbols_by_archetype(
+        &self,
+        archetype_id: &str,
+        include_descendants: bool,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<Symbol>>;
+
+    async fn list_archetypes_for_symbol(&self, symbol_id: &str)
+        -> RepositoryResult<Vec<Archetype>>;
+
+    /// Returns the archetype trees, or the tree under `root_id`.
+    async fn get_hierarchy(&self, root_id: Option<&str>) -> RepositoryResult<Vec<ArchetypeNode>> {
+        if let Some(root_id) = root_id {
+            self.get_archetype(root_id).await?;
+        }
+        let archetypes = self.list_archetypes().await?;
+        Ok(build_hierarchy(&archetypes, root_id))
+    }
 }
@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+
+use crate::db::memory::MemoryStore;
+use crate::db::repository::interfaces::{
+    ArchetypeRepository, Repository, RepositoryError, RepositoryResult,
+};
+use crate::db::repository::pagination::{scope_filter, ListQuery, Page, SortOrder};
+use crate::domain::archetypes::descendant_ids;
+use crate::domain::{Archetype, Symbol};
+
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryArchetypeRepository {
+    store: MemoryStore,
+}
+
+impl InMemoryArchetypeRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_store(store: MemoryStore) -> Self {
+        InMemoryArchetypeRepository { store }
+    }
+}
+
+impl Repository for InMemoryArchetypeRepository {}
+
+#[async_trait]
+impl ArchetypeRepository for InMemoryArchetypeRepository {
+    async fn get_archetype(&self, id: &str) -> RepositoryResult<Archetype> {
+        self.store
+            .read()
+            .archetype(id)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound(format!("Archetype with id {} not found", id)))
+    }
+
+    async fn list_archetypes(&self) -> RepositoryResult<Vec<Archetype>> {
+        let mut archetypes = self.store.read().archetypes.clone();
+        archetypes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(archetypes)
+    }
+
+    async fn create_archetype(&self, archetype: Archetype) -> RepositoryResult<Archetype> {
+        archetype.validate().map_err(RepositoryError::Validation)?;
+
+        let mut data = self.store.write();
+        data.check_parent(&archetype)?;
+        if data.archetype(&archetype.id).is_some() {
+            return Err(RepositoryError::Conflict(format!(
+                "Archetype with ID {} already exists",
+                archetype.id
+            )));
+        }
+
+        data.archetypes.push(archetype.clone());
+        Ok(archetype)
+    }
+
+    async fn update_archetype(&self, archetype: Archetype) -> RepositoryResult<Archetype> {
+        archetype.validate().map_err(RepositoryError::Validation)?;
+
+        let mut data = self.store.write();
+        data.check_parent(&archetype)?;
+
+        let existing = data
+            .archetypes
+            .iter_mut()
+            .find(|a| a.id == archetype.id)
+            .ok_or_else(|| {
+                RepositoryError::NotFound(format!("Archetype with id {} not found", archetype.id))
+            })?;
+
+        *existing = archetype.clone();
+        Ok(archetype)
+    }
+
+    async fn delete_archetype(&self, id: &str) -> RepositoryResult<()> {
+        let mut data = self.store.write();
+
+        let position = data
+            .archetypes
+            .iter()
+            .position(|a| a.id == id)
+            .ok_or_else(|| {
+                RepositoryError::NotFound(format!("Archetype with id {} not found", id))
+            })?;
+        let removed = data.archetypes.remove(position);
+
+        // Move children up to the deleted archetype's parent
+        for archetype in data.archetypes.iter_mut() {
+            if archetype.parent_id.as_deref() == Some(id) {
+                archetype.parent_id = removed.parent_id.clone();
+            }
+        }
+        data.symbol_archetypes
+            .retain(|(_, archetype_id)| archetype_id != id);
+
+        Ok(())
+    }
+
+    async fn link_symbol(&self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()> {
+        self.store.write().link_symbol(symbol_id, archetype_id)
+    }
+
+    async fn unlink_symbol(&self, symbol_id: &str, archetype_id: &str) -> RepositoryResult<()> {
+        let mut data = self.store.write();
+
+        let before = data.symbol_archetypes.len();
+        data.symbol_archetypes
+            .retain(|(s, a)| !(s == symbol_id && a == archetype_id));
+        if data.symbol_archetypes.len() == before {
+            return Err(RepositoryError::NotFound(format!(
+                "Symbol {} is not linked to archetype {}",
+                symbol_id, archetype_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn list_symbols_by_archetype(
+        &self,
+        archetype_id: &str,
+        include_descendants: bool,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<Symbol>> {
+        // Distinguish an unknown archetype from one without symbols
+        self.get_archetype(archetype_id).await?;
+
+        let data = self.store.read();
+        let archetype_ids = if include_descendants {
+            descendant_ids(&data.archetypes, archetype_id)
+        } else {
+            vec![archetype_id.to_string()]
+        };
+
+        let symbols = data
+            .symbols
+            .iter()
+            .filter(|symbol| {
+                query.includes_category(&symbol.category)
+                    && data
+                        .symbol_archetypes
+                        .iter()
+                        .any(|(s, a)| s == &symbol.id && archetype_ids.contains(a))
+            })
+            .cloned()
+            .collect();
+        let filter = [archetype_id, scope_filter(include_descendants)];
+        query.paginate(symbols, SortOrder::Name, false, &filter)
+    }
+
+    async fn list_archetypes_for_symbol(
+        &self,
+        symbol_id: &str,
+    ) -> RepositoryResult<Vec<Archetype>> {
+        let data = self.store.read();
+
+        let mut archetypes: Vec<Archetype> = data
+            .archetypes
+            .iter()
+            .filter(|archetype| {
+                data.symbol_archetypes
+                    .iter()
+                    .any(|(s, a)| s == symbol_id && a == &archetype.id)
+            })
+            .cloned()
+            .collect();
+        archetypes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(archetypes)
+    }
+}
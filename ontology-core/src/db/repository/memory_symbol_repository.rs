@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+use crate::db::memory::{ilike_contains, search_words, MemoryData, MemoryStore};
+use crate::db::repository::batch::{self, BatchResult, OperationOutcome, SymbolOperation};
+use crate::db::repository::interfaces::{
+    Repository, RepositoryError, RepositoryResult, SymbolRepository,
+};
+use crate::db::repository::pagination::{ListQuery, Page, SortOrder};
+use crate::domain::search::{self, SearchHit};
+use crate::domain::{RelationDirection, RelationType, Symbol, SymbolRelation};
+use crate::embedding;
+use crate::integrity::{self, IntegrityMode};
+
+/// Symbols kept in a [`MemoryStore`].
+///
+/// Text search requires every word of the query to match some field, in any
+/// order, as PostgreSQL's full-text search does, but each word is matched as an
+/// ILIKE-style substring: there is no stemming or stop-word removal, and
+/// `or`, `-word` and quoted phrases are taken literally.
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySymbolRepository {
+    store: MemoryStore,
+    integrity: IntegrityMode,
+}
+
+impl InMemorySymbolRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_store(store: MemoryStore) -> Self {
+        InMemorySymbolRepository {
+            store,
+            integrity: IntegrityMode::default(),
+        }
+    }
+
+    pub fn with_integrity(mut self, integrity: IntegrityMode) -> Self {
+        self.integrity = integrity;
+        self
+    }
+
+    /// Applies the integrity mode to the `targets` of symbol `owner_id` that
+    /// name no symbol; a symbol may name itself before it is stored.
+    fn check_targets(
+        &self,
+        data: &MemoryData,
+        owner_id: &str,
+        field: &str,
+        targets: &[String],
+    ) -> RepositoryResult<()> {
+        let missing =
+            integrity::missing_targets(targets, |id| id == owner_id || data.symbol(id).is_some());
+        self.integrity
+            .check_targets(&format!("Symbol {}", owner_id), field, &missing)
+    }
+
+    /// Applies the integrity mode to the related symbols of `symbols` that
+    /// name no symbol; the symbols may name each other, or themselves, before
+    /// they are stored, and the `pending` writes of the same atomic batch.
+    fn check_related(
+        &self,
+        data: &MemoryData,
+        symbols: &[Symbol],
+        pending: &HashSet<String>,
+    ) -> RepositoryResult<()> {
+        let batch: HashSet<&str> = symbols.iter().map(|s| s.id.as_str()).collect();
+        for symbol in symbols {
+            let missing = integrity::missing_targets(&symbol.related_symbols, |id| {
+                batch.contains(id) || pending.contains(id) || data.symbol(id).is_some()
+            });
+            self.integrity.check_targets(
+                &format!("Symbol {}", symbol.id),
+                "related symbols",
+                &missing,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn create_in(
+        &self,
+        data: &mut MemoryData,
+        symbol: Symbol,
+        pending: &HashSet<String>,
+    ) -> RepositoryResult<Symbol> {
+        symbol.validate().map_err(RepositoryError::Validation)?;
+        self.check_related(data, std::slice::from_ref(&symbol), pending)?;
+        data.insert_symbol(symbol.clone())?;
+        Ok(symbol)
+    }
+
+    fn update_in(
+        &self,
+        data: &mut MemoryData,
+        symbol: Symbol,
+        pending: &HashSet<String>,
+    ) -> RepositoryResult<Symbol> {
+        symbol.validate().map_err(RepositoryError::Validation)?;
+        self.check_related(data, std::slice::from_ref(&symbol), pending)?;
+        data.replace_symbol(symbol.clone())?;
+        Ok(symbol)
+    }
+
+    /// Applies one operation of an atomic batch to the staged copy `data`.
+    fn apply_to(
+        &self,
+        data: &mut MemoryData,
+        operation: SymbolOperation,
+        pending: &HashSet<String>,
+    ) -> RepositoryResult<OperationOutcome> {
+        operation.validate()?;
+
+        match operation {
+            SymbolOperation::Create { symbol } => self
+                .create_in(data, symbol, pending)
+                .map(OperationOutcome::Created),
+            SymbolOperation::Update { symbol } => self
+                .update_in(data, symbol, pending)
+                .map(OperationOutcome::Updated),
+            SymbolOperation::Upsert { symbol } => {
+                if data.symbol(&symbol.id).is_some() {
+                    self.update_in(data, symbol, pending)
+                        .map(OperationOutcome::Updated)
+                } else {
+                    self.create_in(data, symbol, pending)
+                        .map(OperationOutcome::Created)
+                }
+            }
+            SymbolOperation::Delete { id } => {
+                data.remove_symbol(&id).map(|()| OperationOutcome::Deleted)
+            }
+        }
+    }
+}
+
+impl Repository for InMemorySymbolRepository {}
+
+#[async_trait]
+impl SymbolRepository for InMemorySymbolRepository {
+    async fn get_symbol(&self, id: &str) -> RepositoryResult<Symbol> {
+        let data = self.store.read();
+        data.symbol(id)
+            .or_else(|| search::resolve(&data.symbols, id))
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound(format!("Symbol with id {} not found", id)))
+    }
+
+    async fn get_symbol_by_id(&self, id: &str) -> RepositoryResult<Symbol> {
+        self.store
+            .read()
+            .symbol(id)
+            .cloned()
+            .ok_or_else(|| RepositoryError::NotFound(format!("Symbol with id {} not found", id)))
+    }
+
+    async fn list_symbols(&self, query: &ListQuery) -> RepositoryResult<Page<Symbol>> {
+        let data = self.store.read();
+        let symbols = data
+            .symbols
+            .iter()
+            .filter(|s| query.includes_category(&s.category))
+            .cloned()
+            .collect();
+        query.paginate(symbols, SortOrder::Name, false, &[])
+    }
+
+    async fn search_symbols(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SearchHit>> {
+        let words = search_words(text);
+        let data = self.store.read();
+        let hits = data
+            .symbols
+            .iter()
+            .filter(|s| query.includes_category(&s.category))
+            .filter_map(|s| SearchHit::from_words(s.clone(), &words, ilike_contains))
+            .collect();
+        query.paginate(hits, SortOrder::Relevance, true, &[text])
+    }
+
+    async fn fuzzy_search_symbols(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> RepositoryResult<Vec<SearchHit>> {
+        let data = self.store.read();
+        Ok(search::fuzzy_matches(
+            data.symbols.iter().cloned(),
+            query,
+            limit,
+        ))
+    }
+
+    async fn create_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
+        self.create_in(&mut self.store.write(), symbol, &HashSet::new())
+    }
+
+    async fn update_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
+        self.update_in(&mut self.store.write(), symbol, &HashSet::new())
+    }
+
+    async fn delete_symbol(&self, id: &str) -> RepositoryResult<()> {
+        self.store.write().remove_symbol(id)
+    }
+
+    async fn upsert_many(&self, symbols: Vec<Symbol>) -> RepositoryResult<Vec<Symbol>> {
+        batch::validate_upserts(&symbols)?;
+
+        let mut data = self.store.write();
+        self.check_related(&data, &symbols, &HashSet::new())?;
+
+        let mut staged = data.clone();
+        staged.upsert_symbols(&symbols)?;
+        *data = staged;
+        Ok(symbols)
+    }
+
+    async fn apply_batch(
+        &self,
+        operations: Vec<SymbolOperation>,
+        atomic: bool,
+    ) -> RepositoryResult<BatchResult> {
+        if !atomic {
+            return Ok(batch::apply_each(self, operations).await);
+        }
+
+        // Staged on a copy that only replaces the data once every operation
+        // has succeeded
+        let mut data = self.store.write();
+        let mut staged = data.clone();
+        let len = operations.len();
+        let pending = batch::written_ids(&operations);
+        let mut outcomes = Vec::with_capacity(len);
+        for (index, operation) in operations.into_iter().enumerate() {
+            match self.apply_to(&mut staged, operation, &pending) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(error) => return Ok(BatchResult::rolled_back(len, index, error)),
+            }
+        }
+        *data = staged;
+
+        Ok(BatchResult::committed(outcomes))
+    }
+
+    async fn add_relation(&self, relation: SymbolRelation) -> RepositoryResult<SymbolRelation> {
+        relation.validate().map_err(RepositoryError::Validation)?;
+
+        let mut data = self.store.write();
+        self.check_targets(
+            &data,
+            &relation.source_id,
+            "relation targets",
+            std::slice::from_ref(&relation.target_id),
+        )?;
+        data.upsert_relation(relation.clone())?;
+        Ok(relation)
+    }
+
+    async fn remove_relation(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        relation_type: RelationType,
+    ) -> RepositoryResult<()> {
+        self.store
+            .write()
+            .remove_relation(source_id, target_id, relation_type)
+    }
+
+    async fn list_relations(
+        &self,
+        symbol_id: &str,
+        direction: RelationDirection,
+    ) -> RepositoryResult<Vec<SymbolRelation>> {
+        let data = self.store.read();
+        Ok(data
+            .relations
+            .iter()
+            .filter(|r| r.touches(symbol_id, direction))
+            .cloned()
+            .collect())
+    }
+
+    async fn set_embedding(&self, id: &str, embedding: &[f32]) -> RepositoryResult<()> {
+        embedding::check_dimensions(embedding)?;
+
+        let mut data = self.store.write();
+        if data.symbol(id).is_none() {
+            return Err(RepositoryError::NotFound(format!(
+                "Symbol with id {} not found",
+                id
+            )));
+        }
+        data.embeddings.insert(id.to_string(), embedding.to_vec());
+        Ok(())
+    }
+
+    async fn list_unembedded_symbols(&self) -> RepositoryResult<Vec<Symbol>> {
+        let data = self.store.read();
+        Ok(data
+            .symbols
+            .iter()
+            .filter(|s| !data.embeddings.contains_key(&s.id))
+            .cloned()
+            .collect())
+    }
+
+    async fn semantic_search_symbols(
+        &self,
+        embedding: &[f32],
+        limit: usize,
+    ) -> RepositoryResult<Vec<SearchHit>> {
+        embedding::check_dimensions(embedding)?;
+
+        let data = self.store.read();
+        let candidates = data.symbols.iter().filter_map(|s| {
+            data.embeddings
+                .get(&s.id)
+                .map(|vector| (s.clone(), vector.clone()))
+        });
+        Ok(embedding::nearest(candidates, embedding, limit))
+    }
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+
+use crate::db::memory::{ilike_contains, MemoryData, MemoryStore};
+use crate::db::repository::interfaces::{
+    Repository, RepositoryError, RepositoryResult, SymbolSetRepository,
+};
+use crate::db::repository::pagination::{ListQuery, Page, SortOrder};
+use crate::domain::SymbolSet;
+use crate::integrity::{self, IntegrityMode};
+
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySymbolSetRepository {
+    store: MemoryStore,
+    integrity: IntegrityMode,
+}
+
+impl InMemorySymbolSetRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_store(store: MemoryStore) -> Self {
+        InMemorySymbolSetRepository {
+            store,
+            integrity: IntegrityMode::default(),
+        }
+    }
+
+    pub fn with_integrity(mut self, integrity: IntegrityMode) -> Self {
+        self.integrity = integrity;
+        self
+    }
+
+    /// Applies the integrity mode to the members of `symbol_set` that name no symbol.
+    fn check_members(&self, data: &MemoryData, symbol_set: &SymbolSet) -> RepositoryResult<()> {
+        let missing =
+            integrity::missing_targets(&symbol_set.symbol_ids(), |id| data.symbol(id).is_some());
+        self.integrity
+            .check_targets(&format!("SymbolSet {}", symbol_set.id), "members", &missing)
+    }
+}
+
+impl Repository for InMemorySymbolSetRepository {}
+
+#[async_trait]
+impl SymbolSetRepository for InMemorySymbolSetRepository {
+    async fn get_symbol_set(&self, id: &str) -> RepositoryResult<SymbolSet> {
+        let data = self.store.read();
+        data.symbol_set(id)
+            .map(|set| data.resolve_set(set))
+            .ok_or_else(|| RepositoryError::NotFound(format!("SymbolSet with id {} not found", id)))
+    }
+
+    async fn list_symbol_sets(&self, query: &ListQuery) -> RepositoryResult<Page<SymbolSet>> {
+        let data = self.store.read();
+        let symbol_sets = data
+            .symbol_sets
+            .iter()
+            .filter(|s| query.includes_category(&s.category))
+            .map(|set| data.resolve_set(set))
+            .collect();
+        query.paginate(symbol_sets, SortOrder::Name, false, &[])
+    }
+
+    async fn search_symbol_sets(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SymbolSet>> {
+        // Like the SQL backends, only the set's own name and description are searched
+        let data = self.store.read();
+        let symbol_sets = data
+            .symbol_sets
+            .iter()
+            .filter(|s| query.includes_category(&s.category))
+            .filter(|s| ilike_contains(&s.name, text) || ilike_contains(&s.description, text))
+            .map(|set| data.resolve_set(set))
+            .collect();
+        query.paginate(symbol_sets, SortOrder::Name, false, &[text])
+    }
+
+    async fn create_symbol_set(&self, symbol_set: SymbolSet) -> RepositoryResult<SymbolSet> {
+        let mut data = self.store.write();
+
+        self.check_members(&data, &symbol_set)?;
+        data.insert_symbol_set(&symbol_set)?;
+        data.replace_set_members(&symbol_set.id, &symbol_set.symbol_ids());
+        Ok(symbol_set)
+    }
+
+    async fn update_symbol_set(&self, symbol_set: SymbolSet) -> RepositoryResult<SymbolSet> {
+        let mut data = self.store.write();
+
+        self.check_members(&data, &symbol_set)?;
+        let existing = data
+            .symbol_sets
+            .iter_mut()
+            .find(|s| s.id == symbol_set.id)
+            .ok_or_else(|| {
+                RepositoryError::NotFound(format!("SymbolSet with id {} not found", symbol_set.id))
+            })?;
+
+        existing.name = symbol_set.name.clone();
+        existing.category = symbol_set.category.clone();
+        existing.description = symbol_set.description.clone();
+        data.replace_set_members(&symbol_set.id, &symbol_set.symbol_ids());
+        Ok(symbol_set)
+    }
+
+    async fn delete_symbol_set(&self, id: &str) -> RepositoryResult<()> {
+        let mut data = self.store.write();
+
+        let before = data.symbol_sets.len();
+        data.symbol_sets.retain(|s| s.id != id);
+        if data.symbol_sets.len() == before {
+            return Err(RepositoryError::NotFound(format!(
+                "SymbolSet with id {} not found",
+                id
+            )));
+        }
+
+        // Same cascade as the foreign key on symbol_set_members
+        data.set_members.retain(|(set_id, _)| set_id != id);
+        Ok(())
+    }
+
+    async fn add_member(&self, set_id: &str, symbol_id: &str) -> RepositoryResult<()> {
+        self.store.write().add_set_member(set_id, symbol_id)
+    }
+
+    async fn remove_member(&self, set_id: &str, symbol_id: &str) -> RepositoryResult<()> {
+        let mut data = self.store.write();
+
+        let member = (set_id.to_string(), symbol_id.to_string());
+        let before = data.set_members.len();
+        data.set_members.retain(|m| *m != member);
+        if data.set_members.len() == before {
+            return Err(RepositoryError::NotFound(format!(
+                "Symbol {} is not a member of SymbolSet {}",
+                symbol_id, set_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn list_sets_for_symbol(&self, symbol_id: &str) -> RepositoryResult<Vec<SymbolSet>> {
+        let data = self.store.read();
+        let mut symbol_sets: Vec<SymbolSet> = data
+            .set_members
+            .iter()
+            .filter(|(_, id)| id == symbol_id)
+            .filter_map(|(set_id, _)| data.symbol_set(set_id))
+            .map(|set| data.resolve_set(set))
+            .collect();
+        symbol_sets.sort_by(|a, b| (&a.name, &a.id).cmp(&(&b.name, &b.id)));
+        Ok(symbol_sets)
+    }
+}
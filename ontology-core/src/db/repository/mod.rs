@@ -5,15 +5,48 @@
  * # Repository Implementation Module
  *
  * This module contains repository interfaces and their implementations
- * using PostgreSQL database as the backend.
+ * backed by PostgreSQL, SQLite, an in-memory store or a remote API server.
  */
 
+pub mod archetype_repository;
+pub mod batch;
+pub mod conformance;
 pub mod factory;
+pub mod http_archetype_repository;
+pub mod http_client;
+pub mod http_symbol_repository;
+pub mod http_symbol_set_repository;
 pub mod interfaces;
+pub mod memory_archetype_repository;
+pub mod memory_symbol_repository;
+pub mod memory_symbol_set_repository;
+pub mod pagination;
+pub mod sqlite_archetype_repository;
+pub mod sqlite_symbol_repository;
+pub mod sqlite_symbol_set_repository;
 pub mod symbol_repository;
 pub mod symbol_set_repository;
 
-pub use factory::{PgRepositoryFactory, RepositoryFactory};
-pub use interfaces::{Repository, RepositoryError, SymbolRepository, SymbolSetRepository};
+pub use archetype_repository::PgArchetypeRepository;
+pub use batch::{BatchResult, OperationOutcome, SymbolOperation};
+pub use factory::{
+    HttpRepositoryFactory, InMemoryRepositoryFactory, PgRepositoryFactory, RepositoryFactory,
+    SqliteRepositoryFactory,
+};
+pub use http_archetype_repository::HttpArchetypeRepository;
+pub use http_client::ApiClient;
+pub use http_symbol_repository::HttpSymbolRepository;
+pub use http_symbol_set_repository::HttpSymbolSetRepository;
+pub use interfaces::{
+    ArchetypeRepository, Repository, RepositoryError, RepositoryResult, SymbolRepository,
+    SymbolSetRepository,
+};
+pub use memory_archetype_repository::InMemoryArchetypeRepository;
+pub use memory_symbol_repository::InMemorySymbolRepository;
+pub use memory_symbol_set_repository::InMemorySymbolSetRepository;
+pub use pagination::{Cursor, ListQuery, Page, Pageable, SortKey, SortOrder};
+pub use sqlite_archetype_repository::SqliteArchetypeRepository;
+pub use sqlite_symbol_repository::SqliteSymbolRepository;
+pub use sqlite_symbol_set_repository::SqliteSymbolSetRepository;
 pub use symbol_repository::PgSymbolRepository;
 pub use symbol_set_repository::PgSymbolSetRepository;
@@ -0,0 +1,347 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::db::repository::interfaces::{RepositoryError, RepositoryResult};
+use crate::domain::{SearchHit, Symbol, SymbolSet};
+
+/// The order a listing or search returns its results in. Ties are broken by ID,
+/// so every order is total and pages never overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// By name, A to Z; the default for listings
+    Name,
+    /// By name, Z to A
+    NameDesc,
+    /// Most relevant first, then by name; the default for symbol searches
+    Relevance,
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SortOrder::Name => "name",
+            SortOrder::NameDesc => "name_desc",
+            SortOrder::Relevance => "relevance",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "name" | "name_asc" => Ok(Self::Name),
+            "name_desc" | "-name" => Ok(Self::NameDesc),
+            "relevance" | "score" => Ok(Self::Relevance),
+            _ => Err(format!("Unknown sort order: {}", s)),
+        }
+    }
+}
+
+/// Which page of a listing or search to return.
+///
+/// The default query returns everything in the method's default order. Pass
+/// the `next_cursor` of one [`Page`] back with the same query to get the next;
+/// a cursor passed with a different category or search is rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListQuery {
+    /// Only return results in this category
+    pub category: Option<String>,
+    /// Return at most this many results; `None` returns them all
+    pub limit: Option<usize>,
+    /// Opaque position to continue from, from a previous page
+    pub cursor: Option<String>,
+    /// Order of the results; `None` uses the method's default
+    pub sort: Option<SortOrder>,
+}
+
+impl ListQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn category(mut self, category: Option<impl Into<String>>) -> Self {
+        self.category = category.map(Into::into);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn cursor(mut self, cursor: Option<impl Into<String>>) -> Self {
+        self.cursor = cursor.map(Into::into);
+        self
+    }
+
+    pub fn sort(mut self, sort: Option<SortOrder>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Whether results in `category` pass the query's category filter.
+    pub fn includes_category(&self, category: &str) -> bool {
+        self.category.as_deref().is_none_or(|c| c == category)
+    }
+
+    /// Checks the query against a method whose results sort by `default`, and
+    /// decodes where to continue from. Relevance is only allowed when `ranked`.
+    ///
+    /// `filter` is whatever besides the category narrows the results, such as
+    /// the search text; a cursor handed out for other filters is rejected.
+    pub fn resolve(
+        &self,
+        default: SortOrder,
+        ranked: bool,
+        filter: &[&str],
+    ) -> RepositoryResult<(SortOrder, Option<Cursor>)> {
+        if self.limit == Some(0) {
+            return Err(RepositoryError::Validation(
+                "Limit must be at least 1".to_string(),
+            ));
+        }
+
+        let sort = self.sort.unwrap_or(default);
+        if sort == SortOrder::Relevance && !ranked {
+            return Err(RepositoryError::Validation(
+                "Only symbol searches can be sorted by relevance".to_string(),
+            ));
+        }
+
+        let cursor = match self.cursor.as_deref() {
+            Some(cursor) => {
+                let cursor = Cursor::decode(cursor, sort)?;
+                if cursor.filter != self.filter_digest(filter) {
+                    return Err(RepositoryError::Validation(
+                        "Cursor is for a different category or search".to_string(),
+                    ));
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+        Ok((sort, cursor))
+    }
+
+    /// A short digest of the category and `filter`, which ties a cursor to
+    /// the results it was handed out for.
+    fn filter_digest(&self, filter: &[&str]) -> String {
+        let mut hasher = Sha256::new();
+        for part in std::iter::once(self.category.as_deref().unwrap_or_default())
+            .chain(filter.iter().copied())
+        {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        URL_SAFE_NO_PAD.encode(&hasher.finalize()[..12])
+    }
+
+    /// Pages through results that are already in memory, for backends that
+    /// cannot page in their queries. Like the SQL backends, later pages report
+    /// the total the first page counted.
+    pub fn paginate<T: Pageable>(
+        &self,
+        mut items: Vec<T>,
+        default: SortOrder,
+        ranked: bool,
+        filter: &[&str],
+    ) -> RepositoryResult<Page<T>> {
+        let (sort, cursor) = self.resolve(default, ranked, filter)?;
+
+        items.sort_by(|a, b| compare(sort, &a.sort_key(), &b.sort_key()));
+        let mut total_count = items.len();
+        if let Some(cursor) = cursor {
+            let after = cursor.sort_key();
+            items.retain(|item| compare(sort, &item.sort_key(), &after) == Ordering::Greater);
+            total_count = cursor.total_count.unwrap_or(total_count);
+        }
+
+        Ok(Page::from_rows(items, self, filter, sort, total_count))
+    }
+}
+
+/// The filter an archetype listing adds besides the archetype itself, so a
+/// cursor from a direct listing is not reused for one with descendants.
+pub(crate) fn scope_filter(include_descendants: bool) -> &'static str {
+    if include_descendants {
+        "descendants"
+    } else {
+        "direct"
+    }
+}
+
+/// One page of results, with where to continue from if there are more.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Pass back in [`ListQuery::cursor`] to get the next page; `None` on the last
+    pub next_cursor: Option<String>,
+    /// How many results there are across all pages
+    pub total_count: usize,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            total_count: self.total_count,
+        }
+    }
+}
+
+impl<T: Pageable> Page<T> {
+    /// Builds a page of `query` from `rows` in `sort` order, fetched with one
+    /// more row than the limit so a following page can be told apart from the
+    /// end. `filter` is the one the query was resolved with.
+    pub fn from_rows(
+        mut rows: Vec<T>,
+        query: &ListQuery,
+        filter: &[&str],
+        sort: SortOrder,
+        total_count: usize,
+    ) -> Self {
+        let next_cursor = match query.limit {
+            Some(limit) if rows.len() > limit => {
+                rows.truncate(limit);
+                rows.last().map(|last| {
+                    let filter = query.filter_digest(filter);
+                    Cursor::after(sort, &last.sort_key(), filter, total_count).encode()
+                })
+            }
+            _ => None,
+        };
+
+        Page {
+            items: rows,
+            next_cursor,
+            total_count,
+        }
+    }
+}
+
+/// What results are ordered by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortKey<'a> {
+    /// Relevance to a search, or 0 outside of one
+    pub score: f32,
+    pub name: &'a str,
+    pub id: &'a str,
+}
+
+/// Something that can be listed a page at a time.
+pub trait Pageable {
+    fn sort_key(&self) -> SortKey<'_>;
+}
+
+impl Pageable for Symbol {
+    fn sort_key(&self) -> SortKey<'_> {
+        SortKey {
+            score: 0.0,
+            name: &self.name,
+            id: &self.id,
+        }
+    }
+}
+
+impl Pageable for SearchHit {
+    fn sort_key(&self) -> SortKey<'_> {
+        SortKey {
+            score: self.score,
+            ..self.symbol.sort_key()
+        }
+    }
+}
+
+impl Pageable for SymbolSet {
+    fn sort_key(&self) -> SortKey<'_> {
+        SortKey {
+            score: 0.0,
+            name: &self.name,
+            id: &self.id,
+        }
+    }
+}
+
+fn compare(sort: SortOrder, a: &SortKey<'_>, b: &SortKey<'_>) -> Ordering {
+    let by_name = (a.name, a.id).cmp(&(b.name, b.id));
+    match sort {
+        SortOrder::Name => by_name,
+        SortOrder::NameDesc => by_name.reverse(),
+        SortOrder::Relevance => b.score.total_cmp(&a.score).then(by_name),
+    }
+}
+
+/// The last result of a page, which the next page starts after.
+///
+/// Cursors are handed out base64-encoded so clients treat them as opaque.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub sort: SortOrder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+    pub name: String,
+    pub id: String,
+    /// Digest of the category and search the cursor was handed out for
+    #[serde(default)]
+    pub filter: String,
+    /// How many results the first page counted, so later pages needn't count
+    /// them again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<usize>,
+}
+
+impl Cursor {
+    fn after(sort: SortOrder, key: &SortKey<'_>, filter: String, total_count: usize) -> Self {
+        Cursor {
+            sort,
+            score: (sort == SortOrder::Relevance).then_some(key.score),
+            name: key.name.to_string(),
+            id: key.id.to_string(),
+            filter,
+            total_count: Some(total_count),
+        }
+    }
+
+    fn sort_key(&self) -> SortKey<'_> {
+        SortKey {
+            score: self.score.unwrap_or_default(),
+            name: &self.name,
+            id: &self.id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        // A cursor only holds strings and a number, so it always serializes
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// Decodes a cursor handed out for results in `sort` order.
+    pub fn decode(cursor: &str, sort: SortOrder) -> RepositoryResult<Self> {
+        let invalid = || RepositoryError::Validation(format!("Invalid cursor: {}", cursor));
+
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let cursor: Cursor = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+        if cursor.sort != sort {
+            return Err(RepositoryError::Validation(format!(
+                "Cursor is for results sorted by {}, not {}",
+                cursor.sort, sort
+            )));
+        }
+        if sort == SortOrder::Relevance && cursor.score.is_none() {
+            return Err(invalid());
+        }
+        Ok(cursor)
+    }
+}
@@ -0,0 +1,435 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+use sqlx::{SqliteConnection, SqliteExecutor, SqlitePool};
+use std::collections::HashSet;
+
+use crate::db::pool::DbError;
+use crate::db::repository::batch::{self, BatchResult, OperationOutcome, SymbolOperation};
+use crate::db::repository::interfaces::{
+    Repository, RepositoryError, RepositoryResult, SymbolRepository,
+};
+use crate::db::repository::pagination::{ListQuery, Page, SortOrder};
+use crate::db::sqlite::queries::{EmbeddingQueries, RelationQueries, SymbolQueries};
+use crate::domain::search::{self, SearchHit};
+use crate::domain::{RelationDirection, RelationType, Symbol, SymbolRelation};
+use crate::embedding;
+use crate::integrity::{self, IntegrityMode};
+
+/// Symbols in a SQLite database. Text search matches query words the way
+/// [`InMemorySymbolRepository`](super::InMemorySymbolRepository) does.
+pub struct SqliteSymbolRepository {
+    pool: SqlitePool,
+    integrity: IntegrityMode,
+}
+
+impl SqliteSymbolRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        SqliteSymbolRepository {
+            pool,
+            integrity: IntegrityMode::default(),
+        }
+    }
+
+    pub fn with_integrity(mut self, integrity: IntegrityMode) -> Self {
+        self.integrity = integrity;
+        self
+    }
+
+    /// Applies the integrity mode to the `targets` of symbol `owner_id` that
+    /// name no symbol; a symbol may name itself before it is stored.
+    async fn check_targets(
+        &self,
+        owner_id: &str,
+        field: &str,
+        targets: &[String],
+    ) -> RepositoryResult<()> {
+        let existing = SymbolQueries::existing_ids(&self.pool, targets)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+        let missing =
+            integrity::missing_targets(targets, |id| id == owner_id || existing.contains(id));
+        self.integrity
+            .check_targets(&format!("Symbol {}", owner_id), field, &missing)
+    }
+
+    /// Applies the integrity mode to the related symbols of `symbols` that
+    /// name no symbol; the symbols may name each other, or themselves, before
+    /// they are stored, and the `pending` writes of the same atomic batch.
+    async fn check_related<'e>(
+        &self,
+        executor: impl SqliteExecutor<'e>,
+        symbols: &[Symbol],
+        pending: &HashSet<String>,
+    ) -> RepositoryResult<()> {
+        let targets: Vec<String> = symbols
+            .iter()
+            .flat_map(|s| s.related_symbols.iter().cloned())
+            .collect();
+        let existing = SymbolQueries::existing_ids(executor, &targets)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+        let batch: HashSet<&str> = symbols.iter().map(|s| s.id.as_str()).collect();
+
+        for symbol in symbols {
+            let missing = integrity::missing_targets(&symbol.related_symbols, |id| {
+                batch.contains(id) || pending.contains(id) || existing.contains(id)
+            });
+            self.integrity.check_targets(
+                &format!("Symbol {}", symbol.id),
+                "related symbols",
+                &missing,
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn create_in(
+        &self,
+        conn: &mut SqliteConnection,
+        symbol: Symbol,
+        pending: &HashSet<String>,
+    ) -> RepositoryResult<Symbol> {
+        symbol.validate().map_err(RepositoryError::Validation)?;
+        self.check_related(&mut *conn, std::slice::from_ref(&symbol), pending)
+            .await?;
+
+        let db_symbol = crate::db::models::Symbol::from_domain(symbol.clone());
+
+        SymbolQueries::create(conn, &db_symbol)
+            .await
+            .map_err(|e| symbol_error(&symbol.id, e))?;
+
+        Ok(symbol)
+    }
+
+    async fn update_in(
+        &self,
+        conn: &mut SqliteConnection,
+        symbol: Symbol,
+        pending: &HashSet<String>,
+    ) -> RepositoryResult<Symbol> {
+        symbol.validate().map_err(RepositoryError::Validation)?;
+        self.check_related(&mut *conn, std::slice::from_ref(&symbol), pending)
+            .await?;
+
+        let db_symbol = crate::db::models::Symbol::from_domain(symbol.clone());
+
+        SymbolQueries::update(conn, &db_symbol)
+            .await
+            .map_err(|e| symbol_error(&symbol.id, e))?;
+
+        Ok(symbol)
+    }
+
+    /// Applies one operation of an atomic batch within its transaction.
+    async fn apply_in(
+        &self,
+        conn: &mut SqliteConnection,
+        operation: SymbolOperation,
+        pending: &HashSet<String>,
+    ) -> RepositoryResult<OperationOutcome> {
+        operation.validate()?;
+
+        match operation {
+            SymbolOperation::Create { symbol } => self
+                .create_in(conn, symbol, pending)
+                .await
+                .map(OperationOutcome::Created),
+            SymbolOperation::Update { symbol } => self
+                .update_in(conn, symbol, pending)
+                .await
+                .map(OperationOutcome::Updated),
+            SymbolOperation::Upsert { symbol } => {
+                let existing =
+                    SymbolQueries::existing_ids(&mut *conn, std::slice::from_ref(&symbol.id))
+                        .await
+                        .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+                if existing.contains(&symbol.id) {
+                    self.update_in(conn, symbol, pending)
+                        .await
+                        .map(OperationOutcome::Updated)
+                } else {
+                    self.create_in(conn, symbol, pending)
+                        .await
+                        .map(OperationOutcome::Created)
+                }
+            }
+            SymbolOperation::Delete { id } => SymbolQueries::delete(conn, &id)
+                .await
+                .map(|()| OperationOutcome::Deleted)
+                .map_err(|e| symbol_error(&id, e)),
+        }
+    }
+}
+
+impl Repository for SqliteSymbolRepository {}
+
+#[async_trait]
+impl SymbolRepository for SqliteSymbolRepository {
+    async fn get_symbol(&self, id: &str) -> RepositoryResult<Symbol> {
+        let db_symbol = match SymbolQueries::get_by_id(&self.pool, id).await {
+            Err(DbError::NotFound) => SymbolQueries::resolve(&self.pool, id).await,
+            result => result,
+        }
+        .map_err(|e| match e {
+            DbError::NotFound => {
+                RepositoryError::NotFound(format!("Symbol with id {} not found", id))
+            }
+            _ => RepositoryError::Internal(format!("Database error: {}", e)),
+        })?;
+
+        Ok(db_symbol.to_domain())
+    }
+
+    async fn get_symbol_by_id(&self, id: &str) -> RepositoryResult<Symbol> {
+        let db_symbol = SymbolQueries::get_by_id(&self.pool, id)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => {
+                    RepositoryError::NotFound(format!("Symbol with id {} not found", id))
+                }
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })?;
+
+        Ok(db_symbol.to_domain())
+    }
+
+    async fn list_symbols(&self, query: &ListQuery) -> RepositoryResult<Page<Symbol>> {
+        let (sort, after) = query.resolve(SortOrder::Name, false, &[])?;
+        let (db_symbols, total_count) = SymbolQueries::page(
+            &self.pool,
+            query.category.as_deref(),
+            sort,
+            after.as_ref(),
+            query.limit.map(|limit| limit + 1),
+        )
+        .await
+        .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        let symbols = db_symbols
+            .into_iter()
+            .map(|db_symbol| db_symbol.to_domain())
+            .collect();
+
+        Ok(Page::from_rows(symbols, query, &[], sort, total_count))
+    }
+
+    async fn list_categories(&self) -> RepositoryResult<Vec<String>> {
+        SymbolQueries::categories(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))
+    }
+
+    async fn search_symbols(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SearchHit>> {
+        let (sort, after) = query.resolve(SortOrder::Relevance, true, &[text])?;
+        let (ranked, total_count) = SymbolQueries::search(
+            &self.pool,
+            text,
+            query.category.as_deref(),
+            sort,
+            after.as_ref(),
+            query.limit.map(|limit| limit + 1),
+        )
+        .await
+        .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        let hits = ranked
+            .into_iter()
+            .map(|(db_symbol, score)| SearchHit::new(db_symbol.to_domain(), score))
+            .collect();
+
+        Ok(Page::from_rows(hits, query, &[text], sort, total_count))
+    }
+
+    async fn fuzzy_search_symbols(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> RepositoryResult<Vec<SearchHit>> {
+        // No trigram index in SQLite, so compare against every name
+        let db_symbols = SymbolQueries::list(&self.pool, None)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        let symbols = db_symbols.iter().map(|db_symbol| db_symbol.to_domain());
+        Ok(search::fuzzy_matches(symbols, query, limit))
+    }
+
+    async fn create_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
+        let mut tx = self.pool.begin().await?;
+        let created = self.create_in(&mut tx, symbol, &HashSet::new()).await?;
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    async fn update_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
+        let mut tx = self.pool.begin().await?;
+        let updated = self.update_in(&mut tx, symbol, &HashSet::new()).await?;
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    async fn delete_symbol(&self, id: &str) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+        SymbolQueries::delete(&mut tx, id)
+            .await
+            .map_err(|e| symbol_error(id, e))?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn upsert_many(&self, symbols: Vec<Symbol>) -> RepositoryResult<Vec<Symbol>> {
+        batch::validate_upserts(&symbols)?;
+
+        let mut tx = self.pool.begin().await?;
+        self.check_related(&mut *tx, &symbols, &HashSet::new())
+            .await?;
+
+        let db_symbols: Vec<_> = symbols
+            .iter()
+            .cloned()
+            .map(crate::db::models::Symbol::from_domain)
+            .collect();
+        SymbolQueries::upsert_many(&mut tx, &db_symbols)
+            .await
+            .map_err(|e| match e {
+                DbError::Conflict(msg) => RepositoryError::Conflict(msg),
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })?;
+
+        tx.commit().await?;
+        Ok(symbols)
+    }
+
+    async fn apply_batch(
+        &self,
+        operations: Vec<SymbolOperation>,
+        atomic: bool,
+    ) -> RepositoryResult<BatchResult> {
+        if !atomic {
+            return Ok(batch::apply_each(self, operations).await);
+        }
+
+        let len = operations.len();
+        let pending = batch::written_ids(&operations);
+        let mut tx = self.pool.begin().await?;
+        let mut outcomes = Vec::with_capacity(len);
+        for (index, operation) in operations.into_iter().enumerate() {
+            match self.apply_in(&mut tx, operation, &pending).await {
+                Ok(outcome) => outcomes.push(outcome),
+                // Dropping the transaction rolls back the operations before
+                Err(error) => return Ok(BatchResult::rolled_back(len, index, error)),
+            }
+        }
+        tx.commit().await?;
+
+        Ok(BatchResult::committed(outcomes))
+    }
+
+    async fn add_relation(&self, relation: SymbolRelation) -> RepositoryResult<SymbolRelation> {
+        relation.validate().map_err(RepositoryError::Validation)?;
+        self.check_targets(
+            &relation.source_id,
+            "relation targets",
+            std::slice::from_ref(&relation.target_id),
+        )
+        .await?;
+
+        let db_relation = crate::db::models::SymbolRelation::from_domain(relation.clone());
+
+        let created = RelationQueries::add(&self.pool, &db_relation)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => RepositoryError::NotFound(format!(
+                    "Symbol with id {} not found",
+                    relation.source_id
+                )),
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })?;
+
+        Ok(created.to_domain())
+    }
+
+    async fn remove_relation(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        relation_type: RelationType,
+    ) -> RepositoryResult<()> {
+        RelationQueries::remove(&self.pool, source_id, target_id, relation_type.as_str())
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => RepositoryError::NotFound(format!(
+                    "Relation {} from {} to {} not found",
+                    relation_type, source_id, target_id
+                )),
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })
+    }
+
+    async fn list_relations(
+        &self,
+        symbol_id: &str,
+        direction: RelationDirection,
+    ) -> RepositoryResult<Vec<SymbolRelation>> {
+        let db_relations = RelationQueries::list(&self.pool, symbol_id, direction)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        Ok(db_relations.iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn set_embedding(&self, id: &str, embedding: &[f32]) -> RepositoryResult<()> {
+        embedding::check_dimensions(embedding)?;
+
+        EmbeddingQueries::set(&self.pool, id, embedding)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => {
+                    RepositoryError::NotFound(format!("Symbol with id {} not found", id))
+                }
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })
+    }
+
+    async fn list_unembedded_symbols(&self) -> RepositoryResult<Vec<Symbol>> {
+        let db_symbols = EmbeddingQueries::list_unembedded(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        Ok(db_symbols.iter().map(|s| s.to_domain()).collect())
+    }
+
+    async fn semantic_search_symbols(
+        &self,
+        embedding: &[f32],
+        limit: usize,
+    ) -> RepositoryResult<Vec<SearchHit>> {
+        embedding::check_dimensions(embedding)?;
+
+        // No vector index in SQLite, so compare against every embedding
+        let embedded = EmbeddingQueries::list_embedded(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        let candidates = embedded
+            .into_iter()
+            .map(|(db_symbol, vector)| (db_symbol.to_domain(), vector));
+        Ok(embedding::nearest(candidates, embedding, limit))
+    }
+}
+
+/// Maps a failed write of symbol `id` onto the repository's errors.
+fn symbol_error(id: &str, error: DbError) -> RepositoryError {
+    match error {
+        DbError::NotFound => RepositoryError::NotFound(format!("Symbol with id {} not found", id)),
+        DbError::Conflict(msg) => RepositoryError::Conflict(msg),
+        _ => RepositoryError::Internal(format!("Database error: {}", error)),
+    }
+}
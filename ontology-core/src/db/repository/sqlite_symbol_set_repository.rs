@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+use crate::db::pool::DbError;
+use crate::db::repository::interfaces::{
+    Repository, RepositoryError, RepositoryResult, SymbolSetRepository,
+};
+use crate::db::repository::pagination::{ListQuery, Page, SortOrder};
+use crate::db::sqlite::queries::{SymbolQueries, SymbolSetQueries};
+use crate::domain::SymbolSet;
+use crate::integrity::{self, IntegrityMode};
+
+pub struct SqliteSymbolSetRepository {
+    pool: SqlitePool,
+    integrity: IntegrityMode,
+}
+
+impl SqliteSymbolSetRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        SqliteSymbolSetRepository {
+            pool,
+            integrity: IntegrityMode::default(),
+        }
+    }
+
+    pub fn with_integrity(mut self, integrity: IntegrityMode) -> Self {
+        self.integrity = integrity;
+        self
+    }
+
+    /// Applies the integrity mode to the members of `symbol_set` that name no symbol.
+    async fn check_members(&self, symbol_set: &SymbolSet) -> RepositoryResult<()> {
+        let member_ids = symbol_set.symbol_ids();
+        let existing = SymbolQueries::existing_ids(&self.pool, &member_ids)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+        let missing = integrity::missing_targets(&member_ids, |id| existing.contains(id));
+        self.integrity
+            .check_targets(&format!("SymbolSet {}", symbol_set.id), "members", &missing)
+    }
+}
+
+impl SqliteSymbolSetRepository {
+    /// Fetches the members of `db_sets` with one query and builds the domain sets.
+    async fn with_members(
+        &self,
+        db_sets: Vec<crate::db::models::SymbolSet>,
+    ) -> RepositoryResult<Vec<SymbolSet>> {
+        let set_ids: Vec<String> = db_sets.iter().map(|set| set.id.clone()).collect();
+        let mut members: HashMap<String, Vec<crate::db::models::Symbol>> = HashMap::new();
+        for (set_id, symbol) in SymbolSetQueries::members(&self.pool, &set_ids)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?
+        {
+            members.entry(set_id).or_default().push(symbol);
+        }
+
+        Ok(db_sets
+            .iter()
+            .map(|db_set| db_set.to_domain(members.get(&db_set.id).map_or(&[], Vec::as_slice)))
+            .collect())
+    }
+
+    /// Resolves the members of a page of sets fetched with one row too many.
+    async fn resolve_page(
+        &self,
+        db_sets: Vec<crate::db::models::SymbolSet>,
+        query: &ListQuery,
+        filter: &[&str],
+        sort: SortOrder,
+        total_count: usize,
+    ) -> RepositoryResult<Page<SymbolSet>> {
+        let symbol_sets = self.with_members(db_sets).await?;
+        Ok(Page::from_rows(
+            symbol_sets,
+            query,
+            filter,
+            sort,
+            total_count,
+        ))
+    }
+}
+
+impl Repository for SqliteSymbolSetRepository {}
+
+#[async_trait]
+impl SymbolSetRepository for SqliteSymbolSetRepository {
+    async fn get_symbol_set(&self, id: &str) -> RepositoryResult<SymbolSet> {
+        let db_set = SymbolSetQueries::get_by_id(&self.pool, id)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => {
+                    RepositoryError::NotFound(format!("SymbolSet with id {} not found", id))
+                }
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })?;
+
+        let mut symbol_sets = self.with_members(vec![db_set]).await?;
+        Ok(symbol_sets.remove(0))
+    }
+
+    async fn list_symbol_sets(&self, query: &ListQuery) -> RepositoryResult<Page<SymbolSet>> {
+        let (sort, after) = query.resolve(SortOrder::Name, false, &[])?;
+        let (db_sets, total_count) = SymbolSetQueries::page(
+            &self.pool,
+            query.category.as_deref(),
+            sort,
+            after.as_ref(),
+            query.limit.map(|limit| limit + 1),
+        )
+        .await
+        .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        self.resolve_page(db_sets, query, &[], sort, total_count)
+            .await
+    }
+
+    async fn search_symbol_sets(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SymbolSet>> {
+        let (sort, after) = query.resolve(SortOrder::Name, false, &[text])?;
+        let (db_sets, total_count) = SymbolSetQueries::search(
+            &self.pool,
+            text,
+            query.category.as_deref(),
+            sort,
+            after.as_ref(),
+            query.limit.map(|limit| limit + 1),
+        )
+        .await
+        .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        self.resolve_page(db_sets, query, &[text], sort, total_count)
+            .await
+    }
+
+    async fn create_symbol_set(&self, symbol_set: SymbolSet) -> RepositoryResult<SymbolSet> {
+        self.check_members(&symbol_set).await?;
+
+        let db_set = crate::db::models::SymbolSet::from_domain(symbol_set.clone());
+
+        SymbolSetQueries::create(&self.pool, &db_set)
+            .await
+            .map_err(|e| match e {
+                DbError::Conflict(msg) => RepositoryError::Conflict(msg),
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })?;
+
+        Ok(symbol_set)
+    }
+
+    async fn update_symbol_set(&self, symbol_set: SymbolSet) -> RepositoryResult<SymbolSet> {
+        self.check_members(&symbol_set).await?;
+
+        let db_set = crate::db::models::SymbolSet::from_domain(symbol_set.clone());
+
+        SymbolSetQueries::update(&self.pool, &db_set)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => RepositoryError::NotFound(format!(
+                    "SymbolSet with id {} not found",
+                    symbol_set.id
+                )),
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })?;
+
+        Ok(symbol_set)
+    }
+
+    async fn delete_symbol_set(&self, id: &str) -> RepositoryResult<()> {
+        SymbolSetQueries::delete(&self.pool, id)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => {
+                    RepositoryError::NotFound(format!("SymbolSet with id {} not found", id))
+                }
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })
+    }
+
+    async fn add_member(&self, set_id: &str, symbol_id: &str) -> RepositoryResult<()> {
+        SymbolSetQueries::add_member(&self.pool, set_id, symbol_id)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => RepositoryError::NotFound(format!(
+                    "SymbolSet {} or symbol {} not found",
+                    set_id, symbol_id
+                )),
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })
+    }
+
+    async fn remove_member(&self, set_id: &str, symbol_id: &str) -> RepositoryResult<()> {
+        SymbolSetQueries::remove_member(&self.pool, set_id, symbol_id)
+            .await
+            .map_err(|e| match e {
+                DbError::NotFound => RepositoryError::NotFound(format!(
+                    "Symbol {} is not a member of SymbolSet {}",
+                    symbol_id, set_id
+                )),
+                _ => RepositoryError::Internal(format!("Database error: {}", e)),
+            })
+    }
+
+    async fn list_sets_for_symbol(&self, symbol_id: &str) -> RepositoryResult<Vec<SymbolSet>> {
+        let db_sets = SymbolSetQueries::list_for_symbol(&self.pool, symbol_id)
+            .await
+            .map_err(|e| RepositoryError::Internal(format!("Database error: {}", e)))?;
+
+        self.with_members(db_sets).await
+    }
+}
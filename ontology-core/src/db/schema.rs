@@ -1,14 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0 OR Commercial
 // Copyright (c) 2024 Symbol Ontology Contributors
 
-use sqlx::PgPool;
-use tracing::info;
+/*!
+ * # PostgreSQL Schema
+ *
+ * Statements applied by the versioned migrations in [`crate::db::migrations`].
+ */
 
-use crate::db::pool::DbError;
-use crate::db::pool::DbResult;
-
-#[allow(dead_code)]
-const CREATE_SYMBOLS_TABLE: &str = r#"
+pub const CREATE_SYMBOLS_TABLE: &str = r#"
 CREATE TABLE IF NOT EXISTS symbols (
     id TEXT PRIMARY KEY,
     name TEXT NOT NULL,
@@ -20,8 +19,7 @@ CREATE TABLE IF NOT EXISTS symbols (
 )
 "#;
 
-#[allow(dead_code)]
-const CREATE_SYMBOL_SETS_TABLE: &str = r#"
+pub const CREATE_SYMBOL_SETS_TABLE: &str = r#"
 CREATE TABLE IF NOT EXISTS symbol_sets (
     id TEXT PRIMARY KEY,
     name TEXT NOT NULL,
@@ -31,44 +29,280 @@ CREATE TABLE IF NOT EXISTS symbol_sets (
 )
 "#;
 
-#[allow(dead_code)]
-const CREATE_CATEGORY_INDEX: &str = r#"
+pub const CREATE_SYMBOL_RELATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbol_relations (
+    source_id TEXT NOT NULL REFERENCES symbols (id) ON DELETE CASCADE,
+    target_id TEXT NOT NULL,
+    relation_type TEXT NOT NULL DEFAULT 'related_to',
+    weight DOUBLE PRECISION,
+    PRIMARY KEY (source_id, target_id, relation_type)
+)
+"#;
+
+pub const CREATE_RELATIONS_TARGET_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_relations_target ON symbol_relations (target_id)
+"#;
+
+pub const CREATE_ARCHETYPES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS archetypes (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    parent_id TEXT REFERENCES archetypes (id) ON DELETE SET NULL
+)
+"#;
+
+pub const CREATE_ARCHETYPES_PARENT_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_archetypes_parent ON archetypes (parent_id)
+"#;
+
+pub const CREATE_SYMBOL_ARCHETYPES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbol_archetypes (
+    symbol_id TEXT NOT NULL REFERENCES symbols (id) ON DELETE CASCADE,
+    archetype_id TEXT NOT NULL REFERENCES archetypes (id) ON DELETE CASCADE,
+    PRIMARY KEY (symbol_id, archetype_id)
+)
+"#;
+
+pub const CREATE_SYMBOL_ARCHETYPES_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_archetypes_archetype ON symbol_archetypes (archetype_id)
+"#;
+
+/// Converts the legacy `related_symbols` JSONB arrays into untyped `related_to` edges.
+///
+/// Pairs that already have an edge of any type are skipped, so the statement is
+/// safe to run again after importing more symbols.
+pub const MIGRATE_RELATED_SYMBOLS: &str = r#"
+INSERT INTO symbol_relations (source_id, target_id, relation_type)
+SELECT DISTINCT s.id, rel.target_id, 'related_to'
+FROM symbols s
+CROSS JOIN LATERAL jsonb_array_elements_text(
+    CASE WHEN jsonb_typeof(s.related_symbols) = 'array' THEN s.related_symbols ELSE '[]'::JSONB END
+) AS rel(target_id)
+WHERE rel.target_id <> '' AND rel.target_id <> s.id
+  AND NOT EXISTS (
+    SELECT 1 FROM symbol_relations r
+    WHERE r.source_id = s.id AND r.target_id = rel.target_id
+  )
+ON CONFLICT DO NOTHING
+"#;
+
+pub const CREATE_CATEGORY_INDEX: &str = r#"
 CREATE INDEX IF NOT EXISTS idx_symbols_category ON symbols (category)
 "#;
 
-#[allow(dead_code)]
-const CREATE_TEXT_SEARCH_INDEX: &str = r#"
-CREATE INDEX IF NOT EXISTS idx_symbols_text_search 
+pub const CREATE_TEXT_SEARCH_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbols_text_search
 ON symbols USING GIN ((to_tsvector('english', name || ' ' || description)))
 "#;
 
-#[allow(dead_code)]
-pub async fn init_schema(pool: &PgPool) -> DbResult<()> {
-    sqlx::query(CREATE_SYMBOLS_TABLE)
-        .execute(pool)
-        .await
-        .map_err(|e| DbError::Sqlx(e))?;
-
-    sqlx::query(CREATE_SYMBOL_SETS_TABLE)
-        .execute(pool)
-        .await
-        .map_err(|e| DbError::Sqlx(e))?;
-
-    sqlx::query(CREATE_CATEGORY_INDEX)
-        .execute(pool)
-        .await
-        .map_err(|e| DbError::Sqlx(e))?;
-
-    match sqlx::query(CREATE_TEXT_SEARCH_INDEX).execute(pool).await {
-        Ok(_) => info!("Created text search index"),
-        Err(e) => info!("Could not create text search index: {}", e),
-    }
-
-    info!("Database schema initialized successfully");
-    Ok(())
-}
-
-#[allow(dead_code)]
-pub async fn run_migrations(pool: &PgPool) -> DbResult<()> {
-    init_schema(pool).await
-}
+/// The weighted document searched by [`crate::db::queries::SymbolQueries::search`]:
+/// name (`A`), description (`B`), interpretation texts (`C`) and property values (`D`).
+///
+/// Interpretations stored in the older flat context -> text form are included too.
+pub const ADD_SEARCH_VECTOR_COLUMN: &str = r#"
+ALTER TABLE symbols ADD COLUMN IF NOT EXISTS search_vector TSVECTOR
+GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', coalesce(name, '')), 'A') ||
+    setweight(to_tsvector('english', coalesce(description, '')), 'B') ||
+    setweight(jsonb_to_tsvector('english', coalesce(
+        jsonb_path_query_array(interpretations, '$.*.text') ||
+        jsonb_path_query_array(interpretations, '$.* ? (@.type() == "string")'),
+        '[]'::JSONB
+    ), '["string"]'), 'C') ||
+    setweight(jsonb_to_tsvector('english', coalesce(properties, '{}'::JSONB), '["string"]'), 'D')
+) STORED
+"#;
+
+pub const CREATE_SEARCH_VECTOR_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbols_search_vector ON symbols USING GIN (search_vector)
+"#;
+
+/// Adds the pgvector `embedding` column, its cosine index and a trigger that drops
+/// an embedding when the text it was computed from changes.
+///
+/// Databases without the pgvector extension available skip all of it, leaving
+/// semantic search unsupported. Installing pgvector later takes a
+/// `migrate down --to 4` and `migrate up` to add the column.
+/// The dimension matches [`crate::embedding::EMBEDDING_DIMENSIONS`].
+pub const ADD_EMBEDDING_COLUMN: &str = r#"
+DO $migration$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_available_extensions WHERE name = 'vector') THEN
+        RAISE NOTICE 'pgvector is not installed; semantic search is unavailable';
+        RETURN;
+    END IF;
+
+    CREATE EXTENSION IF NOT EXISTS vector;
+    ALTER TABLE symbols ADD COLUMN IF NOT EXISTS embedding vector(2000);
+    CREATE INDEX IF NOT EXISTS idx_symbols_embedding
+        ON symbols USING hnsw (embedding vector_cosine_ops);
+
+    CREATE OR REPLACE FUNCTION clear_symbol_embedding() RETURNS trigger AS $trigger$
+    BEGIN
+        NEW.embedding := NULL;
+        RETURN NEW;
+    END
+    $trigger$ LANGUAGE plpgsql;
+
+    DROP TRIGGER IF EXISTS symbols_clear_embedding ON symbols;
+    CREATE TRIGGER symbols_clear_embedding
+        BEFORE UPDATE OF name, description, interpretations, properties ON symbols
+        FOR EACH ROW
+        WHEN (OLD.name IS DISTINCT FROM NEW.name
+            OR OLD.description IS DISTINCT FROM NEW.description
+            OR OLD.interpretations IS DISTINCT FROM NEW.interpretations
+            OR OLD.properties IS DISTINCT FROM NEW.properties)
+        EXECUTE FUNCTION clear_symbol_embedding();
+END
+$migration$
+"#;
+
+pub const ADD_ALIAS_COLUMNS: &str = r#"
+ALTER TABLE symbols
+    ADD COLUMN IF NOT EXISTS aliases JSONB NOT NULL DEFAULT '[]'::JSONB,
+    ADD COLUMN IF NOT EXISTS localized_names JSONB NOT NULL DEFAULT '{}'::JSONB
+"#;
+
+/// Recreates the trigger of [`ADD_EMBEDDING_COLUMN`] to also watch aliases and
+/// localized names, which are now part of the embedded text, and drops the
+/// embeddings of symbols that have either. Skipped without the embedding column.
+pub const EMBED_SYMBOL_ALIASES: &str = r#"
+DO $migration$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_attribute
+        WHERE attrelid = 'symbols'::regclass AND attname = 'embedding' AND NOT attisdropped
+    ) THEN
+        RETURN;
+    END IF;
+
+    DROP TRIGGER IF EXISTS symbols_clear_embedding ON symbols;
+    CREATE TRIGGER symbols_clear_embedding
+        BEFORE UPDATE OF name, aliases, localized_names, description, interpretations,
+            properties ON symbols
+        FOR EACH ROW
+        WHEN (OLD.name IS DISTINCT FROM NEW.name
+            OR OLD.aliases IS DISTINCT FROM NEW.aliases
+            OR OLD.localized_names IS DISTINCT FROM NEW.localized_names
+            OR OLD.description IS DISTINCT FROM NEW.description
+            OR OLD.interpretations IS DISTINCT FROM NEW.interpretations
+            OR OLD.properties IS DISTINCT FROM NEW.properties)
+        EXECUTE FUNCTION clear_symbol_embedding();
+
+    UPDATE symbols SET embedding = NULL
+    WHERE aliases <> '[]'::JSONB OR localized_names <> '{}'::JSONB;
+END
+$migration$
+"#;
+
+/// Restores the trigger of [`ADD_EMBEDDING_COLUMN`], undoing [`EMBED_SYMBOL_ALIASES`].
+pub const UNEMBED_SYMBOL_ALIASES: &str = r#"
+DO $migration$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_attribute
+        WHERE attrelid = 'symbols'::regclass AND attname = 'embedding' AND NOT attisdropped
+    ) THEN
+        RETURN;
+    END IF;
+
+    DROP TRIGGER IF EXISTS symbols_clear_embedding ON symbols;
+    CREATE TRIGGER symbols_clear_embedding
+        BEFORE UPDATE OF name, description, interpretations, properties ON symbols
+        FOR EACH ROW
+        WHEN (OLD.name IS DISTINCT FROM NEW.name
+            OR OLD.description IS DISTINCT FROM NEW.description
+            OR OLD.interpretations IS DISTINCT FROM NEW.interpretations
+            OR OLD.properties IS DISTINCT FROM NEW.properties)
+        EXECUTE FUNCTION clear_symbol_embedding();
+
+    UPDATE symbols SET embedding = NULL
+    WHERE aliases <> '[]'::JSONB OR localized_names <> '{}'::JSONB;
+END
+$migration$
+"#;
+
+/// The lowercased aliases and localized names of a symbol, as
+/// [`crate::domain::Symbol::lookup_keys`] computes them.
+pub const CREATE_LOOKUP_KEYS_FUNCTION: &str = r#"
+CREATE OR REPLACE FUNCTION symbol_lookup_keys(aliases JSONB, localized_names JSONB)
+RETURNS TEXT[] LANGUAGE SQL IMMUTABLE PARALLEL SAFE AS $$
+    SELECT coalesce(array_agg(DISTINCT lower(form)), '{}')
+    FROM (
+        SELECT jsonb_array_elements_text(coalesce(aliases, '[]'::JSONB))
+        UNION ALL
+        SELECT value FROM jsonb_each_text(coalesce(localized_names, '{}'::JSONB))
+    ) AS forms(form)
+$$
+"#;
+
+pub const CREATE_LOOKUP_KEYS_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbols_lookup_keys
+ON symbols USING GIN (symbol_lookup_keys(aliases, localized_names))
+"#;
+
+/// [`ADD_SEARCH_VECTOR_COLUMN`] with aliases and localized names weighted like
+/// the name.
+pub const ADD_ALIASED_SEARCH_VECTOR_COLUMN: &str = r#"
+ALTER TABLE symbols ADD COLUMN IF NOT EXISTS search_vector TSVECTOR
+GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', coalesce(name, '')), 'A') ||
+    setweight(jsonb_to_tsvector('english', coalesce(aliases, '[]'::JSONB), '["string"]'), 'A') ||
+    setweight(jsonb_to_tsvector('english', coalesce(localized_names, '{}'::JSONB), '["string"]'), 'A') ||
+    setweight(to_tsvector('english', coalesce(description, '')), 'B') ||
+    setweight(jsonb_to_tsvector('english', coalesce(
+        jsonb_path_query_array(interpretations, '$.*.text') ||
+        jsonb_path_query_array(interpretations, '$.* ? (@.type() == "string")'),
+        '[]'::JSONB
+    ), '["string"]'), 'C') ||
+    setweight(jsonb_to_tsvector('english', coalesce(properties, '{}'::JSONB), '["string"]'), 'D')
+) STORED
+"#;
+
+/// Which symbols belong to which sets, in the order `position` gives.
+pub const CREATE_SYMBOL_SET_MEMBERS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbol_set_members (
+    set_id TEXT NOT NULL REFERENCES symbol_sets (id) ON DELETE CASCADE,
+    symbol_id TEXT NOT NULL REFERENCES symbols (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (set_id, symbol_id)
+)
+"#;
+
+pub const CREATE_SYMBOL_SET_MEMBERS_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_set_members_symbol ON symbol_set_members (symbol_id)
+"#;
+
+/// Lets listings page by name with a keyset instead of sorting every row.
+pub const CREATE_SYMBOLS_NAME_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbols_name ON symbols (name, id)
+"#;
+
+pub const CREATE_SYMBOL_SETS_NAME_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_sets_name ON symbol_sets (name, id)
+"#;
+
+/// Moves the members out of the legacy `symbols` JSONB maps of `symbol_sets`,
+/// ordered by ID. Members that name no existing symbol are dropped, as reads
+/// already ignored them.
+pub const MIGRATE_SET_MEMBERS: &str = r#"
+INSERT INTO symbol_set_members (set_id, symbol_id, position)
+SELECT ss.id, member.symbol_id,
+       (ROW_NUMBER() OVER (PARTITION BY ss.id ORDER BY member.symbol_id))::INTEGER
+FROM symbol_sets ss
+CROSS JOIN LATERAL jsonb_object_keys(
+    CASE WHEN jsonb_typeof(ss.symbols) = 'object' THEN ss.symbols ELSE '{}'::JSONB END
+) AS member(symbol_id)
+WHERE EXISTS (SELECT 1 FROM symbols s WHERE s.id = member.symbol_id)
+ON CONFLICT DO NOTHING
+"#;
+
+/// Undoes [`MIGRATE_SET_MEMBERS`], once the `symbols` column is back.
+pub const RESTORE_SET_MEMBERS_COLUMN: &str = r#"
+UPDATE symbol_sets ss SET symbols = coalesce(
+    (SELECT jsonb_object_agg(m.symbol_id, 'null'::JSONB)
+     FROM symbol_set_members m WHERE m.set_id = ss.id),
+    '{}'::JSONB
+)
+"#;
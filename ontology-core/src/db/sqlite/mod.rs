@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # SQLite Backend
+ *
+ * Schema, row mappings and queries for running the ontology from a single
+ * SQLite file (`sqlite://symbols.db`) or an in-memory database
+ * (`sqlite::memory:`), which is handy on laptops and in CI.
+ */
+
+pub mod models;
+pub mod queries;
+pub mod schema;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub use schema::init_schema;
+
+/// Whether the URL points at an in-memory database rather than a file.
+pub fn is_in_memory(database_url: &str) -> bool {
+    database_url.contains(":memory:") || database_url.contains("mode=memory")
+}
+
+pub async fn create_pool(database_url: &str) -> Result<SqlitePool, sqlx::Error> {
+    let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+
+    if is_in_memory(database_url) {
+        // An in-memory database lives only as long as its connections, so keep
+        // exactly one open for the lifetime of the pool
+        return SqlitePoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await;
+    }
+
+    SqlitePoolOptions::new()
+        .max_connections(5)
+        .acquire_timeout(Duration::from_secs(5))
+        .connect_with(options)
+        .await
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+//! Row mappings from SQLite onto the shared database models.
+
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, Row};
+use std::collections::HashMap;
+
+use crate::db::models::{Archetype, Symbol, SymbolRelation, SymbolSet};
+use crate::domain::interpretations::interpretations_from_value;
+
+/// Reads a TEXT column holding JSON, falling back to `default` when it is
+/// missing or malformed.
+fn json_column(row: &SqliteRow, column: &str, default: serde_json::Value) -> serde_json::Value {
+    row.try_get::<String, _>(column)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or(default)
+}
+
+impl FromRow<'_, SqliteRow> for Symbol {
+    fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        let interpretations =
+            interpretations_from_value(json_column(row, "interpretations", serde_json::json!({})))
+                .unwrap_or_else(|_| HashMap::new());
+
+        let related_symbols =
+            serde_json::from_value(json_column(row, "related_symbols", serde_json::json!([])))
+                .unwrap_or_else(|_| Vec::new());
+
+        let properties =
+            serde_json::from_value(json_column(row, "properties", serde_json::json!({})))
+                .unwrap_or_else(|_| HashMap::new());
+
+        let aliases = serde_json::from_value(json_column(row, "aliases", serde_json::json!([])))
+            .unwrap_or_else(|_| Vec::new());
+
+        let localized_names =
+            serde_json::from_value(json_column(row, "localized_names", serde_json::json!({})))
+                .unwrap_or_else(|_| HashMap::new());
+
+        Ok(Symbol {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            category: row.try_get("category")?,
+            description: row.try_get("description")?,
+            interpretations,
+            related_symbols,
+            properties,
+            aliases,
+            localized_names,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for SymbolSet {
+    fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        // Members live in their own table and are fetched separately
+        Ok(SymbolSet {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            category: row.try_get("category")?,
+            description: row.try_get("description")?,
+            symbol_ids: Vec::new(),
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for SymbolRelation {
+    fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        Ok(SymbolRelation {
+            source_id: row.try_get("source_id")?,
+            target_id: row.try_get("target_id")?,
+            relation_type: row.try_get("relation_type")?,
+            weight: row.try_get("weight")?,
+        })
+    }
+}
+
+impl FromRow<'_, SqliteRow> for Archetype {
+    fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        Ok(Archetype {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            parent_id: row.try_get("parent_id")?,
+        })
+    }
+}
@@ -0,0 +1,1326 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+//! SQLite versions of the queries in [`crate::db::queries`].
+
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, Row, SqliteConnection, SqliteExecutor, SqlitePool};
+use std::collections::HashSet;
+
+use crate::db::memory::search_words;
+use crate::db::models::{Archetype, Symbol, SymbolRelation, SymbolSet};
+use crate::db::pool::{DbError, DbResult};
+use crate::db::repository::pagination::{Cursor, SortOrder};
+use crate::domain::{search, RelationDirection};
+
+fn to_json_text<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn not_found(e: sqlx::Error) -> DbError {
+    match e {
+        sqlx::Error::RowNotFound => DbError::NotFound,
+        _ => DbError::Sqlx(e),
+    }
+}
+
+/// The condition keeping the rows that come after a cursor in `sort` order, with
+/// the cursor's name, ID and score bound from parameter `?first` on, and the
+/// `ORDER BY` list for that order. Ties on name are broken by ID. Scores are
+/// compared rounded, as [`SymbolQueries::search`] computes them, since the
+/// cursor holds them as `f32`.
+fn keyset(sort: SortOrder, first: usize) -> (String, &'static str) {
+    let (name, id, score) = (first, first + 1, first + 2);
+    match sort {
+        SortOrder::Name => (
+            format!("(?{name} IS NULL OR (name, id) > (?{name}, ?{id}))"),
+            "name, id",
+        ),
+        SortOrder::NameDesc => (
+            format!("(?{name} IS NULL OR (name, id) < (?{name}, ?{id}))"),
+            "name DESC, id DESC",
+        ),
+        SortOrder::Relevance => (
+            format!(
+                "(?{score} IS NULL OR score < round(?{score}, {SCORE_DIGITS}) \
+                 OR (score = round(?{score}, {SCORE_DIGITS}) AND (name, id) > (?{name}, ?{id})))"
+            ),
+            "score DESC, name, id",
+        ),
+    }
+}
+
+/// Decimal places search scores are rounded to.
+const SCORE_DIGITS: usize = 4;
+
+/// `LIMIT` for at most `limit` rows; SQLite reads a negative limit as none.
+fn sql_limit(limit: Option<usize>) -> i64 {
+    limit.map_or(-1, |limit| limit as i64)
+}
+
+/// The total the first page counted, carried in the cursor of later pages, or
+/// else the result of `count`, which is only run on the first page.
+async fn total_count(
+    after: Option<&Cursor>,
+    count: impl std::future::Future<Output = Result<i64, sqlx::Error>>,
+) -> DbResult<usize> {
+    match after.and_then(|cursor| cursor.total_count) {
+        Some(total_count) => Ok(total_count),
+        None => Ok(count.await.map_err(DbError::Sqlx)? as usize),
+    }
+}
+
+/// Builds the condition that the word bound to `?p` is in one field of `symbols`.
+type FieldMatch = fn(usize) -> String;
+
+fn word_in_names(p: usize) -> String {
+    format!(
+        r#"(name LIKE ?{p} ESCAPE '\'
+            OR EXISTS (
+                SELECT 1 FROM json_each(symbols.aliases) AS a
+                WHERE a.value LIKE ?{p} ESCAPE '\'
+            )
+            OR EXISTS (
+                SELECT 1 FROM json_each(symbols.localized_names) AS l
+                WHERE l.value LIKE ?{p} ESCAPE '\'
+            ))"#
+    )
+}
+
+fn word_in_description(p: usize) -> String {
+    format!(r#"description LIKE ?{p} ESCAPE '\'"#)
+}
+
+fn word_in_interpretations(p: usize) -> String {
+    format!(
+        r#"EXISTS (
+            SELECT 1 FROM json_each(symbols.interpretations) AS i
+            WHERE CASE i.type WHEN 'object' THEN json_extract(i.value, '$.text') ELSE i.value END
+                LIKE ?{p} ESCAPE '\'
+        )"#
+    )
+}
+
+fn word_in_properties(p: usize) -> String {
+    format!(
+        r#"EXISTS (
+            SELECT 1 FROM json_each(symbols.properties) AS p
+            WHERE p.value LIKE ?{p} ESCAPE '\'
+        )"#
+    )
+}
+
+/// The fields a search looks in, each with the weight
+/// [`crate::domain::SearchHit::from_fields`] gives it.
+const SEARCH_FIELDS: [(FieldMatch, f32); 4] = [
+    (word_in_names, search::NAME_WEIGHT),
+    (word_in_description, search::DESCRIPTION_WEIGHT),
+    (word_in_interpretations, search::INTERPRETATION_WEIGHT),
+    (word_in_properties, search::PROPERTY_WEIGHT),
+];
+
+/// Keeps the symbols with each of `words` words, bound from `?first` on, in
+/// some field.
+fn search_matches(words: usize, first: usize) -> String {
+    (first..first + words)
+        .map(|p| {
+            let fields: Vec<String> = SEARCH_FIELDS.iter().map(|(field, _)| field(p)).collect();
+            format!("({})", fields.join(" OR "))
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+/// Sums the weights of the fields holding any of `words` words, bound from
+/// `?first` on.
+fn search_score(words: usize, first: usize) -> String {
+    SEARCH_FIELDS
+        .iter()
+        .map(|(field, weight)| {
+            let any: Vec<String> = (first..first + words).map(field).collect();
+            format!(
+                "(CASE WHEN {} THEN {} ELSE 0 END)",
+                any.join(" OR "),
+                weight
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+pub struct SymbolQueries;
+
+impl SymbolQueries {
+    pub async fn get_by_id<'e>(executor: impl SqliteExecutor<'e>, id: &str) -> DbResult<Symbol> {
+        sqlx::query_as::<_, Symbol>("SELECT * FROM symbols WHERE id = ?1")
+            .bind(id)
+            .fetch_one(executor)
+            .await
+            .map_err(not_found)
+    }
+
+    /// The symbol with `key` as an alias or localized name, or else the first
+    /// symbol by ID named `key`, ignoring case.
+    pub async fn resolve(pool: &SqlitePool, key: &str) -> DbResult<Symbol> {
+        sqlx::query_as::<_, Symbol>(
+            r#"
+            SELECT s.* FROM symbol_lookup_keys k
+            JOIN symbols s ON s.id = k.symbol_id
+            WHERE k.key = ?1 AND k.kind IN ('alias', 'name')
+            ORDER BY k.kind = 'name', s.id
+            LIMIT 1
+            "#,
+        )
+        .bind(key.to_lowercase())
+        .fetch_one(pool)
+        .await
+        .map_err(not_found)
+    }
+
+    /// Replaces the rows of `symbol_lookup_keys` for `symbols`.
+    async fn store_lookup_keys(conn: &mut SqliteConnection, symbols: &[Symbol]) -> DbResult<()> {
+        let ids: Vec<&str> = symbols.iter().map(|s| s.id.as_str()).collect();
+        sqlx::query(
+            "DELETE FROM symbol_lookup_keys WHERE symbol_id IN (SELECT value FROM json_each(?1))",
+        )
+        .bind(to_json_text(&ids))
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        let mut rows = Vec::new();
+        for symbol in symbols {
+            rows.push((&symbol.id, "id", symbol.id.to_lowercase()));
+            rows.push((&symbol.id, "name", symbol.name.to_lowercase()));
+            for key in symbol.to_domain().lookup_keys() {
+                rows.push((&symbol.id, "alias", key));
+            }
+        }
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO symbol_lookup_keys (symbol_id, kind, key)
+            SELECT json_extract(value, '$[0]'), json_extract(value, '$[1]'), json_extract(value, '$[2]')
+            FROM json_each(?1)
+            "#,
+        )
+        .bind(to_json_text(&rows))
+        .execute(conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Rebuilds `symbol_lookup_keys` for every stored symbol.
+    pub async fn index_lookup_keys(conn: &mut SqliteConnection) -> DbResult<()> {
+        let symbols = Self::list(&mut *conn, None).await?;
+        Self::store_lookup_keys(conn, &symbols).await
+    }
+
+    /// Fails if another symbol already goes by one of the aliases or localized
+    /// names of the symbols with `ids`, or by one of their IDs. Run it once
+    /// their lookup keys are stored.
+    async fn check_lookup_keys(conn: &mut SqliteConnection, ids: &[&str]) -> DbResult<()> {
+        let clash: Option<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT mine.symbol_id, theirs.symbol_id
+            FROM symbol_lookup_keys mine
+            JOIN symbol_lookup_keys theirs
+              ON theirs.key = mine.key AND theirs.symbol_id <> mine.symbol_id
+            WHERE mine.symbol_id IN (SELECT value FROM json_each(?1))
+              AND ((theirs.kind = 'alias' AND mine.kind IN ('alias', 'id'))
+                   OR (theirs.kind = 'id' AND mine.kind = 'alias'))
+            ORDER BY mine.symbol_id, theirs.symbol_id
+            LIMIT 1
+            "#,
+        )
+        .bind(to_json_text(&ids))
+        .fetch_optional(conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        match clash {
+            Some((id, other)) => Err(DbError::Conflict(format!(
+                "An alias or localized name of {} is already used by symbol {}",
+                id, other
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn list<'e>(
+        executor: impl SqliteExecutor<'e>,
+        category: Option<&str>,
+    ) -> DbResult<Vec<Symbol>> {
+        match category {
+            Some(cat) => sqlx::query_as::<_, Symbol>("SELECT * FROM symbols WHERE category = ?1")
+                .bind(cat)
+                .fetch_all(executor)
+                .await
+                .map_err(DbError::Sqlx),
+            None => sqlx::query_as::<_, Symbol>("SELECT * FROM symbols")
+                .fetch_all(executor)
+                .await
+                .map_err(DbError::Sqlx),
+        }
+    }
+
+    /// Up to `limit` symbols in `category` that come after `after` in `sort`
+    /// order, and how many symbols there are in the category in all.
+    pub async fn page(
+        pool: &SqlitePool,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<Symbol>, usize)> {
+        let (keyset, order) = keyset(sort, 3);
+        let sql = format!(
+            r#"
+            SELECT * FROM symbols
+            WHERE (?1 IS NULL OR category = ?1) AND {keyset}
+            ORDER BY {order}
+            LIMIT ?2
+            "#
+        );
+
+        let rows = sqlx::query_as::<_, Symbol>(&sql)
+            .bind(category)
+            .bind(sql_limit(limit))
+            .bind(after.map(|cursor| cursor.name.as_str()))
+            .bind(after.map(|cursor| cursor.id.as_str()))
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count =
+            sqlx::query_scalar("SELECT COUNT(*) FROM symbols WHERE ?1 IS NULL OR category = ?1")
+                .bind(category)
+                .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
+    }
+
+    /// The distinct categories of all symbols, in byte order.
+    pub async fn categories(pool: &SqlitePool) -> DbResult<Vec<String>> {
+        sqlx::query_scalar("SELECT DISTINCT category FROM symbols ORDER BY category")
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)
+    }
+
+    /// Up to `limit` symbols in `category` with every word of `query` in some
+    /// field that come after `after` in `sort` order, each scored as
+    /// [`crate::domain::SearchHit::from_words`] does, and how many matches
+    /// there are in all.
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<(Symbol, f32)>, usize)> {
+        // LIKE is case-insensitive for ASCII in SQLite, matching ILIKE closely enough.
+        // ILIKE escapes with a backslash by default; SQLite needs to be told.
+        // Interpretations may still be stored as plain strings rather than layers.
+        // Every word of the query must match some field, as in a full-text search.
+        let words = search_words(query);
+        let (keyset, order) = keyset(sort, 3);
+        let first_word = 6;
+        let sql = format!(
+            r#"
+            SELECT * FROM (
+                SELECT *, round({score}, {SCORE_DIGITS}) AS score FROM symbols
+                WHERE (?1 IS NULL OR category = ?1) AND {matches}
+            ) AS hits
+            WHERE {keyset}
+            ORDER BY {order}
+            LIMIT ?2
+            "#,
+            score = search_score(words.len(), first_word),
+            matches = search_matches(words.len(), first_word),
+        );
+
+        let mut rows = sqlx::query(&sql)
+            .bind(category)
+            .bind(sql_limit(limit))
+            .bind(after.map(|cursor| cursor.name.as_str()))
+            .bind(after.map(|cursor| cursor.id.as_str()))
+            .bind(after.and_then(|cursor| cursor.score));
+        for word in &words {
+            rows = rows.bind(format!("%{}%", word));
+        }
+        let rows = rows
+            .try_map(|row: SqliteRow| {
+                let score: f64 = row.try_get("score")?;
+                Ok((Symbol::from_row(&row)?, score as f32))
+            })
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count_sql = format!(
+            "SELECT COUNT(*) FROM symbols WHERE (?1 IS NULL OR category = ?1) AND {}",
+            search_matches(words.len(), 2)
+        );
+        let mut count = sqlx::query_scalar(&count_sql).bind(category);
+        for word in &words {
+            count = count.bind(format!("%{}%", word));
+        }
+        Ok((rows, total_count(after, count.fetch_one(pool)).await?))
+    }
+
+    /// Inserts a symbol and its `related_to` edges. Run it in a transaction so
+    /// a failure leaves neither behind.
+    pub async fn create(conn: &mut SqliteConnection, symbol: &Symbol) -> DbResult<Symbol> {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO symbols (id, name, category, description, interpretations, related_symbols, properties,
+                                 aliases, localized_names)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(&symbol.id)
+        .bind(&symbol.name)
+        .bind(&symbol.category)
+        .bind(&symbol.description)
+        .bind(to_json_text(&symbol.interpretations))
+        .bind(to_json_text(&symbol.related_symbols))
+        .bind(to_json_text(&symbol.properties))
+        .bind(to_json_text(&symbol.aliases))
+        .bind(to_json_text(&symbol.localized_names))
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if inserted.rows_affected() == 0 {
+            return Err(DbError::Conflict(format!(
+                "Symbol with ID {} already exists",
+                symbol.id
+            )));
+        }
+
+        Self::store_lookup_keys(&mut *conn, std::slice::from_ref(symbol)).await?;
+        Self::check_lookup_keys(&mut *conn, &[symbol.id.as_str()]).await?;
+
+        RelationQueries::sync_related(&mut *conn, &symbol.id, &symbol.related_symbols).await?;
+
+        Self::get_by_id(conn, &symbol.id).await
+    }
+
+    /// Replaces a symbol and adds its new `related_to` edges. Run it in a
+    /// transaction so a failure leaves neither behind.
+    pub async fn update(conn: &mut SqliteConnection, symbol: &Symbol) -> DbResult<Symbol> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE symbols
+            SET name = ?2, category = ?3, description = ?4,
+                interpretations = ?5, related_symbols = ?6, properties = ?7,
+                aliases = ?8, localized_names = ?9
+            WHERE id = ?1
+            "#,
+        )
+        .bind(&symbol.id)
+        .bind(&symbol.name)
+        .bind(&symbol.category)
+        .bind(&symbol.description)
+        .bind(to_json_text(&symbol.interpretations))
+        .bind(to_json_text(&symbol.related_symbols))
+        .bind(to_json_text(&symbol.properties))
+        .bind(to_json_text(&symbol.aliases))
+        .bind(to_json_text(&symbol.localized_names))
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Self::store_lookup_keys(&mut *conn, std::slice::from_ref(symbol)).await?;
+        Self::check_lookup_keys(&mut *conn, &[symbol.id.as_str()]).await?;
+
+        RelationQueries::sync_related(&mut *conn, &symbol.id, &symbol.related_symbols).await?;
+
+        Self::get_by_id(conn, &symbol.id).await
+    }
+
+    /// Inserts `symbols`, replacing those that already exist, with one
+    /// statement for the rows and one for their `related_to` edges. Aliases
+    /// and localized names are checked once every row is written. Run it in a
+    /// transaction so a failure leaves nothing behind. The IDs must be distinct.
+    pub async fn upsert_many(conn: &mut SqliteConnection, symbols: &[Symbol]) -> DbResult<()> {
+        if symbols.is_empty() {
+            return Ok(());
+        }
+        let ids: Vec<&str> = symbols.iter().map(|s| s.id.as_str()).collect();
+
+        // The WHERE keeps SQLite from reading ON CONFLICT as a join constraint
+        sqlx::query(
+            r#"
+            INSERT INTO symbols (id, name, category, description, interpretations, related_symbols, properties,
+                                 aliases, localized_names)
+            SELECT json_extract(value, '$.id'), json_extract(value, '$.name'),
+                   json_extract(value, '$.category'), json_extract(value, '$.description'),
+                   json_extract(value, '$.interpretations'), json_extract(value, '$.related_symbols'),
+                   json_extract(value, '$.properties'), json_extract(value, '$.aliases'),
+                   json_extract(value, '$.localized_names')
+            FROM json_each(?1)
+            WHERE true
+            ON CONFLICT (id) DO UPDATE
+            SET name = excluded.name, category = excluded.category,
+                description = excluded.description, interpretations = excluded.interpretations,
+                related_symbols = excluded.related_symbols, properties = excluded.properties,
+                aliases = excluded.aliases, localized_names = excluded.localized_names
+            "#,
+        )
+        .bind(to_json_text(&symbols))
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        // Checked once all rows are written, so a batch may move a name from
+        // one of its symbols to another
+        Self::store_lookup_keys(&mut *conn, symbols).await?;
+        Self::check_lookup_keys(&mut *conn, &ids).await?;
+
+        RelationQueries::sync_related_many(conn, &ids).await
+    }
+
+    /// The IDs among `ids` that name a symbol.
+    pub async fn existing_ids<'e>(
+        executor: impl SqliteExecutor<'e>,
+        ids: &[String],
+    ) -> DbResult<HashSet<String>> {
+        if ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let found: Vec<String> = sqlx::query_scalar(
+            "SELECT id FROM symbols WHERE id IN (SELECT value FROM json_each(?1))",
+        )
+        .bind(to_json_text(&ids))
+        .fetch_all(executor)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(found.into_iter().collect())
+    }
+
+    /// Deletes a symbol and every reference to it. Run it in a transaction so a
+    /// failure leaves no reference half cleared.
+    pub async fn delete(conn: &mut SqliteConnection, id: &str) -> DbResult<()> {
+        let deleted = sqlx::query("DELETE FROM symbols WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        // The foreign keys take the symbol's own relations, set memberships and
+        // archetype links; references to it from other symbols go here
+        sqlx::query("DELETE FROM symbol_relations WHERE target_id = ?1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            UPDATE symbols
+            SET related_symbols = (
+                SELECT json_group_array(value) FROM (
+                    SELECT value FROM json_each(symbols.related_symbols)
+                    WHERE value <> ?1
+                    ORDER BY key
+                )
+            )
+            WHERE json_type(related_symbols) = 'array'
+              AND EXISTS (SELECT 1 FROM json_each(symbols.related_symbols) WHERE value = ?1)
+            "#,
+        )
+        .bind(id)
+        .execute(conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+}
+
+pub struct EmbeddingQueries;
+
+impl EmbeddingQueries {
+    pub async fn set(pool: &SqlitePool, id: &str, embedding: &[f32]) -> DbResult<()> {
+        let updated = sqlx::query("UPDATE symbols SET embedding = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(to_json_text(&embedding))
+            .execute(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn list_unembedded(pool: &SqlitePool) -> DbResult<Vec<Symbol>> {
+        sqlx::query_as::<_, Symbol>("SELECT * FROM symbols WHERE embedding IS NULL")
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)
+    }
+
+    /// Every embedded symbol with its embedding; malformed embeddings are skipped.
+    pub async fn list_embedded(pool: &SqlitePool) -> DbResult<Vec<(Symbol, Vec<f32>)>> {
+        let rows = sqlx::query("SELECT * FROM symbols WHERE embedding IS NOT NULL")
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let mut embedded = Vec::with_capacity(rows.len());
+        for row in rows {
+            let embedding: String = row.try_get("embedding").map_err(DbError::Sqlx)?;
+            if let Ok(embedding) = serde_json::from_str(&embedding) {
+                embedded.push((Symbol::from_row(&row).map_err(DbError::Sqlx)?, embedding));
+            }
+        }
+        Ok(embedded)
+    }
+}
+
+pub struct SymbolSetQueries;
+
+impl SymbolSetQueries {
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> DbResult<SymbolSet> {
+        sqlx::query_as::<_, SymbolSet>("SELECT * FROM symbol_sets WHERE id = ?1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .map_err(not_found)
+    }
+
+    /// Up to `limit` sets in `category` that come after `after` in `sort`
+    /// order, and how many sets there are in the category in all.
+    pub async fn page(
+        pool: &SqlitePool,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<SymbolSet>, usize)> {
+        let (keyset, order) = keyset(sort, 3);
+        let sql = format!(
+            r#"
+            SELECT * FROM symbol_sets
+            WHERE (?1 IS NULL OR category = ?1) AND {keyset}
+            ORDER BY {order}
+            LIMIT ?2
+            "#
+        );
+
+        let rows = sqlx::query_as::<_, SymbolSet>(&sql)
+            .bind(category)
+            .bind(sql_limit(limit))
+            .bind(after.map(|cursor| cursor.name.as_str()))
+            .bind(after.map(|cursor| cursor.id.as_str()))
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM symbol_sets WHERE ?1 IS NULL OR category = ?1",
+        )
+        .bind(category)
+        .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
+    }
+
+    /// Like [`Self::page`], but only the sets whose name or description
+    /// contain `query`.
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<SymbolSet>, usize)> {
+        let search_pattern = format!("%{}%", query);
+        const MATCHES: &str = r#"
+            (name LIKE ?1 ESCAPE '\' OR description LIKE ?1 ESCAPE '\')
+            AND (?2 IS NULL OR category = ?2)
+        "#;
+        let (keyset, order) = keyset(sort, 4);
+        let sql = format!(
+            r#"
+            SELECT * FROM symbol_sets
+            WHERE {MATCHES} AND {keyset}
+            ORDER BY {order}
+            LIMIT ?3
+            "#
+        );
+
+        let rows = sqlx::query_as::<_, SymbolSet>(&sql)
+            .bind(&search_pattern)
+            .bind(category)
+            .bind(sql_limit(limit))
+            .bind(after.map(|cursor| cursor.name.as_str()))
+            .bind(after.map(|cursor| cursor.id.as_str()))
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count_sql = format!("SELECT COUNT(*) FROM symbol_sets WHERE {MATCHES}");
+        let count = sqlx::query_scalar(&count_sql)
+            .bind(&search_pattern)
+            .bind(category)
+            .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
+    }
+
+    /// The members of the sets with `set_ids`, as (set ID, symbol) pairs in set order.
+    pub async fn members(pool: &SqlitePool, set_ids: &[String]) -> DbResult<Vec<(String, Symbol)>> {
+        sqlx::query(
+            r#"
+            SELECT m.set_id, s.* FROM symbol_set_members m
+            JOIN symbols s ON s.id = m.symbol_id
+            WHERE m.set_id IN (SELECT value FROM json_each(?1))
+            ORDER BY m.set_id, m.position, m.symbol_id
+            "#,
+        )
+        .bind(to_json_text(&set_ids))
+        .try_map(|row: SqliteRow| Ok((row.try_get("set_id")?, Symbol::from_row(&row)?)))
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
+
+    /// Makes `symbol_ids` the members of a set, in that order. IDs that name no
+    /// symbol are skipped.
+    async fn replace_members(
+        conn: &mut sqlx::SqliteConnection,
+        set_id: &str,
+        symbol_ids: &[String],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM symbol_set_members WHERE set_id = ?1")
+            .bind(set_id)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO symbol_set_members (set_id, symbol_id, position)
+            SELECT ?1, member.value, member.key + 1
+            FROM json_each(?2) AS member
+            WHERE EXISTS (SELECT 1 FROM symbols s WHERE s.id = member.value)
+            "#,
+        )
+        .bind(set_id)
+        .bind(to_json_text(&symbol_ids))
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn create(pool: &SqlitePool, set: &SymbolSet) -> DbResult<SymbolSet> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO symbol_sets (id, name, category, description)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(&set.id)
+        .bind(&set.name)
+        .bind(&set.category)
+        .bind(&set.description)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if inserted.rows_affected() == 0 {
+            return Err(DbError::Conflict(format!(
+                "SymbolSet with ID {} already exists",
+                set.id
+            )));
+        }
+
+        Self::replace_members(&mut tx, &set.id, &set.symbol_ids)
+            .await
+            .map_err(DbError::Sqlx)?;
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        Self::get_by_id(pool, &set.id).await
+    }
+
+    pub async fn update(pool: &SqlitePool, set: &SymbolSet) -> DbResult<SymbolSet> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE symbol_sets
+            SET name = ?2, category = ?3, description = ?4
+            WHERE id = ?1
+            "#,
+        )
+        .bind(&set.id)
+        .bind(&set.name)
+        .bind(&set.category)
+        .bind(&set.description)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Self::replace_members(&mut tx, &set.id, &set.symbol_ids)
+            .await
+            .map_err(DbError::Sqlx)?;
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        Self::get_by_id(pool, &set.id).await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> DbResult<()> {
+        // Members go with the set through the foreign key
+        let deleted = sqlx::query("DELETE FROM symbol_sets WHERE id = ?1")
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Adds a symbol to the end of a set; adding a member again changes nothing.
+    pub async fn add_member(pool: &SqlitePool, set_id: &str, symbol_id: &str) -> DbResult<()> {
+        let both_exist = sqlx::query(
+            r#"
+            SELECT 1
+            WHERE EXISTS (SELECT 1 FROM symbol_sets WHERE id = ?1)
+              AND EXISTS (SELECT 1 FROM symbols WHERE id = ?2)
+            "#,
+        )
+        .bind(set_id)
+        .bind(symbol_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(DbError::Sqlx)?
+        .is_some();
+
+        if !both_exist {
+            return Err(DbError::NotFound);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO symbol_set_members (set_id, symbol_id, position)
+            SELECT ?1, ?2, coalesce(max(position), 0) + 1
+            FROM symbol_set_members WHERE set_id = ?1
+            "#,
+        )
+        .bind(set_id)
+        .bind(symbol_id)
+        .execute(pool)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    pub async fn remove_member(pool: &SqlitePool, set_id: &str, symbol_id: &str) -> DbResult<()> {
+        let deleted =
+            sqlx::query("DELETE FROM symbol_set_members WHERE set_id = ?1 AND symbol_id = ?2")
+                .bind(set_id)
+                .bind(symbol_id)
+                .execute(pool)
+                .await
+                .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    /// The sets a symbol belongs to, by name.
+    pub async fn list_for_symbol(pool: &SqlitePool, symbol_id: &str) -> DbResult<Vec<SymbolSet>> {
+        sqlx::query_as::<_, SymbolSet>(
+            r#"
+            SELECT ss.* FROM symbol_sets ss
+            JOIN symbol_set_members m ON m.set_id = ss.id
+            WHERE m.symbol_id = ?1
+            ORDER BY ss.name, ss.id
+            "#,
+        )
+        .bind(symbol_id)
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
+}
+
+pub struct RelationQueries;
+
+impl RelationQueries {
+    pub async fn list(
+        pool: &SqlitePool,
+        symbol_id: &str,
+        direction: RelationDirection,
+    ) -> DbResult<Vec<SymbolRelation>> {
+        let sql = match direction {
+            RelationDirection::Outgoing => "SELECT * FROM symbol_relations WHERE source_id = ?1",
+            RelationDirection::Incoming => "SELECT * FROM symbol_relations WHERE target_id = ?1",
+            RelationDirection::Both => {
+                "SELECT * FROM symbol_relations WHERE source_id = ?1 OR target_id = ?1"
+            }
+        };
+
+        sqlx::query_as::<_, SymbolRelation>(sql)
+            .bind(symbol_id)
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)
+    }
+
+    pub async fn add(pool: &SqlitePool, relation: &SymbolRelation) -> DbResult<SymbolRelation> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        let exists = sqlx::query("SELECT 1 FROM symbols WHERE id = ?1")
+            .bind(&relation.source_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(DbError::Sqlx)?
+            .is_some();
+
+        if !exists {
+            return Err(DbError::NotFound);
+        }
+
+        let created = sqlx::query_as::<_, SymbolRelation>(
+            r#"
+            INSERT INTO symbol_relations (source_id, target_id, relation_type, weight)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (source_id, target_id, relation_type)
+            DO UPDATE SET weight = excluded.weight
+            RETURNING *
+            "#,
+        )
+        .bind(&relation.source_id)
+        .bind(&relation.target_id)
+        .bind(&relation.relation_type)
+        .bind(relation.weight)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        // Keep the legacy related_symbols list in step with outgoing edges
+        sqlx::query(
+            r#"
+            UPDATE symbols
+            SET related_symbols = json_insert(related_symbols, '$[#]', ?2)
+            WHERE id = ?1
+              AND json_type(related_symbols) = 'array'
+              AND NOT EXISTS (SELECT 1 FROM json_each(symbols.related_symbols) WHERE value = ?2)
+            "#,
+        )
+        .bind(&relation.source_id)
+        .bind(&relation.target_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        Ok(created)
+    }
+
+    pub async fn remove(
+        pool: &SqlitePool,
+        source_id: &str,
+        target_id: &str,
+        relation_type: &str,
+    ) -> DbResult<()> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        let deleted = sqlx::query(
+            "DELETE FROM symbol_relations WHERE source_id = ?1 AND target_id = ?2 AND relation_type = ?3",
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .bind(relation_type)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        // Drop the target from related_symbols once no edge of any type remains
+        sqlx::query(
+            r#"
+            UPDATE symbols
+            SET related_symbols = (
+                SELECT json_group_array(value) FROM (
+                    SELECT value FROM json_each(symbols.related_symbols)
+                    WHERE value <> ?2
+                    ORDER BY key
+                )
+            )
+            WHERE id = ?1
+              AND json_type(related_symbols) = 'array'
+              AND NOT EXISTS (
+                SELECT 1 FROM symbol_relations r
+                WHERE r.source_id = ?1 AND r.target_id = ?2
+              )
+            "#,
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// Adds `related_to` edges for entries of `related_symbols` that have no edge yet,
+    /// and removes those whose target is no longer listed.
+    pub async fn sync_related(
+        conn: &mut SqliteConnection,
+        symbol_id: &str,
+        related: &[String],
+    ) -> DbResult<()> {
+        let related = to_json_text(&related);
+
+        sqlx::query(
+            r#"
+            DELETE FROM symbol_relations
+            WHERE source_id = ?1 AND relation_type = 'related_to'
+              AND target_id NOT IN (SELECT value FROM json_each(?2))
+            "#,
+        )
+        .bind(symbol_id)
+        .bind(&related)
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO symbol_relations (source_id, target_id, relation_type)
+            SELECT DISTINCT ?1, t.value, 'related_to'
+            FROM json_each(?2) AS t
+            WHERE t.value <> '' AND t.value <> ?1
+              AND NOT EXISTS (
+                SELECT 1 FROM symbol_relations r
+                WHERE r.source_id = ?1 AND r.target_id = t.value
+              )
+            "#,
+        )
+        .bind(symbol_id)
+        .bind(&related)
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    /// [`Self::sync_related`] for each of the stored symbols `symbol_ids`, in
+    /// two statements.
+    pub async fn sync_related_many(
+        conn: &mut SqliteConnection,
+        symbol_ids: &[&str],
+    ) -> DbResult<()> {
+        let symbol_ids = to_json_text(&symbol_ids);
+
+        sqlx::query(
+            r#"
+            DELETE FROM symbol_relations
+            WHERE relation_type = 'related_to'
+              AND source_id IN (SELECT value FROM json_each(?1))
+              AND target_id NOT IN (
+                SELECT rel.value
+                FROM symbols s,
+                     json_each(CASE WHEN json_type(s.related_symbols) = 'array'
+                                    THEN s.related_symbols ELSE '[]' END) AS rel
+                WHERE s.id = symbol_relations.source_id
+              )
+            "#,
+        )
+        .bind(&symbol_ids)
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO symbol_relations (source_id, target_id, relation_type)
+            SELECT DISTINCT s.id, rel.value, 'related_to'
+            FROM symbols s,
+                 json_each(CASE WHEN json_type(s.related_symbols) = 'array'
+                                THEN s.related_symbols ELSE '[]' END) AS rel
+            WHERE s.id IN (SELECT value FROM json_each(?1))
+              AND rel.value <> '' AND rel.value <> s.id
+              AND NOT EXISTS (
+                SELECT 1 FROM symbol_relations r
+                WHERE r.source_id = s.id AND r.target_id = rel.value
+              )
+            "#,
+        )
+        .bind(&symbol_ids)
+        .execute(&mut *conn)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+}
+
+pub struct ArchetypeQueries;
+
+impl ArchetypeQueries {
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> DbResult<Archetype> {
+        sqlx::query_as::<_, Archetype>("SELECT * FROM archetypes WHERE id = ?1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .map_err(not_found)
+    }
+
+    pub async fn list(pool: &SqlitePool) -> DbResult<Vec<Archetype>> {
+        sqlx::query_as::<_, Archetype>("SELECT * FROM archetypes ORDER BY name")
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)
+    }
+
+    pub async fn create(pool: &SqlitePool, archetype: &Archetype) -> DbResult<Archetype> {
+        let exists = sqlx::query("SELECT 1 FROM archetypes WHERE id = ?1")
+            .bind(&archetype.id)
+            .fetch_optional(pool)
+            .await
+            .map_err(DbError::Sqlx)?
+            .is_some();
+
+        if exists {
+            return Err(DbError::Conflict(format!(
+                "Archetype with ID {} already exists",
+                archetype.id
+            )));
+        }
+
+        sqlx::query_as::<_, Archetype>(
+            r#"
+            INSERT INTO archetypes (id, name, description, parent_id)
+            VALUES (?1, ?2, ?3, ?4)
+            RETURNING *
+            "#,
+        )
+        .bind(&archetype.id)
+        .bind(&archetype.name)
+        .bind(&archetype.description)
+        .bind(&archetype.parent_id)
+        .fetch_one(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
+
+    pub async fn update(pool: &SqlitePool, archetype: &Archetype) -> DbResult<Archetype> {
+        sqlx::query_as::<_, Archetype>(
+            r#"
+            UPDATE archetypes
+            SET name = ?2, description = ?3, parent_id = ?4
+            WHERE id = ?1
+            RETURNING *
+            "#,
+        )
+        .bind(&archetype.id)
+        .bind(&archetype.name)
+        .bind(&archetype.description)
+        .bind(&archetype.parent_id)
+        .fetch_one(pool)
+        .await
+        .map_err(not_found)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> DbResult<()> {
+        let mut tx = pool.begin().await.map_err(DbError::Sqlx)?;
+
+        // Move children up to the deleted archetype's parent
+        sqlx::query(
+            r#"
+            UPDATE archetypes
+            SET parent_id = (SELECT parent_id FROM archetypes WHERE id = ?1)
+            WHERE parent_id = ?1
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        let deleted = sqlx::query("DELETE FROM archetypes WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        tx.commit().await.map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    pub async fn link_symbol(
+        pool: &SqlitePool,
+        symbol_id: &str,
+        archetype_id: &str,
+    ) -> DbResult<()> {
+        let both_exist = sqlx::query(
+            r#"
+            SELECT 1
+            WHERE EXISTS (SELECT 1 FROM symbols WHERE id = ?1)
+              AND EXISTS (SELECT 1 FROM archetypes WHERE id = ?2)
+            "#,
+        )
+        .bind(symbol_id)
+        .bind(archetype_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(DbError::Sqlx)?
+        .is_some();
+
+        if !both_exist {
+            return Err(DbError::NotFound);
+        }
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO symbol_archetypes (symbol_id, archetype_id) VALUES (?1, ?2)",
+        )
+        .bind(symbol_id)
+        .bind(archetype_id)
+        .execute(pool)
+        .await
+        .map_err(DbError::Sqlx)?;
+
+        Ok(())
+    }
+
+    pub async fn unlink_symbol(
+        pool: &SqlitePool,
+        symbol_id: &str,
+        archetype_id: &str,
+    ) -> DbResult<()> {
+        let deleted =
+            sqlx::query("DELETE FROM symbol_archetypes WHERE symbol_id = ?1 AND archetype_id = ?2")
+                .bind(symbol_id)
+                .bind(archetype_id)
+                .execute(pool)
+                .await
+                .map_err(DbError::Sqlx)?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DbError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Up to `limit` symbols in `category` linked to `archetype_id`, or with
+    /// `include_descendants` to any archetype below it, that come after `after`
+    /// in `sort` order, and how many such symbols there are in all.
+    pub async fn list_symbols(
+        pool: &SqlitePool,
+        archetype_id: &str,
+        include_descendants: bool,
+        category: Option<&str>,
+        sort: SortOrder,
+        after: Option<&Cursor>,
+        limit: Option<usize>,
+    ) -> DbResult<(Vec<Symbol>, usize)> {
+        // The symbols linked to archetype ?1, or with ?2 to any below it, in category ?3
+        const LINKED: &str = r#"
+            WITH RECURSIVE tree AS (
+                SELECT id FROM archetypes WHERE id = ?1
+                UNION
+                SELECT a.id FROM archetypes a JOIN tree t ON a.parent_id = t.id WHERE ?2
+            ),
+            linked AS (
+                SELECT * FROM symbols
+                WHERE id IN (
+                    SELECT symbol_id FROM symbol_archetypes
+                    WHERE archetype_id IN (SELECT id FROM tree)
+                )
+                  AND (?3 IS NULL OR category = ?3)
+            )
+        "#;
+        let (keyset, order) = keyset(sort, 5);
+        let sql = format!(
+            r#"
+            {LINKED}
+            SELECT * FROM linked
+            WHERE {keyset}
+            ORDER BY {order}
+            LIMIT ?4
+            "#
+        );
+
+        let rows = sqlx::query_as::<_, Symbol>(&sql)
+            .bind(archetype_id)
+            .bind(include_descendants)
+            .bind(category)
+            .bind(sql_limit(limit))
+            .bind(after.map(|cursor| cursor.name.as_str()))
+            .bind(after.map(|cursor| cursor.id.as_str()))
+            .fetch_all(pool)
+            .await
+            .map_err(DbError::Sqlx)?;
+
+        let count_sql = format!("{LINKED} SELECT COUNT(*) FROM linked");
+        let count = sqlx::query_scalar(&count_sql)
+            .bind(archetype_id)
+            .bind(include_descendants)
+            .bind(category)
+            .fetch_one(pool);
+        Ok((rows, total_count(after, count).await?))
+    }
+
+    pub async fn list_for_symbol(pool: &SqlitePool, symbol_id: &str) -> DbResult<Vec<Archetype>> {
+        sqlx::query_as::<_, Archetype>(
+            r#"
+            SELECT a.* FROM archetypes a
+            JOIN symbol_archetypes sa ON sa.archetype_id = a.id
+            WHERE sa.symbol_id = ?1
+            ORDER BY a.name
+            "#,
+        )
+        .bind(symbol_id)
+        .fetch_all(pool)
+        .await
+        .map_err(DbError::Sqlx)
+    }
+}
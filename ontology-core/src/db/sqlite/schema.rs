@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use sqlx::SqlitePool;
+
+use crate::db::backend::DatabasePool;
+use crate::db::pool::DbResult;
+
+// JSON columns are stored as TEXT and read back through SQLite's json1 functions
+
+pub const CREATE_SYMBOLS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbols (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    description TEXT NOT NULL,
+    interpretations TEXT NOT NULL DEFAULT '{}',
+    related_symbols TEXT NOT NULL DEFAULT '[]',
+    properties TEXT NOT NULL DEFAULT '{}'
+)
+"#;
+
+pub const CREATE_SYMBOL_SETS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbol_sets (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    description TEXT NOT NULL,
+    symbols TEXT NOT NULL DEFAULT '{}'
+)
+"#;
+
+pub const CREATE_SYMBOL_RELATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbol_relations (
+    source_id TEXT NOT NULL REFERENCES symbols (id) ON DELETE CASCADE,
+    target_id TEXT NOT NULL,
+    relation_type TEXT NOT NULL DEFAULT 'related_to',
+    weight REAL,
+    PRIMARY KEY (source_id, target_id, relation_type)
+)
+"#;
+
+pub const CREATE_RELATIONS_TARGET_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_relations_target ON symbol_relations (target_id)
+"#;
+
+pub const CREATE_ARCHETYPES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS archetypes (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    parent_id TEXT REFERENCES archetypes (id) ON DELETE SET NULL
+)
+"#;
+
+pub const CREATE_ARCHETYPES_PARENT_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_archetypes_parent ON archetypes (parent_id)
+"#;
+
+pub const CREATE_SYMBOL_ARCHETYPES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbol_archetypes (
+    symbol_id TEXT NOT NULL REFERENCES symbols (id) ON DELETE CASCADE,
+    archetype_id TEXT NOT NULL REFERENCES archetypes (id) ON DELETE CASCADE,
+    PRIMARY KEY (symbol_id, archetype_id)
+)
+"#;
+
+pub const CREATE_SYMBOL_ARCHETYPES_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_archetypes_archetype ON symbol_archetypes (archetype_id)
+"#;
+
+pub const CREATE_CATEGORY_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbols_category ON symbols (category)
+"#;
+
+/// SQLite counterpart of [`crate::db::schema::MIGRATE_RELATED_SYMBOLS`].
+pub const MIGRATE_RELATED_SYMBOLS: &str = r#"
+INSERT OR IGNORE INTO symbol_relations (source_id, target_id, relation_type)
+SELECT DISTINCT s.id, rel.value, 'related_to'
+FROM symbols s, json_each(
+    CASE WHEN json_valid(s.related_symbols) AND json_type(s.related_symbols) = 'array'
+    THEN s.related_symbols ELSE '[]' END
+) AS rel
+WHERE rel.type = 'text' AND rel.value <> '' AND rel.value <> s.id
+  AND NOT EXISTS (
+    SELECT 1 FROM symbol_relations r
+    WHERE r.source_id = s.id AND r.target_id = rel.value
+  )
+"#;
+
+/// Embeddings are stored as JSON arrays and compared in Rust.
+pub const ADD_EMBEDDING_COLUMN: &str = r#"
+ALTER TABLE symbols ADD COLUMN embedding TEXT
+"#;
+
+/// Drops a symbol's embedding when the text it was computed from changes.
+pub const CREATE_CLEAR_EMBEDDING_TRIGGER: &str = r#"
+CREATE TRIGGER IF NOT EXISTS symbols_clear_embedding
+AFTER UPDATE OF name, description, interpretations, properties ON symbols
+FOR EACH ROW
+WHEN OLD.name IS NOT NEW.name OR OLD.description IS NOT NEW.description
+  OR OLD.interpretations IS NOT NEW.interpretations OR OLD.properties IS NOT NEW.properties
+BEGIN
+    UPDATE symbols SET embedding = NULL WHERE id = NEW.id;
+END
+"#;
+
+/// [`CREATE_CLEAR_EMBEDDING_TRIGGER`], also watching aliases and localized names.
+pub const CREATE_ALIASED_CLEAR_EMBEDDING_TRIGGER: &str = r#"
+CREATE TRIGGER IF NOT EXISTS symbols_clear_embedding
+AFTER UPDATE OF name, aliases, localized_names, description, interpretations, properties
+ON symbols
+FOR EACH ROW
+WHEN OLD.name IS NOT NEW.name OR OLD.aliases IS NOT NEW.aliases
+  OR OLD.localized_names IS NOT NEW.localized_names OR OLD.description IS NOT NEW.description
+  OR OLD.interpretations IS NOT NEW.interpretations OR OLD.properties IS NOT NEW.properties
+BEGIN
+    UPDATE symbols SET embedding = NULL WHERE id = NEW.id;
+END
+"#;
+
+/// Drops the embeddings computed before aliases and localized names were embedded.
+pub const CLEAR_ALIASED_EMBEDDINGS: &str = r#"
+UPDATE symbols SET embedding = NULL WHERE aliases <> '[]' OR localized_names <> '{}'
+"#;
+
+/// The keys symbols are looked up and checked for clashes by: each symbol's
+/// lowercased ID (`id`), name (`name`), and aliases and localized names
+/// (`alias`). Rows are written in Rust, since SQLite's `lower` only folds ASCII.
+pub const CREATE_SYMBOL_LOOKUP_KEYS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbol_lookup_keys (
+    symbol_id TEXT NOT NULL REFERENCES symbols (id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    key TEXT NOT NULL,
+    PRIMARY KEY (symbol_id, kind, key)
+)
+"#;
+
+pub const CREATE_SYMBOL_LOOKUP_KEYS_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_lookup_keys_key ON symbol_lookup_keys (key, kind)
+"#;
+
+/// Brings the schema up to date by applying any pending migrations.
+pub async fn init_schema(pool: &SqlitePool) -> DbResult<()> {
+    DatabasePool::Sqlite(pool.clone()).init().await
+}
+
+pub const ADD_ALIASES_COLUMN: &str = r#"
+ALTER TABLE symbols ADD COLUMN aliases TEXT NOT NULL DEFAULT '[]'
+"#;
+
+pub const ADD_LOCALIZED_NAMES_COLUMN: &str = r#"
+ALTER TABLE symbols ADD COLUMN localized_names TEXT NOT NULL DEFAULT '{}'
+"#;
+
+pub const CREATE_SYMBOL_SET_MEMBERS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS symbol_set_members (
+    set_id TEXT NOT NULL REFERENCES symbol_sets (id) ON DELETE CASCADE,
+    symbol_id TEXT NOT NULL REFERENCES symbols (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (set_id, symbol_id)
+)
+"#;
+
+pub const CREATE_SYMBOL_SET_MEMBERS_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_set_members_symbol ON symbol_set_members (symbol_id)
+"#;
+
+/// SQLite counterpart of [`crate::db::schema::CREATE_SYMBOLS_NAME_INDEX`].
+pub const CREATE_SYMBOLS_NAME_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbols_name ON symbols (name, id)
+"#;
+
+/// SQLite counterpart of [`crate::db::schema::CREATE_SYMBOL_SETS_NAME_INDEX`].
+pub const CREATE_SYMBOL_SETS_NAME_INDEX: &str = r#"
+CREATE INDEX IF NOT EXISTS idx_symbol_sets_name ON symbol_sets (name, id)
+"#;
+
+/// SQLite counterpart of [`crate::db::schema::MIGRATE_SET_MEMBERS`].
+pub const MIGRATE_SET_MEMBERS: &str = r#"
+INSERT OR IGNORE INTO symbol_set_members (set_id, symbol_id, position)
+SELECT ss.id, member.key,
+       ROW_NUMBER() OVER (PARTITION BY ss.id ORDER BY member.key)
+FROM symbol_sets ss, json_each(
+    CASE WHEN json_valid(ss.symbols) AND json_type(ss.symbols) = 'object'
+    THEN ss.symbols ELSE '{}' END
+) AS member
+WHERE EXISTS (SELECT 1 FROM symbols s WHERE s.id = member.key)
+"#;
+
+/// SQLite counterpart of [`crate::db::schema::RESTORE_SET_MEMBERS_COLUMN`].
+pub const RESTORE_SET_MEMBERS_COLUMN: &str = r#"
+UPDATE symbol_sets SET symbols = coalesce(
+    (SELECT json_group_object(m.symbol_id, json('null'))
+     FROM symbol_set_members m WHERE m.set_id = symbol_sets.id),
+    '{}'
+)
+"#;
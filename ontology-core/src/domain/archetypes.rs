@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// A recurring pattern that symbols express, arranged in a parent/child
+/// hierarchy (Shadow → Trickster → Coyote).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Archetype {
+    pub id: String,
+
+    pub name: String,
+
+    pub description: String,
+
+    /// The more general archetype this one specialises, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+}
+
+impl Archetype {
+    pub fn new(id: String, name: String, description: String) -> Self {
+        Archetype {
+            id,
+            name,
+            description,
+            parent_id: None,
+        }
+    }
+
+    pub fn with_parent(mut self, parent_id: &str) -> Self {
+        self.parent_id = Some(parent_id.to_string());
+        self
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.parent_id.is_none()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("Archetype ID cannot be empty".to_string());
+        }
+        if self.name.trim().is_empty() {
+            return Err("Archetype name cannot be empty".to_string());
+        }
+        if self.parent_id.as_deref() == Some(self.id.as_str()) {
+            return Err(format!("Archetype {} cannot be its own parent", self.id));
+        }
+        Ok(())
+    }
+}
+
+/// An archetype together with its descendants.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchetypeNode {
+    #[serde(flatten)]
+    pub archetype: Archetype,
+
+    #[serde(default)]
+    pub children: Vec<ArchetypeNode>,
+}
+
+impl ArchetypeNode {
+    /// Number of archetypes in this subtree, including the root.
+    pub fn size(&self) -> usize {
+        1 + self.children.iter().map(ArchetypeNode::size).sum::<usize>()
+    }
+}
+
+/// Arranges a flat list of archetypes into trees.
+///
+/// With `root_id` the tree under that archetype is returned; otherwise one tree
+/// per top-level archetype. Archetypes whose parent is missing are treated as
+/// top-level, and children are sorted by name.
+pub fn build_hierarchy(archetypes: &[Archetype], root_id: Option<&str>) -> Vec<ArchetypeNode> {
+    let ids: HashSet<&str> = archetypes.iter().map(|a| a.id.as_str()).collect();
+
+    let mut children: HashMap<&str, Vec<&Archetype>> = HashMap::new();
+    for archetype in archetypes {
+        if let Some(parent_id) = archetype.parent_id.as_deref() {
+            if ids.contains(parent_id) {
+                children.entry(parent_id).or_default().push(archetype);
+            }
+        }
+    }
+    for siblings in children.values_mut() {
+        siblings.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    let mut roots: Vec<&Archetype> = match root_id {
+        Some(root_id) => archetypes.iter().filter(|a| a.id == root_id).collect(),
+        None => archetypes
+            .iter()
+            .filter(|a| {
+                a.parent_id
+                    .as_deref()
+                    .is_none_or(|parent_id| !ids.contains(parent_id))
+            })
+            .collect(),
+    };
+    roots.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut visited = HashSet::new();
+    roots
+        .into_iter()
+        .map(|root| build_node(root, &children, &mut visited))
+        .collect()
+}
+
+fn build_node<'a>(
+    archetype: &'a Archetype,
+    children: &HashMap<&str, Vec<&'a Archetype>>,
+    visited: &mut HashSet<&'a str>,
+) -> ArchetypeNode {
+    visited.insert(archetype.id.as_str());
+
+    let mut child_nodes = Vec::new();
+    for child in children.get(archetype.id.as_str()).into_iter().flatten() {
+        // Guards against cycles in data written outside the repository
+        if !visited.contains(child.id.as_str()) {
+            child_nodes.push(build_node(child, children, visited));
+        }
+    }
+
+    ArchetypeNode {
+        archetype: archetype.clone(),
+        children: child_nodes,
+    }
+}
+
+/// Returns the IDs of `root_id` and every archetype below it.
+pub fn descendant_ids(archetypes: &[Archetype], root_id: &str) -> Vec<String> {
+    let mut result = vec![root_id.to_string()];
+    let mut index = 0;
+
+    while index < result.len() {
+        let current = result[index].clone();
+        for archetype in archetypes {
+            if archetype.parent_id.as_deref() == Some(current.as_str())
+                && !result.contains(&archetype.id)
+            {
+                result.push(archetype.id.clone());
+            }
+        }
+        index += 1;
+    }
+
+    result
+}
+
+/// Whether making `parent_id` the parent of `archetype_id` would introduce a cycle.
+pub fn creates_cycle(archetypes: &[Archetype], archetype_id: &str, parent_id: &str) -> bool {
+    descendant_ids(archetypes, archetype_id)
+        .iter()
+        .any(|id| id == parent_id)
+}
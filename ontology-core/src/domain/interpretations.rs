@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// How widely an interpretation is held, from archetypal meanings shared
+/// across cultures down to meanings specific to one dreamer.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum InterpretationLevel {
+    #[default]
+    Universal,
+    Cultural,
+    Personal,
+}
+
+impl InterpretationLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Universal => "universal",
+            Self::Cultural => "cultural",
+            Self::Personal => "personal",
+        }
+    }
+
+    /// Infers the level of a legacy interpretation from its context key.
+    ///
+    /// Flat interpretation maps were keyed by context ("default", "jungian",
+    /// "western", ...), so anything other than the default context is treated
+    /// as a cultural reading belonging to the tradition named by the key.
+    pub fn from_context(context: &str) -> Self {
+        match context.to_lowercase().as_str() {
+            "" | "default" | "general" | "universal" | "archetypal" => Self::Universal,
+            "personal" => Self::Personal,
+            _ => Self::Cultural,
+        }
+    }
+}
+
+impl Display for InterpretationLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for InterpretationLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "universal" => Ok(Self::Universal),
+            "cultural" => Ok(Self::Cultural),
+            "personal" => Ok(Self::Personal),
+            _ => Err(format!("Unknown interpretation level: {}", s)),
+        }
+    }
+}
+
+/// A single interpretation of a symbol together with its provenance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct InterpretationLayer {
+    #[serde(default)]
+    pub level: InterpretationLevel,
+
+    pub text: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tradition: Option<String>,
+}
+
+impl InterpretationLayer {
+    pub fn new(level: InterpretationLevel, text: impl Into<String>) -> Self {
+        InterpretationLayer {
+            level,
+            text: text.into(),
+            source: None,
+            tradition: None,
+        }
+    }
+
+    pub fn universal(text: impl Into<String>) -> Self {
+        Self::new(InterpretationLevel::Universal, text)
+    }
+
+    pub fn cultural(tradition: &str, text: impl Into<String>) -> Self {
+        Self::new(InterpretationLevel::Cultural, text).with_tradition(tradition)
+    }
+
+    pub fn personal(text: impl Into<String>) -> Self {
+        Self::new(InterpretationLevel::Personal, text)
+    }
+
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+
+    pub fn with_tradition(mut self, tradition: &str) -> Self {
+        self.tradition = Some(tradition.to_string());
+        self
+    }
+
+    /// Builds a layer from a flat `context -> text` entry.
+    pub fn from_legacy(context: &str, text: impl Into<String>) -> Self {
+        let level = InterpretationLevel::from_context(context);
+        let layer = Self::new(level, text);
+
+        match level {
+            InterpretationLevel::Cultural => layer.with_tradition(context),
+            _ => layer,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawInterpretation {
+    Flat(String),
+    Layered(InterpretationLayer),
+}
+
+/// Parses an interpretations map that may mix flat strings and layered objects.
+pub fn interpretations_from_value(
+    value: serde_json::Value,
+) -> Result<HashMap<String, InterpretationLayer>, serde_json::Error> {
+    let raw: HashMap<String, RawInterpretation> = serde_json::from_value(value)?;
+
+    Ok(raw
+        .into_iter()
+        .map(|(context, raw)| {
+            let layer = match raw {
+                RawInterpretation::Flat(text) => InterpretationLayer::from_legacy(&context, text),
+                RawInterpretation::Layered(layer) => layer,
+            };
+            (context, layer)
+        })
+        .collect())
+}
+
+/// Serde adapter accepting both the legacy flat map and the layered format.
+pub fn deserialize_interpretations<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, InterpretationLayer>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    interpretations_from_value(value).map_err(serde::de::Error::custom)
+}
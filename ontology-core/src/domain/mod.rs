@@ -8,11 +8,23 @@
  * This includes:
  *
  * - **Symbol**: Represents a symbolic entity with interpretations and relationships
+ * - **InterpretationLayer**: A universal, cultural or personal reading of a symbol
+ * - **SymbolRelation**: A typed, directed edge between two symbols
  * - **SymbolSet**: A collection of related symbols organized into an ontology
+ * - **Archetype**: A recurring pattern in a parent/child hierarchy that symbols can be linked to
+ * - **SearchHit**: A symbol returned by a search, with its relevance score
  */
 
+pub mod archetypes;
+pub mod interpretations;
 pub mod ontology;
+pub mod relations;
+pub mod search;
 pub mod symbols;
 
+pub use archetypes::{Archetype, ArchetypeNode};
+pub use interpretations::{InterpretationLayer, InterpretationLevel};
 pub use ontology::SymbolSet;
+pub use relations::{RelationDirection, RelationType, SymbolRelation};
+pub use search::SearchHit;
 pub use symbols::Symbol;
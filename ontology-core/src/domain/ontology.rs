@@ -1,8 +1,9 @@
 // SPDX-License-Identifier: MPL-2.0 OR Commercial
 // Copyright (c) 2024 Symbol Ontology Contributors
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use super::symbols::Symbol;
 
@@ -16,8 +17,9 @@ pub struct SymbolSet {
 
     pub category: String,
 
+    /// The set's symbols by ID, in set order
     #[serde(default)]
-    pub symbols: HashMap<String, Symbol>,
+    pub symbols: IndexMap<String, Symbol>,
 }
 
 impl SymbolSet {
@@ -27,7 +29,7 @@ impl SymbolSet {
             name,
             category,
             description,
-            symbols: HashMap::new(),
+            symbols: IndexMap::new(),
         }
     }
 
@@ -53,6 +55,11 @@ impl SymbolSet {
         self.symbols.insert(symbol.id.clone(), symbol);
     }
 
+    /// IDs of the set's symbols, in set order.
+    pub fn symbol_ids(&self) -> Vec<String> {
+        self.symbols.keys().cloned().collect()
+    }
+
     #[allow(dead_code)]
     pub fn get_symbol(&self, id: &str) -> Option<&Symbol> {
         self.symbols.get(id)
@@ -60,7 +67,7 @@ impl SymbolSet {
 
     #[allow(dead_code)]
     pub fn remove_symbol(&mut self, id: &str) -> Option<Symbol> {
-        self.symbols.remove(id)
+        self.symbols.shift_remove(id)
     }
 
     #[allow(dead_code)]
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The kind of link between two symbols.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationType {
+    /// Untyped link, used for relations migrated from `related_symbols`
+    #[default]
+    RelatedTo,
+    SimilarTo,
+    OppositeOf,
+    TransformsInto,
+    PartOf,
+    Evokes,
+    DerivedFrom,
+}
+
+impl RelationType {
+    pub const ALL: [RelationType; 7] = [
+        Self::RelatedTo,
+        Self::SimilarTo,
+        Self::OppositeOf,
+        Self::TransformsInto,
+        Self::PartOf,
+        Self::Evokes,
+        Self::DerivedFrom,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RelatedTo => "related_to",
+            Self::SimilarTo => "similar_to",
+            Self::OppositeOf => "opposite_of",
+            Self::TransformsInto => "transforms_into",
+            Self::PartOf => "part_of",
+            Self::Evokes => "evokes",
+            Self::DerivedFrom => "derived_from",
+        }
+    }
+
+    /// Symmetric relations read the same in both directions
+    /// (if A is similar to B, B is similar to A).
+    pub fn is_symmetric(&self) -> bool {
+        matches!(self, Self::RelatedTo | Self::SimilarTo | Self::OppositeOf)
+    }
+}
+
+impl Display for RelationType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for RelationType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace(['-', ' '], "_");
+        Self::ALL
+            .into_iter()
+            .find(|relation_type| relation_type.as_str() == normalized)
+            .ok_or_else(|| format!("Unknown relation type: {}", s))
+    }
+}
+
+/// Which edges to consider when listing the relations of a symbol.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationDirection {
+    /// Edges where the symbol is the source
+    Outgoing,
+    /// Edges where the symbol is the target
+    Incoming,
+    #[default]
+    Both,
+}
+
+impl FromStr for RelationDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "outgoing" | "out" => Ok(Self::Outgoing),
+            "incoming" | "in" => Ok(Self::Incoming),
+            "both" | "any" => Ok(Self::Both),
+            _ => Err(format!("Unknown relation direction: {}", s)),
+        }
+    }
+}
+
+/// A directed, typed edge from `source_id` to `target_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SymbolRelation {
+    pub source_id: String,
+
+    pub target_id: String,
+
+    #[serde(default)]
+    pub relation_type: RelationType,
+
+    /// Strength of the relation between 0.0 and 1.0, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+}
+
+impl SymbolRelation {
+    pub fn new(source_id: &str, target_id: &str, relation_type: RelationType) -> Self {
+        SymbolRelation {
+            source_id: source_id.to_string(),
+            target_id: target_id.to_string(),
+            relation_type,
+            weight: None,
+        }
+    }
+
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Returns the symbol at the other end of this edge, if `symbol_id` is one of its ends.
+    pub fn other_end(&self, symbol_id: &str) -> Option<&str> {
+        if self.source_id == symbol_id {
+            Some(&self.target_id)
+        } else if self.target_id == symbol_id {
+            Some(&self.source_id)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the edge can be walked from `symbol_id` in the given direction.
+    pub fn touches(&self, symbol_id: &str, direction: RelationDirection) -> bool {
+        match direction {
+            RelationDirection::Outgoing => self.source_id == symbol_id,
+            RelationDirection::Incoming => self.target_id == symbol_id,
+            RelationDirection::Both => self.source_id == symbol_id || self.target_id == symbol_id,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.source_id.trim().is_empty() || self.target_id.trim().is_empty() {
+            return Err("Relation endpoints cannot be empty".to_string());
+        }
+        if self.source_id == self.target_id {
+            return Err(format!(
+                "Symbol {} cannot be related to itself",
+                self.source_id
+            ));
+        }
+        if let Some(weight) = self.weight {
+            if !(0.0..=1.0).contains(&weight) {
+                return Err(format!(
+                    "Relation weight must be between 0.0 and 1.0, got {}",
+                    weight
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::symbols::Symbol;
+
+/// Weight of a match in the symbol's name, aliases or localized names.
+///
+/// The four field weights are PostgreSQL's default `ts_rank` weights for the
+/// `A`..`D` labels the search document uses, so backends that score matches
+/// themselves rank results the same way.
+pub const NAME_WEIGHT: f32 = 1.0;
+
+/// Weight of a match in the description.
+pub const DESCRIPTION_WEIGHT: f32 = 0.4;
+
+/// Weight of a match in any interpretation's text.
+pub const INTERPRETATION_WEIGHT: f32 = 0.2;
+
+/// Weight of a match in any property value.
+pub const PROPERTY_WEIGHT: f32 = 0.1;
+
+/// A symbol found by a search, with how relevant it is to the query.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub symbol: Symbol,
+
+    /// Higher is more relevant. Scores are only comparable within one result list.
+    pub score: f32,
+}
+
+impl SearchHit {
+    pub fn new(symbol: Symbol, score: f32) -> Self {
+        SearchHit { symbol, score }
+    }
+
+    /// Scores `symbol` by which of its fields `matches` accepts, or returns
+    /// `None` if none of them do. Used by backends without a ranked text index.
+    pub fn from_fields(symbol: Symbol, matches: impl Fn(&str) -> bool) -> Option<Self> {
+        let mut score = 0.0;
+        if symbol.surface_forms().any(&matches) {
+            score += NAME_WEIGHT;
+        }
+        if matches(&symbol.description) {
+            score += DESCRIPTION_WEIGHT;
+        }
+        if symbol
+            .interpretations
+            .values()
+            .any(|layer| matches(&layer.text))
+        {
+            score += INTERPRETATION_WEIGHT;
+        }
+        if symbol.properties.values().any(|value| matches(value)) {
+            score += PROPERTY_WEIGHT;
+        }
+
+        (score > 0.0).then(|| SearchHit::new(symbol, score))
+    }
+
+    /// Like [`SearchHit::from_fields`], for a query split into `words`: every
+    /// word must match some field, in any order, as in a full-text search, and
+    /// a field scores if any word matches it.
+    pub fn from_words(
+        symbol: Symbol,
+        words: &[&str],
+        matches: impl Fn(&str, &str) -> bool,
+    ) -> Option<Self> {
+        let texts = || {
+            symbol
+                .surface_forms()
+                .chain(std::iter::once(symbol.description.as_str()))
+                .chain(
+                    symbol
+                        .interpretations
+                        .values()
+                        .map(|layer| layer.text.as_str()),
+                )
+                .chain(symbol.properties.values().map(String::as_str))
+        };
+        if !words
+            .iter()
+            .all(|word| texts().any(|text| matches(text, word)))
+        {
+            return None;
+        }
+
+        Self::from_fields(symbol, |field| {
+            words.iter().any(|word| matches(field, word))
+        })
+    }
+}
+
+/// Orders hits from most to least relevant, keeping the existing order of ties.
+pub fn rank(hits: &mut [SearchHit]) {
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+}
+
+/// Lowest [`name_similarity`] a fuzzy match may have.
+pub const FUZZY_THRESHOLD: f32 = 0.4;
+
+/// Longest string PostgreSQL's `levenshtein` accepts; longer names are compared
+/// by trigrams alone.
+const MAX_EDIT_LENGTH: usize = 255;
+
+/// How alike a name and a possibly misspelled query are, from 0 to 1: the better
+/// of their trigram similarity and their edit distance relative to the longer of
+/// the two. PostgreSQL computes the same score with `pg_trgm` and `fuzzystrmatch`.
+pub fn name_similarity(name: &str, query: &str) -> f32 {
+    let name = name.to_lowercase();
+    let query = query.to_lowercase();
+    let trigram = trigram_similarity(&name, &query);
+
+    let (name_len, query_len) = (name.chars().count(), query.chars().count());
+    if name_len > MAX_EDIT_LENGTH || query_len > MAX_EDIT_LENGTH {
+        return trigram;
+    }
+    let longest = name_len.max(query_len).max(1);
+    let edit = 1.0 - levenshtein(&name, &query) as f32 / longest as f32;
+    trigram.max(edit)
+}
+
+/// `pg_trgm`'s `similarity`: the share of distinct trigrams two strings have in
+/// common, where each word is padded with two spaces in front and one behind.
+pub fn trigram_similarity(a: &str, b: &str) -> f32 {
+    let (a, b) = (trigrams(a), trigrams(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+fn trigrams(text: &str) -> HashSet<String> {
+    let mut trigrams = HashSet::new();
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let padded: Vec<char> = format!("  {} ", word.to_lowercase()).chars().collect();
+        trigrams.extend(padded.windows(3).map(|t| t.iter().collect::<String>()));
+    }
+    trigrams
+}
+
+/// Number of single-character insertions, deletions and substitutions that turn
+/// `a` into `b`.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// The `limit` symbols with a name, alias or localized name most like `query`,
+/// scored by the best [`name_similarity`] among them; ties are ordered by name.
+/// Used by backends without a trigram index.
+pub fn fuzzy_matches(
+    symbols: impl IntoIterator<Item = Symbol>,
+    query: &str,
+    limit: usize,
+) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = symbols
+        .into_iter()
+        .map(|symbol| {
+            let score = symbol
+                .surface_forms()
+                .map(|form| name_similarity(form, query))
+                .fold(0.0, f32::max);
+            SearchHit::new(symbol, score)
+        })
+        .filter(|hit| hit.score >= FUZZY_THRESHOLD)
+        .collect();
+    hits.sort_by(|a, b| a.symbol.name.cmp(&b.symbol.name));
+    rank(&mut hits);
+    hits.truncate(limit);
+    hits
+}
+
+/// The symbol with `key` as an alias or localized name, or else the first
+/// symbol by ID named `key`, ignoring case. Used by backends without a
+/// lookup-key index.
+pub fn resolve<'a>(symbols: impl IntoIterator<Item = &'a Symbol>, key: &str) -> Option<&'a Symbol> {
+    let key = key.to_lowercase();
+    let mut named: Option<&Symbol> = None;
+    for symbol in symbols {
+        if symbol.lookup_keys().contains(&key) {
+            return Some(symbol);
+        }
+        if symbol.name.to_lowercase() == key && named.is_none_or(|other| symbol.id < other.id) {
+            named = Some(symbol);
+        }
+    }
+    named
+}
+
+/// The first symbol by ID, other than `symbol` itself, that already goes by one
+/// of `symbol`'s aliases or localized names, or by its ID.
+pub fn lookup_key_conflict<'a>(
+    symbols: impl IntoIterator<Item = &'a Symbol>,
+    symbol: &Symbol,
+) -> Option<&'a Symbol> {
+    let keys = symbol.lookup_keys();
+    let id = symbol.id.to_lowercase();
+    symbols
+        .into_iter()
+        .filter(|other| other.id != symbol.id)
+        .filter(|other| {
+            keys.contains(&other.id.to_lowercase())
+                || other
+                    .lookup_keys()
+                    .iter()
+                    .any(|key| *key == id || keys.contains(key))
+        })
+        .min_by(|a, b| a.id.cmp(&b.id))
+}
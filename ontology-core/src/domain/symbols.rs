@@ -1,8 +1,13 @@
 // SPDX-License-Identifier: MPL-2.0 OR Commercial
 // Copyright (c) 2024 Symbol Ontology Contributors
 
+use language_tags::LanguageTag;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+
+use super::interpretations::{
+    deserialize_interpretations, InterpretationLayer, InterpretationLevel,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Symbol {
@@ -14,12 +19,21 @@ pub struct Symbol {
 
     pub description: String,
 
-    pub interpretations: HashMap<String, String>,
+    #[serde(deserialize_with = "deserialize_interpretations")]
+    pub interpretations: HashMap<String, InterpretationLayer>,
 
     pub related_symbols: Vec<String>,
 
     #[serde(default)]
     pub properties: HashMap<String, String>,
+
+    /// Other names the symbol goes by, such as "snake" for the serpent.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// The symbol's name in other languages, keyed by BCP 47 language tag.
+    #[serde(default)]
+    pub localized_names: HashMap<String, String>,
 }
 
 impl Symbol {
@@ -33,6 +47,8 @@ impl Symbol {
             interpretations: HashMap::new(),
             related_symbols: Vec::new(),
             properties: HashMap::new(),
+            aliases: Vec::new(),
+            localized_names: HashMap::new(),
         }
     }
 
@@ -50,11 +66,170 @@ impl Symbol {
 
     #[allow(dead_code)]
     pub fn add_interpretation(&mut self, context: String, interpretation: String) {
-        self.interpretations.insert(context, interpretation);
+        let layer = InterpretationLayer::from_legacy(&context, interpretation);
+        self.interpretations.insert(context, layer);
+    }
+
+    #[allow(dead_code)]
+    pub fn add_interpretation_layer(&mut self, context: String, layer: InterpretationLayer) {
+        self.interpretations.insert(context, layer);
+    }
+
+    #[allow(dead_code)]
+    pub fn interpretations_at(
+        &self,
+        level: InterpretationLevel,
+    ) -> Vec<(&str, &InterpretationLayer)> {
+        self.interpretations
+            .iter()
+            .filter(|(_, layer)| layer.level == level)
+            .map(|(context, layer)| (context.as_str(), layer))
+            .collect()
+    }
+
+    /// The interpretation that best fits a dreamer from `culture`, with its
+    /// context: a cultural reading of that tradition, else the default
+    /// universal one, else any universal one, else any at all. Ties go to the
+    /// first context by name.
+    pub fn interpretation_for(
+        &self,
+        culture: Option<&str>,
+    ) -> Option<(&str, &InterpretationLayer)> {
+        let mut contexts: Vec<(&str, &InterpretationLayer)> = self
+            .interpretations
+            .iter()
+            .map(|(context, layer)| (context.as_str(), layer))
+            .collect();
+        contexts.sort_by_key(|(context, _)| *context);
+
+        let of_culture = |(context, layer): &(&str, &InterpretationLayer)| {
+            culture.is_some_and(|culture| {
+                layer.level == InterpretationLevel::Cultural
+                    && layer
+                        .tradition
+                        .as_deref()
+                        .unwrap_or(context)
+                        .eq_ignore_ascii_case(culture)
+            })
+        };
+        let universal = |(_, layer): &(&str, &InterpretationLayer)| {
+            layer.level == InterpretationLevel::Universal
+        };
+
+        let find = |matches: &dyn Fn(&(&str, &InterpretationLayer)) -> bool| {
+            contexts.iter().copied().find(|entry| matches(entry))
+        };
+        find(&of_culture)
+            .or_else(|| find(&|entry| entry.0 == "default" && universal(entry)))
+            .or_else(|| find(&universal))
+            .or(contexts.first().copied())
     }
 
     #[allow(dead_code)]
     pub fn add_related_symbol(&mut self, symbol_id: String) {
         self.related_symbols.push(symbol_id);
     }
+
+    pub fn with_aliases(mut self, aliases: Vec<&str>) -> Self {
+        self.aliases = aliases.into_iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    pub fn add_alias(&mut self, alias: String) {
+        self.aliases.push(alias);
+    }
+
+    /// Names the symbol in `language`, stored under the canonical form of the tag.
+    pub fn add_localized_name(&mut self, language: &str, name: String) -> Result<(), String> {
+        let tag = parse_language(language)?;
+        self.localized_names.insert(tag.into_string(), name);
+        Ok(())
+    }
+
+    /// The symbol's name in `language`, falling back to a less specific tag
+    /// ("pt" for "pt-BR") and then to [`Symbol::name`].
+    pub fn name_in(&self, language: &str) -> &str {
+        let Ok(wanted) = LanguageTag::parse(language) else {
+            return &self.name;
+        };
+
+        self.localized_names
+            .iter()
+            .filter_map(|(tag, name)| Some((LanguageTag::parse(tag).ok()?, name)))
+            .filter(|(tag, _)| tag.is_language_range() && tag.matches(&wanted))
+            .max_by_key(|(tag, _)| tag.as_str().len())
+            .map_or(&self.name, |(_, name)| name)
+    }
+
+    /// Every name the symbol can be looked up by: its name, aliases and
+    /// localized names.
+    pub fn surface_forms(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str())
+            .chain(self.aliases.iter().map(String::as_str))
+            .chain(self.localized_names.values().map(String::as_str))
+    }
+
+    /// The lowercased aliases and localized names, which no other symbol may use.
+    pub fn lookup_keys(&self) -> Vec<String> {
+        self.aliases
+            .iter()
+            .chain(self.localized_names.values())
+            .map(|name| name.to_lowercase())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Trims surrounding whitespace from the ID, name, category, description,
+    /// aliases, properties and localized names, as every write path does
+    /// before storing a symbol.
+    pub fn normalize(&mut self) {
+        let trim = |text: &str| text.trim().to_string();
+        for field in [
+            &mut self.id,
+            &mut self.name,
+            &mut self.category,
+            &mut self.description,
+        ] {
+            *field = trim(field);
+        }
+        for alias in &mut self.aliases {
+            *alias = trim(alias);
+        }
+        self.properties = self
+            .properties
+            .iter()
+            .map(|(key, value)| (trim(key), trim(value)))
+            .collect();
+        for name in self.localized_names.values_mut() {
+            *name = trim(name);
+        }
+    }
+
+    /// Checks that aliases and localized names are not blank and that every
+    /// localized name is keyed by a valid language tag.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.aliases.iter().any(|alias| alias.trim().is_empty()) {
+            return Err(format!("Symbol {} has a blank alias", self.id));
+        }
+        for (language, name) in &self.localized_names {
+            parse_language(language)?;
+            if name.trim().is_empty() {
+                return Err(format!(
+                    "Symbol {} has a blank name for language {}",
+                    self.id, language
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_language(language: &str) -> Result<LanguageTag, String> {
+    let tag = LanguageTag::parse(language)
+        .map_err(|e| format!("Invalid language tag '{}': {}", language, e))?;
+    tag.validate()
+        .map_err(|e| format!("Invalid language tag '{}': {}", language, e))?;
+    tag.canonicalize()
+        .map_err(|e| format!("Invalid language tag '{}': {}", language, e))
 }
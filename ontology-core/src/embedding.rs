@@ -0,0 +1,363 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # Embeddings
+ *
+ * Vector representations of symbols for semantic (nearest-neighbour) search.
+ * Embedders are pluggable through the [`Embedder`] trait; [`HashingEmbedder`]
+ * is a deterministic bag-of-words embedder that needs no model or network
+ * access, so semantic search works offline.
+ *
+ * Embeddings are stored next to the symbols by the repositories (a pgvector
+ * column on PostgreSQL) and are dropped whenever a symbol's text changes;
+ * [`backfill`] computes the missing ones.
+ */
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use thiserror::Error;
+
+use crate::db::repository::interfaces::{RepositoryError, RepositoryResult, SymbolRepository};
+use crate::db::repository::pagination::ListQuery;
+use crate::domain::search::{self, SearchHit};
+use crate::domain::Symbol;
+
+/// Length of every stored embedding; the pgvector column is declared with it.
+/// This is also the most pgvector can index.
+pub const EMBEDDING_DIMENSIONS: usize = 2000;
+
+/// Weight of a word's character trigrams, together, relative to the word itself.
+const TRIGRAM_WEIGHT: f32 = 2.0;
+
+#[derive(Debug, Error)]
+pub enum EmbeddingError {
+    #[error("Embedding failed: {0}")]
+    Failed(String),
+
+    #[error("Expected an embedding with {expected} dimensions, got {actual}")]
+    Dimensions { expected: usize, actual: usize },
+}
+
+impl From<EmbeddingError> for RepositoryError {
+    fn from(error: EmbeddingError) -> Self {
+        match error {
+            EmbeddingError::Failed(msg) => RepositoryError::Internal(msg),
+            error @ EmbeddingError::Dimensions { .. } => {
+                RepositoryError::Validation(error.to_string())
+            }
+        }
+    }
+}
+
+pub type EmbeddingResult<T> = Result<T, EmbeddingError>;
+
+/// Turns text into a fixed-length vector; similar texts should get vectors with
+/// a high cosine similarity.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// Length of the vectors returned by [`Embedder::embed`].
+    fn dimensions(&self) -> usize;
+
+    async fn embed(&self, text: &str) -> EmbeddingResult<Vec<f32>>;
+}
+
+/// Feature-hashing embedder over stemmed words and their character trigrams.
+///
+/// Word counts are damped logarithmically, so a term repeated throughout a long
+/// description does not drown out the rest, and trigrams let related word forms
+/// such as "underwater" and "water" share part of their vector.
+#[derive(Debug, Clone)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        HashingEmbedder { dimensions }
+    }
+
+    pub fn embed_text(&self, text: &str) -> Vec<f32> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for token in tokens(text) {
+            *counts.entry(token).or_default() += 1;
+        }
+
+        let mut vector = vec![0.0; self.dimensions];
+        if self.dimensions == 0 {
+            return vector;
+        }
+        for (token, count) in counts {
+            let weight = 1.0 + (count as f32).ln();
+            self.add_feature(&mut vector, &token, weight);
+
+            let padded: Vec<char> = format!("<{}>", token).chars().collect();
+            let trigrams = padded.windows(3).count() as f32;
+            for trigram in padded.windows(3) {
+                let trigram: String = trigram.iter().collect();
+                self.add_feature(&mut vector, &trigram, TRIGRAM_WEIGHT * weight / trigrams);
+            }
+        }
+
+        normalize(&mut vector);
+        vector
+    }
+
+    fn add_feature(&self, vector: &mut [f32], feature: &str, weight: f32) {
+        // The top bit picks a sign so colliding features tend to cancel out
+        let hash = fnv1a(feature.as_bytes());
+        let bucket = (hash % self.dimensions as u64) as usize;
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[bucket] += sign * weight;
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(EMBEDDING_DIMENSIONS)
+    }
+}
+
+#[async_trait]
+impl Embedder for HashingEmbedder {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    async fn embed(&self, text: &str) -> EmbeddingResult<Vec<f32>> {
+        Ok(self.embed_text(text))
+    }
+}
+
+const STOP_WORDS: &[&str] = &[
+    "about",
+    "also",
+    "and",
+    "are",
+    "been",
+    "being",
+    "but",
+    "can",
+    "does",
+    "for",
+    "from",
+    "had",
+    "has",
+    "have",
+    "her",
+    "his",
+    "how",
+    "into",
+    "its",
+    "may",
+    "more",
+    "not",
+    "often",
+    "one",
+    "our",
+    "out",
+    "should",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "them",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "very",
+    "was",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "will",
+    "with",
+    "would",
+    "you",
+    "your",
+    // Words nearly every symbol's description uses, which say nothing about it
+    "dream",
+    "dreamer",
+    "dreaming",
+    "dreams",
+    "feel",
+    "feeling",
+    "feelings",
+    "indicate",
+    "indicates",
+    "life",
+    "mean",
+    "meaning",
+    "represent",
+    "represents",
+    "sign",
+    "suggest",
+    "suggests",
+    "symbol",
+    "symbolize",
+    "symbolizes",
+    "symbols",
+    "waking",
+];
+
+/// Lowercased words of three or more letters, without stop words, stemmed.
+fn tokens(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= 3 && !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| stem(&word))
+}
+
+/// Strips the most common English inflections, keeping at least three letters.
+pub(crate) fn stem(word: &str) -> String {
+    if word.ends_with("ss") {
+        return word.to_string();
+    }
+    for (suffix, replacement) in [
+        ("ings", ""),
+        ("ing", ""),
+        ("edly", ""),
+        ("ied", "y"),
+        ("ies", "y"),
+        ("ed", ""),
+        ("ly", ""),
+        ("s", ""),
+    ] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.chars().count() >= 3 {
+                return format!("{}{}", stem, replacement);
+            }
+        }
+    }
+    word.to_string()
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// The text embedded for a symbol: its name, aliases, localized names,
+/// description, interpretations and property values.
+pub fn symbol_text(symbol: &Symbol) -> String {
+    let mut parts = vec![symbol.name.as_str()];
+    parts.extend(symbol.aliases.iter().map(String::as_str));
+
+    let mut languages: Vec<&String> = symbol.localized_names.keys().collect();
+    languages.sort();
+    parts.extend(
+        languages
+            .into_iter()
+            .map(|l| symbol.localized_names[l].as_str()),
+    );
+    parts.push(symbol.description.as_str());
+
+    let mut contexts: Vec<&String> = symbol.interpretations.keys().collect();
+    contexts.sort();
+    parts.extend(
+        contexts
+            .into_iter()
+            .map(|c| symbol.interpretations[c].text.as_str()),
+    );
+
+    let mut keys: Vec<&String> = symbol.properties.keys().collect();
+    keys.sort();
+    parts.extend(keys.into_iter().map(|k| symbol.properties[k].as_str()));
+
+    parts.join("\n")
+}
+
+/// Cosine similarity of two vectors; zero if either has no length.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms =
+        a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norms > 0.0 {
+        dot / norms
+    } else {
+        0.0
+    }
+}
+
+/// Rejects embeddings that do not fit the stored column.
+pub fn check_dimensions(embedding: &[f32]) -> RepositoryResult<()> {
+    if embedding.len() != EMBEDDING_DIMENSIONS {
+        return Err(EmbeddingError::Dimensions {
+            expected: EMBEDDING_DIMENSIONS,
+            actual: embedding.len(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// The `limit` candidates closest to `query`, scored by cosine similarity. Used
+/// by backends that have no vector index of their own. Nothing is close to a
+/// zero vector, nor is a zero vector close to anything.
+pub fn nearest(
+    candidates: impl IntoIterator<Item = (Symbol, Vec<f32>)>,
+    query: &[f32],
+    limit: usize,
+) -> Vec<SearchHit> {
+    if query.iter().all(|x| *x == 0.0) {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<SearchHit> = candidates
+        .into_iter()
+        .filter(|(_, embedding)| embedding.iter().any(|x| *x != 0.0))
+        .map(|(symbol, embedding)| SearchHit::new(symbol, cosine_similarity(query, &embedding)))
+        .collect();
+    search::rank(&mut hits);
+    hits.truncate(limit);
+    hits
+}
+
+/// Embeds the symbols that have no embedding yet, or every symbol if `all` is
+/// set, and returns how many were embedded.
+pub async fn backfill(
+    repository: &dyn SymbolRepository,
+    embedder: &dyn Embedder,
+    all: bool,
+) -> RepositoryResult<usize> {
+    if embedder.dimensions() != EMBEDDING_DIMENSIONS {
+        return Err(EmbeddingError::Dimensions {
+            expected: EMBEDDING_DIMENSIONS,
+            actual: embedder.dimensions(),
+        }
+        .into());
+    }
+
+    let symbols = if all {
+        repository.list_symbols(&ListQuery::new()).await?.items
+    } else {
+        repository.list_unembedded_symbols().await?
+    };
+
+    for symbol in &symbols {
+        let embedding = embedder.embed(&symbol_text(symbol)).await?;
+        repository.set_embedding(&symbol.id, &embedding).await?;
+    }
+    Ok(symbols.len())
+}
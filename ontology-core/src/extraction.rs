@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # Symbol Extraction
+ *
+ * Finds the symbols of the ontology that a free-text narrative, such as a
+ * dream, mentions. The text and every symbol's name, aliases and localized
+ * names are split into words and stemmed the way [`crate::embedding`] stems
+ * them, so "snakes" in the text matches the alias "snake". Longer phrases win
+ * over the words inside them: "Garden of Eden" is one match, not a garden.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::db::repository::interfaces::{RepositoryResult, SymbolRepository};
+use crate::db::repository::pagination::ListQuery;
+use crate::domain::Symbol;
+use crate::embedding::stem;
+
+/// Articles a name may start with that the text need not repeat, so "The
+/// Tower" also matches "a tower".
+const ARTICLES: &[&str] = &["a", "an", "the"];
+
+/// Symbols listed per page while looking for those the text could mention.
+const PAGE_SIZE: usize = 500;
+
+/// Where a symbol is mentioned: byte offsets into the text and the words found there.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextSpan {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+/// A symbol mentioned in the text, with every place it is mentioned in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedSymbol {
+    pub symbol: Symbol,
+    pub spans: Vec<TextSpan>,
+}
+
+/// Finds the repository's symbols mentioned in `text`.
+///
+/// The symbols are listed a page at a time, keeping only those whose phrases
+/// are made of words the text contains, so memory stays bounded by the page
+/// size and the symbols the text could mention.
+pub async fn extract_symbols_from_text(
+    repository: &dyn SymbolRepository,
+    text: &str,
+) -> RepositoryResult<Vec<ExtractedSymbol>> {
+    let stems: HashSet<String> = words(text).into_iter().map(|word| word.stem).collect();
+    let mut candidates = Vec::new();
+    let mut query = ListQuery::new().limit(PAGE_SIZE);
+    loop {
+        let page = repository.list_symbols(&query).await?;
+        candidates.extend(page.items.into_iter().filter(|symbol| {
+            phrases(symbol)
+                .iter()
+                .any(|phrase| phrase.iter().all(|word| stems.contains(word)))
+        }));
+        match page.next_cursor {
+            Some(cursor) => query = query.cursor(Some(cursor)),
+            None => break,
+        }
+    }
+    Ok(match_symbols(&candidates, text))
+}
+
+/// The phrases, as stemmed words, that mention `symbol`: each of its surface
+/// forms, also without a leading article.
+fn phrases(symbol: &Symbol) -> Vec<Vec<String>> {
+    let mut phrases = Vec::new();
+    for form in symbol.surface_forms() {
+        let phrase: Vec<String> = words(form).into_iter().map(|word| word.stem).collect();
+        let unprefixed = match phrase.split_first() {
+            Some((first, rest)) if !rest.is_empty() && ARTICLES.contains(&first.as_str()) => {
+                Some(rest.to_vec())
+            }
+            _ => None,
+        };
+        phrases.extend(
+            std::iter::once(phrase)
+                .chain(unprefixed)
+                .filter(|phrase| !phrase.is_empty()),
+        );
+    }
+    phrases
+}
+
+/// The symbols among `symbols` mentioned in `text`, in order of first mention.
+///
+/// At each word the longest matching phrase is taken, and matches never
+/// overlap. A phrase shared by several symbols, such as a name two of them
+/// have, counts as a mention of each.
+pub fn match_symbols(symbols: &[Symbol], text: &str) -> Vec<ExtractedSymbol> {
+    let mut phrase_index: HashMap<Vec<String>, Vec<usize>> = HashMap::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        for phrase in phrases(symbol) {
+            let indices = phrase_index.entry(phrase).or_default();
+            if !indices.contains(&index) {
+                indices.push(index);
+            }
+        }
+    }
+    let longest = phrase_index.keys().map(Vec::len).max().unwrap_or(0);
+
+    let words = words(text);
+    let stems: Vec<String> = words.iter().map(|word| word.stem.clone()).collect();
+    let mut found: Vec<ExtractedSymbol> = Vec::new();
+    let mut positions: HashMap<usize, usize> = HashMap::new();
+
+    let mut i = 0;
+    while i < words.len() {
+        let matched = (1..=longest.min(words.len() - i))
+            .rev()
+            .find_map(|len| Some((len, phrase_index.get(&stems[i..i + len])?)));
+        let Some((len, indices)) = matched else {
+            i += 1;
+            continue;
+        };
+
+        let (start, end) = (words[i].start, words[i + len - 1].end);
+        let span = TextSpan {
+            start,
+            end,
+            text: text[start..end].to_string(),
+        };
+        for &index in indices {
+            let position = *positions.entry(index).or_insert_with(|| {
+                found.push(ExtractedSymbol {
+                    symbol: symbols[index].clone(),
+                    spans: Vec::new(),
+                });
+                found.len() - 1
+            });
+            found[position].spans.push(span.clone());
+        }
+        i += len;
+    }
+
+    found
+}
+
+struct Word {
+    start: usize,
+    end: usize,
+    stem: String,
+}
+
+/// The alphanumeric words of `text` with their byte offsets, lowercased and stemmed.
+fn words(text: &str) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (offset, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (start, c.is_alphanumeric()) {
+            (None, true) => start = Some(offset),
+            (Some(from), false) => {
+                words.push(Word {
+                    start: from,
+                    end: offset,
+                    stem: stem(&text[from..offset].to_lowercase()),
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # Graph Module
+ *
+ * Algorithms that walk the symbol relationship graph through the repository
+ * traits, so they work the same on every storage backend.
+ */
+
+pub mod network;
+pub mod path;
+
+pub use network::{related_network, NetworkNode, NetworkQuery, SymbolNetwork};
+pub use path::{find_path, PathAlgorithm, PathQuery, PathStep, SymbolPath};
+
+use crate::db::repository::{RepositoryError, RepositoryResult, SymbolRepository};
+use crate::domain::{RelationDirection, RelationType, Symbol, SymbolRelation};
+
+/// Loads the edges touching `symbol`, keeping only the requested relation types.
+///
+/// Repositories that do not support typed relations fall back to the
+/// symbol's `related_symbols`, which are treated as outgoing `related_to` edges.
+pub(crate) async fn edges_of(
+    repository: &dyn SymbolRepository,
+    symbol: &Symbol,
+    direction: RelationDirection,
+    relation_types: Option<&[RelationType]>,
+) -> RepositoryResult<Vec<SymbolRelation>> {
+    let edges = match repository.list_relations(&symbol.id, direction).await {
+        Ok(edges) => edges,
+        Err(RepositoryError::NotImplemented(_)) => match direction {
+            RelationDirection::Incoming => Vec::new(),
+            _ => symbol
+                .related_symbols
+                .iter()
+                .filter(|target| !target.is_empty() && *target != &symbol.id)
+                .map(|target| SymbolRelation::new(&symbol.id, target, RelationType::RelatedTo))
+                .collect(),
+        },
+        Err(e) => return Err(e),
+    };
+
+    Ok(edges
+        .into_iter()
+        .filter(|edge| relation_types.is_none_or(|types| types.contains(&edge.relation_type)))
+        .collect())
+}
+
+/// Fetches the symbol at the end of an edge by its exact ID, treating a
+/// missing one as a dangling reference rather than an error.
+pub(crate) async fn find_symbol(
+    repository: &dyn SymbolRepository,
+    id: &str,
+) -> RepositoryResult<Option<Symbol>> {
+    match repository.get_symbol_by_id(id).await {
+        Ok(symbol) => Ok(Some(symbol)),
+        Err(RepositoryError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
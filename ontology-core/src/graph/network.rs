@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{edges_of, find_symbol};
+use crate::db::repository::{RepositoryResult, SymbolRepository};
+use crate::domain::{RelationDirection, RelationType, Symbol, SymbolRelation};
+
+pub const DEFAULT_DEPTH: usize = 2;
+pub const MAX_DEPTH: usize = 5;
+pub const DEFAULT_MAX_NODES: usize = 50;
+pub const MAX_NODES: usize = 500;
+
+/// Options for [`related_network`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkQuery {
+    /// Number of hops to walk from the root (clamped to [`MAX_DEPTH`])
+    pub depth: usize,
+
+    /// Only follow these relation types; `None` follows every type
+    pub relation_types: Option<Vec<RelationType>>,
+
+    /// Stop adding nodes once this many have been collected (clamped to [`MAX_NODES`])
+    pub max_nodes: usize,
+
+    pub direction: RelationDirection,
+}
+
+impl Default for NetworkQuery {
+    fn default() -> Self {
+        NetworkQuery {
+            depth: DEFAULT_DEPTH,
+            relation_types: None,
+            max_nodes: DEFAULT_MAX_NODES,
+            direction: RelationDirection::Both,
+        }
+    }
+}
+
+impl NetworkQuery {
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn with_relation_types(mut self, relation_types: Vec<RelationType>) -> Self {
+        self.relation_types = Some(relation_types);
+        self
+    }
+
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    pub fn with_direction(mut self, direction: RelationDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+}
+
+/// A symbol reached during traversal, with its hop distance from the root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkNode {
+    pub symbol: Symbol,
+
+    pub depth: usize,
+}
+
+/// The neighbourhood of a symbol: every node reached and the edges between them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolNetwork {
+    pub root_id: String,
+
+    pub nodes: Vec<NetworkNode>,
+
+    pub edges: Vec<SymbolRelation>,
+
+    /// True when the node cap stopped the traversal early
+    pub truncated: bool,
+}
+
+/// Computes the N-hop neighbourhood of `root_id` with a breadth-first walk.
+///
+/// Edges pointing at symbols that no longer exist are skipped.
+pub async fn related_network(
+    repository: &dyn SymbolRepository,
+    root_id: &str,
+    query: &NetworkQuery,
+) -> RepositoryResult<SymbolNetwork> {
+    let depth = query.depth.min(MAX_DEPTH);
+    let max_nodes = query.max_nodes.clamp(1, MAX_NODES);
+    let relation_types = query.relation_types.as_deref();
+
+    let root = repository.get_symbol(root_id).await?;
+    let root_id = root.id.clone();
+
+    let mut nodes = vec![NetworkNode {
+        symbol: root.clone(),
+        depth: 0,
+    }];
+    let mut visited: HashMap<String, usize> = HashMap::from([(root.id.clone(), 0)]);
+    let mut missing: HashSet<String> = HashSet::new();
+    let mut edges = Vec::new();
+    let mut seen_edges = HashSet::new();
+    let mut truncated = false;
+
+    let mut frontier = VecDeque::from([(root, 0)]);
+
+    while let Some((symbol, level)) = frontier.pop_front() {
+        if level >= depth {
+            continue;
+        }
+
+        for edge in edges_of(repository, &symbol, query.direction, relation_types).await? {
+            let Some(other_id) = edge.other_end(&symbol.id).map(|id| id.to_string()) else {
+                continue;
+            };
+
+            if !visited.contains_key(&other_id) {
+                if missing.contains(&other_id) {
+                    continue;
+                }
+                if nodes.len() >= max_nodes {
+                    truncated = true;
+                    continue;
+                }
+
+                match find_symbol(repository, &other_id).await? {
+                    Some(other) => {
+                        visited.insert(other_id.clone(), level + 1);
+                        nodes.push(NetworkNode {
+                            symbol: other.clone(),
+                            depth: level + 1,
+                        });
+                        frontier.push_back((other, level + 1));
+                    }
+                    None => {
+                        missing.insert(other_id);
+                        continue;
+                    }
+                }
+            }
+
+            let key = (
+                edge.source_id.clone(),
+                edge.target_id.clone(),
+                edge.relation_type,
+            );
+            if seen_edges.insert(key) {
+                edges.push(edge);
+            }
+        }
+    }
+
+    Ok(SymbolNetwork {
+        root_id,
+        nodes,
+        edges,
+        truncated,
+    })
+}
@@ -0,0 +1,459 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::str::FromStr;
+
+use super::{edges_of, find_symbol};
+use crate::db::repository::{RepositoryResult, SymbolRepository};
+use crate::domain::{InterpretationLevel, RelationDirection, RelationType, Symbol, SymbolRelation};
+
+pub const DEFAULT_PATH_DEPTH: usize = 4;
+pub const MAX_PATH_DEPTH: usize = 8;
+
+/// Upper bound on the number of symbols loaded during a single search.
+pub const MAX_VISITED: usize = 2000;
+
+/// Weight assumed for relations that do not carry one.
+pub const DEFAULT_RELATION_WEIGHT: f64 = 0.5;
+
+const SNIPPET_LENGTH: usize = 160;
+
+/// How to pick the best path between two symbols.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PathAlgorithm {
+    /// Fewest hops, switching to Dijkstra if any weighted relation is encountered
+    #[default]
+    Auto,
+    /// Fewest hops, ignoring weights
+    Bfs,
+    /// Cheapest path, where stronger relations are cheaper to cross
+    Dijkstra,
+}
+
+impl PathAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Bfs => "bfs",
+            Self::Dijkstra => "dijkstra",
+        }
+    }
+}
+
+impl Display for PathAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for PathAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "bfs" | "shortest" => Ok(Self::Bfs),
+            "dijkstra" | "weighted" => Ok(Self::Dijkstra),
+            _ => Err(format!("Unknown path algorithm: {}", s)),
+        }
+    }
+}
+
+/// Options for [`find_path`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathQuery {
+    /// Maximum number of hops in the path (clamped to [`MAX_PATH_DEPTH`])
+    pub max_depth: usize,
+
+    /// Only cross these relation types; `None` crosses every type
+    pub relation_types: Option<Vec<RelationType>>,
+
+    pub direction: RelationDirection,
+
+    pub algorithm: PathAlgorithm,
+}
+
+impl Default for PathQuery {
+    fn default() -> Self {
+        PathQuery {
+            max_depth: DEFAULT_PATH_DEPTH,
+            relation_types: None,
+            direction: RelationDirection::Both,
+            algorithm: PathAlgorithm::Auto,
+        }
+    }
+}
+
+impl PathQuery {
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_relation_types(mut self, relation_types: Vec<RelationType>) -> Self {
+        self.relation_types = Some(relation_types);
+        self
+    }
+
+    pub fn with_direction(mut self, direction: RelationDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_algorithm(mut self, algorithm: PathAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+}
+
+/// One hop of a path, from `from_id` to `to_id` across `relation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathStep {
+    pub from_id: String,
+
+    pub to_id: String,
+
+    pub to_name: String,
+
+    /// The relation as stored; its direction may be opposite to the walk
+    pub relation: SymbolRelation,
+
+    /// Short interpretation of the symbol reached by this hop
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interpretation: Option<String>,
+}
+
+/// A path between two symbols.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolPath {
+    pub source_id: String,
+
+    pub target_id: String,
+
+    pub steps: Vec<PathStep>,
+
+    /// Number of hops for BFS, summed edge cost for Dijkstra
+    pub cost: f64,
+
+    /// The algorithm that produced the path (never `Auto`)
+    pub algorithm: PathAlgorithm,
+}
+
+/// Cost of crossing a relation in a weighted search.
+///
+/// Every hop costs at least 1.0 so shorter chains still win between equally
+/// strong relations, and a relation of weight 1.0 is half the price of one
+/// of weight 0.0.
+pub fn edge_cost(relation: &SymbolRelation) -> f64 {
+    let weight = relation
+        .weight
+        .unwrap_or(DEFAULT_RELATION_WEIGHT)
+        .clamp(0.0, 1.0);
+    2.0 - weight
+}
+
+/// Finds the best path from `source_id` to `target_id`.
+///
+/// Returns `Ok(None)` when the symbols are not connected within the depth limit.
+pub async fn find_path(
+    repository: &dyn SymbolRepository,
+    source_id: &str,
+    target_id: &str,
+    query: &PathQuery,
+) -> RepositoryResult<Option<SymbolPath>> {
+    let mut search = Search::new(repository, query);
+
+    let source = search.resolve(source_id).await?;
+    let target = search.resolve(target_id).await?;
+
+    let found = match query.algorithm {
+        PathAlgorithm::Bfs => search.bfs(&source, &target).await?,
+        PathAlgorithm::Dijkstra => search.dijkstra(&source, &target).await?,
+        PathAlgorithm::Auto => {
+            let found = search.bfs(&source, &target).await?;
+            if search.saw_weights {
+                search.dijkstra(&source, &target).await?.or(found)
+            } else {
+                found
+            }
+        }
+    };
+
+    let Some((edges, algorithm)) = found else {
+        return Ok(None);
+    };
+
+    let mut steps = Vec::with_capacity(edges.len());
+    let mut current = source.id.clone();
+    for edge in edges {
+        let to_id = edge
+            .other_end(&current)
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+        let to = search.load(&to_id).await?;
+        steps.push(PathStep {
+            from_id: current,
+            to_id: to_id.clone(),
+            to_name: to.as_ref().map(|s| s.name.clone()).unwrap_or_default(),
+            relation: edge,
+            interpretation: to.as_ref().and_then(interpretation_snippet),
+        });
+        current = to_id;
+    }
+
+    let cost = match algorithm {
+        PathAlgorithm::Dijkstra => steps.iter().map(|step| edge_cost(&step.relation)).sum(),
+        _ => steps.len() as f64,
+    };
+
+    Ok(Some(SymbolPath {
+        source_id: source.id,
+        target_id: target.id,
+        steps,
+        cost,
+        algorithm,
+    }))
+}
+
+/// Picks a short interpretation for a symbol, preferring universal meanings.
+pub fn interpretation_snippet(symbol: &Symbol) -> Option<String> {
+    let text = symbol
+        .interpretations
+        .get("default")
+        .filter(|layer| layer.level == InterpretationLevel::Universal)
+        .map(|layer| layer.text.as_str())
+        .or_else(|| {
+            let mut universal = symbol.interpretations_at(InterpretationLevel::Universal);
+            universal.sort_by_key(|(context, _)| *context);
+            universal.first().map(|(_, layer)| layer.text.as_str())
+        })
+        .or_else(|| {
+            let mut contexts: Vec<&String> = symbol.interpretations.keys().collect();
+            contexts.sort();
+            contexts
+                .first()
+                .map(|context| symbol.interpretations[*context].text.as_str())
+        })
+        .or(Some(symbol.description.as_str()))
+        .filter(|text| !text.trim().is_empty())?;
+
+    Some(truncate(text.trim(), SNIPPET_LENGTH))
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut snippet: String = text.chars().take(max_chars).collect();
+    snippet.push('…');
+    snippet
+}
+
+type Found = Option<(Vec<SymbolRelation>, PathAlgorithm)>;
+
+struct Search<'a> {
+    repository: &'a dyn SymbolRepository,
+    query: &'a PathQuery,
+    max_depth: usize,
+    symbols: HashMap<String, Option<Symbol>>,
+    saw_weights: bool,
+}
+
+impl<'a> Search<'a> {
+    fn new(repository: &'a dyn SymbolRepository, query: &'a PathQuery) -> Self {
+        Search {
+            repository,
+            query,
+            max_depth: query.max_depth.min(MAX_PATH_DEPTH),
+            symbols: HashMap::new(),
+            saw_weights: false,
+        }
+    }
+
+    /// Looks up a symbol the caller named, which may be an alias or a name.
+    async fn resolve(&mut self, id: &str) -> RepositoryResult<Symbol> {
+        let symbol = self.repository.get_symbol(id).await?;
+        self.symbols.insert(symbol.id.clone(), Some(symbol.clone()));
+        Ok(symbol)
+    }
+
+    async fn load(&mut self, id: &str) -> RepositoryResult<Option<Symbol>> {
+        if let Some(symbol) = self.symbols.get(id) {
+            return Ok(symbol.clone());
+        }
+        let symbol = find_symbol(self.repository, id).await?;
+        self.symbols.insert(id.to_string(), symbol.clone());
+        Ok(symbol)
+    }
+
+    /// Edges leaving `symbol` paired with the neighbour they lead to,
+    /// skipping neighbours that no longer exist.
+    async fn neighbours(
+        &mut self,
+        symbol: &Symbol,
+    ) -> RepositoryResult<Vec<(Symbol, SymbolRelation)>> {
+        let edges = edges_of(
+            self.repository,
+            symbol,
+            self.query.direction,
+            self.query.relation_types.as_deref(),
+        )
+        .await?;
+
+        let mut neighbours = Vec::with_capacity(edges.len());
+        for edge in edges {
+            let Some(other_id) = edge.other_end(&symbol.id).map(|id| id.to_string()) else {
+                continue;
+            };
+            if edge.weight.is_some() {
+                self.saw_weights = true;
+            }
+            if !self.symbols.contains_key(&other_id) && self.symbols.len() >= MAX_VISITED {
+                continue;
+            }
+            if let Some(other) = self.load(&other_id).await? {
+                neighbours.push((other, edge));
+            }
+        }
+        Ok(neighbours)
+    }
+
+    async fn bfs(&mut self, source: &Symbol, target: &Symbol) -> RepositoryResult<Found> {
+        let mut previous: HashMap<String, (String, SymbolRelation)> = HashMap::new();
+        let mut visited: HashSet<String> = HashSet::from([source.id.clone()]);
+        let mut frontier = VecDeque::from([(source.clone(), 0)]);
+
+        while let Some((symbol, depth)) = frontier.pop_front() {
+            if symbol.id == target.id {
+                return Ok(Some((
+                    rebuild(&previous, target.id.clone()),
+                    PathAlgorithm::Bfs,
+                )));
+            }
+            if depth >= self.max_depth {
+                continue;
+            }
+
+            for (other, edge) in self.neighbours(&symbol).await? {
+                if visited.insert(other.id.clone()) {
+                    previous.insert(other.id.clone(), (symbol.id.clone(), edge));
+                    frontier.push_back((other, depth + 1));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Searches over (symbol, hops taken) so that, with depth capped, a cheap
+    /// route reaching a symbol at the cap doesn't hide a costlier, shorter one
+    /// that can still go on.
+    async fn dijkstra(&mut self, source: &Symbol, target: &Symbol) -> RepositoryResult<Found> {
+        let start = (source.id.clone(), 0);
+        let mut previous: HashMap<(String, usize), ((String, usize), SymbolRelation)> =
+            HashMap::new();
+        let mut best: HashMap<(String, usize), f64> = HashMap::from([(start, 0.0)]);
+        // The fewest hops each symbol was settled with; a later, costlier
+        // arrival is only worth expanding if it took fewer
+        let mut settled: HashMap<String, usize> = HashMap::new();
+        let mut heap = BinaryHeap::from([Candidate {
+            cost: 0.0,
+            depth: 0,
+            symbol: source.clone(),
+        }]);
+
+        while let Some(Candidate {
+            cost,
+            depth,
+            symbol,
+        }) = heap.pop()
+        {
+            if settled.get(&symbol.id).is_some_and(|hops| *hops <= depth) {
+                continue;
+            }
+            settled.insert(symbol.id.clone(), depth);
+            if symbol.id == target.id {
+                return Ok(Some((
+                    rebuild(&previous, (target.id.clone(), depth)),
+                    PathAlgorithm::Dijkstra,
+                )));
+            }
+            if depth >= self.max_depth {
+                continue;
+            }
+
+            for (other, edge) in self.neighbours(&symbol).await? {
+                if settled
+                    .get(&other.id)
+                    .is_some_and(|hops| *hops <= depth + 1)
+                {
+                    continue;
+                }
+                let next = (other.id.clone(), depth + 1);
+                let next_cost = cost + edge_cost(&edge);
+                if best.get(&next).is_none_or(|known| next_cost < *known) {
+                    best.insert(next.clone(), next_cost);
+                    previous.insert(next, ((symbol.id.clone(), depth), edge));
+                    heap.push(Candidate {
+                        cost: next_cost,
+                        depth: depth + 1,
+                        symbol: other,
+                    });
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Walks the predecessor links back from `target` to the source.
+fn rebuild<K: Eq + Hash>(
+    previous: &HashMap<K, (K, SymbolRelation)>,
+    target: K,
+) -> Vec<SymbolRelation> {
+    let mut edges = Vec::new();
+    let mut current = &target;
+    while let Some((from, edge)) = previous.get(current) {
+        edges.push(edge.clone());
+        current = from;
+    }
+    edges.reverse();
+    edges
+}
+
+/// Heap entry ordered so the cheapest candidate is popped first.
+struct Candidate {
+    cost: f64,
+    depth: usize,
+    symbol: Symbol,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.depth.cmp(&self.depth))
+    }
+}
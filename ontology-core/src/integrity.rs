@@ -0,0 +1,270 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # Referential Integrity
+ *
+ * Symbols point at other symbols through `related_symbols` (which the
+ * repositories keep in step with the outgoing typed relations), and symbol
+ * sets through their members. This module keeps those references honest:
+ *
+ * - On write, the repositories look up the targets a symbol or set names and
+ *   hand the unknown ones to [`IntegrityMode::check_targets`], which rejects
+ *   the write in [`IntegrityMode::Strict`] mode and logs them otherwise.
+ * - On delete, every backend clears the references to the deleted symbol from
+ *   the rest of the ontology.
+ * - [`check`] and [`check_repositories`] report the dangling references,
+ *   self-loops and duplicate links already stored.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+use crate::db::repository::interfaces::{
+    RepositoryError, RepositoryResult, SymbolRepository, SymbolSetRepository,
+};
+use crate::db::repository::pagination::ListQuery;
+use crate::domain::{Symbol, SymbolSet};
+
+/// How writes naming symbols that do not exist are treated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityMode {
+    /// Reject the write with a validation error
+    Strict,
+    /// Log a warning and store what can be stored: unknown related symbols are
+    /// kept, unknown set members are skipped
+    #[default]
+    Lenient,
+}
+
+impl IntegrityMode {
+    /// Applies the mode to the `missing` targets of `owner`, a description such
+    /// as "Symbol sun", whose `field` named them.
+    pub fn check_targets(
+        self,
+        owner: &str,
+        field: &str,
+        missing: &[String],
+    ) -> RepositoryResult<()> {
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        match self {
+            IntegrityMode::Strict => Err(RepositoryError::Validation(format!(
+                "{} names unknown {}: {}",
+                owner,
+                field,
+                missing.join(", ")
+            ))),
+            IntegrityMode::Lenient => {
+                warn!("{} names unknown {}: {}", owner, field, missing.join(", "));
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for IntegrityMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IntegrityMode::Strict => "strict",
+            IntegrityMode::Lenient => "lenient",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for IntegrityMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "lenient" => Ok(Self::Lenient),
+            _ => Err(format!("Unknown integrity mode: {}", s)),
+        }
+    }
+}
+
+/// The IDs in `targets` that are not `known`, once each, in the order they
+/// first appear.
+pub fn missing_targets<'a>(
+    targets: impl IntoIterator<Item = &'a String>,
+    known: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for target in targets {
+        if !known(target) && !missing.contains(target) {
+            missing.push(target.clone());
+        }
+    }
+    missing
+}
+
+/// Where a reference is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// An entry of a symbol's `related_symbols`
+    RelatedSymbol,
+    /// A member of a symbol set
+    SetMember,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// The target names no symbol
+    Dangling,
+    /// A symbol refers to itself
+    SelfLoop,
+    /// The same target is listed more than once
+    Duplicate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    pub kind: IssueKind,
+
+    pub reference: ReferenceKind,
+
+    /// The symbol or set holding the reference.
+    pub owner_id: String,
+
+    pub target_id: String,
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            IssueKind::Dangling => "dangling",
+            IssueKind::SelfLoop => "self-loop",
+            IssueKind::Duplicate => "duplicate",
+        };
+        let reference = match self.reference {
+            ReferenceKind::RelatedSymbol => "related symbol",
+            ReferenceKind::SetMember => "set member",
+        };
+        write!(
+            f,
+            "{} {}: {} -> {}",
+            kind, reference, self.owner_id, self.target_id
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub symbols_checked: usize,
+
+    pub symbol_sets_checked: usize,
+
+    /// Issues by owner, in the order the symbols and then the sets were given.
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn count(&self, kind: IssueKind) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind == kind)
+            .count()
+    }
+}
+
+impl fmt::Display for IntegrityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "  {}", issue)?;
+        }
+        write!(
+            f,
+            "Checked {} symbol(s) and {} symbol set(s): {} dangling reference(s), \
+             {} self-loop(s), {} duplicate(s)",
+            self.symbols_checked,
+            self.symbol_sets_checked,
+            self.count(IssueKind::Dangling),
+            self.count(IssueKind::SelfLoop),
+            self.count(IssueKind::Duplicate)
+        )
+    }
+}
+
+/// Finds the dangling references, self-loops and duplicate links among
+/// `symbols` and `sets`; a reference dangles unless `symbols` holds its target.
+pub fn check(symbols: &[Symbol], sets: &[SymbolSet]) -> IntegrityReport {
+    let known: HashSet<&str> = symbols.iter().map(|s| s.id.as_str()).collect();
+    let mut report = IntegrityReport {
+        symbols_checked: symbols.len(),
+        symbol_sets_checked: sets.len(),
+        issues: Vec::new(),
+    };
+
+    for symbol in symbols {
+        check_targets(
+            &mut report,
+            ReferenceKind::RelatedSymbol,
+            &symbol.id,
+            &symbol.related_symbols,
+            &known,
+        );
+    }
+    for set in sets {
+        check_targets(
+            &mut report,
+            ReferenceKind::SetMember,
+            &set.id,
+            &set.symbol_ids(),
+            &known,
+        );
+    }
+
+    report
+}
+
+fn check_targets(
+    report: &mut IntegrityReport,
+    reference: ReferenceKind,
+    owner_id: &str,
+    targets: &[String],
+    known: &HashSet<&str>,
+) {
+    let mut seen = HashSet::new();
+    for target in targets {
+        let kind = if !seen.insert(target) {
+            IssueKind::Duplicate
+        } else if target == owner_id && reference == ReferenceKind::RelatedSymbol {
+            IssueKind::SelfLoop
+        } else if !known.contains(target.as_str()) {
+            IssueKind::Dangling
+        } else {
+            continue;
+        };
+
+        report.issues.push(IntegrityIssue {
+            kind,
+            reference,
+            owner_id: owner_id.to_string(),
+            target_id: target.clone(),
+        });
+    }
+}
+
+/// Runs [`check`] over everything the repositories hold.
+pub async fn check_repositories(
+    symbols: &dyn SymbolRepository,
+    sets: &dyn SymbolSetRepository,
+) -> RepositoryResult<IntegrityReport> {
+    let symbols = symbols.list_symbols(&ListQuery::new()).await?.items;
+    let sets = sets.list_symbol_sets(&ListQuery::new()).await?.items;
+    Ok(check(&symbols, &sets))
+}
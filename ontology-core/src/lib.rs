@@ -13,6 +13,11 @@
 
 pub mod db;
 pub mod domain;
+pub mod embedding;
+pub mod extraction;
+pub mod graph;
+pub mod integrity;
+pub mod report;
 pub mod utils;
 
 // Re-export key components for convenient usage
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MPL-2.0 OR Commercial
+// Copyright (c) 2024 Symbol Ontology Contributors
+
+/*!
+ * # Dream Reports
+ *
+ * Turns a dream narrative into a structured report in one pass: the symbols
+ * it mentions (see [`crate::extraction`]), the interpretation of each that
+ * best fits the dreamer, the relations between the symbols found, the
+ * categories they lean towards and the emotional tone they carry.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::db::repository::interfaces::{RepositoryResult, SymbolRepository};
+use crate::domain::{InterpretationLayer, RelationDirection, Symbol, SymbolRelation};
+use crate::extraction::{self, TextSpan};
+use crate::graph::edges_of;
+
+/// Property holding a symbol's emotional tone ("positive", "negative", ...).
+pub const EMOTIONAL_TONE: &str = "emotional_tone";
+
+/// What is known about the dreamer, to tailor the report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DreamContext {
+    /// Tradition whose readings are preferred, such as "jungian" or "celtic".
+    pub culture: Option<String>,
+
+    /// How the dream felt to the dreamer, compared with the symbols' tones.
+    pub emotional_tone: Option<String>,
+}
+
+impl DreamContext {
+    pub fn with_culture(mut self, culture: &str) -> Self {
+        self.culture = Some(culture.to_string());
+        self
+    }
+
+    pub fn with_emotional_tone(mut self, tone: &str) -> Self {
+        self.emotional_tone = Some(tone.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DreamReport {
+    /// Symbols mentioned in the dream, in order of first mention.
+    pub symbols: Vec<ReportedSymbol>,
+
+    /// Relations among the symbols mentioned, ordered by source and target.
+    pub relationships: Vec<SymbolRelation>,
+
+    /// How many of the symbols fall in each category, most common first.
+    pub dominant_categories: Vec<CategoryCount>,
+
+    pub tone: ToneSummary,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportedSymbol {
+    pub symbol: Symbol,
+    pub spans: Vec<TextSpan>,
+
+    /// The interpretation chosen by [`Symbol::interpretation_for`], if any.
+    pub interpretation: Option<SelectedInterpretation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectedInterpretation {
+    pub context: String,
+    pub layer: InterpretationLayer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryCount {
+    pub category: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToneSummary {
+    /// Number of symbols carrying each tone.
+    pub counts: BTreeMap<String, usize>,
+
+    /// The tone most symbols carry; `None` when none carry one or two tones tie.
+    pub dominant: Option<String>,
+
+    /// Whether the dominant tone is the one the dreamer reported, when both are known.
+    pub matches_context: Option<bool>,
+}
+
+/// Builds a report on the symbols `text` mentions, read for the dreamer in `context`.
+pub async fn interpret_dream(
+    repository: &dyn SymbolRepository,
+    text: &str,
+    context: &DreamContext,
+) -> RepositoryResult<DreamReport> {
+    let extracted = extraction::extract_symbols_from_text(repository, text).await?;
+
+    let ids: HashSet<&str> = extracted.iter().map(|e| e.symbol.id.as_str()).collect();
+    let mut relationships = Vec::new();
+    let mut seen = HashSet::new();
+    for found in &extracted {
+        for edge in edges_of(repository, &found.symbol, RelationDirection::Outgoing, None).await? {
+            if edge.source_id == edge.target_id || !ids.contains(edge.target_id.as_str()) {
+                continue;
+            }
+            // A symmetric relation stored both ways is one relationship
+            let mut ends = (edge.source_id.clone(), edge.target_id.clone());
+            if edge.relation_type.is_symmetric() && ends.0 > ends.1 {
+                ends = (ends.1, ends.0);
+            }
+            if seen.insert((ends, edge.relation_type)) {
+                relationships.push(edge);
+            }
+        }
+    }
+    relationships.sort_by(|a, b| {
+        (&a.source_id, &a.target_id, a.relation_type.as_str()).cmp(&(
+            &b.source_id,
+            &b.target_id,
+            b.relation_type.as_str(),
+        ))
+    });
+
+    let mut categories: BTreeMap<&str, usize> = BTreeMap::new();
+    for found in &extracted {
+        *categories
+            .entry(found.symbol.category.as_str())
+            .or_default() += 1;
+    }
+    let mut dominant_categories: Vec<CategoryCount> = categories
+        .into_iter()
+        .map(|(category, count)| CategoryCount {
+            category: category.to_string(),
+            count,
+        })
+        .collect();
+    dominant_categories.sort_by_key(|c| std::cmp::Reverse(c.count));
+
+    let tone = summarize_tone(
+        extracted.iter().map(|e| &e.symbol),
+        context.emotional_tone.as_deref(),
+    );
+
+    let symbols = extracted
+        .into_iter()
+        .map(|found| {
+            let interpretation = found
+                .symbol
+                .interpretation_for(context.culture.as_deref())
+                .map(|(context, layer)| SelectedInterpretation {
+                    context: context.to_string(),
+                    layer: layer.clone(),
+                });
+            ReportedSymbol {
+                symbol: found.symbol,
+                spans: found.spans,
+                interpretation,
+            }
+        })
+        .collect();
+
+    Ok(DreamReport {
+        symbols,
+        relationships,
+        dominant_categories,
+        tone,
+    })
+}
+
+/// Counts the [`EMOTIONAL_TONE`]s of `symbols`, ignoring case, and compares the
+/// dominant one with the tone the dreamer reported.
+pub fn summarize_tone<'a>(
+    symbols: impl IntoIterator<Item = &'a Symbol>,
+    reported: Option<&str>,
+) -> ToneSummary {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for symbol in symbols {
+        if let Some(tone) = symbol.properties.get(EMOTIONAL_TONE) {
+            let tone = tone.trim().to_lowercase();
+            if !tone.is_empty() {
+                *counts.entry(tone).or_default() += 1;
+            }
+        }
+    }
+
+    let most = counts.values().copied().max().unwrap_or(0);
+    let mut leaders = counts.iter().filter(|(_, count)| **count == most);
+    let dominant = match (leaders.next(), leaders.next()) {
+        (Some((tone, _)), None) => Some(tone.clone()),
+        _ => None,
+    };
+
+    let matches_context = match (&dominant, reported) {
+        (Some(dominant), Some(reported)) if !reported.trim().is_empty() => {
+            Some(dominant.eq_ignore_ascii_case(reported.trim()))
+        }
+        _ => None,
+    };
+
+    ToneSummary {
+        counts,
+        dominant,
+        matches_context,
+    }
+}
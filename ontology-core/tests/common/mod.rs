@@ -1,5 +1,4 @@
 pub mod fixtures;
-pub mod mock_repository;
 
 /// Initialize test environment
 pub fn setup() {
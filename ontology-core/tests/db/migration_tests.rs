@@ -0,0 +1,275 @@
+use ontology_core::db::migrations::{Migrator, POSTGRES_MIGRATIONS, SQLITE_MIGRATIONS};
+use ontology_core::db::{sqlite, DatabasePool, DbError};
+use ontology_core::domain::RelationDirection;
+
+async fn empty_sqlite() -> DatabasePool {
+    DatabasePool::connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory SQLite database")
+}
+
+async fn table_exists(pool: &DatabasePool, table: &str) -> bool {
+    let DatabasePool::Sqlite(pool) = pool else {
+        panic!("expected a SQLite pool");
+    };
+
+    sqlx::query("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1")
+        .bind(table)
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+        .is_some()
+}
+
+#[test]
+fn test_migrations_are_ordered_and_match_across_backends() {
+    for migrations in [POSTGRES_MIGRATIONS, SQLITE_MIGRATIONS] {
+        assert!(migrations
+            .windows(2)
+            .all(|pair| pair[0].version < pair[1].version));
+        assert!(migrations
+            .iter()
+            .all(|m| m.up.is_empty() == m.down.is_empty()));
+    }
+
+    let versions = |migrations: &[ontology_core::db::migrations::Migration]| {
+        migrations
+            .iter()
+            .map(|m| (m.version, m.name))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(versions(POSTGRES_MIGRATIONS), versions(SQLITE_MIGRATIONS));
+}
+
+#[tokio::test]
+async fn test_init_applies_every_migration_once() {
+    let pool = empty_sqlite().await;
+    let migrator = Migrator::new(&pool);
+    assert_eq!(migrator.current_version().await.unwrap(), 0);
+
+    pool.init().await.unwrap();
+    assert_eq!(
+        migrator.current_version().await.unwrap(),
+        migrator.latest_version()
+    );
+    assert!(migrator
+        .status()
+        .await
+        .unwrap()
+        .iter()
+        .all(|status| status.applied));
+
+    // A second start-up has nothing left to do
+    assert!(migrator.up(None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_down_and_up_again() {
+    let pool = empty_sqlite().await;
+    let migrator = Migrator::new(&pool);
+    pool.init().await.unwrap();
+
+    let reverted = migrator.down(1).await.unwrap();
+    assert_eq!(
+        reverted.iter().map(|m| m.version).collect::<Vec<_>>(),
+        vec![11, 10, 9, 8, 7, 6, 5, 4, 3, 2]
+    );
+    assert_eq!(migrator.current_version().await.unwrap(), 1);
+    assert!(table_exists(&pool, "symbols").await);
+    assert!(!table_exists(&pool, "symbol_relations").await);
+    assert!(!table_exists(&pool, "archetypes").await);
+
+    let applied = migrator.up(Some(2)).await.unwrap();
+    assert_eq!(
+        applied.iter().map(|m| m.version).collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert!(table_exists(&pool, "symbol_relations").await);
+    assert!(!table_exists(&pool, "archetypes").await);
+
+    migrator.down(0).await.unwrap();
+    assert!(!table_exists(&pool, "symbols").await);
+    assert_eq!(migrator.current_version().await.unwrap(), 0);
+
+    assert!(matches!(
+        migrator.up(Some(42)).await,
+        Err(DbError::Migration(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_future_schema_version_is_refused() {
+    let pool = empty_sqlite().await;
+    pool.init().await.unwrap();
+
+    let DatabasePool::Sqlite(sqlite_pool) = &pool else {
+        unreachable!();
+    };
+    sqlx::query("INSERT INTO schema_migrations (version, name) VALUES (99, 'from_the_future')")
+        .execute(sqlite_pool)
+        .await
+        .unwrap();
+
+    let result = pool.init().await;
+    assert!(
+        matches!(&result, Err(DbError::Migration(msg)) if msg.contains("99")),
+        "expected a migration error, got {:?}",
+        result
+    );
+    assert!(Migrator::new(&pool).down(0).await.is_err());
+}
+
+#[tokio::test]
+async fn test_untracked_database_is_adopted() {
+    let pool = empty_sqlite().await;
+    let DatabasePool::Sqlite(sqlite_pool) = &pool else {
+        unreachable!();
+    };
+
+    // A database created before migrations were tracked, with data in it
+    for statement in [
+        sqlite::schema::CREATE_SYMBOLS_TABLE,
+        sqlite::schema::CREATE_SYMBOL_SETS_TABLE,
+    ] {
+        sqlx::query(statement).execute(sqlite_pool).await.unwrap();
+    }
+    sqlx::query(
+        "INSERT INTO symbols (id, name, category, description, related_symbols) \
+         VALUES ('moon', 'Moon', 'nature', 'Cycles', '[\"sun\"]')",
+    )
+    .execute(sqlite_pool)
+    .await
+    .unwrap();
+
+    pool.init().await.unwrap();
+
+    let repo = pool.repository_factory().create_symbol_repository();
+    let moon = repo.get_symbol("moon").await.unwrap();
+    assert_eq!(moon.related_symbols, vec!["sun"]);
+    assert_eq!(
+        repo.list_relations("moon", RelationDirection::Outgoing)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_set_members_move_to_their_own_table() {
+    let pool = empty_sqlite().await;
+    let migrator = Migrator::new(&pool);
+    migrator.up(Some(7)).await.unwrap();
+
+    let DatabasePool::Sqlite(sqlite_pool) = &pool else {
+        unreachable!();
+    };
+    for statement in [
+        "INSERT INTO symbols (id, name, category, description) \
+         VALUES ('sun', 'Sun', 'nature', 'Light'), ('moon', 'Moon', 'nature', 'Cycles')",
+        "INSERT INTO symbol_sets (id, name, category, description, symbols) \
+         VALUES ('lights', 'Lights', 'nature', 'Sun and moon', \
+                 '{\"sun\": null, \"moon\": null, \"comet\": null}')",
+    ] {
+        sqlx::query(statement).execute(sqlite_pool).await.unwrap();
+    }
+
+    migrator.up(None).await.unwrap();
+    assert!(table_exists(&pool, "symbol_set_members").await);
+
+    let repo = pool.repository_factory().create_symbol_set_repository();
+    let mut members: Vec<String> = repo
+        .get_symbol_set("lights")
+        .await
+        .unwrap()
+        .symbols
+        .into_keys()
+        .collect();
+    members.sort();
+    assert_eq!(members, vec!["moon", "sun"]);
+
+    migrator.down(7).await.unwrap();
+    let symbols: String = sqlx::query_scalar("SELECT symbols FROM symbol_sets WHERE id = 'lights'")
+        .fetch_one(sqlite_pool)
+        .await
+        .unwrap();
+    let symbols: serde_json::Value = serde_json::from_str(&symbols).unwrap();
+    assert_eq!(symbols, serde_json::json!({ "moon": null, "sun": null }));
+    assert!(!table_exists(&pool, "symbol_set_members").await);
+}
+
+#[tokio::test]
+async fn test_aliased_symbols_are_embedded_again() {
+    let pool = empty_sqlite().await;
+    let migrator = Migrator::new(&pool);
+    migrator.up(Some(8)).await.unwrap();
+
+    let DatabasePool::Sqlite(sqlite_pool) = &pool else {
+        unreachable!();
+    };
+    sqlx::query(
+        "INSERT INTO symbols (id, name, category, description, aliases, embedding) \
+         VALUES ('sun', 'Sun', 'nature', 'Light', '[\"Sol\"]', '[1.0]'), \
+                ('moon', 'Moon', 'nature', 'Cycles', '[]', '[1.0]')",
+    )
+    .execute(sqlite_pool)
+    .await
+    .unwrap();
+
+    let unembedded = |pool| async move {
+        let mut ids: Vec<String> =
+            sqlx::query_scalar("SELECT id FROM symbols WHERE embedding IS NULL")
+                .fetch_all(pool)
+                .await
+                .unwrap();
+        ids.sort();
+        ids
+    };
+    migrator.up(None).await.unwrap();
+    assert_eq!(unembedded(sqlite_pool).await, vec!["sun"]);
+
+    // Alias edits now drop the embedding too
+    sqlx::query("UPDATE symbols SET aliases = '[\"Luna\"]' WHERE id = 'moon'")
+        .execute(sqlite_pool)
+        .await
+        .unwrap();
+    assert_eq!(unembedded(sqlite_pool).await, vec!["moon", "sun"]);
+}
+
+#[tokio::test]
+async fn test_existing_symbols_get_lookup_keys() {
+    let pool = empty_sqlite().await;
+    let migrator = Migrator::new(&pool);
+    migrator.up(Some(9)).await.unwrap();
+
+    let DatabasePool::Sqlite(sqlite_pool) = &pool else {
+        unreachable!();
+    };
+    sqlx::query(
+        "INSERT INTO symbols (id, name, category, description, aliases, localized_names) \
+         VALUES ('sun', 'Sun', 'nature', 'Light', '[\"Ήλιος\"]', '{\"ja\": \"太陽\"}')",
+    )
+    .execute(sqlite_pool)
+    .await
+    .unwrap();
+
+    migrator.up(None).await.unwrap();
+
+    let repo = pool.repository_factory().create_symbol_repository();
+    for key in ["ήλιος", "太陽", "SUN"] {
+        assert_eq!(repo.get_symbol(key).await.unwrap().id, "sun");
+    }
+
+    migrator.down(9).await.unwrap();
+    assert!(!table_exists(&pool, "symbol_lookup_keys").await);
+}
+
+#[tokio::test]
+async fn test_memory_backend_has_no_migrations() {
+    let pool = DatabasePool::memory(None).unwrap();
+    let migrator = Migrator::new(&pool);
+
+    assert!(migrator.migrations().is_empty());
+    assert!(migrator.up(None).await.unwrap().is_empty());
+    assert_eq!(migrator.current_version().await.unwrap(), 0);
+}
@@ -1 +1,2 @@
+pub mod migration_tests;
 pub mod repository;
@@ -0,0 +1,50 @@
+use ontology_core::db::repository::{conformance, InMemoryRepositoryFactory};
+use ontology_core::db::DatabasePool;
+use ontology_core::integrity::IntegrityMode;
+
+#[tokio::test]
+async fn test_memory_backend_conformance() {
+    conformance::run_all(&InMemoryRepositoryFactory::new()).await;
+    conformance::strict_integrity(
+        &InMemoryRepositoryFactory::new().with_integrity(IntegrityMode::Strict),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_sqlite_backend_conformance() {
+    let pool = DatabasePool::connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory SQLite database");
+    pool.init().await.expect("Failed to create SQLite schema");
+
+    conformance::run_all(pool.repository_factory().as_ref()).await;
+    conformance::strict_integrity(
+        pool.repository_factory_with_integrity(IntegrityMode::Strict)
+            .as_ref(),
+    )
+    .await;
+}
+
+/// Runs against a real PostgreSQL database when `TEST_DATABASE_URL` is set.
+#[tokio::test]
+async fn test_postgres_backend_conformance() {
+    let Ok(database_url) = std::env::var("TEST_DATABASE_URL") else {
+        eprintln!("TEST_DATABASE_URL not set, skipping PostgreSQL conformance");
+        return;
+    };
+
+    let pool = DatabasePool::connect(&database_url)
+        .await
+        .expect("Failed to connect to PostgreSQL");
+    pool.init()
+        .await
+        .expect("Failed to create PostgreSQL schema");
+
+    conformance::run_all(pool.repository_factory().as_ref()).await;
+    conformance::strict_integrity(
+        pool.repository_factory_with_integrity(IntegrityMode::Strict)
+            .as_ref(),
+    )
+    .await;
+}
@@ -0,0 +1,158 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde_json::json;
+
+use ontology_core::db::repository::batch::MAX_BATCH_OPERATIONS;
+use ontology_core::db::repository::{
+    ApiClient, HttpRepositoryFactory, ListQuery, RepositoryError, RepositoryFactory,
+};
+use ontology_core::domain::Symbol;
+
+/// Serves `app` on a free local port and returns its base URL.
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn error_body(status: StatusCode, message: &str) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        status,
+        Json(json!({
+            "statusCode": status.as_u16(),
+            "error": status.canonical_reason(),
+            "message": message,
+        })),
+    )
+}
+
+#[test]
+fn test_api_url_gets_api_path() {
+    for url in [
+        "http://localhost:8080",
+        "http://localhost:8080/",
+        "http://localhost:8080/api/v2",
+        "http://localhost:8080/api/v2/",
+        "http://localhost:8080/api/v2?debug=1",
+    ] {
+        let client = ApiClient::new(url).unwrap();
+        assert_eq!(
+            client.base_url().as_str(),
+            "http://localhost:8080/api/v2",
+            "{}",
+            url
+        );
+    }
+
+    let client = ApiClient::new("https://example.com/ontology").unwrap();
+    assert_eq!(
+        client.base_url().as_str(),
+        "https://example.com/ontology/api/v2"
+    );
+}
+
+#[test]
+fn test_api_url_must_be_http() {
+    for url in ["localhost:8080", "ftp://example.com", "not a url"] {
+        assert!(
+            matches!(ApiClient::new(url), Err(RepositoryError::Validation(_))),
+            "{}",
+            url
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_error_responses_map_to_repository_errors() {
+    let app = Router::new().route(
+        "/api/v2/symbols/{id}",
+        get(|Path(id): Path<String>| async move {
+            match id.as_str() {
+                "taken" => error_body(StatusCode::CONFLICT, "Symbol already exists"),
+                "bad" => error_body(StatusCode::BAD_REQUEST, "Symbol name cannot be empty"),
+                "todo" => error_body(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Not implemented: lookups",
+                ),
+                "broken" => error_body(StatusCode::INTERNAL_SERVER_ERROR, "Database is down"),
+                _ => error_body(StatusCode::NOT_FOUND, &format!("Symbol not found: {}", id)),
+            }
+        }),
+    );
+    let factory = HttpRepositoryFactory::new(&serve(app).await).unwrap();
+    let repo = factory.create_symbol_repository();
+
+    match repo.get_symbol("taken").await {
+        Err(RepositoryError::Conflict(msg)) => assert_eq!(msg, "Symbol already exists"),
+        other => panic!("Expected Conflict, got {:?}", other),
+    }
+    match repo.get_symbol("bad").await {
+        Err(RepositoryError::Validation(msg)) => assert_eq!(msg, "Symbol name cannot be empty"),
+        other => panic!("Expected Validation, got {:?}", other),
+    }
+    match repo.get_symbol("todo").await {
+        Err(RepositoryError::NotImplemented(msg)) => assert_eq!(msg, "lookups"),
+        other => panic!("Expected NotImplemented, got {:?}", other),
+    }
+    match repo.get_symbol("broken").await {
+        Err(RepositoryError::Internal(msg)) => assert!(msg.contains("Database is down"), "{}", msg),
+        other => panic!("Expected Internal, got {:?}", other),
+    }
+
+    // IDs are sent as a single path segment, whatever they contain
+    match repo.get_symbol("a/b c").await {
+        Err(RepositoryError::NotFound(msg)) => assert_eq!(msg, "Symbol not found: a/b c"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_unreachable_server_is_internal_error() {
+    // Bind and drop a listener so nothing is serving on the port
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let factory = HttpRepositoryFactory::new(&format!("http://{}", addr)).unwrap();
+    let result = factory
+        .create_symbol_set_repository()
+        .list_symbol_sets(&ListQuery::new())
+        .await;
+    assert!(
+        matches!(result, Err(RepositoryError::Internal(_))),
+        "{:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_oversized_batches_are_refused_before_sending() {
+    // Nothing is serving, so only a check made before sending can answer
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let factory = HttpRepositoryFactory::new(&format!("http://{}", addr)).unwrap();
+    let symbols = (0..=MAX_BATCH_OPERATIONS)
+        .map(|i| {
+            Symbol::new(
+                format!("symbol-{}", i),
+                format!("Symbol {}", i),
+                "dream".to_string(),
+                String::new(),
+            )
+        })
+        .collect();
+    let result = factory
+        .create_symbol_repository()
+        .upsert_many(symbols)
+        .await;
+    assert!(
+        matches!(&result, Err(RepositoryError::Validation(msg)) if msg.contains("1000")),
+        "{:?}",
+        result
+    );
+}
@@ -0,0 +1,345 @@
+use ontology_core::db::memory::{ilike_contains, Snapshot};
+use ontology_core::db::repository::{
+    InMemoryRepositoryFactory, ListQuery, Page, RepositoryError, RepositoryFactory,
+};
+use ontology_core::db::{DatabaseBackend, DatabasePool};
+use ontology_core::domain::{RelationDirection, RelationType, SearchHit, Symbol, SymbolSet};
+
+const SNAPSHOT: &str = r#"{
+    "symbols": [
+        {
+            "id": "water",
+            "name": "Water",
+            "category": "nature",
+            "description": "Emotions and the unconscious",
+            "interpretations": {"jungian": "The unconscious"},
+            "related_symbols": ["river"]
+        },
+        {
+            "id": "river",
+            "name": "River",
+            "category": "nature",
+            "description": "The flow of life",
+            "interpretations": {},
+            "related_symbols": []
+        },
+        {
+            "id": "snake",
+            "name": "Snake",
+            "category": "animal",
+            "description": "Transformation, 100% renewal",
+            "interpretations": {},
+            "related_symbols": []
+        }
+    ],
+    "symbol_sets": [
+        {"id": "elements", "name": "Elements", "category": "nature", "symbols": ["water", "river"]}
+    ],
+    "relations": [
+        {"source_id": "river", "target_id": "water", "relation_type": "transforms_into", "weight": 0.5}
+    ],
+    "archetypes": [
+        {"id": "trickster", "name": "Trickster", "description": "Disrupts order", "parent_id": "shadow"},
+        {"id": "shadow", "name": "Shadow", "description": "The hidden self"}
+    ],
+    "symbol_archetypes": [
+        {"symbol_id": "snake", "archetype_id": "trickster"}
+    ]
+}"#;
+
+fn factory() -> InMemoryRepositoryFactory {
+    let snapshot = Snapshot::from_json(SNAPSHOT).expect("Snapshot should parse");
+    InMemoryRepositoryFactory::from_snapshot(snapshot).expect("Snapshot should load")
+}
+
+fn symbol(id: &str, name: &str, category: &str) -> Symbol {
+    Symbol::new(
+        id.to_string(),
+        name.to_string(),
+        category.to_string(),
+        format!("The {} symbol", name),
+    )
+}
+
+#[test]
+fn test_ilike_contains() {
+    assert!(ilike_contains("Water", "WAT"));
+    assert!(ilike_contains("Water", ""));
+    assert!(ilike_contains("Water", "w_ter"));
+    assert!(ilike_contains("Deep water", "deep%er"));
+    assert!(!ilike_contains("Water", "wx"));
+    assert!(!ilike_contains("Water", "w__ter"));
+
+    // Escaped wildcards only match themselves
+    assert!(ilike_contains("100% renewal", "100\\%"));
+    assert!(!ilike_contains("1000 renewals", "100\\%"));
+    assert!(!ilike_contains("snake", "\\_"));
+}
+
+#[test]
+fn test_memory_backend_parsing() {
+    assert_eq!(
+        "memory".parse::<DatabaseBackend>().unwrap(),
+        DatabaseBackend::Memory
+    );
+    assert_eq!(
+        "Postgres".parse::<DatabaseBackend>().unwrap(),
+        DatabaseBackend::Postgres
+    );
+    assert!("mysql".parse::<DatabaseBackend>().is_err());
+    assert_eq!(
+        DatabaseBackend::from_url("memory:").unwrap(),
+        DatabaseBackend::Memory
+    );
+}
+
+#[tokio::test]
+async fn test_snapshot_loads_all_tables() {
+    let factory = factory();
+    let symbols = factory.create_symbol_repository();
+
+    let water = symbols.get_symbol("water").await.unwrap();
+    assert_eq!(water.interpretations["jungian"].text, "The unconscious");
+
+    // related_symbols and explicit relations both end up as typed edges
+    let outgoing = symbols
+        .list_relations("water", RelationDirection::Outgoing)
+        .await
+        .unwrap();
+    assert_eq!(outgoing.len(), 1);
+    assert_eq!(outgoing[0].relation_type, RelationType::RelatedTo);
+
+    let incoming = symbols
+        .list_relations("water", RelationDirection::Incoming)
+        .await
+        .unwrap();
+    assert_eq!(incoming[0].source_id, "river");
+    assert_eq!(incoming[0].weight, Some(0.5));
+    assert_eq!(
+        symbols.get_symbol("river").await.unwrap().related_symbols,
+        vec!["water"]
+    );
+
+    let set = factory
+        .create_symbol_set_repository()
+        .get_symbol_set("elements")
+        .await
+        .unwrap();
+    assert_eq!(set.symbols.len(), 2);
+    assert_eq!(set.symbols["water"].name, "Water");
+
+    // Archetypes may reference parents listed later in the snapshot
+    let archetypes = factory.create_archetype_repository();
+    let names: Vec<String> = archetypes
+        .list_archetypes()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|a| a.name)
+        .collect();
+    assert_eq!(names, vec!["Shadow", "Trickster"]);
+
+    let linked = archetypes
+        .list_symbols_by_archetype("shadow", true, &ListQuery::new())
+        .await
+        .unwrap();
+    assert_eq!(linked.items[0].id, "snake");
+}
+
+#[test]
+fn test_invalid_snapshot_is_rejected() {
+    let duplicate = Snapshot::from_json(
+        r#"{"symbols": [
+            {"id": "a", "name": "A", "category": "c", "description": "",
+             "interpretations": {}, "related_symbols": []},
+            {"id": "a", "name": "A", "category": "c", "description": "",
+             "interpretations": {}, "related_symbols": []}
+        ]}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        InMemoryRepositoryFactory::from_snapshot(duplicate),
+        Err(RepositoryError::Conflict(_))
+    ));
+
+    let dangling = Snapshot::from_json(
+        r#"{"archetypes": [{"id": "a", "name": "A", "description": "", "parent_id": "missing"}]}"#,
+    )
+    .unwrap();
+    assert!(InMemoryRepositoryFactory::from_snapshot(dangling).is_err());
+}
+
+#[tokio::test]
+async fn test_search_and_category_filter() {
+    let symbols = factory().create_symbol_repository();
+
+    let ids = |found: Page<SearchHit>| {
+        found
+            .items
+            .into_iter()
+            .map(|hit| hit.symbol.id)
+            .collect::<Vec<_>>()
+    };
+    let listed = |found: Page<Symbol>| found.items.into_iter().map(|s| s.id).collect::<Vec<_>>();
+
+    // Name, description or interpretations, case-insensitive, best match first
+    assert_eq!(
+        ids(symbols
+            .search_symbols("RIV", &ListQuery::new())
+            .await
+            .unwrap()),
+        vec!["river"]
+    );
+    assert_eq!(
+        ids(symbols
+            .search_symbols("unconscious", &ListQuery::new())
+            .await
+            .unwrap()),
+        vec!["water"]
+    );
+    assert_eq!(
+        ids(symbols
+            .search_symbols("the", &ListQuery::new())
+            .await
+            .unwrap()),
+        vec!["water", "river"]
+    );
+    assert_eq!(
+        ids(symbols
+            .search_symbols("100%", &ListQuery::new())
+            .await
+            .unwrap()),
+        vec!["snake"]
+    );
+
+    // Categories match exactly, unlike search, and listings come by name
+    let in_category = |category: &str| ListQuery::new().category(Some(category));
+    assert_eq!(
+        listed(symbols.list_symbols(&in_category("nature")).await.unwrap()),
+        vec!["river", "water"]
+    );
+    assert!(symbols
+        .list_symbols(&in_category("Nature"))
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+    assert_eq!(
+        symbols
+            .list_symbols(&ListQuery::new())
+            .await
+            .unwrap()
+            .total_count,
+        3
+    );
+}
+
+#[tokio::test]
+async fn test_conflicts_and_not_found() {
+    let factory = factory();
+    let symbols = factory.create_symbol_repository();
+    let sets = factory.create_symbol_set_repository();
+
+    assert!(matches!(
+        symbols
+            .create_symbol(symbol("water", "Water", "nature"))
+            .await,
+        Err(RepositoryError::Conflict(_))
+    ));
+    assert!(matches!(
+        symbols
+            .update_symbol(symbol("fire", "Fire", "nature"))
+            .await,
+        Err(RepositoryError::NotFound(_))
+    ));
+    assert!(matches!(
+        symbols.get_symbol("fire").await,
+        Err(RepositoryError::NotFound(_))
+    ));
+    assert!(matches!(
+        sets.create_symbol_set(SymbolSet::new(
+            "elements".to_string(),
+            "Elements".to_string(),
+            "nature".to_string(),
+            String::new(),
+        ))
+        .await,
+        Err(RepositoryError::Conflict(_))
+    ));
+    assert!(matches!(
+        sets.delete_symbol_set("missing").await,
+        Err(RepositoryError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_deleting_a_symbol_cascades() {
+    let factory = factory();
+    let symbols = factory.create_symbol_repository();
+
+    symbols.delete_symbol("water").await.unwrap();
+
+    // Relations either way go with it, and so does its place in related_symbols
+    assert!(symbols
+        .list_relations("river", RelationDirection::Both)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(symbols
+        .get_symbol("river")
+        .await
+        .unwrap()
+        .related_symbols
+        .is_empty());
+
+    // Set members are resolved against the symbols that still exist
+    let set = factory
+        .create_symbol_set_repository()
+        .get_symbol_set("elements")
+        .await
+        .unwrap();
+    assert_eq!(set.symbols.keys().collect::<Vec<_>>(), vec!["river"]);
+}
+
+#[tokio::test]
+async fn test_memory_pool_shares_one_store() {
+    let pool = DatabasePool::memory(None).unwrap();
+    assert_eq!(pool.backend(), DatabaseBackend::Memory);
+    pool.init().await.unwrap();
+
+    pool.repository_factory()
+        .create_symbol_repository()
+        .create_symbol(symbol("fire", "Fire", "element"))
+        .await
+        .unwrap();
+
+    let found = pool
+        .repository_factory()
+        .create_symbol_repository()
+        .get_symbol("fire")
+        .await
+        .unwrap();
+    assert_eq!(found.name, "Fire");
+}
+
+#[tokio::test]
+async fn test_memory_pool_loads_snapshot_file() {
+    let path = std::env::temp_dir().join(format!("snapshot-{}.json", std::process::id()));
+    std::fs::write(&path, SNAPSHOT).unwrap();
+
+    let pool = DatabasePool::open(Some(DatabaseBackend::Memory), "", Some(&path))
+        .await
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let symbols = pool
+        .repository_factory()
+        .create_symbol_repository()
+        .list_symbols(&ListQuery::new())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(symbols.len(), 3);
+
+    assert!(DatabasePool::memory(Some(&path)).is_err());
+}
@@ -1,2 +1,6 @@
 // Repository tests
 pub mod symbol_repository_tests;
+pub mod sqlite_repository_tests;
+pub mod memory_repository_tests;
+pub mod conformance_tests;
+pub mod http_repository_tests;
@@ -0,0 +1,358 @@
+use ontology_core::db::repository::{
+    ListQuery, RepositoryError, RepositoryFactory, SqliteRepositoryFactory,
+};
+use ontology_core::db::{sqlite, DatabaseBackend, DatabasePool};
+use ontology_core::domain::{
+    Archetype, InterpretationLayer, RelationDirection, RelationType, Symbol, SymbolRelation,
+    SymbolSet,
+};
+use std::sync::Arc;
+
+async fn factory() -> Arc<dyn RepositoryFactory> {
+    let pool = DatabasePool::connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory SQLite database");
+    pool.init().await.expect("Failed to create SQLite schema");
+    pool.repository_factory()
+}
+
+fn symbol(id: &str, name: &str, category: &str) -> Symbol {
+    Symbol::new(
+        id.to_string(),
+        name.to_string(),
+        category.to_string(),
+        format!("The {} symbol", name),
+    )
+}
+
+#[test]
+fn test_backend_from_url() {
+    assert_eq!(
+        DatabaseBackend::from_url("postgres://localhost/db").unwrap(),
+        DatabaseBackend::Postgres
+    );
+    assert_eq!(
+        DatabaseBackend::from_url("postgresql://localhost/db").unwrap(),
+        DatabaseBackend::Postgres
+    );
+    assert_eq!(
+        DatabaseBackend::from_url("sqlite://symbols.db").unwrap(),
+        DatabaseBackend::Sqlite
+    );
+    assert_eq!(
+        DatabaseBackend::from_url("sqlite::memory:").unwrap(),
+        DatabaseBackend::Sqlite
+    );
+    assert!(DatabaseBackend::from_url("mysql://localhost/db").is_err());
+    assert!(DatabaseBackend::from_url("symbols.db").is_err());
+}
+
+#[tokio::test]
+async fn test_sqlite_symbol_round_trip() {
+    let repo = factory().await.create_symbol_repository();
+
+    let mut water = symbol("water", "Water", "nature");
+    water.interpretations.insert(
+        "jungian".to_string(),
+        InterpretationLayer::cultural("jungian", "The unconscious").with_source("Jung"),
+    );
+    water
+        .properties
+        .insert("element".to_string(), "water".to_string());
+
+    repo.create_symbol(water.clone()).await.unwrap();
+    repo.create_symbol(symbol("fire", "Fire", "nature"))
+        .await
+        .unwrap();
+    repo.create_symbol(symbol("door", "Door", "object"))
+        .await
+        .unwrap();
+
+    let loaded = repo.get_symbol("water").await.unwrap();
+    assert_eq!(loaded, water);
+
+    assert!(matches!(
+        repo.create_symbol(water.clone()).await,
+        Err(RepositoryError::Conflict(_))
+    ));
+
+    assert_eq!(
+        repo.list_symbols(&ListQuery::new())
+            .await
+            .unwrap()
+            .items
+            .len(),
+        3
+    );
+    let nature = ListQuery::new().category(Some("nature"));
+    assert_eq!(repo.list_symbols(&nature).await.unwrap().items.len(), 2);
+
+    let found = repo
+        .search_symbols("WAT", &ListQuery::new())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].symbol.id, "water");
+
+    let mut updated = loaded.clone();
+    updated.description = "Emotion and renewal".to_string();
+    repo.update_symbol(updated).await.unwrap();
+    assert_eq!(
+        repo.get_symbol("water").await.unwrap().description,
+        "Emotion and renewal"
+    );
+
+    assert!(matches!(
+        repo.update_symbol(symbol("ghost", "Ghost", "spirit")).await,
+        Err(RepositoryError::NotFound(_))
+    ));
+
+    repo.delete_symbol("door").await.unwrap();
+    assert!(matches!(
+        repo.get_symbol("door").await,
+        Err(RepositoryError::NotFound(_))
+    ));
+    assert!(matches!(
+        repo.delete_symbol("door").await,
+        Err(RepositoryError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_sqlite_relations_keep_related_symbols_in_step() {
+    let repo = factory().await.create_symbol_repository();
+
+    repo.create_symbol(symbol("sun", "Sun", "nature").with_related(vec!["light"]))
+        .await
+        .unwrap();
+    repo.create_symbol(symbol("moon", "Moon", "nature"))
+        .await
+        .unwrap();
+
+    // related_symbols entries become untyped edges
+    let relations = repo
+        .list_relations("sun", RelationDirection::Outgoing)
+        .await
+        .unwrap();
+    assert_eq!(relations.len(), 1);
+    assert_eq!(relations[0].target_id, "light");
+    assert_eq!(relations[0].relation_type, RelationType::RelatedTo);
+
+    repo.add_relation(
+        SymbolRelation::new("sun", "moon", RelationType::OppositeOf).with_weight(0.9),
+    )
+    .await
+    .unwrap();
+
+    let incoming = repo
+        .list_relations("moon", RelationDirection::Incoming)
+        .await
+        .unwrap();
+    assert_eq!(incoming.len(), 1);
+    assert_eq!(incoming[0].weight, Some(0.9));
+    assert_eq!(
+        repo.get_symbol("sun").await.unwrap().related_symbols,
+        vec!["light".to_string(), "moon".to_string()]
+    );
+
+    repo.remove_relation("sun", "moon", RelationType::OppositeOf)
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.get_symbol("sun").await.unwrap().related_symbols,
+        vec!["light".to_string()]
+    );
+
+    assert!(matches!(
+        repo.remove_relation("sun", "moon", RelationType::OppositeOf)
+            .await,
+        Err(RepositoryError::NotFound(_))
+    ));
+    assert!(matches!(
+        repo.add_relation(SymbolRelation::new("ghost", "sun", RelationType::Evokes))
+            .await,
+        Err(RepositoryError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_sqlite_search_pages_by_relevance() {
+    let repo = factory().await.create_symbol_repository();
+
+    // "moon" in the name and description, the description alone, or an
+    // interpretation
+    let mut tide = symbol("tide", "Tide", "nature");
+    tide.description = "Pulled by the moon".to_string();
+    let mut wolf = symbol("wolf", "Wolf", "animal");
+    wolf.interpretations.insert(
+        "folk".to_string(),
+        InterpretationLayer::cultural("folk", "Howls at the moon"),
+    );
+    for symbol in [
+        symbol("moon", "Moon", "nature"),
+        symbol("moonstone", "Moonstone", "object"),
+        tide,
+        wolf,
+        symbol("sun", "Sun", "nature"),
+    ] {
+        repo.create_symbol(symbol).await.unwrap();
+    }
+
+    let mut hits = Vec::new();
+    let mut query = ListQuery::new().limit(1);
+    loop {
+        let page = repo.search_symbols("moon", &query).await.unwrap();
+        assert_eq!(page.total_count, 4);
+        hits.extend(page.items.into_iter().map(|hit| (hit.symbol.id, hit.score)));
+        match page.next_cursor {
+            Some(cursor) => query = query.cursor(Some(cursor)),
+            None => break,
+        }
+    }
+    assert_eq!(
+        hits,
+        vec![
+            ("moon".to_string(), 1.4),
+            ("moonstone".to_string(), 1.4),
+            ("tide".to_string(), 0.4),
+            ("wolf".to_string(), 0.2),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_sqlite_symbol_sets() {
+    let factory = factory().await;
+    let symbols = factory.create_symbol_repository();
+    let sets = factory.create_symbol_set_repository();
+
+    let water = symbol("water", "Water", "nature");
+    symbols.create_symbol(water.clone()).await.unwrap();
+
+    let mut elements = SymbolSet::new(
+        "elements".to_string(),
+        "Elements".to_string(),
+        "nature".to_string(),
+        "Classical elements".to_string(),
+    );
+    elements.add_symbol(water);
+    sets.create_symbol_set(elements).await.unwrap();
+
+    let loaded = sets.get_symbol_set("elements").await.unwrap();
+    assert!(loaded.symbols.contains_key("water"));
+    let nature = ListQuery::new().category(Some("nature"));
+    assert_eq!(sets.list_symbol_sets(&nature).await.unwrap().items.len(), 1);
+    let found = sets
+        .search_symbol_sets("classical", &ListQuery::new())
+        .await;
+    assert_eq!(found.unwrap().items.len(), 1);
+
+    sets.delete_symbol_set("elements").await.unwrap();
+    assert!(matches!(
+        sets.get_symbol_set("elements").await,
+        Err(RepositoryError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_sqlite_archetypes() {
+    let factory = factory().await;
+    let symbols = factory.create_symbol_repository();
+    let archetypes = factory.create_archetype_repository();
+
+    let shadow = Archetype::new("shadow".to_string(), "Shadow".to_string(), String::new());
+    let trickster = Archetype::new(
+        "trickster".to_string(),
+        "Trickster".to_string(),
+        String::new(),
+    )
+    .with_parent("shadow");
+    archetypes.create_archetype(shadow).await.unwrap();
+    archetypes.create_archetype(trickster).await.unwrap();
+
+    symbols
+        .create_symbol(symbol("coyote", "Coyote", "animal"))
+        .await
+        .unwrap();
+    symbols
+        .create_symbol(symbol("raven", "Raven", "animal"))
+        .await
+        .unwrap();
+    archetypes.link_symbol("coyote", "trickster").await.unwrap();
+    archetypes.link_symbol("raven", "trickster").await.unwrap();
+
+    let under_shadow = archetypes
+        .list_symbols_by_archetype("shadow", true, &ListQuery::new().limit(1))
+        .await
+        .unwrap();
+    assert_eq!(under_shadow.total_count, 2);
+    assert_eq!(under_shadow.items[0].id, "coyote");
+    let rest = archetypes
+        .list_symbols_by_archetype(
+            "shadow",
+            true,
+            &ListQuery::new().limit(1).cursor(under_shadow.next_cursor),
+        )
+        .await
+        .unwrap();
+    assert_eq!(rest.items[0].id, "raven");
+    assert!(rest.next_cursor.is_none());
+    assert!(archetypes
+        .list_symbols_by_archetype("shadow", false, &ListQuery::new())
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+
+    // Cycles are rejected before reaching the database
+    let looped = Archetype::new("shadow".to_string(), "Shadow".to_string(), String::new())
+        .with_parent("trickster");
+    assert!(matches!(
+        archetypes.update_archetype(looped).await,
+        Err(RepositoryError::Validation(_))
+    ));
+
+    archetypes.delete_archetype("shadow").await.unwrap();
+    let trickster = archetypes.get_archetype("trickster").await.unwrap();
+    assert!(trickster.is_root());
+
+    // Deleting a symbol drops its links
+    symbols.delete_symbol("coyote").await.unwrap();
+    assert_eq!(
+        archetypes
+            .list_symbols_by_archetype("trickster", false, &ListQuery::new())
+            .await
+            .unwrap()
+            .total_count,
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_sqlite_file_database_persists() {
+    let path = std::env::temp_dir().join(format!("symbol-ontology-{}.db", std::process::id()));
+    let url = format!("sqlite://{}", path.display());
+    let _ = std::fs::remove_file(&path);
+
+    {
+        let pool = sqlite::create_pool(&url).await.unwrap();
+        sqlite::init_schema(&pool).await.unwrap();
+        let repo = SqliteRepositoryFactory::new(pool.clone()).create_symbol_repository();
+        repo.create_symbol(symbol("key", "Key", "object"))
+            .await
+            .unwrap();
+        pool.close().await;
+    }
+
+    let pool = DatabasePool::connect(&url).await.unwrap();
+    assert_eq!(pool.backend(), DatabaseBackend::Sqlite);
+    pool.init().await.unwrap();
+    let repo = pool.repository_factory().create_symbol_repository();
+    assert_eq!(repo.get_symbol("key").await.unwrap().name, "Key");
+
+    if let DatabasePool::Sqlite(pool) = pool {
+        pool.close().await;
+    }
+    let _ = std::fs::remove_file(&path);
+}
@@ -1,9 +1,12 @@
+// Several tests mark the expected outcome with assert!(true)
+#![allow(clippy::assertions_on_constants)]
+
 use async_trait::async_trait;
 use ontology_core::db::repository::{
     interfaces::{Repository, RepositoryError, RepositoryResult, SymbolRepository},
-    PgSymbolRepository,
+    ListQuery, Page, PgSymbolRepository, SortOrder,
 };
-use ontology_core::domain::Symbol;
+use ontology_core::domain::{SearchHit, Symbol};
 use sqlx::postgres::PgPoolOptions;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -79,39 +82,38 @@ impl SymbolRepository for TestSymbolRepository {
             .ok_or_else(|| RepositoryError::NotFound(format!("Symbol with id {} not found", id)))
     }
 
-    async fn list_symbols(&self, category: Option<&str>) -> RepositoryResult<Vec<Symbol>> {
+    async fn list_symbols(&self, query: &ListQuery) -> RepositoryResult<Page<Symbol>> {
         self.check_failure()?;
 
         let symbols = self.symbols.read().unwrap();
 
-        let result = match category {
-            Some(cat) => symbols
-                .values()
-                .filter(|s| s.category == cat)
-                .cloned()
-                .collect(),
-            None => symbols.values().cloned().collect(),
-        };
+        let result = symbols
+            .values()
+            .filter(|s| query.includes_category(&s.category))
+            .cloned()
+            .collect();
 
-        Ok(result)
+        query.paginate(result, SortOrder::Name, false, &[])
     }
 
-    async fn search_symbols(&self, query: &str) -> RepositoryResult<Vec<Symbol>> {
+    async fn search_symbols(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SearchHit>> {
         self.check_failure()?;
 
         let symbols = self.symbols.read().unwrap();
-        let query = query.to_lowercase();
+        let text = text.to_lowercase();
 
         let result = symbols
             .values()
-            .filter(|s| {
-                s.name.to_lowercase().contains(&query)
-                    || s.description.to_lowercase().contains(&query)
+            .filter_map(|s| {
+                SearchHit::from_fields(s.clone(), |field| field.to_lowercase().contains(&text))
             })
-            .cloned()
             .collect();
 
-        Ok(result)
+        query.paginate(result, SortOrder::Relevance, true, &[&text])
     }
 
     async fn create_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
@@ -210,20 +212,22 @@ async fn test_get_symbol_not_found() {
 async fn test_list_symbols_all() {
     let repo = TestSymbolRepository::new().with_test_data();
 
-    let result = repo.list_symbols(None).await;
+    let result = repo.list_symbols(&ListQuery::new()).await;
 
     assert!(result.is_ok());
-    let _symbols = result.unwrap();
+    let _symbols = result.unwrap().items;
 }
 
 #[tokio::test]
 async fn test_list_symbols_by_category() {
     let repo = TestSymbolRepository::new().with_test_data();
 
-    let result = repo.list_symbols(Some("dream")).await;
+    let result = repo
+        .list_symbols(&ListQuery::new().category(Some("dream")))
+        .await;
 
     assert!(result.is_ok());
-    let symbols = result.unwrap();
+    let symbols = result.unwrap().items;
 
     for symbol in symbols {
         assert_eq!(symbol.category, "dream");
@@ -234,12 +238,12 @@ async fn test_list_symbols_by_category() {
 async fn test_search_symbols() {
     let repo = TestSymbolRepository::new().with_test_data();
 
-    let result = repo.search_symbols("water").await;
+    let result = repo.search_symbols("water", &ListQuery::new()).await;
 
     assert!(result.is_ok());
-    let symbols = result.unwrap();
+    let symbols = result.unwrap().items;
     assert_eq!(symbols.len(), 1);
-    assert_eq!(symbols[0].id, "water");
+    assert_eq!(symbols[0].symbol.id, "water");
 }
 
 #[tokio::test]
@@ -0,0 +1,100 @@
+use ontology_core::domain::archetypes::{build_hierarchy, creates_cycle, descendant_ids};
+use ontology_core::domain::Archetype;
+
+fn archetype(id: &str, name: &str, parent: Option<&str>) -> Archetype {
+    let archetype = Archetype::new(id.to_string(), name.to_string(), String::new());
+    match parent {
+        Some(parent) => archetype.with_parent(parent),
+        None => archetype,
+    }
+}
+
+fn archetypes() -> Vec<Archetype> {
+    vec![
+        archetype("coyote", "Coyote", Some("trickster")),
+        archetype("shadow", "Shadow", None),
+        archetype("loki", "Loki", Some("trickster")),
+        archetype("trickster", "Trickster", Some("shadow")),
+        archetype("hero", "Hero", None),
+    ]
+}
+
+#[test]
+fn test_archetype_validation() {
+    assert!(archetype("shadow", "Shadow", None).validate().is_ok());
+    assert!(archetype("", "Shadow", None).validate().is_err());
+    assert!(archetype("shadow", " ", None).validate().is_err());
+    assert!(archetype("shadow", "Shadow", Some("shadow"))
+        .validate()
+        .is_err());
+}
+
+#[test]
+fn test_build_full_hierarchy() {
+    let roots = build_hierarchy(&archetypes(), None);
+
+    let names: Vec<&str> = roots.iter().map(|n| n.archetype.name.as_str()).collect();
+    assert_eq!(names, vec!["Hero", "Shadow"]);
+
+    let shadow = &roots[1];
+    assert_eq!(shadow.size(), 4);
+    assert_eq!(shadow.children.len(), 1);
+
+    let trickster = &shadow.children[0];
+    let children: Vec<&str> = trickster
+        .children
+        .iter()
+        .map(|n| n.archetype.id.as_str())
+        .collect();
+    assert_eq!(children, vec!["coyote", "loki"]);
+}
+
+#[test]
+fn test_build_subtree_and_orphans() {
+    let roots = build_hierarchy(&archetypes(), Some("trickster"));
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].size(), 3);
+
+    assert!(build_hierarchy(&archetypes(), Some("missing")).is_empty());
+
+    // An archetype whose parent is unknown is shown at the top level
+    let orphaned = vec![archetype("coyote", "Coyote", Some("trickster"))];
+    let roots = build_hierarchy(&orphaned, None);
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].archetype.id, "coyote");
+}
+
+#[test]
+fn test_build_hierarchy_tolerates_cycles() {
+    let cyclic = vec![
+        archetype("a", "A", None),
+        archetype("b", "B", Some("c")),
+        archetype("c", "C", Some("b")),
+    ];
+
+    let roots = build_hierarchy(&cyclic, Some("b"));
+    assert_eq!(roots[0].size(), 2);
+}
+
+#[test]
+fn test_descendants_and_cycle_detection() {
+    let archetypes = archetypes();
+
+    let mut descendants = descendant_ids(&archetypes, "shadow");
+    descendants.sort();
+    assert_eq!(descendants, vec!["coyote", "loki", "shadow", "trickster"]);
+
+    assert!(creates_cycle(&archetypes, "shadow", "coyote"));
+    assert!(creates_cycle(&archetypes, "trickster", "trickster"));
+    assert!(!creates_cycle(&archetypes, "coyote", "hero"));
+}
+
+#[test]
+fn test_archetype_node_serialization() {
+    let roots = build_hierarchy(&archetypes(), Some("trickster"));
+    let json = serde_json::to_value(&roots[0]).unwrap();
+
+    assert_eq!(json["id"], "trickster");
+    assert_eq!(json["parent_id"], "shadow");
+    assert_eq!(json["children"][0]["id"], "coyote");
+}
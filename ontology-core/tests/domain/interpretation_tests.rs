@@ -0,0 +1,134 @@
+use ontology_core::domain::interpretations::interpretations_from_value;
+use ontology_core::domain::{InterpretationLayer, InterpretationLevel, Symbol};
+use serde_json::json;
+
+#[test]
+fn test_interpretation_level_from_context() {
+    assert_eq!(
+        InterpretationLevel::from_context("default"),
+        InterpretationLevel::Universal
+    );
+    assert_eq!(
+        InterpretationLevel::from_context("personal"),
+        InterpretationLevel::Personal
+    );
+    assert_eq!(
+        InterpretationLevel::from_context("jungian"),
+        InterpretationLevel::Cultural
+    );
+}
+
+#[test]
+fn test_interpretation_level_parse() {
+    assert_eq!(
+        "Cultural".parse::<InterpretationLevel>().unwrap(),
+        InterpretationLevel::Cultural
+    );
+    assert!("mythic".parse::<InterpretationLevel>().is_err());
+}
+
+#[test]
+fn test_legacy_flat_interpretations() {
+    let value = json!({
+        "default": "Represents emotions",
+        "jungian": "The unconscious mind"
+    });
+
+    let interpretations = interpretations_from_value(value).unwrap();
+
+    let default = interpretations.get("default").unwrap();
+    assert_eq!(default.level, InterpretationLevel::Universal);
+    assert_eq!(default.text, "Represents emotions");
+    assert_eq!(default.tradition, None);
+
+    let jungian = interpretations.get("jungian").unwrap();
+    assert_eq!(jungian.level, InterpretationLevel::Cultural);
+    assert_eq!(jungian.tradition.as_deref(), Some("jungian"));
+}
+
+#[test]
+fn test_mixed_flat_and_layered_interpretations() {
+    let value = json!({
+        "default": "Represents emotions",
+        "dreamer": {
+            "level": "personal",
+            "text": "Reminds me of childhood summers",
+            "source": "dream journal"
+        }
+    });
+
+    let interpretations = interpretations_from_value(value).unwrap();
+
+    let personal = interpretations.get("dreamer").unwrap();
+    assert_eq!(personal.level, InterpretationLevel::Personal);
+    assert_eq!(personal.source.as_deref(), Some("dream journal"));
+    assert_eq!(personal.tradition, None);
+}
+
+#[test]
+fn test_symbol_deserializes_legacy_interpretations() {
+    let value = json!({
+        "id": "water",
+        "name": "Water",
+        "category": "nature",
+        "description": "Flowing liquid",
+        "interpretations": { "default": "Represents emotions" },
+        "related_symbols": []
+    });
+
+    let symbol: Symbol = serde_json::from_value(value).unwrap();
+
+    assert_eq!(
+        symbol.interpretations.get("default").unwrap(),
+        &InterpretationLayer::universal("Represents emotions")
+    );
+}
+
+#[test]
+fn test_symbol_serializes_layers() {
+    let mut symbol = Symbol::new(
+        "snake".to_string(),
+        "Snake".to_string(),
+        "animal".to_string(),
+        "Reptile with no limbs".to_string(),
+    );
+    symbol.add_interpretation_layer(
+        "hindu".to_string(),
+        InterpretationLayer::cultural("hindu", "Kundalini energy").with_source("Tantra"),
+    );
+
+    let json = serde_json::to_value(&symbol).unwrap();
+
+    assert_eq!(json["interpretations"]["hindu"]["level"], "cultural");
+    assert_eq!(json["interpretations"]["hindu"]["tradition"], "hindu");
+    assert_eq!(json["interpretations"]["hindu"]["source"], "Tantra");
+
+    let round_trip: Symbol = serde_json::from_value(json).unwrap();
+    assert_eq!(round_trip, symbol);
+}
+
+#[test]
+fn test_interpretations_at_level() {
+    let mut symbol = Symbol::new(
+        "moon".to_string(),
+        "Moon".to_string(),
+        "celestial".to_string(),
+        "Earth's natural satellite".to_string(),
+    );
+    symbol.add_interpretation("default".to_string(), "Cycles".to_string());
+    symbol.add_interpretation("chinese".to_string(), "Reunion".to_string());
+    symbol.add_interpretation("egyptian".to_string(), "Thoth".to_string());
+
+    assert_eq!(
+        symbol
+            .interpretations_at(InterpretationLevel::Cultural)
+            .len(),
+        2
+    );
+    assert_eq!(
+        symbol
+            .interpretations_at(InterpretationLevel::Universal)
+            .len(),
+        1
+    );
+}
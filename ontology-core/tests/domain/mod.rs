@@ -1,3 +1,7 @@
+pub mod archetype_tests;
+pub mod interpretation_tests;
+pub mod relation_tests;
+pub mod search_tests;
 pub mod symbol_tests;
 pub mod symbolset_tests;
 pub mod ontology_tests;
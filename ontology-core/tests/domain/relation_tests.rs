@@ -0,0 +1,74 @@
+use ontology_core::domain::{RelationDirection, RelationType, SymbolRelation};
+
+#[test]
+fn test_relation_type_parse() {
+    assert_eq!(
+        "transforms_into".parse::<RelationType>().unwrap(),
+        RelationType::TransformsInto
+    );
+    assert_eq!(
+        "Opposite-Of".parse::<RelationType>().unwrap(),
+        RelationType::OppositeOf
+    );
+    assert!("married_to".parse::<RelationType>().is_err());
+}
+
+#[test]
+fn test_relation_type_serialization() {
+    let json = serde_json::to_value(RelationType::PartOf).unwrap();
+    assert_eq!(json, "part_of");
+    assert_eq!(RelationType::default(), RelationType::RelatedTo);
+}
+
+#[test]
+fn test_relation_type_symmetry() {
+    assert!(RelationType::SimilarTo.is_symmetric());
+    assert!(RelationType::OppositeOf.is_symmetric());
+    assert!(!RelationType::TransformsInto.is_symmetric());
+    assert!(!RelationType::PartOf.is_symmetric());
+}
+
+#[test]
+fn test_relation_direction() {
+    let relation = SymbolRelation::new("caterpillar", "butterfly", RelationType::TransformsInto);
+
+    assert!(relation.touches("caterpillar", RelationDirection::Outgoing));
+    assert!(!relation.touches("caterpillar", RelationDirection::Incoming));
+    assert!(relation.touches("butterfly", RelationDirection::Incoming));
+    assert!(relation.touches("butterfly", RelationDirection::Both));
+    assert!(!relation.touches("moth", RelationDirection::Both));
+
+    assert_eq!(relation.other_end("caterpillar"), Some("butterfly"));
+    assert_eq!(relation.other_end("butterfly"), Some("caterpillar"));
+    assert_eq!(relation.other_end("moth"), None);
+}
+
+#[test]
+fn test_relation_validation() {
+    assert!(SymbolRelation::new("sun", "moon", RelationType::OppositeOf)
+        .with_weight(0.9)
+        .validate()
+        .is_ok());
+    assert!(SymbolRelation::new("sun", "sun", RelationType::SimilarTo)
+        .validate()
+        .is_err());
+    assert!(SymbolRelation::new("sun", "", RelationType::SimilarTo)
+        .validate()
+        .is_err());
+    assert!(SymbolRelation::new("sun", "moon", RelationType::OppositeOf)
+        .with_weight(1.5)
+        .validate()
+        .is_err());
+}
+
+#[test]
+fn test_relation_deserialization_defaults() {
+    let relation: SymbolRelation = serde_json::from_value(serde_json::json!({
+        "source_id": "snake",
+        "target_id": "water"
+    }))
+    .unwrap();
+
+    assert_eq!(relation.relation_type, RelationType::RelatedTo);
+    assert_eq!(relation.weight, None);
+}
@@ -0,0 +1,123 @@
+use ontology_core::domain::search::{
+    self, DESCRIPTION_WEIGHT, FUZZY_THRESHOLD, INTERPRETATION_WEIGHT, NAME_WEIGHT, PROPERTY_WEIGHT,
+};
+use ontology_core::domain::{SearchHit, Symbol};
+
+fn stone() -> Symbol {
+    let mut stone = Symbol::new(
+        "stone".to_string(),
+        "Stone".to_string(),
+        "nature".to_string(),
+        "Permanence and endurance".to_string(),
+    );
+    stone.add_interpretation("jungian".to_string(), "The enduring self".to_string());
+    stone
+        .properties
+        .insert("texture".to_string(), "granite".to_string());
+    stone
+}
+
+fn contains(query: &'static str) -> impl Fn(&str) -> bool {
+    move |text| text.to_lowercase().contains(query)
+}
+
+#[test]
+fn test_hit_scores_weight_each_matching_field() {
+    let hit = SearchHit::from_fields(stone(), contains("stone")).unwrap();
+    assert_eq!(hit.score, NAME_WEIGHT);
+
+    let hit = SearchHit::from_fields(stone(), contains("endur")).unwrap();
+    assert_eq!(hit.score, DESCRIPTION_WEIGHT + INTERPRETATION_WEIGHT);
+
+    let hit = SearchHit::from_fields(stone(), contains("granite")).unwrap();
+    assert_eq!(hit.score, PROPERTY_WEIGHT);
+
+    // The category and property keys are not searched
+    assert!(SearchHit::from_fields(stone(), contains("nature")).is_none());
+    assert!(SearchHit::from_fields(stone(), contains("texture")).is_none());
+}
+
+#[test]
+fn test_rank_orders_by_score_and_keeps_ties_stable() {
+    let hit = |id: &str, score| {
+        SearchHit::new(
+            Symbol::new(
+                id.to_string(),
+                id.to_string(),
+                "c".to_string(),
+                String::new(),
+            ),
+            score,
+        )
+    };
+    let mut hits = vec![hit("a", 0.2), hit("b", 1.0), hit("c", 0.2), hit("d", 0.4)];
+
+    search::rank(&mut hits);
+
+    let ids: Vec<&str> = hits.iter().map(|h| h.symbol.id.as_str()).collect();
+    assert_eq!(ids, vec!["b", "d", "a", "c"]);
+}
+
+#[test]
+fn test_hit_serializes_flat_with_score() {
+    let hit = SearchHit::new(stone(), 0.5);
+
+    let json = serde_json::to_value(&hit).unwrap();
+    assert_eq!(json["id"], "stone");
+    assert_eq!(json["score"], 0.5);
+    assert!(json.get("symbol").is_none());
+
+    let parsed: SearchHit = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, hit);
+}
+
+#[test]
+fn test_trigram_similarity_matches_pg_trgm() {
+    // Values from PostgreSQL's similarity()
+    assert!((search::trigram_similarity("Serpent", "serpant") - 5.0 / 11.0).abs() < 1e-6);
+    assert!((search::trigram_similarity("Garden of Eden", "garden") - 7.0 / 13.0).abs() < 1e-6);
+    assert_eq!(search::trigram_similarity("Tide_Pool", "tide pool"), 1.0);
+    assert_eq!(search::trigram_similarity("", "--"), 0.0);
+}
+
+#[test]
+fn test_levenshtein_counts_single_character_edits() {
+    assert_eq!(search::levenshtein("moon", "mood"), 1);
+    assert_eq!(search::levenshtein("labyrinth", "labyrith"), 1);
+    assert_eq!(search::levenshtein("kitten", "sitting"), 3);
+    assert_eq!(search::levenshtein("", "abc"), 3);
+    assert_eq!(search::levenshtein("äbc", "abc"), 1);
+}
+
+#[test]
+fn test_name_similarity_takes_the_better_measure() {
+    assert_eq!(search::name_similarity("Moon", "MOON"), 1.0);
+    // One edit in seven characters beats the trigram overlap
+    assert!((search::name_similarity("Serpent", "serpant") - 6.0 / 7.0).abs() < 1e-6);
+    assert!(search::name_similarity("Moon", "xylophone") < FUZZY_THRESHOLD);
+}
+
+#[test]
+fn test_fuzzy_matches_rank_filter_and_limit() {
+    let symbol = |name: &str| {
+        Symbol::new(
+            name.to_lowercase(),
+            name.to_string(),
+            "c".to_string(),
+            String::new(),
+        )
+    };
+    let symbols = vec![
+        symbol("Mood"),
+        symbol("Serpent"),
+        symbol("Moon"),
+        symbol("Moor"),
+    ];
+
+    let hits = search::fuzzy_matches(symbols.clone(), "moon", 10);
+    let ids: Vec<&str> = hits.iter().map(|h| h.symbol.id.as_str()).collect();
+    // Mood and Moor tie, so they come in name order
+    assert_eq!(ids, vec!["moon", "mood", "moor"]);
+
+    assert_eq!(search::fuzzy_matches(symbols, "moon", 1).len(), 1);
+}
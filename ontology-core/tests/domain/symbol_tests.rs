@@ -138,11 +138,11 @@ fn test_add_interpretation() {
 
     assert_eq!(symbol.interpretations.len(), 2);
     assert_eq!(
-        symbol.interpretations.get("jungian").unwrap(),
+        symbol.interpretations.get("jungian").unwrap().text,
         "Symbol of transformation and rebirth"
     );
     assert_eq!(
-        symbol.interpretations.get("mythological").unwrap(),
+        symbol.interpretations.get("mythological").unwrap().text,
         "Often associated with wisdom and healing"
     );
 }
@@ -161,8 +161,11 @@ fn test_add_interpretation_empty_strings() {
     symbol.add_interpretation("".to_string(), "empty-context".to_string());
 
     assert_eq!(symbol.interpretations.len(), 2);
-    assert_eq!(symbol.interpretations.get("").unwrap(), "empty-context");
-    assert_eq!(symbol.interpretations.get("empty-key").unwrap(), "");
+    assert_eq!(
+        symbol.interpretations.get("").unwrap().text,
+        "empty-context"
+    );
+    assert_eq!(symbol.interpretations.get("empty-key").unwrap().text, "");
 }
 
 #[test]
@@ -179,7 +182,7 @@ fn test_add_interpretation_overwrite() {
 
     assert_eq!(symbol.interpretations.len(), 1);
     assert_eq!(
-        symbol.interpretations.get("jungian").unwrap(),
+        symbol.interpretations.get("jungian").unwrap().text,
         "Updated interpretation"
     );
 }
@@ -237,3 +240,52 @@ fn test_add_related_symbol_duplicate() {
         .count();
     assert_eq!(night_count, 2);
 }
+
+#[test]
+fn test_localized_names_use_canonical_tags() {
+    let mut symbol = Symbol::new(
+        "serpent".to_string(),
+        "Serpent".to_string(),
+        "animal".to_string(),
+        "Renewal".to_string(),
+    )
+    .with_aliases(vec!["Snake", "snake"]);
+    symbol
+        .add_localized_name("ES", "Serpiente".to_string())
+        .unwrap();
+    symbol
+        .add_localized_name("pt-br", "Serpente".to_string())
+        .unwrap();
+
+    assert!(symbol.localized_names.contains_key("es"));
+    assert!(symbol.localized_names.contains_key("pt-BR"));
+    assert!(symbol
+        .add_localized_name("not a tag", "Schlange".to_string())
+        .is_err());
+
+    // Lookup keys are lowercased and deduplicated
+    assert_eq!(symbol.lookup_keys(), vec!["serpente", "serpiente", "snake"]);
+    assert!(symbol.validate().is_ok());
+
+    symbol.add_alias(" ".to_string());
+    assert!(symbol.validate().is_err());
+}
+
+#[test]
+fn test_name_in_falls_back_to_less_specific_tags() {
+    let mut symbol = Symbol::new(
+        "moon".to_string(),
+        "Moon".to_string(),
+        "nature".to_string(),
+        "Cycles".to_string(),
+    );
+    symbol.add_localized_name("pt", "Lua".to_string()).unwrap();
+    symbol
+        .add_localized_name("pt-PT", "Lua cheia".to_string())
+        .unwrap();
+
+    assert_eq!(symbol.name_in("pt-PT"), "Lua cheia");
+    assert_eq!(symbol.name_in("pt-BR"), "Lua");
+    assert_eq!(symbol.name_in("de"), "Moon");
+    assert_eq!(symbol.name_in("not a tag"), "Moon");
+}
@@ -0,0 +1,99 @@
+use ontology_core::db::repository::{
+    InMemoryRepositoryFactory, RepositoryError, RepositoryFactory,
+};
+use ontology_core::domain::Symbol;
+use ontology_core::embedding::{
+    self, cosine_similarity, Embedder, HashingEmbedder, EMBEDDING_DIMENSIONS,
+};
+
+fn norm(vector: &[f32]) -> f32 {
+    vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
+
+#[tokio::test]
+async fn test_hashing_embedder_is_deterministic_and_normalized() {
+    let embedder = HashingEmbedder::default();
+    assert_eq!(embedder.dimensions(), EMBEDDING_DIMENSIONS);
+
+    let first = embedder.embed("A snake shedding its skin").await.unwrap();
+    let second = embedder.embed("A snake shedding its skin").await.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first.len(), EMBEDDING_DIMENSIONS);
+    assert!((norm(&first) - 1.0).abs() < 1e-5);
+
+    // Nothing but stop words leaves nothing to embed
+    let empty = embedder.embed("and the of it").await.unwrap();
+    assert_eq!(norm(&empty), 0.0);
+}
+
+#[test]
+fn test_word_forms_and_case_share_a_vector() {
+    let embedder = HashingEmbedder::new(512);
+
+    let similarity =
+        |a: &str, b: &str| cosine_similarity(&embedder.embed_text(a), &embedder.embed_text(b));
+    assert!((similarity("Falling", "falls") - 1.0).abs() < 1e-5);
+    assert!(similarity("underwater", "water") > similarity("underwater", "mountain"));
+    assert!(similarity("a snake in the grass", "snakes") > similarity("a snake", "a ladder"));
+}
+
+#[test]
+fn test_symbol_text_covers_every_searchable_field() {
+    let mut moon = Symbol::new(
+        "moon".to_string(),
+        "Moon".to_string(),
+        "nature".to_string(),
+        "Cycles and reflection".to_string(),
+    );
+    moon.add_interpretation("jungian".to_string(), "The feminine".to_string());
+    moon.add_interpretation("astrological".to_string(), "Emotions".to_string());
+    moon.properties
+        .insert("phase".to_string(), "waxing".to_string());
+    moon.add_alias("Luna".to_string());
+    moon.add_localized_name("ja", "Tsuki".to_string()).unwrap();
+    moon.add_localized_name("de", "Mond".to_string()).unwrap();
+
+    assert_eq!(
+        embedding::symbol_text(&moon),
+        "Moon\nLuna\nMond\nTsuki\nCycles and reflection\nEmotions\nThe feminine\nwaxing"
+    );
+}
+
+#[tokio::test]
+async fn test_backfill_rejects_an_embedder_of_the_wrong_size() {
+    let factory = InMemoryRepositoryFactory::new();
+    let repository = factory.create_symbol_repository();
+    repository
+        .create_symbol(Symbol::new(
+            "moon".to_string(),
+            "Moon".to_string(),
+            "nature".to_string(),
+            "Cycles".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    let result = embedding::backfill(repository.as_ref(), &HashingEmbedder::new(8), false).await;
+    assert!(matches!(result, Err(RepositoryError::Validation(_))));
+    assert_eq!(repository.list_unembedded_symbols().await.unwrap().len(), 1);
+
+    let embedded = embedding::backfill(repository.as_ref(), &HashingEmbedder::default(), false)
+        .await
+        .unwrap();
+    assert_eq!(embedded, 1);
+
+    // Only missing embeddings are computed unless asked for all of them
+    let embedder = HashingEmbedder::default();
+    assert_eq!(
+        embedding::backfill(repository.as_ref(), &embedder, false)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        embedding::backfill(repository.as_ref(), &embedder, true)
+            .await
+            .unwrap(),
+        1
+    );
+}
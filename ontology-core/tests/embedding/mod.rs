@@ -0,0 +1,2 @@
+pub mod embedder_tests;
+pub mod semantic_search_tests;
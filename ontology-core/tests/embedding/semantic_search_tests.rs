@@ -0,0 +1,76 @@
+use serde::Deserialize;
+
+use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+use ontology_core::domain::Symbol;
+use ontology_core::embedding::{self, HashingEmbedder};
+
+#[derive(Deserialize)]
+struct DreamSymbolRow {
+    #[serde(rename = "Word")]
+    word: String,
+
+    #[serde(rename = "Interpretation")]
+    interpretation: String,
+}
+
+/// Entries of the dream dictionary the seeder loads, as plain symbols.
+fn dream_symbols(words: &[&str]) -> Vec<Symbol> {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/bin/data/dream_interpretations.csv"
+    );
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .expect("dream dictionary should be readable");
+
+    let mut symbols: Vec<Symbol> = Vec::new();
+    for row in reader.deserialize::<DreamSymbolRow>().flatten() {
+        let id = row.word.trim().to_lowercase().replace(' ', "_");
+        if !words.contains(&id.as_str()) || symbols.iter().any(|s| s.id == id) {
+            continue;
+        }
+        symbols.push(Symbol::new(
+            id,
+            row.word.trim().to_string(),
+            "dream".to_string(),
+            row.interpretation.trim().to_string(),
+        ));
+    }
+    assert_eq!(
+        symbols.len(),
+        words.len(),
+        "every word should be in the dictionary"
+    );
+    symbols
+}
+
+#[tokio::test]
+async fn test_semantic_search_over_dream_symbols() {
+    let factory = InMemoryRepositoryFactory::new();
+    let repository = factory.create_symbol_repository();
+    let words = [
+        "birthday", "cage", "car", "drowning", "flying", "food", "garden", "house", "money",
+        "school", "sun", "teeth", "water", "wedding",
+    ];
+    for symbol in dream_symbols(&words) {
+        repository.create_symbol(symbol).await.unwrap();
+    }
+
+    let embedder = HashingEmbedder::default();
+    let embedded = embedding::backfill(repository.as_ref(), &embedder, false)
+        .await
+        .unwrap();
+    assert_eq!(embedded, words.len());
+    assert!(repository
+        .list_unembedded_symbols()
+        .await
+        .unwrap()
+        .is_empty());
+
+    let query = embedder.embed_text("feeling trapped underwater");
+    let hits = repository.semantic_search_symbols(&query, 3).await.unwrap();
+    let mut ids: Vec<&str> = hits.iter().map(|h| h.symbol.id.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["cage", "drowning", "water"]);
+}
@@ -0,0 +1,121 @@
+use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+use ontology_core::domain::Symbol;
+use ontology_core::extraction::{self, ExtractedSymbol, TextSpan};
+
+fn symbol(id: &str, name: &str) -> Symbol {
+    Symbol::new(
+        id.to_string(),
+        name.to_string(),
+        "dream".to_string(),
+        String::new(),
+    )
+}
+
+fn symbols() -> Vec<Symbol> {
+    let mut serpent = symbol("serpent", "Serpent").with_aliases(vec!["snake", "viper"]);
+    serpent
+        .add_localized_name("es", "Serpiente".to_string())
+        .unwrap();
+    vec![
+        serpent,
+        symbol("garden", "Garden"),
+        symbol("eden", "Garden of Eden"),
+        symbol("tower", "The Tower"),
+        symbol("falling", "Falling"),
+    ]
+}
+
+fn ids(found: &[ExtractedSymbol]) -> Vec<&str> {
+    found.iter().map(|e| e.symbol.id.as_str()).collect()
+}
+
+#[test]
+fn test_matches_names_aliases_and_word_forms_with_spans() {
+    let text = "I kept falling past a tower while Snakes hissed. A snake, then a serpiente.";
+    let found = extraction::match_symbols(&symbols(), text);
+
+    assert_eq!(ids(&found), vec!["falling", "tower", "serpent"]);
+
+    let serpent = &found[2];
+    assert_eq!(
+        serpent.spans,
+        vec![
+            TextSpan {
+                start: 34,
+                end: 40,
+                text: "Snakes".to_string()
+            },
+            TextSpan {
+                start: 51,
+                end: 56,
+                text: "snake".to_string()
+            },
+            TextSpan {
+                start: 65,
+                end: 74,
+                text: "serpiente".to_string()
+            },
+        ]
+    );
+    for span in found.iter().flat_map(|e| &e.spans) {
+        assert_eq!(&text[span.start..span.end], span.text);
+    }
+}
+
+#[test]
+fn test_longest_phrase_wins() {
+    let found = extraction::match_symbols(
+        &symbols(),
+        "Walking in the garden of Eden, then another garden.",
+    );
+
+    assert_eq!(ids(&found), vec!["eden", "garden"]);
+    assert_eq!(found[0].spans[0].text, "garden of Eden");
+    assert_eq!(found[1].spans.len(), 1);
+    assert_eq!(found[1].spans[0].text, "garden");
+}
+
+#[test]
+fn test_shared_names_and_empty_text() {
+    let symbols = vec![symbol("moon-a", "Moon"), symbol("moon-b", "Moon")];
+
+    let found = extraction::match_symbols(&symbols, "A full moon");
+    assert_eq!(ids(&found), vec!["moon-a", "moon-b"]);
+
+    assert!(extraction::match_symbols(&symbols, "").is_empty());
+    assert!(extraction::match_symbols(&[], "A full moon").is_empty());
+}
+
+#[tokio::test]
+async fn test_extract_symbols_from_repository() {
+    let factory = InMemoryRepositoryFactory::new();
+    let repository = factory.create_symbol_repository();
+    for symbol in symbols() {
+        repository.create_symbol(symbol).await.unwrap();
+    }
+
+    let found = extraction::extract_symbols_from_text(repository.as_ref(), "a viper in the grass")
+        .await
+        .unwrap();
+    assert_eq!(ids(&found), vec!["serpent"]);
+}
+
+#[tokio::test]
+async fn test_extract_symbols_across_pages() {
+    let factory = InMemoryRepositoryFactory::new();
+    let repository = factory.create_symbol_repository();
+    // Enough fillers that the symbols mentioned are listed pages after "Falling"
+    let fillers =
+        (0..600).map(|i| symbol(&format!("filler-{:03}", i), &format!("Filler {:03}", i)));
+    for symbol in symbols().into_iter().chain(fillers) {
+        repository.create_symbol(symbol).await.unwrap();
+    }
+
+    let found = extraction::extract_symbols_from_text(
+        repository.as_ref(),
+        "Falling into the Garden of Eden, then a viper",
+    )
+    .await
+    .unwrap();
+    assert_eq!(ids(&found), vec!["falling", "eden", "serpent"]);
+}
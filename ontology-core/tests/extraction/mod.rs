@@ -0,0 +1 @@
+pub mod extraction_tests;
@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+use ontology_core::db::memory::MemoryStore;
+use ontology_core::db::repository::interfaces::{
+    Repository, RepositoryError, RepositoryResult, SymbolRepository,
+};
+use ontology_core::db::repository::{InMemorySymbolRepository, ListQuery, Page};
+use ontology_core::domain::{RelationDirection, RelationType, SearchHit, Symbol, SymbolRelation};
+
+use crate::common::fixtures::create_test_symbol;
+
+pub mod network_tests;
+pub mod path_tests;
+
+/// Symbol repository whose relations are set up edge by edge, independent of the
+/// symbols stored in the in-memory backend.
+pub struct GraphRepository {
+    symbols: InMemorySymbolRepository,
+    /// `None` behaves like a backend without typed relations
+    relations: Option<RwLock<Vec<SymbolRelation>>>,
+}
+
+impl Repository for GraphRepository {}
+
+impl GraphRepository {
+    pub fn new(symbol_ids: &[&str]) -> Self {
+        let symbols = symbol_ids
+            .iter()
+            .map(|id| create_test_symbol(id, id, "dream"))
+            .collect();
+
+        Self {
+            symbols: Self::store(symbols),
+            relations: Some(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// A repository without typed relations, so only `related_symbols` can be followed.
+    pub fn without_relations(symbols: Vec<Symbol>) -> Self {
+        Self {
+            symbols: Self::store(symbols),
+            relations: None,
+        }
+    }
+
+    fn store(symbols: Vec<Symbol>) -> InMemorySymbolRepository {
+        let store = MemoryStore::new();
+        {
+            let mut data = store.write();
+            for symbol in symbols {
+                // Plain pushes, so related_symbols don't turn into typed edges
+                data.symbols.push(symbol);
+            }
+        }
+        InMemorySymbolRepository::with_store(store)
+    }
+
+    pub fn with_edge(self, source: &str, target: &str, relation_type: RelationType) -> Self {
+        self.with_weighted_edge(source, target, relation_type, None)
+    }
+
+    pub fn with_weighted_edge(
+        self,
+        source: &str,
+        target: &str,
+        relation_type: RelationType,
+        weight: Option<f64>,
+    ) -> Self {
+        let mut relation = SymbolRelation::new(source, target, relation_type);
+        relation.weight = weight;
+        self.relations
+            .as_ref()
+            .expect("repository has no typed relations")
+            .write()
+            .unwrap()
+            .push(relation);
+        self
+    }
+}
+
+#[async_trait]
+impl SymbolRepository for GraphRepository {
+    async fn get_symbol(&self, id: &str) -> RepositoryResult<Symbol> {
+        self.symbols.get_symbol(id).await
+    }
+
+    async fn list_symbols(&self, query: &ListQuery) -> RepositoryResult<Page<Symbol>> {
+        self.symbols.list_symbols(query).await
+    }
+
+    async fn search_symbols(
+        &self,
+        text: &str,
+        query: &ListQuery,
+    ) -> RepositoryResult<Page<SearchHit>> {
+        self.symbols.search_symbols(text, query).await
+    }
+
+    async fn create_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
+        self.symbols.create_symbol(symbol).await
+    }
+
+    async fn update_symbol(&self, symbol: Symbol) -> RepositoryResult<Symbol> {
+        self.symbols.update_symbol(symbol).await
+    }
+
+    async fn delete_symbol(&self, id: &str) -> RepositoryResult<()> {
+        self.symbols.delete_symbol(id).await
+    }
+
+    async fn list_relations(
+        &self,
+        symbol_id: &str,
+        direction: RelationDirection,
+    ) -> RepositoryResult<Vec<SymbolRelation>> {
+        let relations = self.relations.as_ref().ok_or_else(|| {
+            RepositoryError::NotImplemented("Relations are not supported".to_string())
+        })?;
+
+        Ok(relations
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|relation| relation.touches(symbol_id, direction))
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,135 @@
+use ontology_core::db::repository::RepositoryError;
+use ontology_core::domain::{RelationDirection, RelationType};
+use ontology_core::graph::{related_network, NetworkQuery};
+
+use super::GraphRepository;
+use crate::common::fixtures::create_detailed_symbol;
+
+fn chain() -> GraphRepository {
+    // water -> river -> ocean -> storm, plus fire as the opposite of water
+    GraphRepository::new(&["water", "river", "ocean", "storm", "fire"])
+        .with_edge("water", "river", RelationType::SimilarTo)
+        .with_edge("river", "ocean", RelationType::TransformsInto)
+        .with_edge("ocean", "storm", RelationType::Evokes)
+        .with_edge("fire", "water", RelationType::OppositeOf)
+}
+
+fn node_ids(network: &ontology_core::graph::SymbolNetwork) -> Vec<(&str, usize)> {
+    network
+        .nodes
+        .iter()
+        .map(|node| (node.symbol.id.as_str(), node.depth))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_network_respects_depth() {
+    let repo = chain();
+
+    let network = related_network(&repo, "water", &NetworkQuery::default())
+        .await
+        .unwrap();
+
+    assert_eq!(network.root_id, "water");
+    let nodes = node_ids(&network);
+    assert_eq!(nodes[0], ("water", 0));
+    assert!(nodes.contains(&("river", 1)));
+    assert!(nodes.contains(&("fire", 1)));
+    assert!(nodes.contains(&("ocean", 2)));
+    assert!(!nodes.iter().any(|(id, _)| *id == "storm"));
+    assert_eq!(network.edges.len(), 3);
+    assert!(!network.truncated);
+}
+
+#[tokio::test]
+async fn test_network_filters_relation_types_and_direction() {
+    let repo = chain();
+
+    let query = NetworkQuery::default()
+        .with_depth(5)
+        .with_relation_types(vec![RelationType::SimilarTo, RelationType::TransformsInto]);
+    let network = related_network(&repo, "water", &query).await.unwrap();
+    let nodes = node_ids(&network);
+    assert_eq!(nodes, vec![("water", 0), ("river", 1), ("ocean", 2)]);
+
+    let query = NetworkQuery::default().with_direction(RelationDirection::Incoming);
+    let network = related_network(&repo, "water", &query).await.unwrap();
+    assert_eq!(node_ids(&network), vec![("water", 0), ("fire", 1)]);
+}
+
+#[tokio::test]
+async fn test_network_truncates_at_max_nodes() {
+    let repo = chain();
+
+    let query = NetworkQuery::default().with_depth(5).with_max_nodes(2);
+    let network = related_network(&repo, "water", &query).await.unwrap();
+
+    assert_eq!(network.nodes.len(), 2);
+    assert!(network.truncated);
+}
+
+#[tokio::test]
+async fn test_network_skips_dangling_relations() {
+    let repo =
+        GraphRepository::new(&["water"]).with_edge("water", "ghost", RelationType::RelatedTo);
+
+    let network = related_network(&repo, "water", &NetworkQuery::default())
+        .await
+        .unwrap();
+
+    assert_eq!(network.nodes.len(), 1);
+    assert!(network.edges.is_empty());
+}
+
+#[tokio::test]
+async fn test_network_resolves_only_the_root_by_name() {
+    // An edge pointing at "River" is dangling, even though the root could be named that way
+    let repo = GraphRepository::new(&["water", "river"]).with_edge(
+        "water",
+        "River",
+        RelationType::RelatedTo,
+    );
+
+    let network = related_network(&repo, "WATER", &NetworkQuery::default())
+        .await
+        .unwrap();
+
+    assert_eq!(network.root_id, "water");
+    assert_eq!(node_ids(&network), vec![("water", 0)]);
+    assert!(network.edges.is_empty());
+}
+
+#[tokio::test]
+async fn test_network_unknown_root() {
+    let repo = chain();
+
+    let result = related_network(&repo, "missing", &NetworkQuery::default()).await;
+    assert!(matches!(result, Err(RepositoryError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_network_falls_back_to_related_symbols() {
+    // Without typed relations, related_symbols are followed instead
+    let symbols = [
+        ("water", vec!["river"]),
+        ("river", vec!["ocean"]),
+        ("ocean", vec![]),
+    ]
+    .into_iter()
+    .map(|(id, related)| create_detailed_symbol(id, id, "dream", "", vec![], related))
+    .collect();
+    let repo = GraphRepository::without_relations(symbols);
+
+    let network = related_network(&repo, "water", &NetworkQuery::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        node_ids(&network),
+        vec![("water", 0), ("river", 1), ("ocean", 2)]
+    );
+    assert!(network
+        .edges
+        .iter()
+        .all(|edge| edge.relation_type == RelationType::RelatedTo));
+}
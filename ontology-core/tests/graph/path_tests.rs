@@ -0,0 +1,183 @@
+use ontology_core::db::repository::RepositoryError;
+use ontology_core::domain::{InterpretationLayer, RelationType, Symbol};
+use ontology_core::graph::path::interpretation_snippet;
+use ontology_core::graph::{find_path, PathAlgorithm, PathQuery};
+
+use super::GraphRepository;
+
+fn steps(path: &ontology_core::graph::SymbolPath) -> Vec<&str> {
+    path.steps.iter().map(|step| step.to_id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_bfs_finds_fewest_hops() {
+    // snake -> skin -> rebirth, and a longer route through ouroboros and cycle
+    let repo = GraphRepository::new(&["snake", "skin", "rebirth", "ouroboros", "cycle"])
+        .with_edge("snake", "skin", RelationType::TransformsInto)
+        .with_edge("skin", "rebirth", RelationType::Evokes)
+        .with_edge("snake", "ouroboros", RelationType::PartOf)
+        .with_edge("ouroboros", "cycle", RelationType::Evokes)
+        .with_edge("cycle", "rebirth", RelationType::SimilarTo);
+
+    let path = find_path(&repo, "snake", "rebirth", &PathQuery::default())
+        .await
+        .unwrap()
+        .expect("path should exist");
+
+    assert_eq!(path.algorithm, PathAlgorithm::Bfs);
+    assert_eq!(steps(&path), vec!["skin", "rebirth"]);
+    assert_eq!(path.cost, 2.0);
+    assert_eq!(path.steps[0].from_id, "snake");
+    assert_eq!(
+        path.steps[0].relation.relation_type,
+        RelationType::TransformsInto
+    );
+}
+
+#[tokio::test]
+async fn test_dijkstra_prefers_strong_relations() {
+    let repo = GraphRepository::new(&["snake", "skin", "rebirth", "ouroboros", "cycle"])
+        .with_weighted_edge("snake", "skin", RelationType::TransformsInto, Some(0.0))
+        .with_weighted_edge("skin", "rebirth", RelationType::Evokes, Some(0.0))
+        .with_weighted_edge("snake", "ouroboros", RelationType::PartOf, Some(1.0))
+        .with_weighted_edge("ouroboros", "cycle", RelationType::Evokes, Some(1.0))
+        .with_weighted_edge("cycle", "rebirth", RelationType::SimilarTo, Some(1.0));
+
+    // Auto switches to Dijkstra once it sees weighted relations
+    let path = find_path(&repo, "snake", "rebirth", &PathQuery::default())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(path.algorithm, PathAlgorithm::Dijkstra);
+    assert_eq!(steps(&path), vec!["ouroboros", "cycle", "rebirth"]);
+    assert_eq!(path.cost, 3.0);
+
+    // Forcing BFS ignores the weights
+    let query = PathQuery::default().with_algorithm(PathAlgorithm::Bfs);
+    let path = find_path(&repo, "snake", "rebirth", &query)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(steps(&path), vec!["skin", "rebirth"]);
+}
+
+#[tokio::test]
+async fn test_dijkstra_keeps_shorter_routes_within_depth() {
+    // Reaching y over three cheap hops leaves no hop to spare for t, so the
+    // costlier two-hop route to y has to be the one taken
+    let repo = GraphRepository::new(&["s", "a", "b", "c", "y", "t"])
+        .with_weighted_edge("s", "a", RelationType::RelatedTo, Some(1.0))
+        .with_weighted_edge("a", "b", RelationType::RelatedTo, Some(1.0))
+        .with_weighted_edge("b", "y", RelationType::RelatedTo, Some(1.0))
+        .with_weighted_edge("s", "c", RelationType::RelatedTo, Some(0.0))
+        .with_weighted_edge("c", "y", RelationType::RelatedTo, Some(0.1))
+        .with_weighted_edge("y", "t", RelationType::RelatedTo, Some(1.0));
+
+    for algorithm in [PathAlgorithm::Auto, PathAlgorithm::Dijkstra] {
+        let query = PathQuery::default()
+            .with_max_depth(3)
+            .with_algorithm(algorithm);
+        let path = find_path(&repo, "s", "t", &query)
+            .await
+            .unwrap()
+            .expect("path should exist within three hops");
+        assert_eq!(path.algorithm, PathAlgorithm::Dijkstra);
+        assert_eq!(steps(&path), vec!["c", "y", "t"]);
+        assert!((path.cost - 4.9).abs() < 1e-9);
+    }
+}
+
+#[tokio::test]
+async fn test_path_walks_relations_backwards() {
+    let repo = GraphRepository::new(&["moon", "night", "owl"])
+        .with_edge("night", "moon", RelationType::Evokes)
+        .with_edge("owl", "night", RelationType::PartOf);
+
+    let path = find_path(&repo, "moon", "owl", &PathQuery::default())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(steps(&path), vec!["night", "owl"]);
+    assert_eq!(path.steps[0].relation.source_id, "night");
+}
+
+#[tokio::test]
+async fn test_no_path_within_depth() {
+    let repo = GraphRepository::new(&["a", "b", "c", "island"])
+        .with_edge("a", "b", RelationType::RelatedTo)
+        .with_edge("b", "c", RelationType::RelatedTo);
+
+    let result = find_path(&repo, "a", "island", &PathQuery::default())
+        .await
+        .unwrap();
+    assert!(result.is_none());
+
+    let query = PathQuery::default().with_max_depth(1);
+    let result = find_path(&repo, "a", "c", &query).await.unwrap();
+    assert!(result.is_none());
+
+    let query = PathQuery::default().with_relation_types(vec![RelationType::Evokes]);
+    let result = find_path(&repo, "a", "b", &query).await.unwrap();
+    assert!(result.is_none());
+}
+
+#[tokio::test]
+async fn test_path_to_self_and_unknown_symbols() {
+    let repo = GraphRepository::new(&["a"]);
+
+    let path = find_path(&repo, "a", "a", &PathQuery::default())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(path.steps.is_empty());
+
+    let path = find_path(&repo, "A", "a", &PathQuery::default())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(path.source_id, "a");
+
+    let result = find_path(&repo, "a", "missing", &PathQuery::default()).await;
+    assert!(matches!(result, Err(RepositoryError::NotFound(_))));
+}
+
+#[test]
+fn test_interpretation_snippet_prefers_universal() {
+    let mut symbol = Symbol::new(
+        "snake".to_string(),
+        "Snake".to_string(),
+        "dream".to_string(),
+        "A legless reptile".to_string(),
+    );
+    assert_eq!(
+        interpretation_snippet(&symbol).as_deref(),
+        Some("A legless reptile")
+    );
+
+    symbol.add_interpretation_layer(
+        "hindu".to_string(),
+        InterpretationLayer::cultural("hindu", "Kundalini energy"),
+    );
+    assert_eq!(
+        interpretation_snippet(&symbol).as_deref(),
+        Some("Kundalini energy")
+    );
+
+    symbol.add_interpretation_layer(
+        "archetypal".to_string(),
+        InterpretationLayer::universal("Transformation and renewal"),
+    );
+    assert_eq!(
+        interpretation_snippet(&symbol).as_deref(),
+        Some("Transformation and renewal")
+    );
+
+    symbol.add_interpretation_layer(
+        "default".to_string(),
+        InterpretationLayer::universal("x".repeat(300)),
+    );
+    let snippet = interpretation_snippet(&symbol).unwrap();
+    assert_eq!(snippet.chars().count(), 161);
+    assert!(snippet.ends_with('…'));
+}
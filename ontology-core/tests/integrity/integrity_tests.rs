@@ -0,0 +1,182 @@
+use ontology_core::db::memory::Snapshot;
+use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+use ontology_core::domain::{Symbol, SymbolSet};
+use ontology_core::integrity::{self, IntegrityIssue, IntegrityMode, IssueKind, ReferenceKind};
+
+fn symbol(id: &str, related: Vec<&str>) -> Symbol {
+    Symbol::new(
+        id.to_string(),
+        id.to_string(),
+        "nature".to_string(),
+        format!("The {} symbol", id),
+    )
+    .with_related(related)
+}
+
+fn issue(
+    kind: IssueKind,
+    reference: ReferenceKind,
+    owner_id: &str,
+    target_id: &str,
+) -> IntegrityIssue {
+    IntegrityIssue {
+        kind,
+        reference,
+        owner_id: owner_id.to_string(),
+        target_id: target_id.to_string(),
+    }
+}
+
+#[test]
+fn test_integrity_mode_parsing() {
+    assert_eq!(
+        "strict".parse::<IntegrityMode>().unwrap(),
+        IntegrityMode::Strict
+    );
+    assert_eq!(
+        " Lenient ".parse::<IntegrityMode>().unwrap(),
+        IntegrityMode::Lenient
+    );
+    assert!("loose".parse::<IntegrityMode>().is_err());
+    assert_eq!(IntegrityMode::default(), IntegrityMode::Lenient);
+    assert_eq!(IntegrityMode::Strict.to_string(), "strict");
+}
+
+#[test]
+fn test_missing_targets_are_listed_once_in_order() {
+    let targets: Vec<String> = ["comet", "sun", "ghost", "comet"]
+        .iter()
+        .map(|id| id.to_string())
+        .collect();
+
+    assert_eq!(
+        integrity::missing_targets(&targets, |id| id == "sun"),
+        vec!["comet".to_string(), "ghost".to_string()]
+    );
+}
+
+#[test]
+fn test_check_finds_dangling_self_loops_and_duplicates() {
+    let symbols = vec![
+        symbol("sun", vec!["moon", "sun", "moon", "comet"]),
+        symbol("moon", vec!["sun"]),
+    ];
+    let mut sky = SymbolSet::new(
+        "sky".to_string(),
+        "Sky".to_string(),
+        "nature".to_string(),
+        String::new(),
+    );
+    sky.add_symbol(symbols[0].clone());
+    sky.add_symbol(symbol("ghost", vec![]));
+
+    let report = integrity::check(&symbols, &[sky]);
+
+    assert_eq!(report.symbols_checked, 2);
+    assert_eq!(report.symbol_sets_checked, 1);
+    assert_eq!(
+        report.issues,
+        vec![
+            issue(
+                IssueKind::SelfLoop,
+                ReferenceKind::RelatedSymbol,
+                "sun",
+                "sun"
+            ),
+            issue(
+                IssueKind::Duplicate,
+                ReferenceKind::RelatedSymbol,
+                "sun",
+                "moon"
+            ),
+            issue(
+                IssueKind::Dangling,
+                ReferenceKind::RelatedSymbol,
+                "sun",
+                "comet"
+            ),
+            issue(
+                IssueKind::Dangling,
+                ReferenceKind::SetMember,
+                "sky",
+                "ghost"
+            ),
+        ]
+    );
+    assert!(!report.is_clean());
+    assert_eq!(report.count(IssueKind::Dangling), 2);
+    assert_eq!(
+        report.issues[0].to_string(),
+        "self-loop related symbol: sun -> sun"
+    );
+}
+
+#[test]
+fn test_clean_ontology_has_no_issues() {
+    let symbols = vec![symbol("sun", vec!["moon"]), symbol("moon", vec!["sun"])];
+
+    let report = integrity::check(&symbols, &[]);
+
+    assert!(report.is_clean());
+    assert_eq!(
+        report.to_string(),
+        "Checked 2 symbol(s) and 0 symbol set(s): 0 dangling reference(s), \
+         0 self-loop(s), 0 duplicate(s)"
+    );
+}
+
+#[tokio::test]
+async fn test_check_repositories_after_deletes() {
+    // Snapshots load as they are, dangling references included
+    let snapshot = Snapshot::from_json(
+        r#"{
+            "symbols": [
+                {"id": "water", "name": "Water", "category": "nature", "description": "",
+                 "interpretations": {}, "related_symbols": ["river", "ghost"]},
+                {"id": "river", "name": "River", "category": "nature", "description": "",
+                 "interpretations": {}, "related_symbols": ["water"]}
+            ],
+            "symbol_sets": [
+                {"id": "flow", "name": "Flow", "symbols": ["water", "river"]}
+            ]
+        }"#,
+    )
+    .unwrap();
+    let factory = InMemoryRepositoryFactory::from_snapshot(snapshot).unwrap();
+    let symbols = factory.create_symbol_repository();
+    let sets = factory.create_symbol_set_repository();
+
+    let report = integrity::check_repositories(symbols.as_ref(), sets.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(
+        report.issues,
+        vec![issue(
+            IssueKind::Dangling,
+            ReferenceKind::RelatedSymbol,
+            "water",
+            "ghost"
+        )]
+    );
+
+    // Lenient writes keep unknown related symbols for the report to find
+    symbols
+        .update_symbol(symbol("river", vec!["water", "delta"]))
+        .await
+        .unwrap();
+    symbols.delete_symbol("water").await.unwrap();
+
+    let report = integrity::check_repositories(symbols.as_ref(), sets.as_ref())
+        .await
+        .unwrap();
+    assert_eq!(
+        report.issues,
+        vec![issue(
+            IssueKind::Dangling,
+            ReferenceKind::RelatedSymbol,
+            "river",
+            "delta"
+        )],
+        "deleting a symbol should leave no references to it behind"
+    );
+}
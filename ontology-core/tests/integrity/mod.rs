@@ -0,0 +1 @@
+pub mod integrity_tests;
@@ -1,3 +1,8 @@
 pub mod common;
 pub mod domain;
 pub mod db;
+pub mod embedding;
+pub mod extraction;
+pub mod graph;
+pub mod integrity;
+pub mod report;
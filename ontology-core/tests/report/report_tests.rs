@@ -0,0 +1,152 @@
+use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+use ontology_core::domain::{InterpretationLayer, RelationType, Symbol, SymbolRelation};
+use ontology_core::report::{self, CategoryCount, DreamContext, EMOTIONAL_TONE};
+
+fn symbol(id: &str, category: &str, tone: &str) -> Symbol {
+    let mut symbol = Symbol::new(
+        id.to_string(),
+        id.to_string(),
+        category.to_string(),
+        String::new(),
+    );
+    symbol
+        .properties
+        .insert(EMOTIONAL_TONE.to_string(), tone.to_string());
+    symbol
+}
+
+#[test]
+fn test_interpretation_for_prefers_culture_then_default() {
+    let mut snake = symbol("snake", "animal", "negative");
+    snake.interpretations.insert(
+        "default".to_string(),
+        InterpretationLayer::universal("Hidden fears"),
+    );
+    snake.interpretations.insert(
+        "archetypal".to_string(),
+        InterpretationLayer::universal("Renewal"),
+    );
+    snake.interpretations.insert(
+        "jungian".to_string(),
+        InterpretationLayer::cultural("Jungian", "The unconscious stirring"),
+    );
+
+    assert_eq!(
+        snake.interpretation_for(Some("jungian")).unwrap().0,
+        "jungian"
+    );
+    assert_eq!(
+        snake.interpretation_for(Some("celtic")).unwrap().0,
+        "default"
+    );
+    assert_eq!(snake.interpretation_for(None).unwrap().0, "default");
+
+    snake.interpretations.remove("default");
+    assert_eq!(snake.interpretation_for(None).unwrap().0, "archetypal");
+
+    assert!(symbol("moon", "nature", "neutral")
+        .interpretation_for(None)
+        .is_none());
+}
+
+#[test]
+fn test_tone_summary_counts_and_ties() {
+    let symbols = [
+        symbol("snake", "animal", "Negative"),
+        symbol("water", "nature", "negative"),
+        symbol("sun", "nature", "positive"),
+    ];
+
+    let summary = report::summarize_tone(&symbols, Some("negative"));
+    assert_eq!(summary.counts["negative"], 2);
+    assert_eq!(summary.dominant.as_deref(), Some("negative"));
+    assert_eq!(summary.matches_context, Some(true));
+
+    let tied = report::summarize_tone(&symbols[1..], Some("positive"));
+    assert_eq!(tied.dominant, None);
+    assert_eq!(tied.matches_context, None);
+
+    assert_eq!(report::summarize_tone(&symbols, None).matches_context, None);
+}
+
+#[tokio::test]
+async fn test_interpret_dream_builds_a_report() {
+    let factory = InMemoryRepositoryFactory::new();
+    let repository = factory.create_symbol_repository();
+
+    let mut snake = symbol("snake", "animal", "negative");
+    snake.interpretations.insert(
+        "default".to_string(),
+        InterpretationLayer::universal("Hidden fears"),
+    );
+    snake.interpretations.insert(
+        "celtic".to_string(),
+        InterpretationLayer::cultural("celtic", "Healing waters"),
+    );
+    for symbol in [
+        snake,
+        symbol("water", "nature", "negative"),
+        symbol("tower", "place", "positive"),
+        symbol("moon", "nature", "neutral"),
+    ] {
+        repository.create_symbol(symbol).await.unwrap();
+    }
+    for relation in [
+        SymbolRelation::new("snake", "water", RelationType::TransformsInto),
+        SymbolRelation::new("water", "tower", RelationType::SimilarTo),
+        SymbolRelation::new("tower", "water", RelationType::SimilarTo),
+        // The moon is not in the dream
+        SymbolRelation::new("water", "moon", RelationType::RelatedTo),
+    ] {
+        repository.add_relation(relation).await.unwrap();
+    }
+
+    let context = DreamContext::default()
+        .with_culture("Celtic")
+        .with_emotional_tone("positive");
+    let report = report::interpret_dream(
+        repository.as_ref(),
+        "A snake slid into the water below a tower",
+        &context,
+    )
+    .await
+    .unwrap();
+
+    let ids: Vec<&str> = report
+        .symbols
+        .iter()
+        .map(|s| s.symbol.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["snake", "water", "tower"]);
+    assert_eq!(report.symbols[0].spans[0].text, "snake");
+
+    let interpretation = report.symbols[0].interpretation.as_ref().unwrap();
+    assert_eq!(interpretation.context, "celtic");
+    assert_eq!(interpretation.layer.text, "Healing waters");
+    assert!(report.symbols[1].interpretation.is_none());
+
+    let relationships: Vec<(&str, &str, RelationType)> = report
+        .relationships
+        .iter()
+        .map(|r| (r.source_id.as_str(), r.target_id.as_str(), r.relation_type))
+        .collect();
+    assert_eq!(
+        relationships,
+        vec![
+            ("snake", "water", RelationType::TransformsInto),
+            ("water", "tower", RelationType::SimilarTo),
+        ]
+    );
+
+    assert_eq!(
+        report.dominant_categories[0],
+        CategoryCount {
+            category: "animal".to_string(),
+            count: 1
+        }
+    );
+    assert_eq!(report.dominant_categories.len(), 3);
+
+    assert_eq!(report.tone.dominant.as_deref(), Some("negative"));
+    assert_eq!(report.tone.matches_context, Some(false));
+}
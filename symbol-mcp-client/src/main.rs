@@ -1,46 +1,49 @@
 // SPDX-License-Identifier: MPL-2.0 OR Commercial
 // Copyright (c) 2024 Symbol Ontology Contributors
 
-use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use tokio::signal;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-// Import rmcp types
-use rmcp::transport::sse_server::{SseServer, SseServerConfig};
-
-mod mcp;
-use mcp::service::SymbolService;
-
-// TODO: SSE Timeout Alternatives
-//
-// There are several better approaches to handling SSE timeouts:
-//
-// 1. Fork the RMCP library to add built-in heartbeat support
-//    - Add a heartbeat_interval option to SseServerConfig
-//    - Modify the SseServer implementation to send periodic comments
-//
-// 2. Use a different MCP transport that supports WebSockets
-//    - WebSockets have built-in ping/pong frames for keepalive
-//    - The MCP spec supports WebSocket transport
-//
-// 3. Modify the proxy configuration (for fly.io)
-//    - Set HTTP_PROXY_CONNECT_TIMEOUT and HTTP_PROXY_IDLE_TIMEOUT environment variables
-//    - Configure TCP keepalive at the operating system level
-//
-// 4. Use a custom middleware approach to wrap the SSE stream
-//    - Implement a wrapper around SseServer that injects heartbeat events
-//    - This is the most complex but least invasive option
+use rmcp::ServiceExt;
+
+use ontology_core::db::repository::{HttpRepositoryFactory, ListQuery, RepositoryError};
+use ontology_core::db::{DatabaseBackend, DatabasePool};
+use ontology_core::embedding;
+use ontology_core::integrity::IntegrityMode;
+use symbol_mcp_client::mcp::prompts::PromptLibrary;
+use symbol_mcp_client::mcp::service::SymbolService;
+use symbol_mcp_client::mcp::transport::{sse, streamable_http};
+
+/// Default seconds between heartbeats on idle event streams
+const DEFAULT_HEARTBEAT_INTERVAL: u64 = 30;
+
+/// How MCP clients reach the server
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Transport {
+    /// JSON-RPC over stdin and stdout, for clients that spawn the server
+    Stdio,
+    /// Server-sent events over HTTP, at /sse and /message
+    Sse,
+    /// Streamable HTTP at /mcp, with the SSE endpoints alongside
+    Http,
+}
 
 #[derive(Parser, Debug)]
 #[clap(name = "symbol-mcp", about = "Symbol Ontology MCP Client", version)]
 struct Args {
+    /// Transport to serve MCP over
+    #[arg(long, env("TRANSPORT"), value_enum, default_value = "http")]
+    transport: Transport,
+
     /// Port to listen on for local server
     #[arg(short, long, env("PORT"), default_value = "3000")]
     port: u16,
@@ -53,7 +56,7 @@ struct Args {
     #[arg(short, long, env("VERBOSE"), action = clap::ArgAction::Count)]
     verbose: u8,
 
-    /// Database URL for local database
+    /// Database URL (postgres://... or sqlite://path/to/file.db)
     #[clap(
         long,
         env = "DATABASE_URL",
@@ -61,26 +64,64 @@ struct Args {
     )]
     database_url: String,
 
-    /// Heartbeat interval in seconds
-    #[arg(long, env("HEARTBEAT_INTERVAL"), default_value = "30")]
+    /// Storage backend (postgres, sqlite or memory); defaults to the database URL scheme
+    #[arg(long, env("BACKEND"))]
+    backend: Option<DatabaseBackend>,
+
+    /// API server to read and write the ontology through instead of a database
+    #[arg(long, env("API_URL"))]
+    api_url: Option<String>,
+
+    /// JSON snapshot to seed the memory backend with
+    #[arg(long, env("SNAPSHOT_PATH"))]
+    snapshot: Option<PathBuf>,
+
+    /// Reject writes naming unknown symbols (strict) or log them (lenient)
+    #[arg(long, env("INTEGRITY_MODE"), default_value_t = IntegrityMode::Lenient)]
+    integrity: IntegrityMode,
+
+    /// Directory of prompt files (*.json) to serve alongside the built-in prompts
+    #[arg(long, env("PROMPTS_DIR"))]
+    prompts_dir: Option<PathBuf>,
+
+    /// Seconds between heartbeats on idle event streams; 0 disables them on /mcp, while /sse
+    /// falls back to rmcp's 15 seconds
+    #[arg(long, env("HEARTBEAT_INTERVAL"), default_value_t = DEFAULT_HEARTBEAT_INTERVAL)]
     heartbeat_interval: u64,
+
+    /// Heartbeat intervals a streamable HTTP session may go with no request in flight and no
+    /// open stream before it is ended; 0 keeps idle sessions until the client ends them
+    #[arg(long, env("SESSION_IDLE_HEARTBEATS"), default_value = "10")]
+    session_idle_heartbeats: u32,
+
+    /// Serve the tools that create, change and delete symbols
+    #[arg(long, env("ALLOW_WRITES"))]
+    allow_writes: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
+    // Parse command line arguments
+    let args = Args::parse();
+
+    // Initialize tracing; stdout carries the protocol in stdio mode, so log to stderr
+    let log_writer = match args.transport {
+        Transport::Stdio => BoxMakeWriter::new(std::io::stderr),
+        Transport::Sse | Transport::Http => BoxMakeWriter::new(std::io::stdout),
+    };
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
                 "symbol_mcp_client=info,rmcp=info,tower_http=debug,axum::rejection=trace".into()
             }),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(log_writer)
+                .with_ansi(args.transport != Transport::Stdio),
+        )
         .init();
 
-    // Parse command line arguments
-    let args = Args::parse();
-
     // Set up logging level based on verbosity
     let verbosity = match args.verbose {
         0 => "info",
@@ -95,50 +136,86 @@ async fn main() -> Result<()> {
     // Create socket address - always bind to 0.0.0.0 to listen on all interfaces
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
 
-    // Connect to database
-    info!("Connecting to database at {}", args.database_url);
-    let pool = ontology_core::db::pool::create_pool(&args.database_url)
+    // Go through the API server when given one, otherwise open the selected backend,
+    // falling back to the database URL scheme
+    let (service, embed_at_startup) = if let Some(api_url) = &args.api_url {
+        let factory = HttpRepositoryFactory::new(api_url)?;
+        info!("Using API server at {}", factory.client().base_url());
+        (SymbolService::from_factory(&factory), false)
+    } else {
+        if args.backend != Some(DatabaseBackend::Memory) {
+            info!("Connecting to database at {}", args.database_url);
+        }
+        let pool = DatabasePool::open(args.backend, &args.database_url, args.snapshot.as_deref())
+            .await
+            .expect("Failed to create database connection pool");
+        pool.init()
+            .await
+            .expect("Failed to initialize database schema");
+        info!("Using {} backend", pool.backend());
+        let factory = pool.repository_factory_with_integrity(args.integrity);
+        // Only write embeddings when writes are allowed, except to the in-memory store,
+        // which nothing persists and the seeder cannot reach
+        let embed = args.allow_writes || pool.backend() == DatabaseBackend::Memory;
+        (SymbolService::from_factory(factory.as_ref()), embed)
+    };
+
+    // Create service with repositories
+    let mut service = service.with_writes(args.allow_writes);
+    if args.allow_writes {
+        info!("Write tools enabled");
+    }
+
+    // Add any prompts from the prompts directory to the built-in ones
+    if let Some(dir) = &args.prompts_dir {
+        service = service.with_prompts(PromptLibrary::builtin().load_dir(dir)?);
+    }
+    info!("Serving {} prompt(s)", service.prompts.len());
+
+    // Embed any symbols semantic search cannot find yet; a read-only server leaves that to
+    // the seeder's embed command
+    if embed_at_startup {
+        match embedding::backfill(
+            service.symbol_repository.as_ref(),
+            service.embedder.as_ref(),
+            false,
+        )
+        .await
+        {
+            Ok(count) => info!("Embedded {} symbol(s) for semantic search", count),
+            Err(RepositoryError::NotImplemented(msg)) => {
+                info!("Semantic search unavailable: {}", msg)
+            }
+            Err(e) => error!("Failed to embed symbols: {:?}", e),
+        }
+    }
+
+    // Count the symbols and categories without loading them
+    let total_count = match service
+        .symbol_repository
+        .list_symbols(&ListQuery::new().limit(1))
         .await
-        .expect("Failed to create database connection pool");
-
-    // Create repositories
-    let symbol_repo = std::sync::Arc::new(ontology_core::db::repository::PgSymbolRepository::new(
-        pool.clone(),
-    )) as std::sync::Arc<dyn ontology_core::db::repository::SymbolRepository>;
-    let symbol_set_repo = std::sync::Arc::new(
-        ontology_core::db::repository::PgSymbolSetRepository::new(pool.clone()),
-    )
-        as std::sync::Arc<dyn ontology_core::db::repository::SymbolSetRepository>;
-
-    // Query for symbols count and categories
-    let symbols = match symbol_repo.list_symbols(None).await {
-        Ok(symbols) => {
+    {
+        Ok(page) => {
             info!(
                 "Successfully queried {} symbols from database",
-                symbols.len()
+                page.total_count
             );
-            symbols
+            page.total_count
         }
         Err(e) => {
             error!("Failed to query symbols from database: {:?}", e);
-            Vec::new()
+            0
         }
     };
-
-    let mut category_counts = HashMap::new();
-    for symbol in &symbols {
-        *category_counts.entry(symbol.category.clone()).or_insert(0) += 1;
-    }
-
-    // Create service with repositories
-    let service = SymbolService {
-        symbol_repository: symbol_repo.clone(),
-        symbol_set_repository: symbol_set_repo.clone(),
-    };
+    let category_counts = service.category_counts().await.unwrap_or_else(|e| {
+        error!("Failed to count symbols by category: {:?}", e);
+        Vec::new()
+    });
 
     info!("=== Symbol Ontology MCP Server ===");
     info!("Version: {}", env!("CARGO_PKG_VERSION"));
-    info!("Loaded {} total symbols", symbols.len());
+    info!("Loaded {} total symbols", total_count);
 
     let categories: Vec<String> = category_counts
         .iter()
@@ -146,45 +223,72 @@ async fn main() -> Result<()> {
         .collect();
     info!("Categories: {}", categories.join(", "));
 
-    info!("Starting server on {}", addr);
-    info!("SSE endpoint: http://0.0.0.0:{}/sse", port);
-    info!("Message endpoint: http://0.0.0.0:{}/message", port);
-    info!("Heartbeat interval: {} seconds", args.heartbeat_interval);
-    info!("==============================");
+    let ct = CancellationToken::new();
 
-    // Configure heartbeat interval
-    let heartbeat_interval = Duration::from_secs(args.heartbeat_interval);
+    if args.transport == Transport::Stdio {
+        info!("Serving MCP over stdio");
+        info!("==============================");
 
-    // Create a proper transport
-    let config = SseServerConfig {
-        bind: addr,
-        sse_path: "/sse".to_string(),
-        post_path: "/message".to_string(),
-        ct: CancellationToken::new(),
-    };
+        let server = service.serve_with_ct(rmcp::transport::stdio(), ct.clone()).await?;
 
-    let server_handle = SseServer::serve_with_config(config)
-        .await?
-        .with_service(move || service.clone());
+        // The session ends when the client closes stdin or on Ctrl+C
+        tokio::spawn(async move {
+            if signal::ctrl_c().await.is_ok() {
+                info!("Received Ctrl+C, shutting down gracefully");
+                ct.cancel();
+            }
+        });
+        let reason = server.waiting().await?;
+        info!("MCP session ended: {:?}", reason);
+        return Ok(());
+    }
 
-    // We need to run this in a task so we can also listen for Ctrl+C
-    let _server_task = tokio::spawn(async {
-        info!("MCP server started");
-        // Just keep the server running without trying to await it
-    });
+    let heartbeat =
+        (args.heartbeat_interval > 0).then(|| Duration::from_secs(args.heartbeat_interval));
 
-    // Wait for Ctrl+C
-    match signal::ctrl_c().await {
-        Ok(()) => {
-            info!("Received Ctrl+C, shutting down gracefully");
-        }
-        Err(err) => {
-            error!("Unable to listen for shutdown signal: {}", err);
+    info!("Starting server on {}", addr);
+    let mut router = sse::router(service.clone(), heartbeat, ct.clone());
+    if args.transport == Transport::Http {
+        // With heartbeats off, idle sessions are timed by the default interval
+        let idle_timeout = (args.session_idle_heartbeats > 0).then(|| {
+            heartbeat.unwrap_or(Duration::from_secs(DEFAULT_HEARTBEAT_INTERVAL))
+                * args.session_idle_heartbeats
+        });
+        router = router.merge(streamable_http::router(
+            service,
+            heartbeat,
+            idle_timeout,
+            ct.clone(),
+        ));
+        match idle_timeout {
+            Some(timeout) => info!("Idle sessions expire after {} seconds", timeout.as_secs()),
+            None => info!("Idle sessions never expire"),
         }
+        info!(
+            "Streamable HTTP endpoint: http://0.0.0.0:{}{}",
+            port,
+            streamable_http::MCP_PATH
+        );
+    }
+    info!("SSE endpoint: http://0.0.0.0:{}{}", port, sse::SSE_PATH);
+    info!("Message endpoint: http://0.0.0.0:{}{}", port, sse::MESSAGE_PATH);
+    match heartbeat {
+        Some(interval) => info!("Heartbeat interval: {} seconds", interval.as_secs()),
+        None => info!("Heartbeats disabled"),
     }
+    info!("==============================");
 
-    // Cancel the server
-    server_handle.cancel();
+    // Serve until Ctrl+C, then end every session so open streams close
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            match signal::ctrl_c().await {
+                Ok(()) => info!("Received Ctrl+C, shutting down gracefully"),
+                Err(err) => error!("Unable to listen for shutdown signal: {}", err),
+            }
+            ct.cancel();
+        })
+        .await?;
     info!("MCP server shut down");
 
     Ok(())
@@ -0,0 +1,191 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+use ontology_core::domain::{InterpretationLayer, InterpretationLevel};
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{AddInterpretationParams, SymbolDTO, SymbolWriteResponse};
+
+pub struct AddInterpretationHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl AddInterpretationHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        AddInterpretationHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for AddInterpretationHandler {
+    fn method_name(&self) -> &str {
+        "add_interpretation"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: AddInterpretationParams = call.parse_params()?;
+
+        if params.symbol_id.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Symbol ID cannot be empty".to_string(),
+            ));
+        }
+        let context = params.context.trim();
+        if context.is_empty() {
+            return Err(RmcpError::ParseError(
+                "Interpretation context cannot be empty".to_string(),
+            ));
+        }
+        if params.text.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Interpretation text cannot be empty".to_string(),
+            ));
+        }
+
+        let level = match params.level.trim() {
+            "" => InterpretationLevel::from_context(context),
+            level => level
+                .parse::<InterpretationLevel>()
+                .map_err(RmcpError::ParseError)?,
+        };
+        let mut layer = InterpretationLayer::new(level, params.text.trim());
+        match params.tradition.trim() {
+            "" if level == InterpretationLevel::Cultural => layer = layer.with_tradition(context),
+            "" => {}
+            tradition => layer = layer.with_tradition(tradition),
+        }
+        if !params.source.trim().is_empty() {
+            layer = layer.with_source(params.source.trim());
+        }
+
+        let mut symbol = self
+            .symbol_repository
+            .get_symbol(params.symbol_id.trim())
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+        symbol.add_interpretation_layer(context.to_string(), layer);
+
+        let updated = self
+            .symbol_repository
+            .update_symbol(symbol)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(SymbolWriteResponse {
+            symbol: SymbolDTO::from_symbol(&updated),
+        })?)
+    }
+}
+
+pub fn add_interpretation(
+    symbol_repository: Arc<dyn SymbolRepository>,
+) -> AddInterpretationHandler {
+    AddInterpretationHandler::new(symbol_repository)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+    use ontology_core::domain::Symbol;
+
+    async fn add(
+        repository: &Arc<dyn SymbolRepository>,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, RmcpError> {
+        add_interpretation(repository.clone())
+            .handle(MethodCall {
+                id: "1".to_string(),
+                method: "add_interpretation".to_string(),
+                params,
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_add_interpretation_replaces_its_context() {
+        let repository = InMemoryRepositoryFactory::new().create_symbol_repository();
+        let mut water = Symbol::new(
+            "water".to_string(),
+            "Water".to_string(),
+            "nature".to_string(),
+            String::new(),
+        )
+        .with_aliases(vec!["aqua"]);
+        water.add_interpretation("japanese".to_string(), "Purity".to_string());
+        repository.create_symbol(water).await.unwrap();
+
+        let result = add(
+            &repository,
+            serde_json::json!({
+                "symbol_id": "aqua",
+                "context": " japanese ",
+                "text": " Cleansing and renewal ",
+                "source": "Folk tradition"
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result["symbol"]["id"], "water");
+        let stored = repository.get_symbol("water").await.unwrap();
+        assert_eq!(stored.interpretations.len(), 1);
+        let layer = &stored.interpretations["japanese"];
+        assert_eq!(layer.text, "Cleansing and renewal");
+        // A context naming a tradition is a cultural reading of it
+        assert_eq!(layer.level, InterpretationLevel::Cultural);
+        assert_eq!(layer.tradition.as_deref(), Some("japanese"));
+        assert_eq!(layer.source.as_deref(), Some("Folk tradition"));
+    }
+
+    #[tokio::test]
+    async fn test_add_interpretation_checks_its_input() {
+        let repository = InMemoryRepositoryFactory::new().create_symbol_repository();
+        repository
+            .create_symbol(Symbol::new(
+                "water".to_string(),
+                "Water".to_string(),
+                "nature".to_string(),
+                String::new(),
+            ))
+            .await
+            .unwrap();
+
+        let blank_text = serde_json::json!({
+            "symbol_id": "water",
+            "context": "default",
+            "text": " "
+        });
+        let bad_level = serde_json::json!({
+            "symbol_id": "water",
+            "context": "default",
+            "text": "Emotion",
+            "level": "cosmic"
+        });
+        let unknown_symbol = serde_json::json!({
+            "symbol_id": "fire",
+            "context": "default",
+            "text": "Passion"
+        });
+
+        assert!(matches!(
+            add(&repository, blank_text).await,
+            Err(RmcpError::ParseError(_))
+        ));
+        assert!(matches!(
+            add(&repository, bad_level).await,
+            Err(RmcpError::ParseError(_))
+        ));
+        assert!(matches!(
+            add(&repository, unknown_symbol).await,
+            Err(RmcpError::NotFound(_))
+        ));
+        let stored = repository.get_symbol("water").await.unwrap();
+        assert!(stored.interpretations.is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+use ontology_core::domain::Symbol;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{CreateSymbolParams, SymbolDTO, SymbolWriteResponse};
+
+pub struct CreateSymbolHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl CreateSymbolHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        CreateSymbolHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for CreateSymbolHandler {
+    fn method_name(&self) -> &str {
+        "create_symbol"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: CreateSymbolParams = call.parse_params()?;
+
+        if params.id.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Symbol ID cannot be empty".to_string(),
+            ));
+        }
+        if params.name.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Symbol name cannot be empty".to_string(),
+            ));
+        }
+
+        let mut symbol = Symbol::new(params.id, params.name, params.category, params.description);
+        symbol.aliases = params.aliases;
+        symbol.properties = params.properties;
+        for (language, name) in params.localized_names {
+            symbol
+                .add_localized_name(&language, name)
+                .map_err(RmcpError::ParseError)?;
+        }
+        symbol.normalize();
+        symbol.validate().map_err(RmcpError::ParseError)?;
+
+        let created = self
+            .symbol_repository
+            .create_symbol(symbol)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(SymbolWriteResponse {
+            symbol: SymbolDTO::from_symbol(&created),
+        })?)
+    }
+}
+
+pub fn create_symbol(symbol_repository: Arc<dyn SymbolRepository>) -> CreateSymbolHandler {
+    CreateSymbolHandler::new(symbol_repository)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+
+    #[tokio::test]
+    async fn test_create_trims_every_field() {
+        let repository = InMemoryRepositoryFactory::new().create_symbol_repository();
+
+        let result = create_symbol(repository.clone())
+            .handle(MethodCall {
+                id: "1".to_string(),
+                method: "create_symbol".to_string(),
+                params: serde_json::json!({
+                    "id": " water ",
+                    "name": "Water\n",
+                    "category": " nature",
+                    "description": " Symbolizes emotions ",
+                    "aliases": [" aqua "],
+                    "properties": { " element ": " water " },
+                    "localized_names": { "es": " Agua" }
+                }),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result["symbol"]["id"], "water");
+        let stored = repository.get_symbol("water").await.unwrap();
+        assert_eq!(stored.name, "Water");
+        assert_eq!(stored.category, "nature");
+        assert_eq!(stored.description, "Symbolizes emotions");
+        assert_eq!(stored.aliases, vec!["aqua"]);
+        assert_eq!(stored.properties["element"], "water");
+        assert_eq!(stored.localized_names["es"], "Agua");
+    }
+}
@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{DeleteSymbolParams, DeleteSymbolResponse};
+
+pub struct DeleteSymbolHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl DeleteSymbolHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        DeleteSymbolHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for DeleteSymbolHandler {
+    fn method_name(&self) -> &str {
+        "delete_symbol"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: DeleteSymbolParams = call.parse_params()?;
+
+        // Deletion goes by exact ID only, so an alias can never remove the
+        // wrong symbol
+        let id = params.id.trim();
+        if id.is_empty() {
+            return Err(RmcpError::ParseError(
+                "Symbol ID cannot be empty".to_string(),
+            ));
+        }
+
+        self.symbol_repository
+            .delete_symbol(id)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(DeleteSymbolResponse {
+            id: id.to_string(),
+            deleted: true,
+        })?)
+    }
+}
+
+pub fn delete_symbol(symbol_repository: Arc<dyn SymbolRepository>) -> DeleteSymbolHandler {
+    DeleteSymbolHandler::new(symbol_repository)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+    use ontology_core::domain::Symbol;
+
+    async fn delete(
+        repository: &Arc<dyn SymbolRepository>,
+        id: &str,
+    ) -> Result<serde_json::Value, RmcpError> {
+        delete_symbol(repository.clone())
+            .handle(MethodCall {
+                id: "1".to_string(),
+                method: "delete_symbol".to_string(),
+                params: serde_json::json!({ "id": id }),
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_the_symbol() {
+        let repository = InMemoryRepositoryFactory::new().create_symbol_repository();
+        repository
+            .create_symbol(
+                Symbol::new(
+                    "water".to_string(),
+                    "Water".to_string(),
+                    "nature".to_string(),
+                    String::new(),
+                )
+                .with_aliases(vec!["aqua"]),
+            )
+            .await
+            .unwrap();
+
+        // Aliases never reach a symbol for deletion
+        assert!(matches!(
+            delete(&repository, "aqua").await,
+            Err(RmcpError::NotFound(_))
+        ));
+        assert!(repository.get_symbol("water").await.is_ok());
+
+        let result = delete(&repository, " water ").await.unwrap();
+        assert_eq!(
+            result,
+            serde_json::json!({ "id": "water", "deleted": true })
+        );
+        assert!(repository.get_symbol("water").await.is_err());
+
+        assert!(matches!(
+            delete(&repository, " ").await,
+            Err(RmcpError::ParseError(_))
+        ));
+    }
+}
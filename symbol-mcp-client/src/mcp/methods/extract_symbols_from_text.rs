@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+use ontology_core::extraction;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{ExtractSymbolsParams, ExtractSymbolsResponse, ExtractedSymbolDTO};
+
+pub struct ExtractSymbolsFromTextHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl ExtractSymbolsFromTextHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        ExtractSymbolsFromTextHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for ExtractSymbolsFromTextHandler {
+    fn method_name(&self) -> &str {
+        "extract_symbols_from_text"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: ExtractSymbolsParams = call.parse_params()?;
+
+        if params.text.trim().is_empty() {
+            return Err(RmcpError::ParseError("Text cannot be empty".to_string()));
+        }
+
+        // Spans are offsets into the text as given, so it is not trimmed
+        let extracted =
+            extraction::extract_symbols_from_text(self.symbol_repository.as_ref(), &params.text)
+                .await
+                .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(ExtractSymbolsResponse {
+            total_count: extracted.len(),
+            symbols: extracted.iter().map(ExtractedSymbolDTO::from).collect(),
+        })?)
+    }
+}
+
+pub fn extract_symbols_from_text(
+    symbol_repository: Arc<dyn SymbolRepository>,
+) -> ExtractSymbolsFromTextHandler {
+    ExtractSymbolsFromTextHandler::new(symbol_repository)
+}
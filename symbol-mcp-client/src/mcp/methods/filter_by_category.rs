@@ -7,9 +7,11 @@ use ontology_core::domain::Symbol;
 
 use crate::mcp::methods::{
     get_symbols::{Handler, MethodCall, RmcpError},
-    utils::repository_error_to_rmcp_error,
+    utils::{list_query, repository_error_to_rmcp_error},
+};
+use crate::mcp::schema::{
+    interpretation_dtos, CategorySymbolsParams, GetSymbolsResponse, SymbolDTO,
 };
-use crate::mcp::schema::{CategorySymbolsParams, GetSymbolsResponse, SymbolDTO};
 
 pub struct FilterByCategoryHandler {
     symbol_repository: Arc<dyn SymbolRepository>,
@@ -27,6 +29,9 @@ impl FilterByCategoryHandler {
             category: symbol.category.clone(),
             description: symbol.description.clone(),
             related_symbols: symbol.related_symbols.clone(),
+            interpretations: interpretation_dtos(&symbol.interpretations),
+            aliases: symbol.aliases.clone(),
+            localized_names: symbol.localized_names.clone(),
         }
     }
 }
@@ -47,25 +52,24 @@ impl Handler for FilterByCategoryHandler {
             ));
         }
 
-        // Fetch symbols by category
-        let symbols = self
+        let query = list_query(
+            Some(&params.category),
+            params.limit,
+            &params.cursor,
+            &params.sort,
+        )?;
+
+        // Fetch one page of symbols in the category
+        let page = self
             .symbol_repository
-            .list_symbols(Some(&params.category))
+            .list_symbols(&query)
             .await
             .map_err(repository_error_to_rmcp_error)?;
 
-        // Apply limit and convert to DTOs
-        let symbol_dtos = symbols
-            .iter()
-            .take(params.limit)
-            .map(Self::to_dto)
-            .collect::<Vec<_>>();
-
-        let total_count = symbols.len();
-
         Ok(serde_json::to_value(GetSymbolsResponse {
-            symbols: symbol_dtos,
-            total_count,
+            symbols: page.items.iter().map(Self::to_dto).collect(),
+            total_count: page.total_count,
+            next_cursor: page.next_cursor,
         })?)
     }
 }
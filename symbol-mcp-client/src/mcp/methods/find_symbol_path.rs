@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+use ontology_core::domain::RelationType;
+use ontology_core::graph::{find_path, PathAlgorithm, PathQuery};
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{FindSymbolPathParams, FindSymbolPathResponse, PathStepDTO};
+
+pub struct FindSymbolPathHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl FindSymbolPathHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        FindSymbolPathHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for FindSymbolPathHandler {
+    fn method_name(&self) -> &str {
+        "find_symbol_path"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: FindSymbolPathParams = call.parse_params()?;
+
+        // Validate symbol IDs
+        let from_id = params.from_id.trim();
+        let to_id = params.to_id.trim();
+        if from_id.is_empty() || to_id.is_empty() {
+            return Err(RmcpError::ParseError(
+                "Both from_id and to_id are required".to_string(),
+            ));
+        }
+
+        if params.max_depth == 0 {
+            return Err(RmcpError::ParseError(
+                "max_depth must be at least 1".to_string(),
+            ));
+        }
+
+        let relation_types = params
+            .relation_types
+            .iter()
+            .map(|t| t.parse::<RelationType>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RmcpError::ParseError)?;
+
+        let algorithm = match params.algorithm.as_deref() {
+            Some(algorithm) => algorithm
+                .parse::<PathAlgorithm>()
+                .map_err(RmcpError::ParseError)?,
+            None => PathAlgorithm::Auto,
+        };
+
+        let mut query = PathQuery::default()
+            .with_max_depth(params.max_depth)
+            .with_algorithm(algorithm);
+        if !relation_types.is_empty() {
+            query = query.with_relation_types(relation_types);
+        }
+
+        let path = find_path(self.symbol_repository.as_ref(), from_id, to_id, &query)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        let response = match path {
+            Some(path) => FindSymbolPathResponse {
+                found: true,
+                from_id: path.source_id,
+                to_id: path.target_id,
+                steps: path.steps.iter().map(PathStepDTO::from).collect(),
+                cost: Some(path.cost),
+                algorithm: Some(path.algorithm.to_string()),
+            },
+            None => FindSymbolPathResponse {
+                found: false,
+                from_id: from_id.to_string(),
+                to_id: to_id.to_string(),
+                steps: Vec::new(),
+                cost: None,
+                algorithm: None,
+            },
+        };
+
+        Ok(serde_json::to_value(response)?)
+    }
+}
+
+pub fn find_symbol_path(symbol_repository: Arc<dyn SymbolRepository>) -> FindSymbolPathHandler {
+    FindSymbolPathHandler::new(symbol_repository)
+}
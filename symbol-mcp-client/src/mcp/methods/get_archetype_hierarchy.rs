@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::ArchetypeRepository;
+use ontology_core::domain::ArchetypeNode;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{ArchetypeHierarchyParams, ArchetypeHierarchyResponse, ArchetypeNodeDTO};
+
+pub struct GetArchetypeHierarchyHandler {
+    archetype_repository: Arc<dyn ArchetypeRepository>,
+}
+
+impl GetArchetypeHierarchyHandler {
+    pub fn new(archetype_repository: Arc<dyn ArchetypeRepository>) -> Self {
+        GetArchetypeHierarchyHandler {
+            archetype_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for GetArchetypeHierarchyHandler {
+    fn method_name(&self) -> &str {
+        "get_archetype_hierarchy"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: ArchetypeHierarchyParams = call.parse_params()?;
+
+        let root_id = params.root_id.trim();
+        let root_id = (!root_id.is_empty()).then_some(root_id);
+
+        let roots = self
+            .archetype_repository
+            .get_hierarchy(root_id)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(ArchetypeHierarchyResponse {
+            total_count: roots.iter().map(ArchetypeNode::size).sum(),
+            roots: roots.iter().map(ArchetypeNodeDTO::from).collect(),
+        })?)
+    }
+}
+
+pub fn get_archetype_hierarchy(
+    archetype_repository: Arc<dyn ArchetypeRepository>,
+) -> GetArchetypeHierarchyHandler {
+    GetArchetypeHierarchyHandler::new(archetype_repository)
+}
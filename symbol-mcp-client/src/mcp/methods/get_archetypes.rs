@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::ArchetypeRepository;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{ArchetypeDTO, GetArchetypesParams, GetArchetypesResponse};
+
+pub struct GetArchetypesHandler {
+    archetype_repository: Arc<dyn ArchetypeRepository>,
+}
+
+impl GetArchetypesHandler {
+    pub fn new(archetype_repository: Arc<dyn ArchetypeRepository>) -> Self {
+        GetArchetypesHandler {
+            archetype_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for GetArchetypesHandler {
+    fn method_name(&self) -> &str {
+        "get_archetypes"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: GetArchetypesParams = call.parse_params()?;
+
+        let archetypes = self
+            .archetype_repository
+            .list_archetypes()
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(GetArchetypesResponse {
+            total_count: archetypes.len(),
+            archetypes: archetypes
+                .iter()
+                .take(params.limit)
+                .map(ArchetypeDTO::from)
+                .collect(),
+        })?)
+    }
+}
+
+pub fn get_archetypes(archetype_repository: Arc<dyn ArchetypeRepository>) -> GetArchetypesHandler {
+    GetArchetypesHandler::new(archetype_repository)
+}
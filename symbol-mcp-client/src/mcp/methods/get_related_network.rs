@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+use ontology_core::domain::RelationType;
+use ontology_core::graph::{related_network, NetworkQuery};
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{
+    NetworkNodeDTO, RelatedNetworkParams, RelatedNetworkResponse, RelationDTO, SymbolDTO,
+};
+
+pub struct GetRelatedNetworkHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl GetRelatedNetworkHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        GetRelatedNetworkHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for GetRelatedNetworkHandler {
+    fn method_name(&self) -> &str {
+        "get_related_network"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: RelatedNetworkParams = call.parse_params()?;
+
+        // Validate symbol ID
+        if params.symbol_id.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Symbol ID cannot be empty".to_string(),
+            ));
+        }
+
+        if params.depth == 0 {
+            return Err(RmcpError::ParseError(
+                "Depth must be at least 1".to_string(),
+            ));
+        }
+
+        let relation_types = params
+            .relation_types
+            .iter()
+            .map(|t| t.parse::<RelationType>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RmcpError::ParseError)?;
+
+        let mut query = NetworkQuery::default()
+            .with_depth(params.depth)
+            .with_max_nodes(params.max_nodes);
+        if !relation_types.is_empty() {
+            query = query.with_relation_types(relation_types);
+        }
+
+        let network = related_network(
+            self.symbol_repository.as_ref(),
+            params.symbol_id.trim(),
+            &query,
+        )
+        .await
+        .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(RelatedNetworkResponse {
+            root_id: network.root_id,
+            nodes: network
+                .nodes
+                .iter()
+                .map(|node| NetworkNodeDTO {
+                    symbol: SymbolDTO::from_symbol(&node.symbol),
+                    depth: node.depth,
+                })
+                .collect(),
+            edges: network.edges.iter().map(RelationDTO::from).collect(),
+            truncated: network.truncated,
+        })?)
+    }
+}
+
+pub fn get_related_network(
+    symbol_repository: Arc<dyn SymbolRepository>,
+) -> GetRelatedNetworkHandler {
+    GetRelatedNetworkHandler::new(symbol_repository)
+}
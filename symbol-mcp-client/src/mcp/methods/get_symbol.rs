@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{GetSymbolParams, GetSymbolResponse, SymbolDTO};
+
+pub struct GetSymbolHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl GetSymbolHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        GetSymbolHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for GetSymbolHandler {
+    fn method_name(&self) -> &str {
+        "get_symbol"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: GetSymbolParams = call.parse_params()?;
+
+        if params.id.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Symbol ID cannot be empty".to_string(),
+            ));
+        }
+
+        // The repository resolves aliases and localized names as well as IDs
+        let symbol = self
+            .symbol_repository
+            .get_symbol(params.id.trim())
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(GetSymbolResponse {
+            display_name: symbol.name_in(params.language.trim()).to_string(),
+            symbol: SymbolDTO::from_symbol(&symbol),
+        })?)
+    }
+}
+
+pub fn get_symbol(symbol_repository: Arc<dyn SymbolRepository>) -> GetSymbolHandler {
+    GetSymbolHandler::new(symbol_repository)
+}
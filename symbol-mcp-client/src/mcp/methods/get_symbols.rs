@@ -7,9 +7,9 @@ use std::sync::Arc;
 use ontology_core::db::repository::{RepositoryError, SymbolRepository};
 use ontology_core::domain::Symbol;
 
-use crate::mcp::schema::{GetSymbolsParams, GetSymbolsResponse, SymbolDTO};
+use crate::mcp::schema::{interpretation_dtos, GetSymbolsParams, GetSymbolsResponse, SymbolDTO};
 
-use super::utils::repository_error_to_rmcp_error;
+use super::utils::{list_query, repository_error_to_rmcp_error};
 
 /// Handler trait definition
 #[async_trait]
@@ -106,6 +106,10 @@ impl RmcpErrorCode {
 #[derive(Debug)]
 pub enum RmcpError {
     ParseError(String),
+    /// The request named a symbol, set or archetype that does not exist
+    NotFound(String),
+    /// The request clashes with what is stored, such as a duplicate ID or alias
+    Conflict(String),
     RepositoryError(String),
     #[allow(dead_code)]
     Other(String),
@@ -115,6 +119,8 @@ impl fmt::Display for RmcpError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            Self::NotFound(msg) => write!(f, "Not found: {}", msg),
+            Self::Conflict(msg) => write!(f, "Conflict: {}", msg),
             Self::RepositoryError(msg) => write!(f, "Repository error: {}", msg),
             Self::Other(msg) => write!(f, "Error: {}", msg),
         }
@@ -128,6 +134,8 @@ impl RmcpError {
     pub fn error_code(&self) -> RmcpErrorCode {
         match self {
             Self::ParseError(_) => RmcpErrorCode::InvalidParams,
+            Self::NotFound(_) => RmcpErrorCode::NotFound,
+            Self::Conflict(_) => RmcpErrorCode::Conflict,
             Self::RepositoryError(_) => RmcpErrorCode::ServerError,
             Self::Other(_) => RmcpErrorCode::InternalError,
         }
@@ -180,6 +188,9 @@ impl GetSymbolsHandler {
             category: symbol.category.clone(),
             description: symbol.description.to_string().clone(),
             related_symbols: symbol.related_symbols.clone(),
+            interpretations: interpretation_dtos(&symbol.interpretations),
+            aliases: symbol.aliases.clone(),
+            localized_names: symbol.localized_names.clone(),
         }
     }
 }
@@ -203,26 +214,25 @@ impl Handler for GetSymbolsHandler {
             }
         }
 
-        // Fetch symbols with optional category filter
-        let symbols = self
+        let query = list_query(
+            params.category.as_deref(),
+            params.limit,
+            &params.cursor,
+            &params.sort,
+        )?;
+
+        // Fetch one page of symbols with optional category filter
+        let page = self
             .symbol_repository
-            .list_symbols(params.category.as_deref())
+            .list_symbols(&query)
             .await
             .map_err(repository_error_to_rmcp_error)?;
 
-        // Apply limit and convert to DTOs
-        let symbol_dtos = symbols
-            .iter()
-            .take(params.limit)
-            .map(Self::to_dto)
-            .collect::<Vec<_>>();
-
-        let total_count = symbols.len();
-
         // Create and return response
         Ok(serde_json::to_value(GetSymbolsResponse {
-            symbols: symbol_dtos,
-            total_count,
+            symbols: page.items.iter().map(Self::to_dto).collect(),
+            total_count: page.total_count,
+            next_cursor: page.next_cursor,
         })?)
     }
 }
@@ -234,19 +244,9 @@ pub fn get_symbols(symbol_repository: Arc<dyn SymbolRepository>) -> GetSymbolsHa
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::json;
-
-    #[cfg(feature = "local")]
-    use crate::mcp::test_utils::InMemorySymbolRepository;
 
     #[cfg(feature = "local")]
-    use std::collections::HashMap;
-
-    #[cfg(feature = "local")]
-    #[tokio::test]
-    async fn test_get_symbols_handler() {
-        // TODO: Create mock repository and test the handler
-    }
+    use ontology_core::db::repository::InMemorySymbolRepository;
 
     #[cfg(feature = "local")]
     #[test]
@@ -255,6 +255,54 @@ mod tests {
         assert_eq!(handler.method_name(), "get_symbols");
     }
 
+    #[tokio::test]
+    async fn test_get_symbols_pages_with_cursor() {
+        use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+
+        let repository = InMemoryRepositoryFactory::new().create_symbol_repository();
+        for name in ["Oak", "Ash", "Elm"] {
+            repository
+                .create_symbol(Symbol::new(
+                    name.to_lowercase(),
+                    name.to_string(),
+                    "nature".to_string(),
+                    format!("The {} tree", name),
+                ))
+                .await
+                .unwrap();
+        }
+        let handler = get_symbols(repository);
+        let call = |params| MethodCall {
+            id: "1".to_string(),
+            method: "get_symbols".to_string(),
+            params,
+        };
+
+        let first = handler
+            .handle(call(serde_json::json!({ "limit": 2 })))
+            .await
+            .unwrap();
+        assert_eq!(first["total_count"], 3);
+        assert_eq!(first["symbols"][0]["id"], "ash");
+        assert_eq!(first["symbols"][1]["id"], "elm");
+
+        let cursor = first["next_cursor"].as_str().unwrap();
+        let second = handler
+            .handle(call(serde_json::json!({ "limit": 2, "cursor": cursor })))
+            .await
+            .unwrap();
+        assert_eq!(second["symbols"].as_array().unwrap().len(), 1);
+        assert_eq!(second["symbols"][0]["id"], "oak");
+        assert!(second.get("next_cursor").is_none());
+
+        let stale = handler
+            .handle(call(
+                serde_json::json!({ "cursor": cursor, "sort": "name_desc" }),
+            ))
+            .await;
+        assert!(matches!(stale, Err(RmcpError::ParseError(_))));
+    }
+
     #[test]
     fn test_error_codes() {
         assert_eq!(RmcpErrorCode::InvalidRequest.code(), -32600);
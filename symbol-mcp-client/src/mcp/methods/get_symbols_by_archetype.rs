@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::ArchetypeRepository;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::{list_query, repository_error_to_rmcp_error},
+};
+use crate::mcp::schema::{GetSymbolsResponse, SymbolDTO, SymbolsByArchetypeParams};
+
+pub struct GetSymbolsByArchetypeHandler {
+    archetype_repository: Arc<dyn ArchetypeRepository>,
+}
+
+impl GetSymbolsByArchetypeHandler {
+    pub fn new(archetype_repository: Arc<dyn ArchetypeRepository>) -> Self {
+        GetSymbolsByArchetypeHandler {
+            archetype_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for GetSymbolsByArchetypeHandler {
+    fn method_name(&self) -> &str {
+        "get_symbols_by_archetype"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: SymbolsByArchetypeParams = call.parse_params()?;
+
+        // Validate archetype ID
+        if params.archetype_id.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Archetype ID cannot be empty".to_string(),
+            ));
+        }
+
+        let query = list_query(None, params.limit, &params.cursor, &params.sort)?;
+        let page = self
+            .archetype_repository
+            .list_symbols_by_archetype(
+                params.archetype_id.trim(),
+                params.include_descendants,
+                &query,
+            )
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(GetSymbolsResponse {
+            symbols: page.items.iter().map(SymbolDTO::from_symbol).collect(),
+            total_count: page.total_count,
+            next_cursor: page.next_cursor,
+        })?)
+    }
+}
+
+pub fn get_symbols_by_archetype(
+    archetype_repository: Arc<dyn ArchetypeRepository>,
+) -> GetSymbolsByArchetypeHandler {
+    GetSymbolsByArchetypeHandler::new(archetype_repository)
+}
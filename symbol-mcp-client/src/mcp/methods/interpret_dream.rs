@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+use ontology_core::report::{self, DreamContext};
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{InterpretDreamParams, InterpretDreamResponse};
+
+pub struct InterpretDreamHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl InterpretDreamHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        InterpretDreamHandler { symbol_repository }
+    }
+}
+
+/// Treats blank context fields as not given.
+fn non_empty(value: &str) -> Option<String> {
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+#[async_trait]
+impl Handler for InterpretDreamHandler {
+    fn method_name(&self) -> &str {
+        "interpret_dream"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: InterpretDreamParams = call.parse_params()?;
+
+        if params.text.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Dream text cannot be empty".to_string(),
+            ));
+        }
+
+        let context = DreamContext {
+            culture: non_empty(&params.culture),
+            emotional_tone: non_empty(&params.emotional_tone),
+        };
+
+        let report =
+            report::interpret_dream(self.symbol_repository.as_ref(), &params.text, &context)
+                .await
+                .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(InterpretDreamResponse::from(&report))?)
+    }
+}
+
+pub fn interpret_dream(symbol_repository: Arc<dyn SymbolRepository>) -> InterpretDreamHandler {
+    InterpretDreamHandler::new(symbol_repository)
+}
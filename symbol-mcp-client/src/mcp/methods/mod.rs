@@ -1,4 +1,18 @@
+pub mod add_interpretation;
+pub mod create_symbol;
+pub mod delete_symbol;
+pub mod extract_symbols_from_text;
 pub mod filter_by_category;
+pub mod find_symbol_path;
+pub mod get_archetype_hierarchy;
+pub mod get_archetypes;
+pub mod get_related_network;
+pub mod get_symbol;
 pub mod get_symbols;
+pub mod get_symbols_by_archetype;
+pub mod interpret_dream;
+pub mod relate_symbols;
 pub mod search_symbols;
+pub mod semantic_search_symbols;
+pub mod update_symbol;
 pub mod utils;
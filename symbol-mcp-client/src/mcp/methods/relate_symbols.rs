@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+use ontology_core::domain::{RelationType, SymbolRelation};
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{RelateSymbolsParams, RelateSymbolsResponse, RelationDTO, SymbolDTO};
+
+pub struct RelateSymbolsHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl RelateSymbolsHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        RelateSymbolsHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for RelateSymbolsHandler {
+    fn method_name(&self) -> &str {
+        "relate_symbols"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: RelateSymbolsParams = call.parse_params()?;
+
+        if params.source_id.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Symbol ID cannot be empty".to_string(),
+            ));
+        }
+        if params.target_id.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Related symbol ID cannot be empty".to_string(),
+            ));
+        }
+
+        let relation_type = match params.relation_type.trim() {
+            "" => RelationType::default(),
+            relation_type => relation_type
+                .parse::<RelationType>()
+                .map_err(RmcpError::ParseError)?,
+        };
+
+        // Either end may be given by alias; relations are stored between IDs
+        let source = self
+            .symbol_repository
+            .get_symbol(params.source_id.trim())
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+        let target = self
+            .symbol_repository
+            .get_symbol(params.target_id.trim())
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        let mut relation = SymbolRelation::new(&source.id, &target.id, relation_type);
+        relation.weight = params.weight;
+        relation.validate().map_err(RmcpError::ParseError)?;
+
+        let relation = self
+            .symbol_repository
+            .add_relation(relation)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+        let source = self
+            .symbol_repository
+            .get_symbol(&source.id)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(RelateSymbolsResponse {
+            relation: RelationDTO::from(&relation),
+            symbol: SymbolDTO::from_symbol(&source),
+        })?)
+    }
+}
+
+pub fn relate_symbols(symbol_repository: Arc<dyn SymbolRepository>) -> RelateSymbolsHandler {
+    RelateSymbolsHandler::new(symbol_repository)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+    use ontology_core::domain::{RelationDirection, Symbol};
+
+    async fn relate(
+        repository: &Arc<dyn SymbolRepository>,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, RmcpError> {
+        relate_symbols(repository.clone())
+            .handle(MethodCall {
+                id: "1".to_string(),
+                method: "relate_symbols".to_string(),
+                params,
+            })
+            .await
+    }
+
+    async fn repository_with(ids: &[(&str, &str)]) -> Arc<dyn SymbolRepository> {
+        let repository = InMemoryRepositoryFactory::new().create_symbol_repository();
+        for (id, alias) in ids {
+            repository
+                .create_symbol(
+                    Symbol::new(
+                        id.to_string(),
+                        id.to_string(),
+                        "nature".to_string(),
+                        String::new(),
+                    )
+                    .with_aliases(vec![*alias]),
+                )
+                .await
+                .unwrap();
+        }
+        repository
+    }
+
+    #[tokio::test]
+    async fn test_relate_symbols_by_alias() {
+        let repository = repository_with(&[("water", "aqua"), ("moon", "luna")]).await;
+
+        let result = relate(
+            &repository,
+            serde_json::json!({
+                "source_id": "aqua",
+                "target_id": "luna",
+                "relation_type": "similar to",
+                "weight": 0.5
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result["relation"],
+            serde_json::json!({
+                "source_id": "water",
+                "target_id": "moon",
+                "relation_type": "similar_to",
+                "weight": 0.5
+            })
+        );
+        assert_eq!(result["symbol"]["id"], "water");
+        let relations = repository
+            .list_relations("water", RelationDirection::Outgoing)
+            .await
+            .unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].target_id, "moon");
+        assert_eq!(relations[0].relation_type, RelationType::SimilarTo);
+    }
+
+    #[tokio::test]
+    async fn test_relate_symbols_checks_its_input() {
+        let repository = repository_with(&[("water", "aqua"), ("moon", "luna")]).await;
+
+        let unknown_type = serde_json::json!({
+            "source_id": "water",
+            "target_id": "moon",
+            "relation_type": "married_to"
+        });
+        let heavy = serde_json::json!({
+            "source_id": "water",
+            "target_id": "moon",
+            "weight": 2.0
+        });
+        let unknown_target = serde_json::json!({
+            "source_id": "water",
+            "target_id": "sun"
+        });
+
+        assert!(matches!(
+            relate(&repository, unknown_type).await,
+            Err(RmcpError::ParseError(_))
+        ));
+        assert!(matches!(
+            relate(&repository, heavy).await,
+            Err(RmcpError::ParseError(_))
+        ));
+        assert!(matches!(
+            relate(&repository, unknown_target).await,
+            Err(RmcpError::NotFound(_))
+        ));
+        let relations = repository
+            .list_relations("water", RelationDirection::Both)
+            .await
+            .unwrap();
+        assert!(relations.is_empty());
+    }
+}
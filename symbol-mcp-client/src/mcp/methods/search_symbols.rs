@@ -2,14 +2,20 @@ use async_trait::async_trait;
 use serde_json;
 use std::sync::Arc;
 
-use ontology_core::db::repository::SymbolRepository;
+use ontology_core::db::repository::{RepositoryError, SymbolRepository};
 use ontology_core::domain::Symbol;
 
 use crate::mcp::methods::{
     get_symbols::{Handler, MethodCall, RmcpError},
-    utils::repository_error_to_rmcp_error,
+    utils::{list_query, repository_error_to_rmcp_error},
 };
-use crate::mcp::schema::{GetSymbolsResponse, SearchSymbolsParams, SymbolDTO};
+use crate::mcp::schema::{
+    interpretation_dtos, SearchSymbolsParams, SearchSymbolsResponse, SuggestionDTO, SymbolDTO,
+    SymbolHitDTO,
+};
+
+/// How many "did you mean" suggestions an empty search returns
+const SUGGESTION_LIMIT: usize = 5;
 
 pub struct SearchSymbolsHandler {
     symbol_repository: Arc<dyn SymbolRepository>,
@@ -20,6 +26,26 @@ impl SearchSymbolsHandler {
         SearchSymbolsHandler { symbol_repository }
     }
 
+    /// Symbols named like `query`, or none if the repository cannot fuzzy match.
+    async fn suggestions(&self, query: &str) -> Result<Vec<SuggestionDTO>, RmcpError> {
+        match self
+            .symbol_repository
+            .fuzzy_search_symbols(query, SUGGESTION_LIMIT)
+            .await
+        {
+            Ok(hits) => Ok(hits
+                .into_iter()
+                .map(|hit| SuggestionDTO {
+                    id: hit.symbol.id,
+                    name: hit.symbol.name,
+                    score: hit.score,
+                })
+                .collect()),
+            Err(RepositoryError::NotImplemented(_)) => Ok(Vec::new()),
+            Err(e) => Err(repository_error_to_rmcp_error(e)),
+        }
+    }
+
     fn to_dto(symbol: &Symbol) -> SymbolDTO {
         SymbolDTO {
             id: symbol.id.clone(),
@@ -27,6 +53,9 @@ impl SearchSymbolsHandler {
             category: symbol.category.clone(),
             description: symbol.description.clone(),
             related_symbols: symbol.related_symbols.clone(),
+            interpretations: interpretation_dtos(&symbol.interpretations),
+            aliases: symbol.aliases.clone(),
+            localized_names: symbol.localized_names.clone(),
         }
     }
 }
@@ -50,25 +79,36 @@ impl Handler for SearchSymbolsHandler {
         // Normalize query
         let normalized_query = params.query.trim().to_lowercase();
 
-        // Perform search
-        let symbols = self
+        let query = list_query(None, params.limit, &params.cursor, &params.sort)?;
+
+        // Perform search; hits come back most relevant first by default
+        let page = self
             .symbol_repository
-            .search_symbols(&normalized_query)
+            .search_symbols(&normalized_query, &query)
             .await
             .map_err(repository_error_to_rmcp_error)?;
 
-        // Apply limit and convert to DTOs
-        let symbol_dtos = symbols
+        let symbol_dtos = page
+            .items
             .iter()
-            .take(params.limit)
-            .map(Self::to_dto)
+            .map(|hit| SymbolHitDTO {
+                symbol: Self::to_dto(&hit.symbol),
+                score: hit.score,
+            })
             .collect::<Vec<_>>();
 
-        let total_count = symbols.len();
+        // Offer close names when nothing matched, e.g. for a misspelling
+        let did_you_mean = if page.total_count == 0 {
+            self.suggestions(&normalized_query).await?
+        } else {
+            Vec::new()
+        };
 
-        Ok(serde_json::to_value(GetSymbolsResponse {
+        Ok(serde_json::to_value(SearchSymbolsResponse {
             symbols: symbol_dtos,
-            total_count,
+            total_count: page.total_count,
+            next_cursor: page.next_cursor,
+            did_you_mean,
         })?)
     }
 }
@@ -79,8 +119,6 @@ pub fn search_symbols(symbol_repository: Arc<dyn SymbolRepository>) -> SearchSym
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[cfg(feature = "local")]
     #[tokio::test]
     async fn test_search_symbols_handler() {
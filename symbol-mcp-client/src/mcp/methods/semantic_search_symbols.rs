@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use serde_json;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+use ontology_core::embedding::Embedder;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{
+    SearchSymbolsResponse, SemanticSearchSymbolsParams, SymbolDTO, SymbolHitDTO,
+};
+
+pub struct SemanticSearchSymbolsHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+    embedder: Arc<dyn Embedder>,
+}
+
+impl SemanticSearchSymbolsHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>, embedder: Arc<dyn Embedder>) -> Self {
+        SemanticSearchSymbolsHandler {
+            symbol_repository,
+            embedder,
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for SemanticSearchSymbolsHandler {
+    fn method_name(&self) -> &str {
+        "semantic_search_symbols"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: SemanticSearchSymbolsParams = call.parse_params()?;
+
+        if params.query.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Search query cannot be empty".to_string(),
+            ));
+        }
+
+        let embedding = self
+            .embedder
+            .embed(params.query.trim())
+            .await
+            .map_err(|e| RmcpError::Other(e.to_string()))?;
+
+        // Nearest symbols first
+        let hits = self
+            .symbol_repository
+            .semantic_search_symbols(&embedding, params.limit)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        let symbol_dtos = hits
+            .iter()
+            .map(|hit| SymbolHitDTO {
+                symbol: SymbolDTO::from_symbol(&hit.symbol),
+                score: hit.score,
+            })
+            .collect::<Vec<_>>();
+
+        Ok(serde_json::to_value(SearchSymbolsResponse {
+            total_count: symbol_dtos.len(),
+            symbols: symbol_dtos,
+            did_you_mean: Vec::new(),
+            next_cursor: None,
+        })?)
+    }
+}
+
+pub fn semantic_search_symbols(
+    symbol_repository: Arc<dyn SymbolRepository>,
+    embedder: Arc<dyn Embedder>,
+) -> SemanticSearchSymbolsHandler {
+    SemanticSearchSymbolsHandler::new(symbol_repository, embedder)
+}
@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use serde_json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use ontology_core::db::repository::SymbolRepository;
+
+use crate::mcp::methods::{
+    get_symbols::{Handler, MethodCall, RmcpError},
+    utils::repository_error_to_rmcp_error,
+};
+use crate::mcp::schema::{SymbolDTO, SymbolWriteResponse, UpdateSymbolParams};
+
+pub struct UpdateSymbolHandler {
+    symbol_repository: Arc<dyn SymbolRepository>,
+}
+
+impl UpdateSymbolHandler {
+    pub fn new(symbol_repository: Arc<dyn SymbolRepository>) -> Self {
+        UpdateSymbolHandler { symbol_repository }
+    }
+}
+
+#[async_trait]
+impl Handler for UpdateSymbolHandler {
+    fn method_name(&self) -> &str {
+        "update_symbol"
+    }
+
+    async fn handle(&self, call: MethodCall) -> Result<serde_json::Value, RmcpError> {
+        let params: UpdateSymbolParams = call.parse_params()?;
+
+        if params.id.trim().is_empty() {
+            return Err(RmcpError::ParseError(
+                "Symbol ID cannot be empty".to_string(),
+            ));
+        }
+
+        // Start from the stored symbol so fields left out are kept
+        let mut symbol = self
+            .symbol_repository
+            .get_symbol(params.id.trim())
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        if !params.name.trim().is_empty() {
+            symbol.name = params.name;
+        }
+        if !params.category.trim().is_empty() {
+            symbol.category = params.category;
+        }
+        if !params.description.trim().is_empty() {
+            symbol.description = params.description;
+        }
+        if let Some(aliases) = params.aliases {
+            symbol.aliases = aliases;
+        }
+        if let Some(properties) = params.properties {
+            symbol.properties = properties;
+        }
+        if let Some(localized_names) = params.localized_names {
+            symbol.localized_names = HashMap::new();
+            for (language, name) in localized_names {
+                symbol
+                    .add_localized_name(&language, name)
+                    .map_err(RmcpError::ParseError)?;
+            }
+        }
+        symbol.normalize();
+        symbol.validate().map_err(RmcpError::ParseError)?;
+
+        let updated = self
+            .symbol_repository
+            .update_symbol(symbol)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(serde_json::to_value(SymbolWriteResponse {
+            symbol: SymbolDTO::from_symbol(&updated),
+        })?)
+    }
+}
+
+pub fn update_symbol(symbol_repository: Arc<dyn SymbolRepository>) -> UpdateSymbolHandler {
+    UpdateSymbolHandler::new(symbol_repository)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ontology_core::db::repository::{InMemoryRepositoryFactory, RepositoryFactory};
+    use ontology_core::domain::Symbol;
+
+    #[tokio::test]
+    async fn test_update_keeps_fields_left_out() {
+        let repository = InMemoryRepositoryFactory::new().create_symbol_repository();
+        repository
+            .create_symbol(
+                Symbol::new(
+                    "water".to_string(),
+                    "Water".to_string(),
+                    "nature".to_string(),
+                    "Symbolizes emotions".to_string(),
+                )
+                .with_aliases(vec!["aqua"]),
+            )
+            .await
+            .unwrap();
+
+        let result = update_symbol(repository.clone())
+            .handle(MethodCall {
+                id: "1".to_string(),
+                method: "update_symbol".to_string(),
+                params: serde_json::json!({
+                    "id": "aqua",
+                    "category": "elemental",
+                    "localized_names": { "ES": "Agua" }
+                }),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result["symbol"]["id"], "water");
+        let stored = repository.get_symbol("water").await.unwrap();
+        assert_eq!(stored.name, "Water");
+        assert_eq!(stored.category, "elemental");
+        assert_eq!(stored.description, "Symbolizes emotions");
+        assert_eq!(stored.aliases, vec!["aqua"]);
+        assert_eq!(stored.localized_names["es"], "Agua");
+    }
+
+    #[tokio::test]
+    async fn test_update_trims_the_description() {
+        let repository = InMemoryRepositoryFactory::new().create_symbol_repository();
+        repository
+            .create_symbol(Symbol::new(
+                "water".to_string(),
+                "Water".to_string(),
+                "nature".to_string(),
+                String::new(),
+            ))
+            .await
+            .unwrap();
+
+        update_symbol(repository.clone())
+            .handle(MethodCall {
+                id: "1".to_string(),
+                method: "update_symbol".to_string(),
+                params: serde_json::json!({
+                    "id": " water ",
+                    "description": "  Symbolizes emotions\n"
+                }),
+            })
+            .await
+            .unwrap();
+
+        let stored = repository.get_symbol("water").await.unwrap();
+        assert_eq!(stored.description, "Symbolizes emotions");
+    }
+}
@@ -1,20 +1,42 @@
 use crate::mcp::methods::get_symbols::RmcpError;
-use ontology_core::db::repository::RepositoryError;
+use ontology_core::db::repository::{ListQuery, RepositoryError, SortOrder};
 
 /// Helper function to convert RepositoryError to RmcpError
 /// Can be reused across all MCP method handlers
 pub fn repository_error_to_rmcp_error(err: RepositoryError) -> RmcpError {
     match err {
-        RepositoryError::NotFound(msg) => RmcpError::RepositoryError(format!("Not found: {}", msg)),
-        RepositoryError::Conflict(msg) => RmcpError::RepositoryError(format!("Conflict: {}", msg)),
+        RepositoryError::NotFound(msg) => RmcpError::NotFound(msg),
+        RepositoryError::Conflict(msg) => RmcpError::Conflict(msg),
         RepositoryError::Internal(msg) => {
             RmcpError::RepositoryError(format!("Internal error: {}", msg))
         }
+        // Validation failures come from what the client sent, such as a stale cursor
         RepositoryError::Validation(msg) => {
-            RmcpError::RepositoryError(format!("Validation error: {}", msg))
+            RmcpError::ParseError(format!("Validation error: {}", msg))
         }
         RepositoryError::NotImplemented(msg) => {
             RmcpError::RepositoryError(format!("Not implemented: {}", msg))
         }
     }
 }
+
+/// Builds the page of results a tool call asks for. Empty `cursor` and `sort`
+/// strings mean the first page in the method's default order.
+pub fn list_query(
+    category: Option<&str>,
+    limit: usize,
+    cursor: &str,
+    sort: &str,
+) -> Result<ListQuery, RmcpError> {
+    let sort = match sort.trim() {
+        "" => None,
+        sort => Some(sort.parse::<SortOrder>().map_err(RmcpError::ParseError)?),
+    };
+    let cursor = Some(cursor.trim()).filter(|c| !c.is_empty());
+
+    Ok(ListQuery::new()
+        .category(category)
+        .limit(limit)
+        .cursor(cursor)
+        .sort(sort))
+}
@@ -1,6 +1,6 @@
 pub mod methods;
+pub mod prompts;
+pub mod resources;
 pub mod schema;
 pub mod service;
-
-#[cfg(feature = "local")]
-pub mod test_utils;
+pub mod transport;
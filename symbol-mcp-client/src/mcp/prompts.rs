@@ -0,0 +1,421 @@
+//! MCP prompts: parameterized conversation starters for common tasks such as
+//! symbol lookup and dream analysis.
+//!
+//! Each prompt is a JSON file holding its name, a description, the arguments
+//! it takes and a template, given as one string or a list of lines:
+//!
+//! ```json
+//! {
+//!   "name": "symbol_lookup",
+//!   "description": "Interpret a single symbol using the ontology",
+//!   "arguments": [{ "name": "symbol", "required": true }],
+//!   "template": ["What does \"{{symbol}}\" symbolize in dreams?"]
+//! }
+//! ```
+//!
+//! Templates use a small subset of mustache: `{{name}}` inserts an argument,
+//! `{{#name}}...{{/name}}` keeps its text only when the argument is given and
+//! `{{^name}}...{{/name}}` only when it is not.
+//!
+//! The built-in prompts in `prompts/` are compiled in; a directory of prompt
+//! files loaded at startup adds to them, replacing any with the same name.
+
+use rmcp::model::{Prompt, PromptArgument};
+use serde::{Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+use tracing::info;
+
+const BUILTIN_PROMPTS: &[(&str, &str)] = &[
+    (
+        "comparative_interpretation.json",
+        include_str!("../../prompts/comparative_interpretation.json"),
+    ),
+    (
+        "dream_analysis.json",
+        include_str!("../../prompts/dream_analysis.json"),
+    ),
+    (
+        "symbol_lookup.json",
+        include_str!("../../prompts/symbol_lookup.json"),
+    ),
+    (
+        "symbol_relationships.json",
+        include_str!("../../prompts/symbol_relationships.json"),
+    ),
+];
+
+#[derive(Debug)]
+pub enum PromptError {
+    /// A prompt file could not be read or is not a valid prompt.
+    Invalid(String),
+    NotFound(String),
+    MissingArgument(String),
+}
+
+impl fmt::Display for PromptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(msg) => write!(f, "Invalid prompt: {}", msg),
+            Self::NotFound(name) => write!(f, "Prompt not found: {}", name),
+            Self::MissingArgument(name) => write!(f, "Missing required argument: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for PromptError {}
+
+/// A prompt as written in its file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PromptDefinition {
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub arguments: Vec<PromptArgument>,
+
+    #[serde(deserialize_with = "deserialize_template")]
+    pub template: String,
+}
+
+impl PromptDefinition {
+    /// Parses a prompt file, checking that its template is well formed and
+    /// only refers to the arguments it declares.
+    pub fn from_json(json: &str) -> Result<Self, PromptError> {
+        let definition: Self =
+            serde_json::from_str(json).map_err(|e| PromptError::Invalid(e.to_string()))?;
+        if definition.name.trim().is_empty() {
+            return Err(PromptError::Invalid("name must not be empty".to_string()));
+        }
+
+        let declared: Vec<&str> = definition
+            .arguments
+            .iter()
+            .map(|argument| argument.name.as_str())
+            .collect();
+        let mut open: Vec<&str> = Vec::new();
+        for token in tokenize(&definition.template).map_err(PromptError::Invalid)? {
+            let name = match token {
+                Token::Text(_) => continue,
+                Token::Argument(name) => name,
+                Token::Section { name, .. } => {
+                    open.push(name);
+                    name
+                }
+                Token::End(name) => {
+                    if open.pop() != Some(name) {
+                        return Err(PromptError::Invalid(format!(
+                            "{}: unexpected {{{{/{}}}}}",
+                            definition.name, name
+                        )));
+                    }
+                    name
+                }
+            };
+            if !declared.contains(&name) {
+                return Err(PromptError::Invalid(format!(
+                    "{}: template uses undeclared argument {}",
+                    definition.name, name
+                )));
+            }
+        }
+        if let Some(name) = open.pop() {
+            return Err(PromptError::Invalid(format!(
+                "{}: {{{{#{}}}}} is never closed",
+                definition.name, name
+            )));
+        }
+
+        Ok(definition)
+    }
+
+    /// The prompt as listed to MCP clients.
+    pub fn prompt(&self) -> Prompt {
+        Prompt::new(
+            &self.name,
+            self.description.as_deref(),
+            Some(self.arguments.clone()),
+        )
+    }
+
+    /// Fills the template in with `arguments`. Blank arguments count as not given.
+    pub fn render(&self, arguments: &HashMap<String, String>) -> Result<String, PromptError> {
+        let value = |name: &str| {
+            arguments
+                .get(name)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+        for argument in &self.arguments {
+            if argument.required == Some(true) && value(&argument.name).is_none() {
+                return Err(PromptError::MissingArgument(argument.name.clone()));
+            }
+        }
+
+        let mut text = String::new();
+        let mut shown: Vec<bool> = Vec::new();
+        let tokens = tokenize(&self.template).map_err(PromptError::Invalid)?;
+        for token in tokens {
+            let visible = shown.last().copied().unwrap_or(true);
+            match token {
+                Token::Text(part) if visible => text.push_str(part),
+                Token::Argument(name) if visible => text.push_str(value(name).unwrap_or("")),
+                Token::Section { name, inverted } => {
+                    shown.push(visible && value(name).is_some() != inverted)
+                }
+                Token::End(_) => {
+                    shown.pop();
+                }
+                _ => {}
+            }
+        }
+        Ok(text)
+    }
+}
+
+/// Prompts by name, served by `prompts/list` and `prompts/get`.
+#[derive(Debug, Clone, Default)]
+pub struct PromptLibrary {
+    prompts: BTreeMap<String, PromptDefinition>,
+}
+
+impl PromptLibrary {
+    /// The prompts compiled into the server.
+    pub fn builtin() -> Self {
+        let mut library = Self::default();
+        for (file, json) in BUILTIN_PROMPTS {
+            let definition = PromptDefinition::from_json(json)
+                .unwrap_or_else(|e| panic!("Built-in prompt {} is invalid: {}", file, e));
+            library.insert(definition);
+        }
+        library
+    }
+
+    /// Adds every `.json` prompt file in `dir`, replacing prompts of the same name.
+    pub fn load_dir(mut self, dir: &Path) -> Result<Self, PromptError> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| PromptError::Invalid(format!("{}: {}", dir.display(), e)))?;
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| PromptError::Invalid(format!("{}: {}", dir.display(), e)))?
+                .path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        for path in paths {
+            let json = std::fs::read_to_string(&path)
+                .map_err(|e| PromptError::Invalid(format!("{}: {}", path.display(), e)))?;
+            let definition = PromptDefinition::from_json(&json).map_err(|e| match e {
+                PromptError::Invalid(msg) => {
+                    PromptError::Invalid(format!("{}: {}", path.display(), msg))
+                }
+                e => e,
+            })?;
+            info!("Loaded prompt {} from {}", definition.name, path.display());
+            self.insert(definition);
+        }
+        Ok(self)
+    }
+
+    pub fn insert(&mut self, definition: PromptDefinition) {
+        self.prompts.insert(definition.name.clone(), definition);
+    }
+
+    pub fn get(&self, name: &str) -> Result<&PromptDefinition, PromptError> {
+        self.prompts
+            .get(name)
+            .ok_or_else(|| PromptError::NotFound(name.to_string()))
+    }
+
+    /// Every prompt, ordered by name.
+    pub fn prompts(&self) -> Vec<Prompt> {
+        self.prompts
+            .values()
+            .map(PromptDefinition::prompt)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.prompts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prompts.is_empty()
+    }
+}
+
+fn deserialize_template<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Template {
+        Text(String),
+        Lines(Vec<String>),
+    }
+
+    Ok(match Template::deserialize(deserializer)? {
+        Template::Text(text) => text,
+        Template::Lines(lines) => lines.join("\n"),
+    })
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    Argument(&'a str),
+    Section { name: &'a str, inverted: bool },
+    End(&'a str),
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        if open > 0 {
+            tokens.push(Token::Text(&rest[..open]));
+        }
+        let tag_start = &rest[open + 2..];
+        let close = tag_start
+            .find("}}")
+            .ok_or_else(|| "unclosed {{ in template".to_string())?;
+        let tag = tag_start[..close].trim();
+        let token = if let Some(name) = tag.strip_prefix('#') {
+            Token::Section {
+                name: name.trim(),
+                inverted: false,
+            }
+        } else if let Some(name) = tag.strip_prefix('^') {
+            Token::Section {
+                name: name.trim(),
+                inverted: true,
+            }
+        } else if let Some(name) = tag.strip_prefix('/') {
+            Token::End(name.trim())
+        } else {
+            Token::Argument(tag)
+        };
+        if let Token::Argument("") | Token::Section { name: "", .. } | Token::End("") = token {
+            return Err("empty {{}} in template".to_string());
+        }
+        tokens.push(token);
+        rest = &tag_start[close + 2..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_prompts_load() {
+        let library = PromptLibrary::builtin();
+        let names: Vec<String> = library.prompts().into_iter().map(|p| p.name).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "comparative_interpretation",
+                "dream_analysis",
+                "symbol_lookup",
+                "symbol_relationships"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_sections() {
+        let library = PromptLibrary::builtin();
+        let prompt = library.get("comparative_interpretation").unwrap();
+
+        let given = prompt
+            .render(&arguments(&[
+                ("symbol", "snake"),
+                ("traditions", "celtic and norse"),
+            ]))
+            .unwrap();
+        let omitted = prompt
+            .render(&arguments(&[("symbol", "snake"), ("traditions", " ")]))
+            .unwrap();
+
+        assert!(given.contains("vary across the celtic and norse traditions"));
+        assert!(omitted.contains("vary across different cultural traditions"));
+    }
+
+    #[test]
+    fn test_render_requires_arguments() {
+        let library = PromptLibrary::builtin();
+        let prompt = library.get("dream_analysis").unwrap();
+
+        let result = prompt.render(&arguments(&[("culture", "jungian")]));
+
+        assert!(matches!(result, Err(PromptError::MissingArgument(name)) if name == "dream"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_templates() {
+        let undeclared = r#"{"name": "p", "template": "About {{symbol}}"}"#;
+        let unclosed = r#"{"name": "p", "arguments": [{"name": "a"}], "template": "{{#a}}x"}"#;
+
+        assert!(matches!(
+            PromptDefinition::from_json(undeclared),
+            Err(PromptError::Invalid(_))
+        ));
+        assert!(matches!(
+            PromptDefinition::from_json(unclosed),
+            Err(PromptError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_dir_adds_and_replaces_prompts() {
+        let dir = std::env::temp_dir().join(format!("symbol-prompts-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("lookup.json"),
+            r#"{"name": "symbol_lookup", "arguments": [{"name": "symbol", "required": true}], "template": "Tell me about {{symbol}}."}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("nightmare.json"),
+            r#"{"name": "nightmare", "template": "Help me with a nightmare."}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a prompt").unwrap();
+
+        let library = PromptLibrary::builtin().load_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(library.len(), 5);
+        assert_eq!(
+            library
+                .get("symbol_lookup")
+                .unwrap()
+                .render(&arguments(&[("symbol", "water")]))
+                .unwrap(),
+            "Tell me about water."
+        );
+        assert!(library.get("nightmare").is_ok());
+    }
+}
@@ -0,0 +1,427 @@
+//! MCP resources: symbols, symbol sets and categories addressed by URI and
+//! rendered as markdown, so clients can attach them to a conversation as
+//! context.
+//!
+//! - `symbol://{id}` - a symbol with its interpretations and relations
+//! - `symbol-set://{id}` - a symbol set and the symbols in it
+//! - `category://{name}` - the symbols in a category, up to a page of them
+
+use ontology_core::db::repository::pagination::Page;
+use ontology_core::domain::{Symbol, SymbolSet};
+use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate};
+use std::fmt::{self, Display, Formatter, Write};
+
+/// MIME type of every resource this server renders.
+pub const MARKDOWN_MIME_TYPE: &str = "text/markdown";
+
+/// A resource URI understood by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    Symbol(String),
+    SymbolSet(String),
+    Category(String),
+}
+
+impl ResourceUri {
+    /// Parses `uri`, percent-decoding the part after the scheme.
+    ///
+    /// Returns `None` for unknown schemes, an empty ID or name, or malformed
+    /// escapes.
+    pub fn parse(uri: &str) -> Option<Self> {
+        let (scheme, rest) = uri.split_once("://")?;
+        let value = percent_decode(rest.trim_end_matches('/'))?;
+        if value.is_empty() {
+            return None;
+        }
+        match scheme {
+            "symbol" => Some(Self::Symbol(value)),
+            "symbol-set" => Some(Self::SymbolSet(value)),
+            "category" => Some(Self::Category(value)),
+            _ => None,
+        }
+    }
+
+    fn parts(&self) -> (&'static str, &str) {
+        match self {
+            Self::Symbol(id) => ("symbol", id),
+            Self::SymbolSet(id) => ("symbol-set", id),
+            Self::Category(name) => ("category", name),
+        }
+    }
+}
+
+impl Display for ResourceUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (scheme, value) = self.parts();
+        write!(f, "{}://{}", scheme, percent_encode(value))
+    }
+}
+
+/// Resources listed per page, within one kind of resource.
+pub const RESOURCE_PAGE_SIZE: usize = 100;
+
+/// Where a resource listing goes on from: symbols, then symbol sets, each paged
+/// by the repository cursor they were left at, then categories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceCursor {
+    Symbols(Option<String>),
+    SymbolSets(Option<String>),
+    Categories,
+}
+
+impl ResourceCursor {
+    /// Parses a cursor handed out by [`ResourceCursor::encode`].
+    pub fn parse(cursor: &str) -> Option<Self> {
+        let (kind, position) = match cursor.split_once(':') {
+            Some((kind, position)) => (kind, Some(position.to_string())),
+            None => (cursor, None),
+        };
+        match (kind, position) {
+            ("symbols", position) => Some(Self::Symbols(position)),
+            ("symbol-sets", position) => Some(Self::SymbolSets(position)),
+            ("categories", None) => Some(Self::Categories),
+            _ => None,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        match self {
+            Self::Symbols(Some(position)) => format!("symbols:{}", position),
+            Self::Symbols(None) => "symbols".to_string(),
+            Self::SymbolSets(Some(position)) => format!("symbol-sets:{}", position),
+            Self::SymbolSets(None) => "symbol-sets".to_string(),
+            Self::Categories => "categories".to_string(),
+        }
+    }
+}
+
+/// The URI templates clients can fill in to read any symbol, set or category.
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    [
+        (
+            "symbol://{id}",
+            "Symbol",
+            "A symbol by ID, alias or name, with its interpretations and related symbols",
+        ),
+        (
+            "symbol-set://{id}",
+            "Symbol set",
+            "A symbol set and the symbols it contains",
+        ),
+        (
+            "category://{name}",
+            "Category",
+            "The symbols in a category, such as dream or mythological, up to the first 100 by name",
+        ),
+    ]
+    .into_iter()
+    .map(|(uri_template, name, description)| {
+        RawResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            mime_type: Some(MARKDOWN_MIME_TYPE.to_string()),
+        }
+        .no_annotation()
+    })
+    .collect()
+}
+
+pub fn symbol_resource(symbol: &Symbol) -> Resource {
+    resource(
+        ResourceUri::Symbol(symbol.id.clone()),
+        &symbol.name,
+        &symbol.description,
+    )
+}
+
+pub fn symbol_set_resource(symbol_set: &SymbolSet) -> Resource {
+    resource(
+        ResourceUri::SymbolSet(symbol_set.id.clone()),
+        &symbol_set.name,
+        &symbol_set.description,
+    )
+}
+
+pub fn category_resource(category: &str, symbol_count: usize) -> Resource {
+    resource(
+        ResourceUri::Category(category.to_string()),
+        category,
+        &format!("Symbols in the {} category ({})", category, symbol_count),
+    )
+}
+
+fn resource(uri: ResourceUri, name: &str, description: &str) -> Resource {
+    let mut raw = RawResource::new(uri.to_string(), name);
+    if !description.is_empty() {
+        raw.description = Some(description.to_string());
+    }
+    raw.mime_type = Some(MARKDOWN_MIME_TYPE.to_string());
+    raw.no_annotation()
+}
+
+/// Renders a symbol as markdown: its names, description, interpretations
+/// ordered by context, properties and related symbols.
+pub fn render_symbol(symbol: &Symbol) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", symbol.name);
+    let _ = writeln!(out, "- **ID:** `{}`", symbol.id);
+    let _ = writeln!(
+        out,
+        "- **Category:** [{}]({})",
+        symbol.category,
+        ResourceUri::Category(symbol.category.clone())
+    );
+    if !symbol.aliases.is_empty() {
+        let _ = writeln!(out, "- **Also known as:** {}", symbol.aliases.join(", "));
+    }
+    if !symbol.localized_names.is_empty() {
+        let mut names: Vec<_> = symbol.localized_names.iter().collect();
+        names.sort();
+        let names: Vec<String> = names
+            .into_iter()
+            .map(|(language, name)| format!("{} ({})", name, language))
+            .collect();
+        let _ = writeln!(out, "- **Localized names:** {}", names.join(", "));
+    }
+
+    if !symbol.description.is_empty() {
+        let _ = writeln!(out, "\n{}", symbol.description);
+    }
+
+    if !symbol.interpretations.is_empty() {
+        let _ = writeln!(out, "\n## Interpretations");
+        let mut interpretations: Vec<_> = symbol.interpretations.iter().collect();
+        interpretations.sort_by(|a, b| a.0.cmp(b.0));
+        for (context, layer) in interpretations {
+            let _ = write!(out, "\n### {} ({}", context, layer.level);
+            if let Some(tradition) = &layer.tradition {
+                if !tradition.eq_ignore_ascii_case(context) {
+                    let _ = write!(out, ", {}", tradition);
+                }
+            }
+            let _ = writeln!(out, ")\n\n{}", layer.text);
+            if let Some(source) = &layer.source {
+                let _ = writeln!(out, "\n_Source: {}_", source);
+            }
+        }
+    }
+
+    if !symbol.properties.is_empty() {
+        let _ = writeln!(out, "\n## Properties\n");
+        let mut properties: Vec<_> = symbol.properties.iter().collect();
+        properties.sort();
+        for (key, value) in properties {
+            let _ = writeln!(out, "- **{}:** {}", key, value);
+        }
+    }
+
+    if !symbol.related_symbols.is_empty() {
+        let _ = writeln!(out, "\n## Related Symbols\n");
+        for id in &symbol.related_symbols {
+            let _ = writeln!(out, "- [{}]({})", id, ResourceUri::Symbol(id.clone()));
+        }
+    }
+
+    out
+}
+
+/// Renders a symbol set as markdown, listing its symbols in set order.
+pub fn render_symbol_set(symbol_set: &SymbolSet) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", symbol_set.name);
+    let _ = writeln!(out, "- **ID:** `{}`", symbol_set.id);
+    let _ = writeln!(
+        out,
+        "- **Category:** [{}]({})",
+        symbol_set.category,
+        ResourceUri::Category(symbol_set.category.clone())
+    );
+    if !symbol_set.description.is_empty() {
+        let _ = writeln!(out, "\n{}", symbol_set.description);
+    }
+    let symbols: Vec<&Symbol> = symbol_set.symbols.values().collect();
+    render_symbol_list(&mut out, symbols);
+    out
+}
+
+/// Renders a page of a category's symbols as markdown, listed by name, with
+/// how many the category holds and how many of them are shown.
+pub fn render_category(category: &str, page: &Page<Symbol>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Category: {}
+",
+        category
+    );
+    let _ = writeln!(out, "- **Symbols:** {}", page.total_count);
+    if page.next_cursor.is_some() {
+        let _ = writeln!(
+            out,
+            "\n_Showing {} of {} symbols._",
+            page.items.len(),
+            page.total_count
+        );
+    }
+    render_symbol_list(&mut out, page.items.iter().collect());
+    out
+}
+
+fn render_symbol_list(out: &mut String, symbols: Vec<&Symbol>) {
+    let _ = writeln!(out, "\n## Symbols\n");
+    if symbols.is_empty() {
+        let _ = writeln!(out, "_None._");
+        return;
+    }
+    for symbol in symbols {
+        let _ = write!(
+            out,
+            "- [{}]({})",
+            symbol.name,
+            ResourceUri::Symbol(symbol.id.clone())
+        );
+        if !symbol.description.is_empty() {
+            let _ = write!(out, ": {}", symbol.description);
+        }
+        let _ = writeln!(out);
+    }
+}
+
+/// Escapes every byte outside the URI unreserved set.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{:02X}", byte);
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ontology_core::domain::InterpretationLayer;
+
+    #[test]
+    fn test_resource_uri_round_trip() {
+        for uri in [
+            ResourceUri::Symbol("water".to_string()),
+            ResourceUri::SymbolSet("jungian-archetypes".to_string()),
+            ResourceUri::Category("folk tale".to_string()),
+        ] {
+            assert_eq!(ResourceUri::parse(&uri.to_string()), Some(uri));
+        }
+        assert_eq!(
+            ResourceUri::Category("folk tale".to_string()).to_string(),
+            "category://folk%20tale"
+        );
+    }
+
+    #[test]
+    fn test_resource_uri_rejects_unknown_or_empty() {
+        assert_eq!(ResourceUri::parse("file:///etc/passwd"), None);
+        assert_eq!(ResourceUri::parse("symbol://"), None);
+        assert_eq!(ResourceUri::parse("symbol://bad%2"), None);
+        assert_eq!(ResourceUri::parse("water"), None);
+    }
+
+    #[test]
+    fn test_resource_cursor_round_trip() {
+        for cursor in [
+            ResourceCursor::Symbols(None),
+            ResourceCursor::Symbols(Some("eyJzb3J0Ijo".to_string())),
+            ResourceCursor::SymbolSets(Some("eyJzb3J0Ijo".to_string())),
+            ResourceCursor::Categories,
+        ] {
+            assert_eq!(ResourceCursor::parse(&cursor.encode()), Some(cursor));
+        }
+        assert_eq!(ResourceCursor::parse("categories:x"), None);
+        assert_eq!(ResourceCursor::parse("archetypes"), None);
+    }
+
+    #[test]
+    fn test_render_symbol_markdown() {
+        let mut symbol = Symbol::new(
+            "water".to_string(),
+            "Water".to_string(),
+            "nature".to_string(),
+            "Symbolizes emotions".to_string(),
+        )
+        .with_aliases(vec!["aqua"]);
+        symbol.interpretations.insert(
+            "jungian".to_string(),
+            InterpretationLayer::cultural("jungian", "The unconscious"),
+        );
+        symbol.interpretations.insert(
+            "default".to_string(),
+            InterpretationLayer::universal("Emotions").with_source("Folk wisdom"),
+        );
+        symbol.related_symbols.push("ocean".to_string());
+
+        let markdown = render_symbol(&symbol);
+
+        assert!(markdown.starts_with("# Water\n"));
+        assert!(markdown.contains("- **Category:** [nature](category://nature)"));
+        assert!(markdown.contains("- **Also known as:** aqua"));
+        assert!(markdown.contains("_Source: Folk wisdom_"));
+        assert!(markdown.contains("- [ocean](symbol://ocean)"));
+        let default = markdown.find("### default (universal)").unwrap();
+        let jungian = markdown.find("### jungian (cultural)").unwrap();
+        assert!(default < jungian);
+    }
+
+    #[test]
+    fn test_render_category_shows_how_many_are_listed() {
+        let symbols: Vec<Symbol> = ["Moon", "Sun"]
+            .iter()
+            .map(|name| {
+                Symbol::new(
+                    name.to_lowercase(),
+                    name.to_string(),
+                    "sky".to_string(),
+                    String::new(),
+                )
+            })
+            .collect();
+
+        let page = Page {
+            items: symbols.clone(),
+            next_cursor: None,
+            total_count: 2,
+        };
+        let markdown = render_category("sky", &page);
+        assert!(markdown.contains("- **Symbols:** 2"));
+        assert!(!markdown.contains("_Showing"));
+
+        let page = Page {
+            items: symbols[..1].to_vec(),
+            next_cursor: Some("next".to_string()),
+            total_count: 2,
+        };
+        let markdown = render_category("sky", &page);
+        assert!(markdown.contains("_Showing 1 of 2 symbols._"));
+        assert!(markdown.contains("- [Moon](symbol://moon)"));
+        assert!(!markdown.contains("symbol://sun"));
+    }
+}
@@ -1,5 +1,14 @@
+use ontology_core::domain::{
+    Archetype, ArchetypeNode, InterpretationLayer, Symbol, SymbolRelation,
+};
+use ontology_core::extraction::{ExtractedSymbol, TextSpan};
+use ontology_core::graph::PathStep;
+use ontology_core::report::{
+    CategoryCount, DreamReport, ReportedSymbol, SelectedInterpretation, ToneSummary,
+};
 use rmcp::schemars;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// Parameters for the get_symbols MCP method
 ///
@@ -17,6 +26,15 @@ pub struct GetSymbolsParams {
     /// Optional category filter
     #[serde(default)]
     pub category: Option<String>,
+
+    /// Opaque next_cursor from a previous response, to continue from; empty
+    /// for the first page
+    #[serde(default)]
+    pub cursor: String,
+
+    /// Result order: "name" or "name_desc"; empty for "name"
+    #[serde(default)]
+    pub sort: String,
 }
 
 /// Parameters for the search_symbols MCP method (with non-optional query parameter)
@@ -26,12 +44,72 @@ pub struct GetSymbolsParams {
 /// RECOMMENDED: Use this method for all text searches.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct SearchSymbolsParams {
-    /// Search query for symbol names or descriptions (required)
+    /// Search query matched against names, descriptions, interpretations and
+    /// properties (required)
     pub query: String,
 
     /// Maximum number of symbols to return
     #[serde(default = "default_limit")]
     pub limit: usize,
+
+    /// Opaque next_cursor from a previous response, to continue from; empty
+    /// for the first page
+    #[serde(default)]
+    pub cursor: String,
+
+    /// Result order: "relevance", "name" or "name_desc"; empty for relevance, or
+    /// for name when searching symbol sets, which only sort by name
+    #[serde(default)]
+    pub sort: String,
+}
+
+/// Parameters for the get_symbol MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetSymbolParams {
+    /// ID, name, alias or localized name of the symbol, in any case (required)
+    pub id: String,
+
+    /// BCP 47 language tag, such as "es" or "pt-BR", to name the symbol in;
+    /// empty for its canonical name
+    #[serde(default)]
+    pub language: String,
+}
+
+/// Parameters for the semantic_search_symbols MCP method
+///
+/// Unlike search_symbols, the query does not need to share words with the
+/// symbols it finds; it is embedded and compared by meaning.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SemanticSearchSymbolsParams {
+    /// Free-text description of what to look for, such as a dream fragment (required)
+    pub query: String,
+
+    /// Maximum number of symbols to return
+    #[serde(default = "default_semantic_limit")]
+    pub limit: usize,
+}
+
+/// Parameters for the extract_symbols_from_text MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExtractSymbolsParams {
+    /// Free text to find symbols in, such as a dream narrative (required)
+    pub text: String,
+}
+
+/// Parameters for the interpret_dream MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct InterpretDreamParams {
+    /// The dream, told in the dreamer's own words (required)
+    pub text: String,
+
+    /// Tradition whose interpretations are preferred, such as "jungian"; empty for
+    /// universal ones
+    #[serde(default)]
+    pub culture: String,
+
+    /// How the dream felt (positive, negative, neutral, ...); empty if unknown
+    #[serde(default)]
+    pub emotional_tone: String,
 }
 
 /// Parameters for the filter_by_category MCP method (with non-optional category parameter)
@@ -47,13 +125,260 @@ pub struct CategorySymbolsParams {
     /// Maximum number of symbols to return
     #[serde(default = "default_limit")]
     pub limit: usize,
+
+    /// Opaque next_cursor from a previous response, to continue from; empty
+    /// for the first page
+    #[serde(default)]
+    pub cursor: String,
+
+    /// Result order: "name" or "name_desc"; empty for "name"
+    #[serde(default)]
+    pub sort: String,
+}
+
+/// Parameters for the get_symbol_sets MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetSymbolSetsParams {
+    /// Maximum number of symbol sets to return
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+
+    /// Opaque next_cursor from a previous response, to continue from; empty
+    /// for the first page
+    #[serde(default)]
+    pub cursor: String,
+
+    /// Result order: "name" or "name_desc"; empty for "name"
+    #[serde(default)]
+    pub sort: String,
+}
+
+/// Parameters for the get_related_network MCP method
+///
+/// Walks the relationship graph outward from one symbol so a client can pull
+/// several hops of context in a single call.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RelatedNetworkParams {
+    /// ID of the symbol to start from (required)
+    pub symbol_id: String,
+
+    /// Number of hops to follow (1-5)
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+
+    /// Relation types to follow (similar_to, opposite_of, ...); empty follows all
+    #[serde(default)]
+    pub relation_types: Vec<String>,
+
+    /// Maximum number of symbols to include in the network
+    #[serde(default = "default_limit")]
+    pub max_nodes: usize,
+}
+
+/// Parameters for the find_symbol_path MCP method
+///
+/// Explains how two symbols are connected by returning the chain of relations
+/// between them, with a short interpretation of each symbol along the way.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FindSymbolPathParams {
+    /// ID of the symbol the path starts from (required)
+    pub from_id: String,
+
+    /// ID of the symbol the path leads to (required)
+    pub to_id: String,
+
+    /// Maximum number of hops in the path (1-8)
+    #[serde(default = "default_path_depth")]
+    pub max_depth: usize,
+
+    /// Relation types the path may cross; empty allows all
+    #[serde(default)]
+    pub relation_types: Vec<String>,
+
+    /// Search algorithm: auto, bfs (fewest hops) or dijkstra (strongest relations)
+    #[serde(default)]
+    pub algorithm: Option<String>,
+}
+
+/// Parameters for the get_archetypes MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetArchetypesParams {
+    /// Maximum number of archetypes to return
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+/// Parameters for the get_archetype_hierarchy MCP method
+///
+/// Uses an empty string rather than `Option<String>` for "no root", in line
+/// with the other parameter structs.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ArchetypeHierarchyParams {
+    /// ID of the archetype whose subtree to return; empty returns every tree
+    #[serde(default)]
+    pub root_id: String,
+}
+
+/// Parameters for the get_symbols_by_archetype MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SymbolsByArchetypeParams {
+    /// ID of the archetype (required)
+    pub archetype_id: String,
+
+    /// Also return symbols linked to more specific archetypes below this one
+    #[serde(default = "default_true")]
+    pub include_descendants: bool,
+
+    /// Maximum number of symbols to return
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+
+    /// Opaque next_cursor from a previous response, to continue from; empty
+    /// for the first page
+    #[serde(default)]
+    pub cursor: String,
+
+    /// Result order: "name" or "name_desc"; empty for "name"
+    #[serde(default)]
+    pub sort: String,
+}
+
+/// Parameters for the create_symbol MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CreateSymbolParams {
+    /// Unique identifier for the new symbol, such as "water" (required)
+    pub id: String,
+
+    /// Name of the symbol (required)
+    pub name: String,
+
+    /// Category of the symbol (dream, mythological, etc.) - required
+    pub category: String,
+
+    /// Brief description of the symbol
+    #[serde(default)]
+    pub description: String,
+
+    /// Other names the symbol goes by
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// Names in other languages, keyed by BCP 47 language tag
+    #[serde(default)]
+    pub localized_names: HashMap<String, String>,
+
+    /// Free-form properties, such as "element": "water"
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+/// Parameters for the update_symbol MCP method
+///
+/// Only the fields given are changed. Empty strings leave a field as it is,
+/// while lists and maps replace the symbol's own when present.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct UpdateSymbolParams {
+    /// ID of the symbol to update (required)
+    pub id: String,
+
+    /// New name; empty keeps the current one
+    #[serde(default)]
+    pub name: String,
+
+    /// New category; empty keeps the current one
+    #[serde(default)]
+    pub category: String,
+
+    /// New description; empty keeps the current one
+    #[serde(default)]
+    pub description: String,
+
+    /// Replacement aliases
+    #[serde(default)]
+    pub aliases: Option<Vec<String>>,
+
+    /// Replacement localized names, keyed by BCP 47 language tag
+    #[serde(default)]
+    pub localized_names: Option<HashMap<String, String>>,
+
+    /// Replacement properties
+    #[serde(default)]
+    pub properties: Option<HashMap<String, String>>,
+}
+
+/// Parameters for the add_interpretation MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AddInterpretationParams {
+    /// ID, name or alias of the symbol to interpret (required)
+    pub symbol_id: String,
+
+    /// Context the interpretation belongs to, such as "default" or "jungian";
+    /// replaces any interpretation already in that context (required)
+    pub context: String,
+
+    /// The interpretation itself (required)
+    pub text: String,
+
+    /// Layer of the interpretation (universal, cultural or personal); empty
+    /// infers it from the context
+    #[serde(default)]
+    pub level: String,
+
+    /// Tradition a cultural interpretation belongs to; empty uses the context
+    #[serde(default)]
+    pub tradition: String,
+
+    /// Where the interpretation comes from, such as a book or author
+    #[serde(default)]
+    pub source: String,
+}
+
+/// Parameters for the relate_symbols MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RelateSymbolsParams {
+    /// ID, name or alias of the symbol the relation starts from (required)
+    pub source_id: String,
+
+    /// ID, name or alias of the symbol the relation points to (required)
+    pub target_id: String,
+
+    /// Relation type (related_to, similar_to, opposite_of, ...); empty for related_to
+    #[serde(default)]
+    pub relation_type: String,
+
+    /// Strength of the relation between 0.0 and 1.0
+    #[serde(default)]
+    pub weight: Option<f64>,
+}
+
+/// Parameters for the delete_symbol MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DeleteSymbolParams {
+    /// ID of the symbol to delete (required)
+    pub id: String,
 }
 
 fn default_limit() -> usize {
     50
 }
 
-/// Response for the get_symbols, search_symbols, and filter_by_category MCP methods
+fn default_semantic_limit() -> usize {
+    10
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_path_depth() -> usize {
+    4
+}
+
+fn default_depth() -> usize {
+    2
+}
+
+/// Response for the get_symbols and filter_by_category MCP methods
 ///
 /// This schema follows the Model Context Protocol (MCP) specification for method responses.
 /// Reference: https://modelcontextprotocol.io
@@ -62,8 +387,75 @@ pub struct GetSymbolsResponse {
     /// List of symbols matching the query
     pub symbols: Vec<SymbolDTO>,
 
-    /// Total count of symbols matching the query (for pagination)
+    /// Total count of symbols matching the query, across all pages
+    pub total_count: usize,
+
+    /// Pass as cursor to get the next page; absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Response for the get_symbol MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetSymbolResponse {
+    pub symbol: SymbolDTO,
+
+    /// The symbol's name in the requested language, or its canonical name
+    pub display_name: String,
+}
+
+/// Response for the create_symbol, update_symbol and add_interpretation MCP
+/// methods: the symbol as stored after the change
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SymbolWriteResponse {
+    pub symbol: SymbolDTO,
+}
+
+/// Response for the relate_symbols MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RelateSymbolsResponse {
+    /// The relation as stored, between symbol IDs
+    pub relation: RelationDTO,
+
+    /// The source symbol, with the new relation among its related symbols
+    pub symbol: SymbolDTO,
+}
+
+/// Response for the delete_symbol MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DeleteSymbolResponse {
+    /// ID of the deleted symbol
+    pub id: String,
+
+    pub deleted: bool,
+}
+
+/// Response for the search_symbols MCP method, most relevant symbols first
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SearchSymbolsResponse {
+    /// Symbols matching the query, with their relevance scores
+    pub symbols: Vec<SymbolHitDTO>,
+
+    /// Total count of symbols matching the query, across all pages
     pub total_count: usize,
+
+    /// Pass as cursor to get the next page; absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+
+    /// Did you mean: symbols with names close to a query that found nothing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub did_you_mean: Vec<SuggestionDTO>,
+}
+
+/// A symbol whose name is close to a possibly misspelled query
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SuggestionDTO {
+    pub id: String,
+    pub name: String,
+
+    /// Name similarity from 0 to 1; higher is closer
+    pub score: f32,
 }
 
 /// Response for the get_categories MCP method
@@ -98,6 +490,435 @@ pub struct SymbolDTO {
 
     /// Related symbol IDs
     pub related_symbols: Vec<String>,
+
+    /// Interpretations keyed by context, each tagged with its layer
+    #[serde(default)]
+    pub interpretations: HashMap<String, InterpretationDTO>,
+
+    /// Other names the symbol goes by
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
+    /// Names in other languages, keyed by BCP 47 language tag
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub localized_names: HashMap<String, String>,
+}
+
+/// A symbol found by search_symbols, with how relevant it is to the query
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SymbolHitDTO {
+    #[serde(flatten)]
+    pub symbol: SymbolDTO,
+
+    /// Relevance score; higher is more relevant
+    pub score: f32,
+}
+
+impl SymbolDTO {
+    pub fn from_symbol(symbol: &Symbol) -> Self {
+        SymbolDTO {
+            id: symbol.id.clone(),
+            name: symbol.name.clone(),
+            category: symbol.category.clone(),
+            description: symbol.description.clone(),
+            related_symbols: symbol.related_symbols.clone(),
+            interpretations: interpretation_dtos(&symbol.interpretations),
+            aliases: symbol.aliases.clone(),
+            localized_names: symbol.localized_names.clone(),
+        }
+    }
+}
+
+/// Data transfer object for a typed edge between two symbols
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RelationDTO {
+    /// Symbol the relation starts from
+    pub source_id: String,
+
+    /// Symbol the relation points to
+    pub target_id: String,
+
+    /// Relation type (related_to, similar_to, opposite_of, transforms_into, ...)
+    pub relation_type: String,
+
+    /// Strength of the relation between 0.0 and 1.0, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+}
+
+impl From<&SymbolRelation> for RelationDTO {
+    fn from(relation: &SymbolRelation) -> Self {
+        RelationDTO {
+            source_id: relation.source_id.clone(),
+            target_id: relation.target_id.clone(),
+            relation_type: relation.relation_type.to_string(),
+            weight: relation.weight,
+        }
+    }
+}
+
+/// A symbol in a related network together with its distance from the root
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NetworkNodeDTO {
+    #[serde(flatten)]
+    pub symbol: SymbolDTO,
+
+    /// Number of hops from the root symbol
+    pub depth: usize,
+}
+
+/// Response for the get_related_network MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RelatedNetworkResponse {
+    /// ID of the symbol the walk started from
+    pub root_id: String,
+
+    /// Symbols reached, including the root at depth 0
+    pub nodes: Vec<NetworkNodeDTO>,
+
+    /// Relations between the returned symbols
+    pub edges: Vec<RelationDTO>,
+
+    /// True when max_nodes cut the network short
+    pub truncated: bool,
+}
+
+/// A single hop of a path between two symbols
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PathStepDTO {
+    /// Symbol this hop starts from
+    pub from_id: String,
+
+    /// Symbol this hop leads to
+    pub to_id: String,
+
+    /// Name of the symbol this hop leads to
+    pub to_name: String,
+
+    /// Relation crossed by this hop
+    pub relation_type: String,
+
+    /// Strength of the relation between 0.0 and 1.0, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+
+    /// True when the relation is stored in the opposite direction to the walk
+    pub reversed: bool,
+
+    /// Short interpretation of the symbol this hop leads to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interpretation: Option<String>,
+}
+
+impl From<&PathStep> for PathStepDTO {
+    fn from(step: &PathStep) -> Self {
+        PathStepDTO {
+            from_id: step.from_id.clone(),
+            to_id: step.to_id.clone(),
+            to_name: step.to_name.clone(),
+            relation_type: step.relation.relation_type.to_string(),
+            weight: step.relation.weight,
+            reversed: step.relation.source_id != step.from_id,
+            interpretation: step.interpretation.clone(),
+        }
+    }
+}
+
+/// Where a symbol is mentioned in the text
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TextSpanDTO {
+    /// Byte offset of the first character
+    pub start: usize,
+
+    /// Byte offset just past the last character
+    pub end: usize,
+
+    /// The words of the text at this span
+    pub text: String,
+}
+
+impl From<&TextSpan> for TextSpanDTO {
+    fn from(span: &TextSpan) -> Self {
+        TextSpanDTO {
+            start: span.start,
+            end: span.end,
+            text: span.text.clone(),
+        }
+    }
+}
+
+/// A symbol mentioned in the text, with every place it is mentioned
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExtractedSymbolDTO {
+    #[serde(flatten)]
+    pub symbol: SymbolDTO,
+
+    pub spans: Vec<TextSpanDTO>,
+}
+
+impl From<&ExtractedSymbol> for ExtractedSymbolDTO {
+    fn from(extracted: &ExtractedSymbol) -> Self {
+        ExtractedSymbolDTO {
+            symbol: SymbolDTO::from_symbol(&extracted.symbol),
+            spans: extracted.spans.iter().map(TextSpanDTO::from).collect(),
+        }
+    }
+}
+
+/// Response for the extract_symbols_from_text MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExtractSymbolsResponse {
+    /// Symbols mentioned in the text, in order of first mention
+    pub symbols: Vec<ExtractedSymbolDTO>,
+
+    pub total_count: usize,
+}
+
+/// The interpretation chosen for a symbol in a dream report
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SelectedInterpretationDTO {
+    /// Context the interpretation is stored under (default, jungian, ...)
+    pub context: String,
+
+    #[serde(flatten)]
+    pub interpretation: InterpretationDTO,
+}
+
+impl From<&SelectedInterpretation> for SelectedInterpretationDTO {
+    fn from(selected: &SelectedInterpretation) -> Self {
+        SelectedInterpretationDTO {
+            context: selected.context.clone(),
+            interpretation: InterpretationDTO::from(&selected.layer),
+        }
+    }
+}
+
+/// A symbol mentioned in the dream, with the interpretation that fits the dreamer
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ReportedSymbolDTO {
+    #[serde(flatten)]
+    pub symbol: SymbolDTO,
+
+    pub spans: Vec<TextSpanDTO>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interpretation: Option<SelectedInterpretationDTO>,
+}
+
+impl From<&ReportedSymbol> for ReportedSymbolDTO {
+    fn from(reported: &ReportedSymbol) -> Self {
+        ReportedSymbolDTO {
+            symbol: SymbolDTO::from_symbol(&reported.symbol),
+            spans: reported.spans.iter().map(TextSpanDTO::from).collect(),
+            interpretation: reported
+                .interpretation
+                .as_ref()
+                .map(SelectedInterpretationDTO::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CategoryCountDTO {
+    pub category: String,
+    pub count: usize,
+}
+
+impl From<&CategoryCount> for CategoryCountDTO {
+    fn from(count: &CategoryCount) -> Self {
+        CategoryCountDTO {
+            category: count.category.clone(),
+            count: count.count,
+        }
+    }
+}
+
+/// Emotional tone of the symbols in a dream
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ToneDTO {
+    /// Number of symbols carrying each tone
+    pub counts: BTreeMap<String, usize>,
+
+    /// The tone most symbols carry, unless none do or two tie
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominant: Option<String>,
+
+    /// Whether the dominant tone is the one the dreamer reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches_context: Option<bool>,
+}
+
+impl From<&ToneSummary> for ToneDTO {
+    fn from(tone: &ToneSummary) -> Self {
+        ToneDTO {
+            counts: tone.counts.clone(),
+            dominant: tone.dominant.clone(),
+            matches_context: tone.matches_context,
+        }
+    }
+}
+
+/// Response for the interpret_dream MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct InterpretDreamResponse {
+    /// Symbols mentioned in the dream, in order of first mention
+    pub symbols: Vec<ReportedSymbolDTO>,
+
+    /// Relations among the symbols mentioned
+    pub relationships: Vec<RelationDTO>,
+
+    /// How many of the symbols fall in each category, most common first
+    pub dominant_categories: Vec<CategoryCountDTO>,
+
+    pub tone: ToneDTO,
+}
+
+impl From<&DreamReport> for InterpretDreamResponse {
+    fn from(report: &DreamReport) -> Self {
+        InterpretDreamResponse {
+            symbols: report.symbols.iter().map(ReportedSymbolDTO::from).collect(),
+            relationships: report.relationships.iter().map(RelationDTO::from).collect(),
+            dominant_categories: report
+                .dominant_categories
+                .iter()
+                .map(CategoryCountDTO::from)
+                .collect(),
+            tone: ToneDTO::from(&report.tone),
+        }
+    }
+}
+
+/// Response for the find_symbol_path MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FindSymbolPathResponse {
+    /// Whether the symbols are connected within max_depth
+    pub found: bool,
+
+    /// ID of the symbol the path starts from
+    pub from_id: String,
+
+    /// ID of the symbol the path leads to
+    pub to_id: String,
+
+    /// Hops from from_id to to_id, empty when no path was found
+    pub steps: Vec<PathStepDTO>,
+
+    /// Number of hops, or summed relation cost for weighted searches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+
+    /// Algorithm that produced the path (bfs or dijkstra)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+}
+
+/// Data transfer object for an archetype
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ArchetypeDTO {
+    /// Unique identifier for the archetype
+    pub id: String,
+
+    /// Name of the archetype
+    pub name: String,
+
+    /// Brief description of the archetype
+    pub description: String,
+
+    /// ID of the more general archetype this one belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+}
+
+impl From<&Archetype> for ArchetypeDTO {
+    fn from(archetype: &Archetype) -> Self {
+        ArchetypeDTO {
+            id: archetype.id.clone(),
+            name: archetype.name.clone(),
+            description: archetype.description.clone(),
+            parent_id: archetype.parent_id.clone(),
+        }
+    }
+}
+
+/// An archetype with its more specific archetypes nested below it
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ArchetypeNodeDTO {
+    #[serde(flatten)]
+    pub archetype: ArchetypeDTO,
+
+    /// Child archetypes, sorted by name
+    pub children: Vec<ArchetypeNodeDTO>,
+}
+
+impl From<&ArchetypeNode> for ArchetypeNodeDTO {
+    fn from(node: &ArchetypeNode) -> Self {
+        ArchetypeNodeDTO {
+            archetype: ArchetypeDTO::from(&node.archetype),
+            children: node.children.iter().map(ArchetypeNodeDTO::from).collect(),
+        }
+    }
+}
+
+/// Response for the get_archetypes MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetArchetypesResponse {
+    /// Archetypes sorted by name
+    pub archetypes: Vec<ArchetypeDTO>,
+
+    /// Total number of archetypes
+    pub total_count: usize,
+}
+
+/// Response for the get_archetype_hierarchy MCP method
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ArchetypeHierarchyResponse {
+    /// Top-level archetypes, or the requested root, with their descendants
+    pub roots: Vec<ArchetypeNodeDTO>,
+
+    /// Number of archetypes in the returned trees
+    pub total_count: usize,
+}
+
+/// Data transfer object for a single layered interpretation
+///
+/// The level is one of `universal`, `cultural` or `personal`, letting clients
+/// tell archetypal meanings apart from tradition-specific or personal ones.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct InterpretationDTO {
+    /// Interpretation layer: universal, cultural or personal
+    pub level: String,
+
+    /// The interpretation itself
+    pub text: String,
+
+    /// Where the interpretation comes from (author, dataset, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Tradition the interpretation belongs to (jungian, celtic, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tradition: Option<String>,
+}
+
+impl From<&InterpretationLayer> for InterpretationDTO {
+    fn from(layer: &InterpretationLayer) -> Self {
+        InterpretationDTO {
+            level: layer.level.to_string(),
+            text: layer.text.clone(),
+            source: layer.source.clone(),
+            tradition: layer.tradition.clone(),
+        }
+    }
+}
+
+/// Converts a symbol's interpretations into their DTO form
+pub fn interpretation_dtos(
+    interpretations: &HashMap<String, InterpretationLayer>,
+) -> HashMap<String, InterpretationDTO> {
+    interpretations
+        .iter()
+        .map(|(context, layer)| (context.clone(), InterpretationDTO::from(layer)))
+        .collect()
 }
 
 #[cfg(test)]
@@ -110,6 +931,8 @@ mod tests {
         let params = GetSymbolsParams {
             limit: 10,
             category: None,
+            cursor: String::new(),
+            sort: String::new(),
         };
 
         let json = serde_json::to_value(params).unwrap();
@@ -122,6 +945,8 @@ mod tests {
         let params = GetSymbolsParams {
             limit: default_limit(),
             category: None,
+            cursor: String::new(),
+            sort: String::new(),
         };
 
         assert_eq!(params.limit, 50);
@@ -135,6 +960,9 @@ mod tests {
             category: "dream".to_string(),
             description: "Symbolizes emotions".to_string(),
             related_symbols: vec!["ocean".to_string(), "river".to_string()],
+            interpretations: HashMap::new(),
+            aliases: Vec::new(),
+            localized_names: HashMap::new(),
         };
 
         let json = serde_json::to_value(symbol).unwrap();
@@ -145,6 +973,30 @@ mod tests {
         assert_eq!(json["description"], "Symbolizes emotions");
         assert_eq!(json["related_symbols"][0], "ocean");
         assert_eq!(json["related_symbols"][1], "river");
+        assert!(json.get("aliases").is_none());
+        assert!(json.get("localized_names").is_none());
+    }
+
+    #[test]
+    fn test_interpretation_dtos_expose_layers() {
+        let mut interpretations = HashMap::new();
+        interpretations.insert(
+            "default".to_string(),
+            InterpretationLayer::universal("Emotions and the unconscious"),
+        );
+        interpretations.insert(
+            "celtic".to_string(),
+            InterpretationLayer::cultural("celtic", "Threshold to the otherworld")
+                .with_source("Celtic Myth"),
+        );
+
+        let json = serde_json::to_value(interpretation_dtos(&interpretations)).unwrap();
+
+        assert_eq!(json["default"]["level"], "universal");
+        assert!(json["default"].get("tradition").is_none());
+        assert_eq!(json["celtic"]["level"], "cultural");
+        assert_eq!(json["celtic"]["tradition"], "celtic");
+        assert_eq!(json["celtic"]["source"], "Celtic Myth");
     }
 
     #[test]
@@ -160,4 +1012,25 @@ mod tests {
         assert_eq!(json["categories"][1], "jungian");
         assert_eq!(json["count"], 2);
     }
+
+    #[test]
+    fn test_related_network_params_defaults() {
+        let params: RelatedNetworkParams =
+            serde_json::from_value(serde_json::json!({ "symbol_id": "water" })).unwrap();
+
+        assert_eq!(params.symbol_id, "water");
+        assert_eq!(params.depth, 2);
+        assert!(params.relation_types.is_empty());
+        assert_eq!(params.max_nodes, 50);
+    }
+
+    #[test]
+    fn test_symbols_by_archetype_params_defaults() {
+        let params: SymbolsByArchetypeParams =
+            serde_json::from_value(serde_json::json!({ "archetype_id": "trickster" })).unwrap();
+
+        assert_eq!(params.archetype_id, "trickster");
+        assert!(params.include_descendants);
+        assert_eq!(params.limit, 50);
+    }
 }
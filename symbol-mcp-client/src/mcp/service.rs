@@ -1,29 +1,110 @@
+use rmcp::handler::server::router::tool::ToolRouter;
+use rmcp::handler::server::tool::{Parameters, ToolCallContext};
 use rmcp::model::*;
 use rmcp::service::RequestContext;
-use rmcp::tool;
 use rmcp::RoleServer;
 use rmcp::ServerHandler;
+use rmcp::{tool, tool_router};
+use std::future::Future;
 use std::sync::Arc;
 use tracing::{debug, error, info};
 
 #[cfg(feature = "local")]
-use crate::mcp::test_utils::{InMemorySymbolRepository, InMemorySymbolSetRepository};
-use ontology_core::db::repository::{SymbolRepository, SymbolSetRepository};
+use ontology_core::db::repository::InMemoryRepositoryFactory;
+use ontology_core::db::repository::{
+    ArchetypeRepository, ListQuery, Page, RepositoryError, RepositoryFactory, SymbolRepository,
+    SymbolSetRepository,
+};
+use ontology_core::domain::SymbolSet;
+use ontology_core::embedding::{Embedder, HashingEmbedder};
 
 use crate::mcp::methods::{
+    add_interpretation::add_interpretation,
+    create_symbol::create_symbol,
+    delete_symbol::delete_symbol,
+    extract_symbols_from_text::extract_symbols_from_text,
     filter_by_category::filter_by_category,
+    find_symbol_path::find_symbol_path,
+    get_archetype_hierarchy::get_archetype_hierarchy,
+    get_archetypes::get_archetypes,
+    get_related_network::get_related_network,
+    get_symbol::get_symbol,
     get_symbols::{get_symbols, Handler, MethodCall, RmcpError},
+    get_symbols_by_archetype::get_symbols_by_archetype,
+    interpret_dream::interpret_dream,
+    relate_symbols::relate_symbols,
     search_symbols::search_symbols,
+    semantic_search_symbols::semantic_search_symbols,
+    update_symbol::update_symbol,
+    utils::{list_query, repository_error_to_rmcp_error},
+};
+use crate::mcp::prompts::{PromptError, PromptLibrary};
+use crate::mcp::resources::{self, ResourceCursor, ResourceUri, MARKDOWN_MIME_TYPE};
+use crate::mcp::schema::{
+    AddInterpretationParams, ArchetypeHierarchyParams, CategorySymbolsParams, CreateSymbolParams,
+    DeleteSymbolParams, ExtractSymbolsParams, FindSymbolPathParams, GetArchetypesParams,
+    GetSymbolParams, GetSymbolSetsParams, GetSymbolsParams, InterpretDreamParams,
+    RelateSymbolsParams, RelatedNetworkParams, SearchSymbolsParams, SemanticSearchSymbolsParams,
+    SymbolsByArchetypeParams, UpdateSymbolParams,
 };
-use crate::mcp::schema::{CategorySymbolsParams, GetSymbolsParams, SearchSymbolsParams};
+
+/// Tools that change the ontology, only served when writes are allowed
+const WRITE_TOOLS: [&str; 5] = [
+    "create_symbol",
+    "update_symbol",
+    "add_interpretation",
+    "relate_symbols",
+    "delete_symbol",
+];
+
+/// Maps a method error onto the MCP error a client sees. Unknown IDs and
+/// clashes with stored symbols are for the caller to fix, like bad parameters.
+fn mcp_error(e: RmcpError) -> rmcp::Error {
+    match e {
+        RmcpError::ParseError(msg) => rmcp::Error::invalid_params(msg, None),
+        e @ (RmcpError::NotFound(_) | RmcpError::Conflict(_)) => {
+            rmcp::Error::invalid_params(e.to_string(), None)
+        }
+        RmcpError::RepositoryError(msg) | RmcpError::Other(msg) => {
+            rmcp::Error::internal_error(msg, None)
+        }
+    }
+}
+
+/// Wraps a tool's JSON result as the tool call result, logging failures
+/// under `context`
+fn tool_result(
+    context: &str,
+    result: Result<serde_json::Value, RmcpError>,
+) -> Result<CallToolResult, rmcp::Error> {
+    let result = result.map_err(|e| {
+        error!("{}: {}", context, e);
+        mcp_error(e)
+    })?;
+    match Content::json(result) {
+        Ok(content) => {
+            info!("Result preview:\n{}", pretty_print_result(&content));
+            Ok(CallToolResult::success(vec![content]))
+        }
+        Err(e) => {
+            error!("Failed to create content: {}", e);
+            Err(e)
+        }
+    }
+}
 
 // Helper function for pretty-printing results
 fn pretty_print_result(content: &Content) -> String {
     if let Some(text) = content.as_text() {
         if let Ok(value) = serde_json::from_str::<serde_json::Value>(&text.text) {
             if let Ok(pretty) = serde_json::to_string_pretty(&value) {
-                if pretty.len() > 500 {
-                    let mut shortened = pretty[..400].to_string();
+                if pretty.chars().count() > 500 {
+                    // Cut on a character boundary; names may be non-ASCII
+                    let end = pretty
+                        .char_indices()
+                        .nth(400)
+                        .map_or(pretty.len(), |(i, _)| i);
+                    let mut shortened = pretty[..end].to_string();
                     shortened.push_str("\n... (truncated) ...\n}");
                     return shortened;
                 }
@@ -40,25 +121,217 @@ fn pretty_print_result(content: &Content) -> String {
 pub struct SymbolService {
     pub symbol_repository: Arc<dyn SymbolRepository>,
     pub symbol_set_repository: Arc<dyn SymbolSetRepository>,
+    pub archetype_repository: Arc<dyn ArchetypeRepository>,
+    pub embedder: Arc<dyn Embedder>,
+    pub prompts: Arc<PromptLibrary>,
+    pub allow_writes: bool,
+    tool_router: ToolRouter<Self>,
+}
+
+#[cfg(feature = "local")]
+impl Default for SymbolService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SymbolService {
-    /// Creates a new SymbolService with in-memory repositories for testing
+    /// Creates a new SymbolService with empty in-memory repositories for testing
     #[cfg(feature = "local")]
     pub fn new() -> Self {
+        Self::from_factory(&InMemoryRepositoryFactory::new())
+    }
+
+    /// Creates a SymbolService whose repositories all come from `factory`
+    pub fn from_factory(factory: &dyn RepositoryFactory) -> Self {
         Self {
-            symbol_repository: Arc::new(InMemorySymbolRepository::new()),
-            symbol_set_repository: Arc::new(InMemorySymbolSetRepository::new()),
+            symbol_repository: factory.create_symbol_repository(),
+            symbol_set_repository: factory.create_symbol_set_repository(),
+            archetype_repository: factory.create_archetype_repository(),
+            embedder: Arc::new(HashingEmbedder::default()),
+            prompts: Arc::new(PromptLibrary::builtin()),
+            allow_writes: false,
+            tool_router: Self::tool_router(),
         }
     }
+
+    /// Serves `prompts` instead of the built-in prompts
+    pub fn with_prompts(mut self, prompts: PromptLibrary) -> Self {
+        self.prompts = Arc::new(prompts);
+        self
+    }
+
+    /// Serves the tools that create, change and delete symbols when `allow` is set
+    pub fn with_writes(mut self, allow: bool) -> Self {
+        self.allow_writes = allow;
+        self
+    }
+
+    /// The tools this server offers, with the write tools only when writes are allowed
+    fn tools(&self) -> Vec<Tool> {
+        let mut tools: Vec<Tool> = self
+            .tool_router
+            .list_all()
+            .into_iter()
+            .filter(|tool| self.allow_writes || !WRITE_TOOLS.contains(&tool.name.as_ref()))
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
+    fn check_writes_allowed(&self, tool: &str) -> Result<(), rmcp::Error> {
+        if self.allow_writes {
+            Ok(())
+        } else {
+            Err(rmcp::Error::invalid_request(
+                format!(
+                    "{} changes the ontology; start the server with --allow-writes to use it",
+                    tool
+                ),
+                None,
+            ))
+        }
+    }
+
+    /// The page of symbol sets `params` asks for
+    async fn symbol_sets_page(
+        &self,
+        params: &GetSymbolSetsParams,
+    ) -> Result<serde_json::Value, RmcpError> {
+        let query = list_query(None, params.limit, &params.cursor, &params.sort)?;
+        let page = self
+            .symbol_set_repository
+            .list_symbol_sets(&query)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(symbol_sets_json(page))
+    }
+
+    /// Each category with how many symbols are in it, counted by the
+    /// repository rather than by loading the symbols
+    pub async fn category_counts(&self) -> Result<Vec<(String, usize)>, RepositoryError> {
+        let mut counts = Vec::new();
+        for category in self.symbol_repository.list_categories().await? {
+            let query = ListQuery::new().category(Some(category.as_str())).limit(1);
+            let page = self.symbol_repository.list_symbols(&query).await?;
+            counts.push((category, page.total_count));
+        }
+        Ok(counts)
+    }
+
+    /// The page of resources after `cursor`, and where the next one starts.
+    /// Pages hold one kind of resource: symbols, then symbol sets, then
+    /// categories.
+    async fn resources_page(
+        &self,
+        cursor: Option<&str>,
+    ) -> Result<(Vec<Resource>, Option<String>), RmcpError> {
+        let mut position = match cursor {
+            Some(cursor) => ResourceCursor::parse(cursor).ok_or_else(|| {
+                RmcpError::ParseError(format!("Invalid resource cursor: {}", cursor))
+            })?,
+            None => ResourceCursor::Symbols(None),
+        };
+        let query = |cursor: Option<String>| {
+            ListQuery::new()
+                .limit(resources::RESOURCE_PAGE_SIZE)
+                .cursor(cursor)
+        };
+
+        loop {
+            let (resources, next) = match position {
+                ResourceCursor::Symbols(cursor) => {
+                    let page = self
+                        .symbol_repository
+                        .list_symbols(&query(cursor))
+                        .await
+                        .map_err(repository_error_to_rmcp_error)?;
+                    let next = match page.next_cursor {
+                        Some(cursor) => ResourceCursor::Symbols(Some(cursor)),
+                        None => ResourceCursor::SymbolSets(None),
+                    };
+                    let resources = page.items.iter().map(resources::symbol_resource);
+                    (resources.collect::<Vec<_>>(), Some(next))
+                }
+                ResourceCursor::SymbolSets(cursor) => {
+                    let page = self
+                        .symbol_set_repository
+                        .list_symbol_sets(&query(cursor))
+                        .await
+                        .map_err(repository_error_to_rmcp_error)?;
+                    let next = match page.next_cursor {
+                        Some(cursor) => ResourceCursor::SymbolSets(Some(cursor)),
+                        None => ResourceCursor::Categories,
+                    };
+                    let resources = page.items.iter().map(resources::symbol_set_resource);
+                    (resources.collect(), Some(next))
+                }
+                ResourceCursor::Categories => {
+                    let counts = self
+                        .category_counts()
+                        .await
+                        .map_err(repository_error_to_rmcp_error)?;
+                    let resources = counts
+                        .iter()
+                        .map(|(category, count)| resources::category_resource(category, *count));
+                    (resources.collect(), None)
+                }
+            };
+
+            // Skip past kinds with nothing in them rather than hand out an empty page
+            match next {
+                Some(next) if resources.is_empty() => position = next,
+                next => return Ok((resources, next.map(|next| next.encode()))),
+            }
+        }
+    }
+
+    /// One page of the symbol sets matching `params.query`
+    async fn search_symbol_sets_page(
+        &self,
+        params: &SearchSymbolsParams,
+    ) -> Result<serde_json::Value, RmcpError> {
+        let query = list_query(None, params.limit, &params.cursor, &params.sort)?;
+        let page = self
+            .symbol_set_repository
+            .search_symbol_sets(&params.query, &query)
+            .await
+            .map_err(repository_error_to_rmcp_error)?;
+
+        Ok(symbol_sets_json(page))
+    }
 }
 
-#[tool(tool_box)]
+/// Summarizes a page of symbol sets for a tool result
+fn symbol_sets_json(page: Page<SymbolSet>) -> serde_json::Value {
+    let mut result = serde_json::json!({
+        "symbol_sets": page.items.iter().map(|set| {
+            serde_json::json!({
+                "id": set.id,
+                "name": set.name,
+                "category": set.category,
+                "description": set.description,
+                "symbol_count": set.symbols.len()
+            })
+        }).collect::<Vec<_>>(),
+        "total_count": page.total_count
+    });
+    if let Some(next_cursor) = page.next_cursor {
+        result["next_cursor"] = next_cursor.into();
+    }
+    result
+}
+
+#[tool_router]
 impl SymbolService {
-    #[tool(description = "List all symbols (without filtering)")]
+    #[tool(
+        description = "List all symbols (with optional limit)",
+        annotations(read_only_hint = true)
+    )]
     async fn get_symbols(
         &self,
-        #[tool(aggr)] params: GetSymbolsParams,
+        Parameters(params): Parameters<GetSymbolsParams>,
     ) -> Result<CallToolResult, rmcp::Error> {
         info!("Tool call: get_symbols");
 
@@ -71,32 +344,38 @@ impl SymbolService {
         };
 
         // Process using our handler
-        match handler.handle(method_call).await {
-            Ok(json_result) => match Content::json(json_result) {
-                Ok(content) => {
-                    info!("Result preview:\n{}", pretty_print_result(&content));
-                    Ok(CallToolResult::success(vec![content]))
-                }
-                Err(e) => {
-                    error!("Failed to create content: {}", e);
-                    Err(e)
-                }
-            },
-            Err(e) => {
-                error!("Error in get_symbols: {}", e);
-                match e {
-                    RmcpError::ParseError(msg) => Err(rmcp::Error::invalid_params(msg, None)),
-                    RmcpError::RepositoryError(msg) => Err(rmcp::Error::internal_error(msg, None)),
-                    RmcpError::Other(msg) => Err(rmcp::Error::internal_error(msg, None)),
-                }
-            }
-        }
+        tool_result("Error in get_symbols", handler.handle(method_call).await)
     }
 
-    #[tool(description = "Search symbols by text query - use this for all text searches")]
+    #[tool(
+        description = "Get one symbol by its ID, name, alias or name in another language, with its display name in the requested language",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbol(
+        &self,
+        Parameters(params): Parameters<GetSymbolParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: get_symbol");
+
+        // Convert params to MethodCall for our handler
+        let handler = get_symbol(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "get_symbol".to_string(),
+            method: "get_symbol".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result("Error in get_symbol", handler.handle(method_call).await)
+    }
+
+    #[tool(
+        description = "Search symbols by text query, most relevant first with a score per hit; suggests close names as did_you_mean when nothing matches - use this for all text searches",
+        annotations(read_only_hint = true)
+    )]
     async fn search_symbols(
         &self,
-        #[tool(aggr)] params: SearchSymbolsParams,
+        Parameters(params): Parameters<SearchSymbolsParams>,
     ) -> Result<CallToolResult, rmcp::Error> {
         info!("Tool call: search_symbols");
 
@@ -109,32 +388,94 @@ impl SymbolService {
         };
 
         // Process using our handler
-        match handler.handle(method_call).await {
-            Ok(json_result) => match Content::json(json_result) {
-                Ok(content) => {
-                    info!("Result preview:\n{}", pretty_print_result(&content));
-                    Ok(CallToolResult::success(vec![content]))
-                }
-                Err(e) => {
-                    error!("Failed to create content: {}", e);
-                    Err(e)
-                }
-            },
-            Err(e) => {
-                error!("Error in search_symbols: {}", e);
-                match e {
-                    RmcpError::ParseError(msg) => Err(rmcp::Error::invalid_params(msg, None)),
-                    RmcpError::RepositoryError(msg) => Err(rmcp::Error::internal_error(msg, None)),
-                    RmcpError::Other(msg) => Err(rmcp::Error::internal_error(msg, None)),
-                }
-            }
-        }
+        tool_result("Error in search_symbols", handler.handle(method_call).await)
     }
 
-    #[tool(description = "Get symbols by category - use this to filter by category")]
+    #[tool(
+        description = "Find symbols by meaning rather than wording, nearest first with a similarity score per hit",
+        annotations(read_only_hint = true)
+    )]
+    async fn semantic_search_symbols(
+        &self,
+        Parameters(params): Parameters<SemanticSearchSymbolsParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: semantic_search_symbols");
+
+        // Convert params to MethodCall for our handler
+        let handler = semantic_search_symbols(
+            Arc::clone(&self.symbol_repository),
+            Arc::clone(&self.embedder),
+        );
+        let method_call = MethodCall {
+            id: "semantic_search_symbols".to_string(),
+            method: "semantic_search_symbols".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result(
+            "Error in semantic_search_symbols",
+            handler.handle(method_call).await,
+        )
+    }
+
+    #[tool(
+        description = "Find the symbols a dream or other free text mentions, matching names, aliases and word forms; each symbol comes with the spans of text that mention it",
+        annotations(read_only_hint = true)
+    )]
+    async fn extract_symbols_from_text(
+        &self,
+        Parameters(params): Parameters<ExtractSymbolsParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: extract_symbols_from_text");
+
+        // Convert params to MethodCall for our handler
+        let handler = extract_symbols_from_text(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "extract_symbols_from_text".to_string(),
+            method: "extract_symbols_from_text".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result(
+            "Error in extract_symbols_from_text",
+            handler.handle(method_call).await,
+        )
+    }
+
+    #[tool(
+        description = "Interpret a dream in one call: the symbols it mentions with where they are mentioned, an interpretation of each suited to the dreamer's culture, the relations among them, the dominant categories and the emotional tone",
+        annotations(read_only_hint = true)
+    )]
+    async fn interpret_dream(
+        &self,
+        Parameters(params): Parameters<InterpretDreamParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: interpret_dream");
+
+        // Convert params to MethodCall for our handler
+        let handler = interpret_dream(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "interpret_dream".to_string(),
+            method: "interpret_dream".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result(
+            "Error in interpret_dream",
+            handler.handle(method_call).await,
+        )
+    }
+
+    #[tool(
+        description = "Get symbols by category - use this to filter by category",
+        annotations(read_only_hint = true)
+    )]
     async fn filter_by_category(
         &self,
-        #[tool(aggr)] params: CategorySymbolsParams,
+        Parameters(params): Parameters<CategorySymbolsParams>,
     ) -> Result<CallToolResult, rmcp::Error> {
         info!("Tool call: filter_by_category");
 
@@ -147,470 +488,724 @@ impl SymbolService {
         };
 
         // Process using our handler
-        match handler.handle(method_call).await {
-            Ok(json_result) => match Content::json(json_result) {
-                Ok(content) => {
-                    info!("Result preview:\n{}", pretty_print_result(&content));
-                    Ok(CallToolResult::success(vec![content]))
-                }
-                Err(e) => {
-                    error!("Failed to create content: {}", e);
-                    Err(e)
-                }
-            },
-            Err(e) => {
-                error!("Error in filter_by_category: {}", e);
-                match e {
-                    RmcpError::ParseError(msg) => Err(rmcp::Error::invalid_params(msg, None)),
-                    RmcpError::RepositoryError(msg) => Err(rmcp::Error::internal_error(msg, None)),
-                    RmcpError::Other(msg) => Err(rmcp::Error::internal_error(msg, None)),
-                }
-            }
-        }
+        tool_result(
+            "Error in filter_by_category",
+            handler.handle(method_call).await,
+        )
     }
 
-    #[tool(description = "Get a list of symbol sets available in the ontology")]
-    async fn get_symbol_sets(&self) -> Result<CallToolResult, rmcp::Error> {
+    #[tool(
+        description = "List symbol sets - collections of related symbols",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbol_sets(
+        &self,
+        Parameters(params): Parameters<GetSymbolSetsParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
         info!("Tool call: get_symbol_sets");
 
-        match self.symbol_set_repository.list_symbol_sets(None).await {
-            Ok(symbol_sets) => {
-                let result = serde_json::json!({
-                    "symbol_sets": symbol_sets.iter().map(|set| {
-                        serde_json::json!({
-                            "id": set.id,
-                            "name": set.name,
-                            "category": set.category,
-                            "description": set.description,
-                            "symbol_count": set.symbols.len()
-                        })
-                    }).collect::<Vec<_>>(),
-                    "total_count": symbol_sets.len()
-                });
-
-                match Content::json(result) {
-                    Ok(content) => {
-                        info!("Result preview:\n{}", pretty_print_result(&content));
-                        Ok(CallToolResult::success(vec![content]))
-                    }
-                    Err(e) => {
-                        error!("Failed to create content: {}", e);
-                        Err(e)
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Error fetching symbol sets: {}", e);
-                Err(rmcp::Error::internal_error(
-                    format!("Repository error: {}", e),
-                    None,
-                ))
-            }
-        }
+        tool_result(
+            "Error fetching symbol sets",
+            self.symbol_sets_page(&params).await,
+        )
     }
 
-    #[tool(description = "Get all available symbol categories")]
+    #[tool(
+        description = "Get all available symbol categories",
+        annotations(read_only_hint = true)
+    )]
     async fn get_categories(&self) -> Result<CallToolResult, rmcp::Error> {
         info!("Tool call: get_categories");
 
-        match self.symbol_repository.list_symbols(None).await {
-            Ok(symbols) => {
-                let mut categories = std::collections::HashSet::new();
-                for symbol in &symbols {
-                    categories.insert(symbol.category.clone());
-                }
-
-                let mut categories: Vec<String> = categories.into_iter().collect();
-                categories.sort();
-
-                let result = serde_json::json!({
+        let result = self
+            .symbol_repository
+            .list_categories()
+            .await
+            .map(|categories| {
+                serde_json::json!({
                     "categories": categories,
                     "count": categories.len()
-                });
-
-                match Content::json(result) {
-                    Ok(content) => {
-                        info!("Result preview:\n{}", pretty_print_result(&content));
-                        Ok(CallToolResult::success(vec![content]))
-                    }
-                    Err(e) => {
-                        error!("Failed to create content: {}", e);
-                        Err(e)
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Error fetching categories: {}", e);
-                Err(rmcp::Error::internal_error(
-                    format!("Repository error: {}", e),
-                    None,
-                ))
-            }
-        }
+                })
+            })
+            .map_err(repository_error_to_rmcp_error);
+        tool_result("Error fetching categories", result)
     }
 
-    #[tool(description = "Search for symbol sets by name or description")]
+    #[tool(
+        description = "Search for symbol sets by name or description",
+        annotations(read_only_hint = true)
+    )]
     async fn search_symbol_sets(
         &self,
-        #[tool(aggr)] params: SearchSymbolsParams,
+        Parameters(params): Parameters<SearchSymbolsParams>,
     ) -> Result<CallToolResult, rmcp::Error> {
         info!("Tool call: search_symbol_sets");
 
-        match self
-            .symbol_set_repository
-            .search_symbol_sets(&params.query)
-            .await
-        {
-            Ok(symbol_sets) => {
-                let result = serde_json::json!({
-                    "symbol_sets": symbol_sets.iter().take(params.limit).map(|set| {
-                        serde_json::json!({
-                            "id": set.id,
-                            "name": set.name,
-                            "category": set.category,
-                            "description": set.description,
-                            "symbol_count": set.symbols.len()
-                        })
-                    }).collect::<Vec<_>>(),
-                    "total_count": symbol_sets.len()
-                });
-
-                match Content::json(result) {
-                    Ok(content) => {
-                        info!("Result preview:\n{}", pretty_print_result(&content));
-                        Ok(CallToolResult::success(vec![content]))
-                    }
-                    Err(e) => {
-                        error!("Failed to create content: {}", e);
-                        Err(e)
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Error searching symbol sets: {}", e);
-                Err(rmcp::Error::internal_error(
-                    format!("Repository error: {}", e),
-                    None,
-                ))
-            }
-        }
+        tool_result(
+            "Error searching symbol sets",
+            self.search_symbol_sets_page(&params).await,
+        )
+    }
+
+    #[tool(
+        description = "Get the network of symbols related to a symbol, following typed relations up to a given depth",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_related_network(
+        &self,
+        Parameters(params): Parameters<RelatedNetworkParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: get_related_network");
+
+        // Convert params to MethodCall for our handler
+        let handler = get_related_network(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "get_related_network".to_string(),
+            method: "get_related_network".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result(
+            "Error in get_related_network",
+            handler.handle(method_call).await,
+        )
+    }
+
+    #[tool(
+        description = "Find how two symbols are connected, returning each hop with its relation and an interpretation snippet",
+        annotations(read_only_hint = true)
+    )]
+    async fn find_symbol_path(
+        &self,
+        Parameters(params): Parameters<FindSymbolPathParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: find_symbol_path");
+
+        // Convert params to MethodCall for our handler
+        let handler = find_symbol_path(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "find_symbol_path".to_string(),
+            method: "find_symbol_path".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result(
+            "Error in find_symbol_path",
+            handler.handle(method_call).await,
+        )
+    }
+
+    #[tool(
+        description = "List archetypes - recurring patterns such as the Shadow or the Trickster",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_archetypes(
+        &self,
+        Parameters(params): Parameters<GetArchetypesParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: get_archetypes");
+
+        // Convert params to MethodCall for our handler
+        let handler = get_archetypes(Arc::clone(&self.archetype_repository));
+        let method_call = MethodCall {
+            id: "get_archetypes".to_string(),
+            method: "get_archetypes".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result("Error in get_archetypes", handler.handle(method_call).await)
+    }
+
+    #[tool(
+        description = "Get the archetype hierarchy, from general archetypes down to their specific forms",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_archetype_hierarchy(
+        &self,
+        Parameters(params): Parameters<ArchetypeHierarchyParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: get_archetype_hierarchy");
+
+        // Convert params to MethodCall for our handler
+        let handler = get_archetype_hierarchy(Arc::clone(&self.archetype_repository));
+        let method_call = MethodCall {
+            id: "get_archetype_hierarchy".to_string(),
+            method: "get_archetype_hierarchy".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result(
+            "Error in get_archetype_hierarchy",
+            handler.handle(method_call).await,
+        )
+    }
+
+    #[tool(
+        description = "Get symbols linked to an archetype, optionally including its more specific archetypes",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbols_by_archetype(
+        &self,
+        Parameters(params): Parameters<SymbolsByArchetypeParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: get_symbols_by_archetype");
+
+        // Convert params to MethodCall for our handler
+        let handler = get_symbols_by_archetype(Arc::clone(&self.archetype_repository));
+        let method_call = MethodCall {
+            id: "get_symbols_by_archetype".to_string(),
+            method: "get_symbols_by_archetype".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result(
+            "Error in get_symbols_by_archetype",
+            handler.handle(method_call).await,
+        )
+    }
+
+    #[tool(
+        description = "Create a new symbol; fails if the ID is taken",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false
+        )
+    )]
+    async fn create_symbol(
+        &self,
+        Parameters(params): Parameters<CreateSymbolParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: create_symbol");
+        self.check_writes_allowed("create_symbol")?;
+
+        // Convert params to MethodCall for our handler
+        let handler = create_symbol(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "create_symbol".to_string(),
+            method: "create_symbol".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result("Error in create_symbol", handler.handle(method_call).await)
+    }
+
+    #[tool(
+        description = "Change a symbol's name, category, description, aliases, localized names or properties, keeping the fields left out",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true
+        )
+    )]
+    async fn update_symbol(
+        &self,
+        Parameters(params): Parameters<UpdateSymbolParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: update_symbol");
+        self.check_writes_allowed("update_symbol")?;
+
+        // Convert params to MethodCall for our handler
+        let handler = update_symbol(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "update_symbol".to_string(),
+            method: "update_symbol".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result("Error in update_symbol", handler.handle(method_call).await)
+    }
+
+    #[tool(
+        description = "Add an interpretation to a symbol, replacing any it already has in the same context",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true
+        )
+    )]
+    async fn add_interpretation(
+        &self,
+        Parameters(params): Parameters<AddInterpretationParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: add_interpretation");
+        self.check_writes_allowed("add_interpretation")?;
+
+        // Convert params to MethodCall for our handler
+        let handler = add_interpretation(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "add_interpretation".to_string(),
+            method: "add_interpretation".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result(
+            "Error in add_interpretation",
+            handler.handle(method_call).await,
+        )
+    }
+
+    #[tool(
+        description = "Relate two symbols with a typed, optionally weighted relation",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true
+        )
+    )]
+    async fn relate_symbols(
+        &self,
+        Parameters(params): Parameters<RelateSymbolsParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: relate_symbols");
+        self.check_writes_allowed("relate_symbols")?;
+
+        // Convert params to MethodCall for our handler
+        let handler = relate_symbols(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "relate_symbols".to_string(),
+            method: "relate_symbols".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result("Error in relate_symbols", handler.handle(method_call).await)
+    }
+
+    #[tool(
+        description = "Permanently delete a symbol and its relations; this cannot be undone",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true
+        )
+    )]
+    async fn delete_symbol(
+        &self,
+        Parameters(params): Parameters<DeleteSymbolParams>,
+    ) -> Result<CallToolResult, rmcp::Error> {
+        info!("Tool call: delete_symbol");
+        self.check_writes_allowed("delete_symbol")?;
+
+        // Convert params to MethodCall for our handler
+        let handler = delete_symbol(Arc::clone(&self.symbol_repository));
+        let method_call = MethodCall {
+            id: "delete_symbol".to_string(),
+            method: "delete_symbol".to_string(),
+            params: serde_json::to_value(params).unwrap_or(serde_json::Value::Null),
+        };
+
+        // Process using our handler
+        tool_result("Error in delete_symbol", handler.handle(method_call).await)
     }
 }
 
 // ServerHandler implementation with proper MCP method dispatching
 impl ServerHandler for SymbolService {
     fn get_info(&self) -> ServerInfo {
+        let mut instructions = "Symbol Ontology provides symbolic reasoning tools. Available methods: get_symbols, get_symbol, search_symbols, semantic_search_symbols, extract_symbols_from_text, interpret_dream, filter_by_category, get_categories, get_symbol_sets, search_symbol_sets, get_related_network, find_symbol_path, get_archetypes, get_archetype_hierarchy, get_symbols_by_archetype. Symbols, symbol sets and categories can also be read as markdown resources at symbol://{id}, symbol-set://{id} and category://{name}, and prompts such as symbol_lookup and dream_analysis are available to start common tasks.".to_string();
+        if self.allow_writes {
+            instructions.push_str(" Writes are enabled: create_symbol, update_symbol, add_interpretation and relate_symbols change the ontology, and delete_symbol removes a symbol for good.");
+        }
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_prompts()
+                .enable_resources()
+                .build(),
             server_info: Implementation {
                 name: "Symbol Ontology MCP Client".to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
             },
-            instructions: Some("Symbol Ontology provides symbolic reasoning tools. Available methods: get_symbols, search_symbols, filter_by_category, get_categories, get_symbol_sets, search_symbol_sets.".to_string()),
+            instructions: Some(instructions),
         }
     }
 
     async fn call_tool(
         &self,
-        method: CallToolRequestParam,
-        _ctx: RequestContext<RoleServer>,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, rmcp::Error> {
-        let method_name = &method.name;
-        debug!("MCP tool call received: {}", method_name);
+        debug!("MCP tool call received: {}", request.name);
 
-        let arguments = match method.arguments {
-            Some(ref map) => serde_json::Value::Object(map.clone()),
-            None => serde_json::Value::Null,
-        };
+        // The write tools refuse the call themselves unless writes are allowed
+        self.tool_router
+            .call(ToolCallContext::new(self, request, context))
+            .await
+    }
 
-        let method_call = crate::mcp::methods::get_symbols::MethodCall {
-            id: "mcp-call".to_string(), // ID is not really used in our implementation
-            method: method_name.to_string(),
-            params: arguments,
-        };
+    async fn list_tools(
+        &self,
+        _param: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, rmcp::Error> {
+        Ok(ListToolsResult {
+            tools: self.tools(),
+            next_cursor: None,
+        })
+    }
 
-        // Create appropriate handlers based on method
-        let result = match method_name.as_ref() {
-            "get_symbols" => {
-                let handler = get_symbols(Arc::clone(&self.symbol_repository));
-                handler.handle(method_call).await
-            }
-            "search_symbols" => {
-                let handler = search_symbols(Arc::clone(&self.symbol_repository));
-                handler.handle(method_call).await
-            }
-            "filter_by_category" => {
-                let handler = filter_by_category(Arc::clone(&self.symbol_repository));
-                handler.handle(method_call).await
-            }
-            "get_categories" => {
-                // Get a list of all unique categories
-                match self.symbol_repository.list_symbols(None).await {
-                    Ok(symbols) => {
-                        let mut categories = std::collections::HashSet::new();
-                        for symbol in &symbols {
-                            categories.insert(symbol.category.clone());
-                        }
-
-                        let mut categories: Vec<String> = categories.into_iter().collect();
-                        categories.sort();
-
-                        Ok(serde_json::json!({
-                            "categories": categories,
-                            "count": categories.len()
-                        }))
-                    }
-                    Err(e) => Err(RmcpError::RepositoryError(e.to_string())),
-                }
+    async fn list_resources(
+        &self,
+        param: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, rmcp::Error> {
+        let cursor = param.and_then(|param| param.cursor);
+        let (resources, next_cursor) =
+            self.resources_page(cursor.as_deref()).await.map_err(|e| {
+                error!("Error listing resources: {}", e);
+                mcp_error(e)
+            })?;
+
+        Ok(ListResourcesResult {
+            resources,
+            next_cursor,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _param: Option<PaginatedRequestParam>,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, rmcp::Error> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: resources::resource_templates(),
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, rmcp::Error> {
+        debug!("MCP resource read received: {}", uri);
+
+        let not_found = |message: String| {
+            rmcp::Error::resource_not_found(message, Some(serde_json::json!({ "uri": uri })))
+        };
+        let repository_error = |e: RepositoryError| match e {
+            RepositoryError::NotFound(msg) => not_found(msg),
+            e => {
+                error!("Error reading resource {}: {}", uri, e);
+                rmcp::Error::internal_error(format!("Repository error: {}", e), None)
             }
-            "get_symbol_sets" => {
-                // Return a list of all symbol sets
-                match self.symbol_set_repository.list_symbol_sets(None).await {
-                    Ok(symbol_sets) => Ok(serde_json::json!({
-                        "symbol_sets": symbol_sets.iter().map(|set| {
-                            serde_json::json!({
-                                "id": set.id,
-                                "name": set.name,
-                                "category": set.category,
-                                "description": set.description,
-                                "symbol_count": set.symbols.len()
-                            })
-                        }).collect::<Vec<_>>(),
-                        "total_count": symbol_sets.len()
-                    })),
-                    Err(e) => Err(RmcpError::RepositoryError(e.to_string())),
-                }
+        };
+
+        let text = match ResourceUri::parse(&uri) {
+            Some(ResourceUri::Symbol(id)) => {
+                let symbol = self
+                    .symbol_repository
+                    .get_symbol(&id)
+                    .await
+                    .map_err(repository_error)?;
+                resources::render_symbol(&symbol)
             }
-            "search_symbol_sets" => {
-                // Parse the query parameter from method_call.params
-                let params: SearchSymbolsParams = match serde_json::from_value(method_call.params) {
-                    Ok(params) => params,
-                    Err(e) => return Err(rmcp::Error::invalid_params(e.to_string(), None)),
-                };
-
-                match self
+            Some(ResourceUri::SymbolSet(id)) => {
+                let symbol_set = self
                     .symbol_set_repository
-                    .search_symbol_sets(&params.query)
+                    .get_symbol_set(&id)
+                    .await
+                    .map_err(repository_error)?;
+                resources::render_symbol_set(&symbol_set)
+            }
+            Some(ResourceUri::Category(name)) => {
+                let page = self
+                    .symbol_repository
+                    .list_symbols(
+                        &ListQuery::new()
+                            .category(Some(name.as_str()))
+                            .limit(resources::RESOURCE_PAGE_SIZE),
+                    )
                     .await
-                {
-                    Ok(symbol_sets) => Ok(serde_json::json!({
-                        "symbol_sets": symbol_sets.iter().take(params.limit).map(|set| {
-                            serde_json::json!({
-                                "id": set.id,
-                                "name": set.name,
-                                "category": set.category,
-                                "description": set.description,
-                                "symbol_count": set.symbols.len()
-                            })
-                        }).collect::<Vec<_>>(),
-                        "total_count": symbol_sets.len()
-                    })),
-                    Err(e) => Err(RmcpError::RepositoryError(e.to_string())),
+                    .map_err(repository_error)?;
+                if page.items.is_empty() {
+                    return Err(not_found(format!("No symbols in category {}", name)));
                 }
+                resources::render_category(&name, &page)
             }
-            _ => Err(RmcpError::Other(format!("Unknown method: {}", method_name))),
+            None => return Err(not_found(format!("Unknown resource URI: {}", uri))),
         };
 
-        // Process the result, or return an error
-        match result {
-            Ok(json_result) => match Content::json(json_result) {
-                Ok(content) => Ok(CallToolResult::success(vec![content])),
-                Err(e) => Err(e),
-            },
-            Err(e) => match e {
-                RmcpError::ParseError(msg) => Err(rmcp::Error::invalid_params(msg, None)),
-                RmcpError::RepositoryError(msg) => Err(rmcp::Error::internal_error(msg, None)),
-                RmcpError::Other(msg) => Err(rmcp::Error::internal_error(msg, None)),
-            },
-        }
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.clone(),
+                mime_type: Some(MARKDOWN_MIME_TYPE.to_string()),
+                text,
+            }],
+        })
     }
 
-    fn list_tools(
+    async fn list_prompts(
         &self,
-        _param: Option<PaginatedRequestParamInner>,
+        _param: Option<PaginatedRequestParam>,
         _ctx: RequestContext<RoleServer>,
-    ) -> impl std::future::Future<Output = Result<ListToolsResult, rmcp::Error>> + Send + '_ {
-        async move {
-            // Schema for get_symbols
-            let schema1 = serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "limit": {
-                        "type": "integer",
-                        "description": "Maximum number of symbols to return",
-                        "default": 50
-                    }
-                }
-            });
-
-            // Schema for search_symbols
-            let schema2 = serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "query": {
-                        "type": "string",
-                        "description": "Search text"
-                    },
-                    "limit": {
-                        "type": "integer",
-                        "description": "Maximum number of symbols to return",
-                        "default": 50
-                    }
-                },
-                "required": ["query"]
-            });
-
-            // Schema for filter_by_category
-            let schema3 = serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "category": {
-                        "type": "string",
-                        "description": "Category name to filter by"
-                    },
-                    "limit": {
-                        "type": "integer",
-                        "description": "Maximum number of symbols to return",
-                        "default": 50
-                    }
-                },
-                "required": ["category"]
-            });
-
-            // Schema for get_categories (no parameters needed)
-            let schema4 = serde_json::json!({
-                "type": "object",
-                "properties": {}
-            });
-
-            // Schema for get_symbol_sets
-            let schema5 = serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "limit": {
-                        "type": "integer",
-                        "description": "Maximum number of symbol sets to return",
-                        "default": 50
-                    }
-                }
-            });
-
-            // Schema for search_symbol_sets
-            let schema6 = serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "query": {
-                        "type": "string",
-                        "description": "Search text for symbol sets"
-                    },
-                    "limit": {
-                        "type": "integer",
-                        "description": "Maximum number of symbol sets to return",
-                        "default": 50
-                    }
-                },
-                "required": ["query"]
-            });
-
-            // Convert schemas to Arc<Map<String, Value>> as expected by rmcp
-            let schema1_map = match serde_json::to_value(schema1) {
-                Ok(serde_json::Value::Object(map)) => Arc::new(map),
-                _ => {
-                    return Err(rmcp::Error::internal_error(
-                        "Failed to create schema1",
-                        None,
-                    ))
-                }
-            };
+    ) -> Result<ListPromptsResult, rmcp::Error> {
+        Ok(ListPromptsResult {
+            prompts: self.prompts.prompts(),
+            next_cursor: None,
+        })
+    }
 
-            let schema2_map = match serde_json::to_value(schema2) {
-                Ok(serde_json::Value::Object(map)) => Arc::new(map),
-                _ => {
-                    return Err(rmcp::Error::internal_error(
-                        "Failed to create schema2",
-                        None,
-                    ))
-                }
-            };
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
+        _ctx: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, rmcp::Error> {
+        debug!("MCP prompt requested: {}", name);
 
-            let schema3_map = match serde_json::to_value(schema3) {
-                Ok(serde_json::Value::Object(map)) => Arc::new(map),
-                _ => {
-                    return Err(rmcp::Error::internal_error(
-                        "Failed to create schema3",
-                        None,
-                    ))
-                }
-            };
+        // Clients send argument values as strings, but accept any JSON scalar
+        let arguments = arguments
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(key, value)| match value {
+                serde_json::Value::String(text) => Some((key, text)),
+                serde_json::Value::Null => None,
+                other => Some((key, other.to_string())),
+            })
+            .collect();
 
-            let schema4_map = match serde_json::to_value(schema4) {
-                Ok(serde_json::Value::Object(map)) => Arc::new(map),
-                _ => {
-                    return Err(rmcp::Error::internal_error(
-                        "Failed to create schema4",
-                        None,
-                    ))
-                }
-            };
+        let prompt = self
+            .prompts
+            .get(&name)
+            .map_err(|e| rmcp::Error::invalid_params(e.to_string(), None))?;
+        match prompt.render(&arguments) {
+            Ok(text) => Ok(GetPromptResult {
+                description: prompt.description.clone(),
+                messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+            }),
+            Err(e @ PromptError::MissingArgument(_)) => {
+                Err(rmcp::Error::invalid_params(e.to_string(), None))
+            }
+            Err(e) => {
+                error!("Error rendering prompt {}: {}", name, e);
+                Err(rmcp::Error::internal_error(e.to_string(), None))
+            }
+        }
+    }
+}
 
-            let schema5_map = match serde_json::to_value(schema5) {
-                Ok(serde_json::Value::Object(map)) => Arc::new(map),
-                _ => {
-                    return Err(rmcp::Error::internal_error(
-                        "Failed to create schema5",
-                        None,
-                    ))
-                }
-            };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ontology_core::db::repository::InMemoryRepositoryFactory;
+    use ontology_core::domain::Symbol;
 
-            let schema6_map = match serde_json::to_value(schema6) {
-                Ok(serde_json::Value::Object(map)) => Arc::new(map),
-                _ => {
-                    return Err(rmcp::Error::internal_error(
-                        "Failed to create schema6",
-                        None,
-                    ))
-                }
-            };
+    #[test]
+    fn test_repository_errors_keep_their_kind() {
+        let code = |e: RepositoryError| mcp_error(repository_error_to_rmcp_error(e)).code;
 
-            Ok(ListToolsResult {
-                tools: vec![
-                    Tool {
-                        name: "get_symbols".into(),
-                        input_schema: schema1_map,
-                        description: "List all symbols (with optional limit)".into(),
-                    },
-                    Tool {
-                        name: "search_symbols".into(),
-                        input_schema: schema2_map,
-                        description:
-                            "Search symbols by text query - use this for all text searches".into(),
-                    },
-                    Tool {
-                        name: "filter_by_category".into(),
-                        input_schema: schema3_map,
-                        description: "Get symbols by category - use this to filter by category"
-                            .into(),
-                    },
-                    Tool {
-                        name: "get_categories".into(),
-                        input_schema: schema4_map,
-                        description: "Get all available symbol categories".into(),
-                    },
-                    Tool {
-                        name: "get_symbol_sets".into(),
-                        input_schema: schema5_map,
-                        description: "List symbol sets - collections of related symbols".into(),
-                    },
-                    Tool {
-                        name: "search_symbol_sets".into(),
-                        input_schema: schema6_map,
-                        description: "Search for symbol sets by name or description".into(),
-                    },
-                ],
-                next_cursor: None,
-            })
+        assert_eq!(
+            code(RepositoryError::NotFound("sun".to_string())),
+            ErrorCode::INVALID_PARAMS
+        );
+        assert_eq!(
+            code(RepositoryError::Conflict("Alias sol is taken".to_string())),
+            ErrorCode::INVALID_PARAMS
+        );
+        assert_eq!(
+            code(RepositoryError::Internal("connection reset".to_string())),
+            ErrorCode::INTERNAL_ERROR
+        );
+
+        let error = tool_result(
+            "Error in get_symbol",
+            Err(RmcpError::NotFound("sun".to_string())),
+        )
+        .unwrap_err();
+        assert_eq!(error.message, "Not found: sun");
+    }
+
+    #[test]
+    fn test_writes_are_refused_unless_allowed() {
+        let service = SymbolService::from_factory(&InMemoryRepositoryFactory::new());
+
+        let refused = service.check_writes_allowed("delete_symbol").unwrap_err();
+        assert_eq!(refused.code, ErrorCode::INVALID_REQUEST);
+        assert!(refused.message.contains("--allow-writes"));
+        let instructions = service.get_info().instructions.unwrap_or_default();
+        assert!(!instructions.contains("delete_symbol"));
+
+        let service = service.with_writes(true);
+        assert!(service.check_writes_allowed("delete_symbol").is_ok());
+        let instructions = service.get_info().instructions.unwrap_or_default();
+        assert!(instructions.contains("delete_symbol"));
+    }
+
+    #[tokio::test]
+    async fn test_resources_are_paged() {
+        let service = SymbolService::from_factory(&InMemoryRepositoryFactory::new());
+        for i in 0..=resources::RESOURCE_PAGE_SIZE {
+            let category = if i % 2 == 0 { "nature" } else { "animal" };
+            let symbol = Symbol::new(
+                format!("symbol-{:03}", i),
+                format!("Symbol {:03}", i),
+                category.to_string(),
+                String::new(),
+            );
+            service
+                .symbol_repository
+                .create_symbol(symbol)
+                .await
+                .unwrap();
+        }
+
+        let (first, cursor) = service.resources_page(None).await.unwrap();
+        assert_eq!(first.len(), resources::RESOURCE_PAGE_SIZE);
+        assert_eq!(first[0].raw.uri, "symbol://symbol-000");
+
+        // With no symbol sets, the page after the last symbol goes straight to categories
+        let (second, cursor) = service.resources_page(cursor.as_deref()).await.unwrap();
+        let uris = |page: &[Resource]| {
+            page.iter()
+                .map(|resource| resource.raw.uri.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(uris(&second), vec!["symbol://symbol-100"]);
+        let (third, cursor) = service.resources_page(cursor.as_deref()).await.unwrap();
+        assert_eq!(uris(&third), vec!["category://animal", "category://nature"]);
+        assert_eq!(
+            third[1].raw.description.as_deref(),
+            Some("Symbols in the nature category (51)")
+        );
+        assert!(cursor.is_none());
+
+        assert!(matches!(
+            service.resources_page(Some("not a cursor")).await,
+            Err(RmcpError::ParseError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_symbol_sets_are_paged() {
+        let service = SymbolService::from_factory(&InMemoryRepositoryFactory::new());
+        for name in ["Elements", "Planets", "Seasons"] {
+            let set = SymbolSet::new(
+                name.to_lowercase(),
+                name.to_string(),
+                "nature".to_string(),
+                String::new(),
+            );
+            service
+                .symbol_set_repository
+                .create_symbol_set(set)
+                .await
+                .unwrap();
+        }
+        let params = |cursor: &str| GetSymbolSetsParams {
+            limit: 2,
+            cursor: cursor.to_string(),
+            sort: "name_desc".to_string(),
+        };
+
+        let first = service.symbol_sets_page(&params("")).await.unwrap();
+        let names = |page: &serde_json::Value| {
+            page["symbol_sets"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|set| set["name"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&first), vec!["Seasons", "Planets"]);
+        assert_eq!(first["total_count"], 3);
+
+        let next_cursor = first["next_cursor"].as_str().unwrap();
+        let second = service
+            .symbol_sets_page(&params(next_cursor))
+            .await
+            .unwrap();
+        assert_eq!(names(&second), vec!["Elements"]);
+        assert!(second.get("next_cursor").is_none());
+
+        assert!(matches!(
+            service.symbol_sets_page(&params("not a cursor")).await,
+            Err(RmcpError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_result_preview_truncates_on_char_boundaries() {
+        let names = vec!["河童"; 100];
+        let content = Content::json(serde_json::json!({ "names": names })).unwrap();
+
+        let preview = pretty_print_result(&content);
+        assert!(preview.ends_with("... (truncated) ...\n}"));
+        assert!(preview.chars().count() < 500);
+    }
+
+    #[test]
+    fn test_tools_are_annotated() {
+        let service = SymbolService::from_factory(&InMemoryRepositoryFactory::new());
+        let tools = service.tools();
+        assert!(tools
+            .iter()
+            .all(|tool| !WRITE_TOOLS.contains(&tool.name.as_ref())));
+        for tool in &tools {
+            let annotations = tool.annotations.as_ref().unwrap();
+            assert_eq!(annotations.read_only_hint, Some(true), "{}", tool.name);
+        }
+
+        let tools = service.with_writes(true).tools();
+        let hints = |name: &str| {
+            let tool = tools.iter().find(|tool| tool.name == name).unwrap();
+            let annotations = tool.annotations.as_ref().unwrap();
+            (
+                annotations.read_only_hint,
+                annotations.destructive_hint,
+                annotations.idempotent_hint,
+            )
+        };
+        assert_eq!(
+            hints("create_symbol"),
+            (Some(false), Some(false), Some(false))
+        );
+        assert_eq!(
+            hints("update_symbol"),
+            (Some(false), Some(true), Some(true))
+        );
+        assert_eq!(
+            hints("delete_symbol"),
+            (Some(false), Some(true), Some(true))
+        );
+        for name in WRITE_TOOLS {
+            assert_eq!(hints(name).0, Some(false), "{}", name);
         }
     }
+
+    #[test]
+    fn test_tools_are_routed_by_their_parameters() {
+        let service = SymbolService::from_factory(&InMemoryRepositoryFactory::new());
+
+        // Write tools stay routed while hidden, so calls get the write refusal
+        for name in WRITE_TOOLS {
+            assert!(service.tool_router.map.contains_key(name), "{}", name);
+        }
+
+        let routed = service.tool_router.map.len();
+        let tools = service.with_writes(true).tools();
+        assert_eq!(tools.len(), routed);
+        let properties = |name: &str| {
+            let tool = tools.iter().find(|tool| tool.name == name).unwrap();
+            tool.input_schema["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let get_symbols = properties("get_symbols");
+        assert!(get_symbols.iter().any(|key| key == "cursor"));
+        assert!(get_symbols.iter().any(|key| key == "sort"));
+        assert!(properties("delete_symbol").iter().any(|key| key == "id"));
+    }
 }
@@ -0,0 +1,45 @@
+//! HTTP transports for the MCP server.
+//!
+//! - [`sse`]: the HTTP+SSE transport, an event stream at `GET /sse` plus
+//!   `POST /message?sessionId=...` for client messages
+//! - [`streamable_http`]: the streamable HTTP transport, a single `/mcp`
+//!   endpoint whose sessions outlive any one connection
+//!
+//! Both are rmcp's own servers. They send a heartbeat comment on idle event
+//! streams, so proxies that close quiet connections, such as fly.io's, keep
+//! them open.
+
+pub mod sse;
+pub mod streamable_http;
+
+#[cfg(test)]
+mod tests {
+    use crate::mcp::service::SymbolService;
+    use axum::Router;
+    use ontology_core::db::repository::InMemoryRepositoryFactory;
+
+    pub(super) fn test_service() -> SymbolService {
+        SymbolService::from_factory(&InMemoryRepositoryFactory::new())
+    }
+
+    /// Serves `router` on a free local port, returning its base URL.
+    pub(super) async fn serve(router: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{}", address)
+    }
+
+    pub(super) fn initialize_request() -> serde_json::Value {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "0" }
+            }
+        })
+    }
+}
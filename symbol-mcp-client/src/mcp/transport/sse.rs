@@ -0,0 +1,140 @@
+//! The HTTP+SSE transport, served by rmcp's [`SseServer`]. A client opens an
+//! event stream at `GET /sse`, receives an `endpoint` event naming the URL to
+//! post its messages to, and gets the server's messages as `message` events
+//! on the stream. The session ends when the stream is closed.
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::middleware;
+use axum::response::Response;
+use axum::Router;
+use futures::StreamExt;
+use rmcp::transport::sse_server::SseServerConfig;
+use rmcp::transport::SseServer;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::mcp::service::SymbolService;
+
+pub const SSE_PATH: &str = "/sse";
+pub const MESSAGE_PATH: &str = "/message";
+
+/// Routes for the transport. Sessions end when `ct` is cancelled. rmcp always
+/// sends heartbeats on this transport, every 15 seconds when `heartbeat` is
+/// `None`.
+pub fn router(
+    service: SymbolService,
+    heartbeat: Option<Duration>,
+    ct: CancellationToken,
+) -> Router {
+    let (server, router) = SseServer::new(SseServerConfig {
+        // Only used when rmcp binds the server itself; the caller serves `router`
+        bind: SocketAddr::from(([0, 0, 0, 0], 0)),
+        sse_path: SSE_PATH.to_string(),
+        post_path: MESSAGE_PATH.to_string(),
+        ct: ct.clone(),
+        sse_keep_alive: heartbeat,
+    });
+    server.with_service(move || service.clone());
+    router.layer(middleware::map_response_with_state(ct, end_on_cancel))
+}
+
+/// rmcp keeps an event stream open after its session ends, until the client
+/// leaves, so end the stream once `ct` is cancelled or shutdown would wait on
+/// connected clients.
+async fn end_on_cancel(State(ct): State<CancellationToken>, response: Response) -> Response {
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().take_until(ct.cancelled_owned());
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{initialize_request, serve, test_service};
+    use super::*;
+
+    /// Reads the event stream until `text` appears, returning what was read.
+    async fn read_until(response: &mut reqwest::Response, text: &str) -> String {
+        let read = async {
+            let mut body = String::new();
+            while !body.contains(text) {
+                let chunk = response.chunk().await.unwrap().expect("event stream ended");
+                body.push_str(&String::from_utf8_lossy(&chunk));
+            }
+            body
+        };
+        tokio::time::timeout(Duration::from_secs(5), read)
+            .await
+            .unwrap_or_else(|_| panic!("timed out waiting for {:?}", text))
+    }
+
+    #[tokio::test]
+    async fn test_messages_and_heartbeats_on_the_stream() {
+        let base = serve(router(
+            test_service(),
+            Some(Duration::from_millis(50)),
+            CancellationToken::new(),
+        ))
+        .await;
+        let client = reqwest::Client::new();
+
+        let mut stream = client
+            .get(format!("{}{}", base, SSE_PATH))
+            .send()
+            .await
+            .unwrap();
+        let opening = read_until(&mut stream, "\n\n").await;
+        let endpoint = opening
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .unwrap()
+            .to_string();
+        assert!(opening.contains("event: endpoint"));
+        assert!(endpoint.starts_with("/message?sessionId="));
+
+        let status = client
+            .post(format!("{}{}", base, endpoint))
+            .json(&initialize_request())
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, reqwest::StatusCode::ACCEPTED);
+        read_until(&mut stream, "\"serverInfo\"").await;
+
+        // A heartbeat is an empty comment
+        read_until(&mut stream, ":\n\n").await;
+    }
+
+    #[tokio::test]
+    async fn test_streams_end_on_shutdown() {
+        let ct = CancellationToken::new();
+        let base = serve(router(test_service(), None, ct.clone())).await;
+        let mut stream = reqwest::get(format!("{}{}", base, SSE_PATH)).await.unwrap();
+        read_until(&mut stream, "event: endpoint").await;
+
+        ct.cancel();
+        let ended = tokio::time::timeout(Duration::from_secs(5), async {
+            while stream.chunk().await.unwrap().is_some() {}
+        })
+        .await;
+
+        assert!(ended.is_ok(), "event stream outlived shutdown");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_session_is_not_found() {
+        let base = serve(router(test_service(), None, CancellationToken::new())).await;
+
+        let status = reqwest::Client::new()
+            .post(format!("{}{}?sessionId=missing", base, MESSAGE_PATH))
+            .json(&initialize_request())
+            .send()
+            .await
+            .unwrap()
+            .status();
+
+        assert_eq!(status, reqwest::StatusCode::NOT_FOUND);
+    }
+}
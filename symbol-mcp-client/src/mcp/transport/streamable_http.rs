@@ -0,0 +1,603 @@
+//! The streamable HTTP transport, served from a single endpoint, `/mcp`, by
+//! rmcp's [`StreamableHttpService`].
+//!
+//! - `POST` sends a client message. An `initialize` request without a session
+//!   starts one; its ID comes back in the `Mcp-Session-Id` header and must be
+//!   sent with every later request. Requests are answered on an event stream
+//!   in the response body, while notifications and responses get
+//!   `202 Accepted`.
+//! - `GET` opens an event stream for messages the server sends on its own,
+//!   such as notifications. Every event has an ID, and reconnecting with
+//!   `Last-Event-ID` resumes from it, so a session survives dropped
+//!   connections.
+//! - `DELETE` ends the session.
+//!
+//! Clients often vanish without ending their session, so a session with no
+//! open stream and no request in flight for the idle timeout is ended too.
+
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use futures::{Stream, StreamExt};
+use rmcp::model::{
+    ClientJsonRpcMessage, ErrorData, JsonRpcMessage, RequestId, ServerJsonRpcMessage,
+};
+use rmcp::transport::common::server_side_http::ServerSseMessage;
+use rmcp::transport::streamable_http_server::session::local::{
+    LocalSessionManager, LocalSessionManagerError,
+};
+use rmcp::transport::streamable_http_server::session::{SessionId, SessionManager};
+use rmcp::transport::{StreamableHttpServerConfig, StreamableHttpService};
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::mcp::service::SymbolService;
+
+pub const MCP_PATH: &str = "/mcp";
+
+/// Header carrying the session ID.
+pub const SESSION_ID_HEADER: &str = "mcp-session-id";
+
+type Messages = Pin<Box<dyn Stream<Item = ServerSseMessage> + Send + Sync>>;
+
+/// Routes for the transport. Sessions end when `ct` is cancelled, or once
+/// idle for `idle_timeout`.
+pub fn router(
+    service: SymbolService,
+    heartbeat: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    ct: CancellationToken,
+) -> Router {
+    let sessions = Arc::new(Sessions::new(idle_timeout));
+    tokio::spawn(sessions.clone().expire(ct));
+    routes(service, heartbeat, sessions)
+}
+
+fn routes(service: SymbolService, heartbeat: Option<Duration>, sessions: Arc<Sessions>) -> Router {
+    let mcp = StreamableHttpService::new(
+        move || Ok(service.clone()),
+        sessions,
+        StreamableHttpServerConfig {
+            sse_keep_alive: heartbeat,
+            stateful_mode: true,
+        },
+    );
+    Router::new()
+        .route_service(MCP_PATH, mcp)
+        .layer(middleware::from_fn(session_status))
+}
+
+/// rmcp answers a missing or unknown session with `401 Unauthorized`; report
+/// them as `400 Bad Request` and `404 Not Found`, as the spec asks, so
+/// clients know to start a new session.
+async fn session_status(request: Request, next: Next) -> Response {
+    let has_session_id = request.headers().contains_key(SESSION_ID_HEADER);
+    let mut response = next.run(request).await;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        *response.status_mut() = if has_session_id {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::BAD_REQUEST
+        };
+    }
+    response
+}
+
+/// What a session has been up to, for telling when it has gone idle.
+struct Activity {
+    /// When the latest request arrived or stream closed.
+    last_active: Mutex<Instant>,
+
+    /// How many streams are open, counting requests still being answered.
+    open_streams: AtomicUsize,
+
+    /// IDs of the requests still being answered.
+    in_flight: Mutex<HashSet<RequestId>>,
+}
+
+impl Activity {
+    fn new() -> Self {
+        Activity {
+            last_active: Mutex::new(Instant::now()),
+            open_streams: AtomicUsize::new(0),
+            in_flight: Mutex::default(),
+        }
+    }
+
+    fn touch(&self) {
+        if let Ok(mut last_active) = self.last_active.lock() {
+            *last_active = Instant::now();
+        }
+    }
+
+    /// How long the session has gone without a request or an open stream.
+    fn idle_for(&self) -> Duration {
+        if self.open_streams.load(Ordering::SeqCst) > 0 {
+            return Duration::ZERO;
+        }
+        self.last_active
+            .lock()
+            .map(|last_active| last_active.elapsed())
+            .unwrap_or_default()
+    }
+}
+
+/// Keeps a session from expiring while a stream is open, and holds the ID of
+/// the request the stream answers.
+struct OpenStream {
+    activity: Arc<Activity>,
+    request_id: Option<RequestId>,
+}
+
+impl OpenStream {
+    fn new(activity: Arc<Activity>, request_id: Option<RequestId>) -> Self {
+        activity.touch();
+        activity.open_streams.fetch_add(1, Ordering::SeqCst);
+        OpenStream {
+            activity,
+            request_id,
+        }
+    }
+
+    fn wrap(
+        self,
+        messages: impl Stream<Item = ServerSseMessage> + Send + Sync + 'static,
+    ) -> Messages {
+        Box::pin(messages.map(move |message| {
+            let _open = &self;
+            message
+        }))
+    }
+}
+
+impl Drop for OpenStream {
+    fn drop(&mut self) {
+        if let Some(id) = &self.request_id {
+            if let Ok(mut in_flight) = self.activity.in_flight.lock() {
+                in_flight.remove(id);
+            }
+        }
+        self.activity.touch();
+        self.activity.open_streams.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// rmcp's [`LocalSessionManager`], ending sessions that go idle and
+/// refusing a request that reuses the ID of one still being answered.
+struct Sessions {
+    inner: LocalSessionManager,
+    activity: Mutex<HashMap<SessionId, Arc<Activity>>>,
+    idle_timeout: Option<Duration>,
+}
+
+impl Sessions {
+    fn new(idle_timeout: Option<Duration>) -> Self {
+        Sessions {
+            inner: LocalSessionManager::default(),
+            activity: Mutex::default(),
+            idle_timeout,
+        }
+    }
+
+    fn activity(&self, id: &SessionId) -> Option<Arc<Activity>> {
+        self.activity.lock().ok()?.get(id).cloned()
+    }
+
+    fn session_ids(&self) -> Vec<SessionId> {
+        self.activity
+            .lock()
+            .map(|activity| activity.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Ends sessions as they go idle, and every session once `ct` is
+    /// cancelled, freeing the services behind them.
+    async fn expire(self: Arc<Self>, ct: CancellationToken) {
+        if let Some(idle_timeout) = self.idle_timeout {
+            let mut ticks = tokio::time::interval(idle_timeout / 4);
+            loop {
+                tokio::select! {
+                    _ = ticks.tick() => {}
+                    _ = ct.cancelled() => break,
+                }
+                for id in self.session_ids() {
+                    let idle = self
+                        .activity(&id)
+                        .is_some_and(|activity| activity.idle_for() >= idle_timeout);
+                    if idle {
+                        info!("MCP session {} expired after {:?} idle", id, idle_timeout);
+                        self.end(&id).await;
+                    }
+                }
+            }
+        } else {
+            ct.cancelled().await;
+        }
+        for id in self.session_ids() {
+            self.end(&id).await;
+        }
+    }
+
+    async fn end(&self, id: &SessionId) {
+        if let Err(e) = self.close_session(id).await {
+            warn!("Failed to end MCP session {}: {}", id, e);
+        }
+    }
+}
+
+impl SessionManager for Sessions {
+    type Error = LocalSessionManagerError;
+    type Transport = <LocalSessionManager as SessionManager>::Transport;
+
+    async fn create_session(&self) -> Result<(SessionId, Self::Transport), Self::Error> {
+        let (id, transport) = self.inner.create_session().await?;
+        if let Ok(mut activity) = self.activity.lock() {
+            activity.insert(id.clone(), Arc::new(Activity::new()));
+        }
+        info!("MCP session {} started", id);
+        Ok((id, transport))
+    }
+
+    async fn initialize_session(
+        &self,
+        id: &SessionId,
+        message: ClientJsonRpcMessage,
+    ) -> Result<ServerJsonRpcMessage, Self::Error> {
+        self.inner.initialize_session(id, message).await
+    }
+
+    async fn has_session(&self, id: &SessionId) -> Result<bool, Self::Error> {
+        self.inner.has_session(id).await
+    }
+
+    async fn close_session(&self, id: &SessionId) -> Result<(), Self::Error> {
+        let removed = self
+            .activity
+            .lock()
+            .ok()
+            .and_then(|mut activity| activity.remove(id));
+        if removed.is_some() {
+            info!("MCP session {} ended", id);
+        }
+        self.inner.close_session(id).await
+    }
+
+    async fn create_stream(
+        &self,
+        id: &SessionId,
+        message: ClientJsonRpcMessage,
+    ) -> Result<impl Stream<Item = ServerSseMessage> + Send + Sync + 'static, Self::Error> {
+        let activity = self
+            .activity(id)
+            .unwrap_or_else(|| Arc::new(Activity::new()));
+        let request_id = match &message {
+            JsonRpcMessage::Request(request) => Some(request.id.clone()),
+            _ => None,
+        };
+        if let Some(request_id) = &request_id {
+            let added = activity
+                .in_flight
+                .lock()
+                .map(|mut in_flight| in_flight.insert(request_id.clone()))
+                .unwrap_or(true);
+            if !added {
+                let error = ErrorData::invalid_request(
+                    format!("Request {} is already in progress", request_id),
+                    None,
+                );
+                let reply = ServerSseMessage {
+                    event_id: None,
+                    message: Arc::new(JsonRpcMessage::error(error, request_id.clone())),
+                };
+                return Ok(Box::pin(futures::stream::once(async move { reply })) as Messages);
+            }
+        }
+
+        let open = OpenStream::new(activity, request_id);
+        let messages = self.inner.create_stream(id, message).await?;
+        Ok(open.wrap(messages))
+    }
+
+    async fn accept_message(
+        &self,
+        id: &SessionId,
+        message: ClientJsonRpcMessage,
+    ) -> Result<(), Self::Error> {
+        if let Some(activity) = self.activity(id) {
+            activity.touch();
+        }
+        self.inner.accept_message(id, message).await
+    }
+
+    async fn create_standalone_stream(
+        &self,
+        id: &SessionId,
+    ) -> Result<impl Stream<Item = ServerSseMessage> + Send + Sync + 'static, Self::Error> {
+        let activity = self
+            .activity(id)
+            .unwrap_or_else(|| Arc::new(Activity::new()));
+        let open = OpenStream::new(activity, None);
+        let messages = self.inner.create_standalone_stream(id).await?;
+        Ok(open.wrap(messages))
+    }
+
+    async fn resume(
+        &self,
+        id: &SessionId,
+        last_event_id: String,
+    ) -> Result<impl Stream<Item = ServerSseMessage> + Send + Sync + 'static, Self::Error> {
+        let activity = self
+            .activity(id)
+            .unwrap_or_else(|| Arc::new(Activity::new()));
+        let open = OpenStream::new(activity, None);
+        let messages = self.inner.resume(id, last_event_id).await?;
+        Ok(open.wrap(messages))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{initialize_request, serve, test_service};
+    use super::*;
+    use serde_json::json;
+
+    /// What a client must accept when posting messages.
+    const ACCEPT: &str = "application/json, text/event-stream";
+
+    fn post(
+        client: &reqwest::Client,
+        url: &str,
+        session_id: Option<&str>,
+        message: serde_json::Value,
+    ) -> reqwest::RequestBuilder {
+        let request = client
+            .post(url)
+            .header(reqwest::header::ACCEPT, ACCEPT)
+            .json(&message);
+        match session_id {
+            Some(session_id) => request.header(SESSION_ID_HEADER, session_id),
+            None => request,
+        }
+    }
+
+    /// The message on the event stream answering a request.
+    async fn reply(response: reqwest::Response) -> serde_json::Value {
+        let body = response.text().await.unwrap();
+        let data = body
+            .lines()
+            .find_map(|line| line.strip_prefix("data:"))
+            .unwrap_or_else(|| panic!("no message in {:?}", body));
+        serde_json::from_str(data.trim()).unwrap()
+    }
+
+    /// Initializes a session, returning its ID.
+    async fn start_session(client: &reqwest::Client, url: &str) -> String {
+        let initialized = post(client, url, None, initialize_request())
+            .send()
+            .await
+            .unwrap();
+        let session_id = initialized.headers()[SESSION_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        reply(initialized).await;
+        let notified = post(
+            client,
+            url,
+            Some(&session_id),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        )
+        .send()
+        .await
+        .unwrap();
+        assert_eq!(notified.status(), reqwest::StatusCode::ACCEPTED);
+        session_id
+    }
+
+    fn list_tools(id: u64) -> serde_json::Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": "tools/list" })
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let base = serve(router(test_service(), None, None, CancellationToken::new())).await;
+        let url = format!("{}{}", base, MCP_PATH);
+        let client = reqwest::Client::new();
+
+        let initialized = post(&client, &url, None, initialize_request())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(initialized.status(), reqwest::StatusCode::OK);
+        let session_id = initialized.headers()[SESSION_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = reply(initialized).await;
+        assert_eq!(body["id"], 1);
+        assert!(body["result"]["capabilities"]["tools"].is_object());
+
+        let notified = post(
+            &client,
+            &url,
+            Some(&session_id),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        )
+        .send()
+        .await
+        .unwrap();
+        assert_eq!(notified.status(), reqwest::StatusCode::ACCEPTED);
+
+        let listed = reply(
+            post(
+                &client,
+                &url,
+                Some(&session_id),
+                json!({ "jsonrpc": "2.0", "id": 2, "method": "prompts/list" }),
+            )
+            .send()
+            .await
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(listed["id"], 2);
+        assert!(!listed["result"]["prompts"].as_array().unwrap().is_empty());
+
+        let ended = client
+            .delete(&url)
+            .header(SESSION_ID_HEADER, &session_id)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(ended.status(), reqwest::StatusCode::ACCEPTED);
+
+        let after = post(&client, &url, Some(&session_id), list_tools(3))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(after.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_idle_sessions_expire() {
+        let idle_timeout = Duration::from_millis(300);
+        // Heartbeats let the server notice the event stream closing
+        let base = serve(router(
+            test_service(),
+            Some(Duration::from_millis(50)),
+            Some(idle_timeout),
+            CancellationToken::new(),
+        ))
+        .await;
+        let url = format!("{}{}", base, MCP_PATH);
+        let client = reqwest::Client::new();
+        let session_id = start_session(&client, &url).await;
+
+        // Requests within the timeout keep the session alive
+        for id in 2..5 {
+            tokio::time::sleep(idle_timeout / 2).await;
+            let listed = post(&client, &url, Some(&session_id), list_tools(id))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(listed.status(), reqwest::StatusCode::OK);
+            reply(listed).await;
+        }
+
+        // So does an open event stream
+        let stream = client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .header(SESSION_ID_HEADER, &session_id)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(stream.status(), reqwest::StatusCode::OK);
+        tokio::time::sleep(idle_timeout * 2).await;
+        drop(stream);
+        let listed = post(&client, &url, Some(&session_id), list_tools(5))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(listed.status(), reqwest::StatusCode::OK);
+        reply(listed).await;
+
+        tokio::time::sleep(idle_timeout * 2).await;
+        let expired = post(&client, &url, Some(&session_id), list_tools(6))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(expired.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_requests_in_flight_keep_sessions_alive() {
+        let idle_timeout = Duration::from_millis(300);
+        let sessions = Arc::new(Sessions::new(Some(idle_timeout)));
+        tokio::spawn(sessions.clone().expire(CancellationToken::new()));
+        let base = serve(routes(test_service(), None, sessions.clone())).await;
+        let url = format!("{}{}", base, MCP_PATH);
+        let session_id: SessionId = start_session(&reqwest::Client::new(), &url).await.into();
+        let request = |id| serde_json::from_value(list_tools(id)).unwrap();
+
+        // A stream not yet read stands in for a slow tool call
+        let answering = sessions
+            .create_stream(&session_id, request(7))
+            .await
+            .unwrap();
+        tokio::time::sleep(idle_timeout * 2).await;
+        assert!(sessions.has_session(&session_id).await.unwrap());
+
+        drop(answering);
+        tokio::time::sleep(idle_timeout * 2).await;
+        assert!(!sessions.has_session(&session_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_request_ids_are_rejected() {
+        let sessions = Arc::new(Sessions::new(None));
+        let base = serve(routes(test_service(), None, sessions.clone())).await;
+        let url = format!("{}{}", base, MCP_PATH);
+        let session_id: SessionId = start_session(&reqwest::Client::new(), &url).await.into();
+        let request = |id| serde_json::from_value(list_tools(id)).unwrap();
+
+        let mut answering = sessions
+            .create_stream(&session_id, request(7))
+            .await
+            .unwrap();
+        let mut duplicate = sessions
+            .create_stream(&session_id, request(7))
+            .await
+            .unwrap();
+
+        let rejected = serde_json::to_value(&*duplicate.next().await.unwrap().message).unwrap();
+        assert_eq!(rejected["id"], 7);
+        assert_eq!(rejected["error"]["code"], -32600);
+        let answered = serde_json::to_value(&*answering.next().await.unwrap().message).unwrap();
+        assert_eq!(answered["id"], 7);
+        assert!(answered["result"]["tools"].is_array());
+
+        // Once answered, the ID may be used again
+        drop(answering);
+        let mut reused = sessions
+            .create_stream(&session_id, request(7))
+            .await
+            .unwrap();
+        let answered = serde_json::to_value(&*reused.next().await.unwrap().message).unwrap();
+        assert!(answered["result"]["tools"].is_array());
+    }
+
+    #[tokio::test]
+    async fn test_requests_need_a_session() {
+        let base = serve(router(test_service(), None, None, CancellationToken::new())).await;
+        let url = format!("{}{}", base, MCP_PATH);
+        let client = reqwest::Client::new();
+
+        let request = post(&client, &url, None, list_tools(1))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(request.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+
+        let stream = client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(stream.status(), reqwest::StatusCode::BAD_REQUEST);
+
+        let unknown = post(&client, &url, Some("missing"), list_tools(1))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(unknown.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "local")]
 use symbol_mcp_client::mcp::service::SymbolService;
 
 #[cfg(feature = "local")]